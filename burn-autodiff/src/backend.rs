@@ -31,6 +31,14 @@ impl<B: Backend> Backend for ADBackendDecorator<B> {
     fn seed(seed: u64) {
         B::seed(seed)
     }
+
+    fn memory_allocated(device: &Self::Device) -> Option<usize> {
+        B::memory_allocated(device)
+    }
+
+    fn memory_used(device: &Self::Device) -> Option<usize> {
+        B::memory_used(device)
+    }
 }
 
 impl<B: Backend> ADBackend for ADBackendDecorator<B> {
@@ -61,4 +69,8 @@ impl<B: Backend> ADBackend for ADBackendDecorator<B> {
     fn from_inner<const D: usize>(tensor: B::TensorPrimitive<D>) -> ADTensor<B, D> {
         ADTensor::new(tensor)
     }
+
+    fn int_inner<const D: usize>(tensor: B::IntTensorPrimitive<D>) -> B::IntTensorPrimitive<D> {
+        tensor
+    }
 }
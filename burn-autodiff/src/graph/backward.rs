@@ -5,10 +5,24 @@ use crate::{grads::Gradients, tensor::ADTensor};
 use super::{traversal::BreadthFirstSearch, Graph, NodeRef, StepBoxed};
 
 pub fn backward<B: Backend, const D: usize>(root: ADTensor<B, D>) -> Gradients {
-    let grads = Gradients::new::<B, D>(root.node.clone(), root.primitive);
+    let mut grads = Gradients::new::<B, D>(root.node.clone(), root.primitive);
     let tape = build_tape(root.node, root.graph);
 
-    execute_steps(tape, grads)
+    execute_steps(tape, &mut grads);
+    grads
+}
+
+/// Backpropagate the given gradient of the root tensor, accumulating the gradients of the
+/// graph into existing gradients.
+pub fn backward_with_grad<B: Backend, const D: usize>(
+    root: ADTensor<B, D>,
+    grad: B::TensorPrimitive<D>,
+    grads: &mut Gradients,
+) {
+    grads.register::<B, D>(root.node.clone(), grad);
+    let tape = build_tape(root.node, root.graph);
+
+    execute_steps(tape, grads);
 }
 
 fn build_tape(root: NodeRef, graph: Graph) -> Vec<Vec<StepBoxed>> {
@@ -29,9 +43,8 @@ fn build_tape(root: NodeRef, graph: Graph) -> Vec<Vec<StepBoxed>> {
     tape
 }
 
-fn execute_steps(tape: Vec<Vec<StepBoxed>>, mut grads: Gradients) -> Gradients {
+fn execute_steps(tape: Vec<Vec<StepBoxed>>, grads: &mut Gradients) {
     tape.into_iter()
         .rev()
-        .for_each(|steps| steps.into_iter().for_each(|step| step.step(&mut grads)));
-    grads
+        .for_each(|steps| steps.into_iter().for_each(|step| step.step(grads)));
 }
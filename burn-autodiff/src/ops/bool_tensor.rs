@@ -80,4 +80,24 @@ impl<B: Backend> BoolTensorOps<ADBackendDecorator<B>> for ADBackendDecorator<B>
     fn bool_equal_elem<const D: usize>(lhs: BoolTensor<B, D>, rhs: bool) -> BoolTensor<B, D> {
         B::bool_equal_elem(lhs, rhs)
     }
+
+    fn bool_not<const D: usize>(tensor: BoolTensor<B, D>) -> BoolTensor<B, D> {
+        B::bool_not(tensor)
+    }
+
+    fn bool_and<const D: usize>(lhs: BoolTensor<B, D>, rhs: BoolTensor<B, D>) -> BoolTensor<B, D> {
+        B::bool_and(lhs, rhs)
+    }
+
+    fn bool_or<const D: usize>(lhs: BoolTensor<B, D>, rhs: BoolTensor<B, D>) -> BoolTensor<B, D> {
+        B::bool_or(lhs, rhs)
+    }
+
+    fn bool_any<const D: usize>(tensor: BoolTensor<B, D>) -> BoolTensor<B, 1> {
+        B::bool_any(tensor)
+    }
+
+    fn bool_all<const D: usize>(tensor: BoolTensor<B, D>) -> BoolTensor<B, 1> {
+        B::bool_all(tensor)
+    }
 }
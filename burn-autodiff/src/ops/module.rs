@@ -1,10 +1,13 @@
 use crate::grads::Gradients;
+use crate::graph::backward::backward_with_grad;
+use crate::graph::{NodeRef, Requirement, Step};
 use crate::ops::{unary, Backward, Ops};
 use crate::tensor::{ADTensor, IntTensor};
 use crate::ADBackendDecorator;
 
 use burn_tensor::backend::Backend;
 use burn_tensor::ops::*;
+use burn_tensor::{Distribution, ElementConversion, Shape};
 
 use super::OpsKind;
 
@@ -341,6 +344,64 @@ impl<B: Backend> ModuleOps<ADBackendDecorator<B>> for ADBackendDecorator<B> {
         );
         MaxPool2dBackward::new(ADTensor::new(output.x_grad))
     }
+
+    fn memory_format(x: ADTensor<B, 4>, format: MemoryFormat) -> ADTensor<B, 4> {
+        #[derive(Debug)]
+        struct ToMemoryFormat;
+
+        impl<B: Backend> Backward<B, 4, 1> for ToMemoryFormat {
+            type State = ();
+
+            fn backward(self, ops: Ops<Self::State, 1>, grads: &mut Gradients) {
+                unary::<B, 4, 4, _>(ops.parents, ops.node, grads, |grad| grad);
+            }
+        }
+
+        ToMemoryFormat
+            .prepare([x.node], [x.graph])
+            .stateless(B::memory_format(x.primitive, format))
+    }
+
+    fn checkpoint<const D_IN: usize, const D_OUT: usize>(
+        x: ADTensor<B, D_IN>,
+        func: CheckpointFn<ADBackendDecorator<B>, D_IN, D_OUT>,
+    ) -> ADTensor<B, D_OUT> {
+        // The recomputation must sample the same random values as the forward pass. The backend
+        // is then reseeded from its own stream, so the following random values don't depend on
+        // the ones sampled by the function.
+        let device = B::device(&x.primitive);
+        let seed = checkpoint_seed::<B>(&device);
+        let seed_resume = checkpoint_seed::<B>(&device);
+        B::seed(seed);
+
+        let output = func(checkpoint_input(&x));
+        B::seed(seed_resume);
+        let requirement = match output.is_tracked() {
+            true => Requirement::GradInBackward,
+            false => Requirement::None,
+        };
+        // The graph of the function, holding the intermediate activations, is dropped here.
+        let output = ADTensor::from_parents(
+            output.primitive,
+            std::slice::from_ref(&x.node),
+            [x.graph].into_iter(),
+            requirement,
+        );
+
+        if requirement.is_none() {
+            return output;
+        }
+
+        let step = CheckpointStep {
+            parent: x.node.clone_if_require_grad(),
+            node: output.node.clone(),
+            input: x.primitive,
+            func,
+            seed,
+        };
+
+        output.register_step(step)
+    }
 }
 
 #[derive(Debug)]
@@ -368,3 +429,76 @@ impl<B: Backend> Backward<B, 4, 1> for MaxPool2D {
         }
     }
 }
+
+/// Backward step of a [checkpoint](ModuleOps::checkpoint), recomputing the function to
+/// backpropagate through its graph.
+struct CheckpointStep<B: Backend, const D_IN: usize, const D_OUT: usize> {
+    parent: Option<NodeRef>,
+    node: NodeRef,
+    input: B::TensorPrimitive<D_IN>,
+    func: CheckpointFn<ADBackendDecorator<B>, D_IN, D_OUT>,
+    seed: u64,
+}
+
+impl<B: Backend, const D_IN: usize, const D_OUT: usize> Step for CheckpointStep<B, D_IN, D_OUT> {
+    fn step(self: Box<Self>, grads: &mut Gradients) {
+        let grad = grads.consume::<B, D_OUT>(&self.node);
+        let device = B::device(&self.input);
+        let input = ADTensor::new(self.input);
+        let input = match self.parent {
+            Some(_) => input.require_grad(),
+            None => input,
+        };
+
+        // The random state is restored after the recomputation, otherwise it would be rewound to
+        // the state following the forward pass and the next steps would replay the same values.
+        let seed_resume = checkpoint_seed::<B>(&device);
+        B::seed(self.seed);
+        let output = (self.func)(input.clone());
+        B::seed(seed_resume);
+        backward_with_grad(output, grad, grads);
+
+        if let Some(parent) = self.parent {
+            if let Some(grad) = grads.remove(&input) {
+                grads.register::<B, D_IN>(parent, grad);
+            }
+        }
+    }
+
+    fn node(&self) -> NodeRef {
+        self.node.clone()
+    }
+}
+
+impl<B: Backend, const D_IN: usize, const D_OUT: usize> std::fmt::Debug
+    for CheckpointStep<B, D_IN, D_OUT>
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CheckpointStep")
+            .field("node", &self.node)
+            .finish()
+    }
+}
+
+/// The input of the checkpointed function, as a new leaf so that its graph isn't traversed when
+/// backpropagating through the function.
+fn checkpoint_input<B: Backend, const D: usize>(x: &ADTensor<B, D>) -> ADTensor<B, D> {
+    let input = ADTensor::new(x.primitive.clone());
+
+    match x.is_tracked() {
+        true => input.require_grad(),
+        false => input,
+    }
+}
+
+/// Draw a seed from the random state of the backend.
+fn checkpoint_seed<B: Backend>(device: &B::Device) -> u64 {
+    let random = B::random(
+        Shape::new([1]),
+        Distribution::Uniform(0.0.elem(), 1.0.elem()),
+        device,
+    );
+    let value: f64 = B::to_data(&random).value[0].elem();
+
+    (value * u32::MAX as f64) as u64
+}
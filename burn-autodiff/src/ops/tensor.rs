@@ -78,6 +78,10 @@ impl<B: Backend> TensorOps<ADBackendDecorator<B>> for ADBackendDecorator<B> {
         B::arange(range, device)
     }
 
+    fn one_hot(indices: IntTensor<B, 1>, num_classes: usize) -> ADTensor<B, 2> {
+        ADTensor::new(B::one_hot(indices, num_classes))
+    }
+
     fn empty<const D: usize>(shape: Shape<D>, device: &B::Device) -> ADTensor<B, D> {
         ADTensor::new(B::empty(shape, device))
     }
@@ -0,0 +1,31 @@
+#[burn_tensor_testgen::testgen(ad_cast)]
+mod tests {
+    use super::*;
+    use burn_tensor::{backend::Backend, Data, Tensor};
+
+    type TestADBackendFull = <TestADBackend as Backend>::FullPrecisionBackend;
+
+    #[test]
+    fn should_diff_cast() {
+        let data_1: Data<f32, 2> = Data::from([[1.0, 7.0], [2.0, 3.0]]);
+        let data_2: Data<f32, 2> = Data::from([[4.0, 7.0], [2.0, 3.0]]);
+
+        let tensor_1 = TestADTensor::from_data(data_1).require_grad();
+        let tensor_2 = TestADTensor::from_data(data_2).require_grad();
+
+        let tensor_3: Tensor<TestADBackendFull, 2> = tensor_1.clone().cast();
+        let tensor_4 = tensor_3.matmul(tensor_2.clone().cast());
+        let tensor_5: TestADTensor<2, _> = tensor_4.cast();
+        let grads = tensor_5.backward();
+
+        let grad_1 = tensor_1.grad(&grads).unwrap();
+        let grad_2 = tensor_2.grad(&grads).unwrap();
+
+        assert_eq!(grad_1.to_data(), Data::from([[11.0, 5.0], [11.0, 5.0]]));
+        assert_eq!(grad_2.to_data(), Data::from([[3.0, 3.0], [10.0, 10.0]]));
+        assert_eq!(
+            tensor_5.into_data(),
+            Data::from([[18.0, 28.0], [14.0, 23.0]])
+        );
+    }
+}
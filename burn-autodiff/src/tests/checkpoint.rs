@@ -0,0 +1,104 @@
+#[burn_tensor_testgen::testgen(ad_checkpoint)]
+mod tests {
+    use super::*;
+    use burn_tensor::{module::checkpoint, Data, Distribution};
+
+    #[test]
+    fn should_diff_checkpoint_like_without() {
+        let data_1 = Data::<f32, 2>::from([[1.0, 7.0], [-2.0, -3.0]]);
+        let data_2 = Data::<f32, 2>::from([[4.0, -7.0], [2.0, 3.0]]);
+        let func = |x: TestADTensor<2, burn_tensor::Float>, weight: TestADTensor<2, _>| {
+            x.matmul(weight.exp()).tanh()
+        };
+
+        let tensor_1 = TestADTensor::from_data(data_1.clone()).require_grad();
+        let tensor_2 = TestADTensor::from_data(data_2.clone()).require_grad();
+        let tensor_3 = func(tensor_1.clone(), tensor_2.clone());
+        let grads = tensor_3.clone().mul(tensor_1.clone()).backward();
+
+        let tensor_1_checkpoint = TestADTensor::from_data(data_1).require_grad();
+        let tensor_2_checkpoint = TestADTensor::from_data(data_2).require_grad();
+        let weight = tensor_2_checkpoint.clone();
+        let tensor_3_checkpoint = checkpoint(tensor_1_checkpoint.clone(), move |x| {
+            func(x, weight.clone())
+        });
+        let grads_checkpoint = tensor_3_checkpoint
+            .clone()
+            .mul(tensor_1_checkpoint.clone())
+            .backward();
+
+        tensor_3_checkpoint
+            .into_data()
+            .assert_approx_eq(&tensor_3.into_data(), 5);
+        tensor_1_checkpoint
+            .grad(&grads_checkpoint)
+            .unwrap()
+            .into_data()
+            .assert_approx_eq(&tensor_1.grad(&grads).unwrap().into_data(), 4);
+        tensor_2_checkpoint
+            .grad(&grads_checkpoint)
+            .unwrap()
+            .into_data()
+            .assert_approx_eq(&tensor_2.grad(&grads).unwrap().into_data(), 4);
+    }
+
+    #[test]
+    fn should_recompute_the_same_random_values() {
+        let tensor_1 = TestADTensor::<2, burn_tensor::Float>::ones([8, 8]).require_grad();
+
+        let tensor_2 = checkpoint(tensor_1.clone(), |x| {
+            let mask = x.random_like(Distribution::Bernoulli(0.5));
+            x * mask
+        });
+        let grads = tensor_2.clone().backward();
+
+        // With an input of ones, the gradient is the mask used during the forward pass.
+        tensor_1
+            .grad(&grads)
+            .unwrap()
+            .into_data()
+            .assert_approx_eq(&tensor_2.inner().into_data(), 5);
+    }
+
+    #[test]
+    fn should_draw_different_random_values_at_each_step() {
+        let random = |x: TestADTensor<2, burn_tensor::Float>| {
+            x.random_like(Distribution::Bernoulli(0.5)) + x.mul_scalar(0.0)
+        };
+        let step = || {
+            let tensor_1 = TestADTensor::<2, burn_tensor::Float>::ones([8, 8]).require_grad();
+            let tensor_2 = checkpoint(tensor_1, random);
+            let tensor_3 = checkpoint(tensor_2.clone(), random);
+            tensor_2.clone().add(tensor_3.clone()).sum().backward();
+
+            [tensor_2.into_data(), tensor_3.into_data()]
+        };
+
+        let [mask_1, mask_2] = step();
+        let [mask_3, mask_4] = step();
+
+        // The backward pass must not rewind the random state to the one following the first
+        // checkpoint of the previous step.
+        assert_ne!(mask_3, mask_2);
+        assert_ne!(mask_3, mask_1);
+        assert_ne!(mask_4, mask_2);
+    }
+
+    #[test]
+    fn should_diff_parameters_of_untracked_input() {
+        let tensor_1 = TestADTensor::from_data(Data::<f32, 2>::from([[1.0, 2.0], [3.0, 4.0]]));
+        let tensor_2 =
+            TestADTensor::from_data(Data::<f32, 2>::from([[1.0, -1.0], [2.0, 0.5]])).require_grad();
+
+        let weight = tensor_2.clone();
+        let tensor_3 = checkpoint(tensor_1.clone(), move |x| x.matmul(weight.clone()));
+        let grads = tensor_3.backward();
+
+        assert!(tensor_1.grad(&grads).is_none());
+        tensor_2
+            .grad(&grads)
+            .unwrap()
+            .into_data()
+            .assert_approx_eq(&Data::from([[4.0, 4.0], [6.0, 6.0]]), 5);
+    }
+}
@@ -1,7 +1,9 @@
 mod add;
 mod aggregation;
 mod backward;
+mod cast;
 mod cat;
+mod checkpoint;
 mod complex;
 mod conv1d;
 mod conv2d;
@@ -42,12 +44,14 @@ macro_rules! testgen_all {
         burn_autodiff::testgen_ad_conv2d!();
         burn_autodiff::testgen_ad_max_pool2d!();
         burn_autodiff::testgen_module_backward!();
+        burn_autodiff::testgen_ad_checkpoint!();
 
         // Tensor
         burn_autodiff::testgen_ad_complex!();
         burn_autodiff::testgen_ad_multithread!();
         burn_autodiff::testgen_ad_add!();
         burn_autodiff::testgen_ad_aggregation!();
+        burn_autodiff::testgen_ad_cast!();
         burn_autodiff::testgen_ad_cat!();
         burn_autodiff::testgen_ad_cos!();
         burn_autodiff::testgen_ad_cross_entropy_loss!();
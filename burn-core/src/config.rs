@@ -28,12 +28,16 @@ impl core::fmt::Display for ConfigError {
 #[cfg(feature = "std")]
 impl std::error::Error for ConfigError {}
 
+/// Configuration that can be serialized to JSON, so that experiments can be reproduced by
+/// reloading the exact configuration used to create a model.
 pub trait Config: serde::Serialize + serde::de::DeserializeOwned {
+    /// Save the configuration as JSON to the given file.
     #[cfg(feature = "std")]
     fn save(&self, file: &str) -> std::io::Result<()> {
         std::fs::write(file, config_to_json(self))
     }
 
+    /// Load the configuration from a JSON file.
     #[cfg(feature = "std")]
     fn load(file: &str) -> Result<Self, ConfigError> {
         let content = std::fs::read_to_string(file)
@@ -41,6 +45,7 @@ pub trait Config: serde::Serialize + serde::de::DeserializeOwned {
         config_from_str(&content)
     }
 
+    /// Load the configuration from JSON bytes.
     fn load_binary(data: &[u8]) -> Result<Self, ConfigError> {
         let content = core::str::from_utf8(data).map_err(|_| {
             ConfigError::InvalidFormat("Could not parse data as utf-8.".to_string())
@@ -56,3 +61,36 @@ pub fn config_to_json<C: Config>(config: &C) -> String {
 fn config_from_str<C: Config>(content: &str) -> Result<C, ConfigError> {
     serde_json::from_str(content).map_err(|err| ConfigError::InvalidFormat(format!("{err}")))
 }
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::nn::transformer::TransformerEncoderConfig;
+
+    #[test]
+    fn test_save_load_transformer_encoder_config() {
+        let config = TransformerEncoderConfig::new(64, 128, 4, 3)
+            .with_dropout(0.2)
+            .with_norm_first(true);
+        let file = std::env::temp_dir().join("burn-test-transformer-encoder-config.json");
+        let file = file.to_str().unwrap();
+
+        config.save(file).unwrap();
+        let config_loaded = TransformerEncoderConfig::load(file).unwrap();
+        std::fs::remove_file(file).ok();
+
+        assert_eq!(config_loaded.d_model, config.d_model);
+        assert_eq!(config_loaded.d_ff, config.d_ff);
+        assert_eq!(config_loaded.n_heads, config.n_heads);
+        assert_eq!(config_loaded.n_layers, config.n_layers);
+        assert_eq!(config_loaded.dropout, config.dropout);
+        assert_eq!(config_loaded.norm_first, config.norm_first);
+    }
+
+    #[test]
+    fn test_load_missing_file_should_fail() {
+        let result = TransformerEncoderConfig::load("/non/existing/config.json");
+
+        assert!(matches!(result, Err(ConfigError::FileNotFound(_))));
+    }
+}
@@ -122,7 +122,7 @@ mod tests {
     use super::*;
     use crate::data::dataloader::batcher::TestBatcher;
     use crate::data::dataloader::FixBatchStrategy;
-    use crate::data::dataset::FakeDataset;
+    use crate::data::dataset::{transform::MapDataset, FakeDataset, InMemDataset};
 
     #[test]
     fn test_batch_dataloader() {
@@ -176,4 +176,33 @@ mod tests {
 
         assert_eq!(items_single_thread, items_multi_thread);
     }
+
+    #[test]
+    fn test_ordered_multi_thread_batch_dataloader_should_keep_dataset_order() {
+        let batcher = Arc::new(TestBatcher::new());
+        let dataset = Arc::new(InMemDataset::new((0..27).collect::<Vec<i64>>()));
+        let dataloader =
+            BatchDataLoader::multi_thread(Box::new(FixBatchStrategy::new(2)), dataset, batcher, 4)
+                .ordered();
+
+        let items: Vec<i64> = dataloader.iter().flatten().collect();
+
+        assert_eq!(items, (0..27).collect::<Vec<i64>>());
+    }
+
+    #[test]
+    fn test_batch_dataloader_with_map_dataset() {
+        let batcher = Arc::new(TestBatcher::new());
+        let dataset = InMemDataset::new((0..10).collect::<Vec<i64>>());
+        let dataset = MapDataset::new(dataset, |item: i64| item * item);
+        let dataloader = BatchDataLoader::new(
+            Box::new(FixBatchStrategy::new(3)),
+            Arc::new(dataset),
+            batcher,
+        );
+
+        let items: Vec<i64> = dataloader.iter().flatten().collect();
+
+        assert_eq!(items, vec![0, 1, 4, 9, 16, 25, 36, 49, 64, 81]);
+    }
 }
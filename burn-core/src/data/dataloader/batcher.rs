@@ -1,7 +1,197 @@
+use crate::module::{State, StateNamed};
+use crate::tensor::{backend::Backend, Bool, Int, Tensor};
+use std::sync::Arc;
+
 pub trait Batcher<I, O>: Send + Sync {
     fn batch(&self, items: Vec<I>) -> O;
 }
 
+/// Batch that can be moved to a device.
+pub trait BatchToDevice<B: Backend> {
+    /// Move the batch to the given device.
+    fn to_device(self, device: &B::Device) -> Self;
+
+    /// The devices of the tensors of the batch, empty when they are unknown.
+    fn devices(&self) -> Vec<B::Device> {
+        Vec::new()
+    }
+
+    /// The values of the tensors of the batch, used to save the inputs of a training step with
+    /// [non-finite gradients](crate::optim::GradientsParams::is_finite). Empty when they are
+    /// unknown.
+    fn state(&self) -> State<f32> {
+        State::StateNamed(StateNamed::new())
+    }
+}
+
+impl<B: Backend, const D: usize> BatchToDevice<B> for Tensor<B, D> {
+    fn to_device(self, device: &B::Device) -> Self {
+        Tensor::to_device(self, device)
+    }
+
+    fn devices(&self) -> Vec<B::Device> {
+        vec![self.device()]
+    }
+
+    fn state(&self) -> State<f32> {
+        State::Data(self.to_data().convert().serialize())
+    }
+}
+
+impl<B: Backend, const D: usize> BatchToDevice<B> for Tensor<B, D, Int> {
+    fn to_device(self, device: &B::Device) -> Self {
+        Tensor::to_device(self, device)
+    }
+
+    fn devices(&self) -> Vec<B::Device> {
+        vec![self.device()]
+    }
+
+    fn state(&self) -> State<f32> {
+        State::Data(self.to_data().convert().serialize())
+    }
+}
+
+impl<B: Backend, const D: usize> BatchToDevice<B> for Tensor<B, D, Bool> {
+    fn to_device(self, device: &B::Device) -> Self {
+        Tensor::to_device(self, device)
+    }
+
+    fn devices(&self) -> Vec<B::Device> {
+        vec![self.device()]
+    }
+
+    fn state(&self) -> State<f32> {
+        State::Data(self.to_data().convert().serialize())
+    }
+}
+
+impl<B: Backend, T1: BatchToDevice<B>, T2: BatchToDevice<B>> BatchToDevice<B> for (T1, T2) {
+    fn to_device(self, device: &B::Device) -> Self {
+        (self.0.to_device(device), self.1.to_device(device))
+    }
+
+    fn devices(&self) -> Vec<B::Device> {
+        let mut devices = self.0.devices();
+        devices.extend(self.1.devices());
+        devices
+    }
+
+    fn state(&self) -> State<f32> {
+        let mut state = StateNamed::new();
+        state.register_state("0", self.0.state());
+        state.register_state("1", self.1.state());
+        State::StateNamed(state)
+    }
+}
+
+/// Batcher moving each batch to a device right after it's created.
+///
+/// Since batches are created by the dataloader workers, the transfer happens in the worker
+/// threads and overlaps with the computation done on the previous batches.
+pub struct DeviceBatcher<B: Backend, I, O> {
+    batcher: Arc<dyn Batcher<I, O>>,
+    device: B::Device,
+}
+
+impl<B: Backend, I, O> DeviceBatcher<B, I, O> {
+    pub fn new(batcher: Arc<dyn Batcher<I, O>>, device: B::Device) -> Self {
+        Self { batcher, device }
+    }
+}
+
+impl<B, I, O> Batcher<I, O> for DeviceBatcher<B, I, O>
+where
+    B: Backend,
+    O: BatchToDevice<B>,
+{
+    fn batch(&self, items: Vec<I>) -> O {
+        self.batcher.batch(items).to_device(&self.device)
+    }
+}
+
+/// Batcher splitting the items of each batch in one sub-batch per device, each sub-batch being
+/// moved to its device right after it's created.
+///
+/// The items are split as evenly as possible, the first sub-batches being larger when the number
+/// of items isn't a multiple of the number of devices. There are fewer sub-batches than devices
+/// when there are fewer items than devices.
+pub struct PerDeviceBatcher<B: Backend, I, O> {
+    batcher: Arc<dyn Batcher<I, O>>,
+    devices: Vec<B::Device>,
+}
+
+impl<B: Backend, I, O> PerDeviceBatcher<B, I, O> {
+    pub fn new(batcher: Arc<dyn Batcher<I, O>>, devices: Vec<B::Device>) -> Self {
+        if devices.is_empty() {
+            panic!("At least one device is required to split the batches");
+        }
+
+        Self { batcher, devices }
+    }
+}
+
+impl<B, I, O> Batcher<I, Vec<O>> for PerDeviceBatcher<B, I, O>
+where
+    B: Backend,
+    O: BatchToDevice<B>,
+{
+    fn batch(&self, items: Vec<I>) -> Vec<O> {
+        let num_devices = self.devices.len();
+        let num_items = items.len();
+        let mut items = items.into_iter();
+        let mut batches = Vec::with_capacity(num_devices);
+
+        for (index, device) in self.devices.iter().enumerate() {
+            let size = num_items / num_devices + usize::from(index < num_items % num_devices);
+
+            if size == 0 {
+                break;
+            }
+
+            let items = items.by_ref().take(size).collect();
+            batches.push(self.batcher.batch(items).to_device(device));
+        }
+
+        batches
+    }
+}
+
+/// Batcher for datasets whose items can fail to load, the failed items are skipped with a
+/// warning and the remaining ones are given to the wrapped batcher.
+///
+/// The batches containing failed items are smaller, unless the batch strategy already removed
+/// them, as done by the [skip errors strategy](super::SkipErrorsBatchStrategy).
+pub struct SkipErrorsBatcher<I, O> {
+    batcher: Arc<dyn Batcher<I, O>>,
+}
+
+impl<I, O> SkipErrorsBatcher<I, O> {
+    pub fn new(batcher: Arc<dyn Batcher<I, O>>) -> Self {
+        Self { batcher }
+    }
+}
+
+impl<I, O, E> Batcher<Result<I, E>, O> for SkipErrorsBatcher<I, O>
+where
+    E: core::fmt::Display,
+{
+    fn batch(&self, items: Vec<Result<I, E>>) -> O {
+        let items = items
+            .into_iter()
+            .filter_map(|item| match item {
+                Ok(item) => Some(item),
+                Err(err) => {
+                    log::warn!("Skipping an item that failed to load: {err}");
+                    None
+                }
+            })
+            .collect();
+
+        self.batcher.batch(items)
+    }
+}
+
 #[cfg(test)]
 #[derive(new)]
 pub struct TestBatcher;
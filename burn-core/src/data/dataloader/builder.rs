@@ -1,5 +1,13 @@
-use super::{batcher::Batcher, BatchDataLoader, BatchStrategy, DataLoader, FixBatchStrategy};
-use burn_dataset::{transform::ShuffledDataset, Dataset};
+use super::{
+    batcher::{BatchToDevice, Batcher, DeviceBatcher, PerDeviceBatcher, SkipErrorsBatcher},
+    BatchDataLoader, BatchStrategy, DataLoader, FixBatchStrategy, Progress, ProgressCallback,
+    SkipErrorsBatchStrategy,
+};
+use crate::tensor::backend::Backend;
+use burn_dataset::{
+    transform::{CachedDataset, RepeatDataset, ShuffledDataset},
+    Dataset,
+};
 use std::sync::Arc;
 
 pub struct DataLoaderBuilder<I, O> {
@@ -7,6 +15,10 @@ pub struct DataLoaderBuilder<I, O> {
     batcher: Arc<dyn Batcher<I, O>>,
     num_threads: Option<usize>,
     shuffle: Option<u64>,
+    repeat: Option<usize>,
+    cache: bool,
+    ordered: bool,
+    progress: Option<ProgressCallback>,
 }
 
 impl<I, O> DataLoaderBuilder<I, O>
@@ -20,6 +32,10 @@ where
             strategy: None,
             num_threads: None,
             shuffle: None,
+            repeat: None,
+            cache: false,
+            ordered: false,
+            progress: None,
         }
     }
 
@@ -33,29 +49,292 @@ where
         self
     }
 
+    /// Cycle over the dataset until yielding `len` items, so that a training defined by a number
+    /// of steps can run as a single epoch.
+    ///
+    /// When [shuffling](Self::shuffle), the items are reshuffled every time the dataset is
+    /// exhausted.
+    pub fn repeat(mut self, len: usize) -> Self {
+        self.repeat = Some(len);
+        self
+    }
+
+    /// Cycle over the dataset forever, see [repeat](Self::repeat).
+    pub fn repeat_infinite(self) -> Self {
+        self.repeat(usize::MAX)
+    }
+
+    /// Keep each item of the dataset in memory after its first access, so that it is only
+    /// loaded once during the whole training.
+    pub fn cache(mut self) -> Self {
+        self.cache = true;
+        self
+    }
+
+    /// Move each batch to the given device in the dataloader workers, so that the transfer
+    /// overlaps with the computation done on the previous batches.
+    pub fn device<B: Backend>(mut self, device: B::Device) -> Self
+    where
+        O: BatchToDevice<B>,
+    {
+        self.batcher = Arc::new(DeviceBatcher::<B, I, O>::new(self.batcher, device));
+        self
+    }
+
+    /// Split each batch in one sub-batch per device, each moved to its device in the dataloader
+    /// workers, so that multi-device training receives batches that are already distributed.
+    ///
+    /// The [batch size](Self::batch_size) is the number of items of all the sub-batches.
+    pub fn per_device<B: Backend>(self, devices: Vec<B::Device>) -> DataLoaderBuilder<I, Vec<O>>
+    where
+        O: BatchToDevice<B>,
+    {
+        DataLoaderBuilder {
+            strategy: self.strategy,
+            batcher: Arc::new(PerDeviceBatcher::<B, I, O>::new(self.batcher, devices)),
+            num_threads: self.num_threads,
+            shuffle: self.shuffle,
+            repeat: self.repeat,
+            cache: self.cache,
+            ordered: self.ordered,
+            progress: self.progress,
+        }
+    }
+
     pub fn num_workers(mut self, num_workers: usize) -> Self {
         self.num_threads = Some(num_workers);
         self
     }
 
+    /// Yield the batches in the order of the dataset even when loading with multiple
+    /// [workers](Self::num_workers).
+    pub fn ordered(mut self) -> Self {
+        self.ordered = true;
+        self
+    }
+
+    /// Call the given callback each time a batch is prepared by the
+    /// [workers](Self::num_workers), with the number of items loaded by all the workers.
+    ///
+    /// This reports the progress of the data pipeline, which runs ahead of the training.
+    pub fn on_progress<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(Progress) + Send + Sync + 'static,
+    {
+        self.progress = Some(Arc::new(callback));
+        self
+    }
+
     pub fn build(self, dataset: Arc<dyn Dataset<I>>) -> Arc<dyn DataLoader<O>> {
-        let dataset = match self.shuffle {
-            Some(seed) => Arc::new(ShuffledDataset::with_seed(dataset, seed)),
-            None => dataset,
+        let dataset = match self.cache {
+            true => Arc::new(CachedDataset::new(dataset)),
+            false => dataset,
+        };
+        let dataset: Arc<dyn Dataset<I>> = match (self.repeat, self.shuffle) {
+            (Some(len), Some(seed)) => Arc::new(RepeatDataset::new(dataset, len).shuffle(seed)),
+            (Some(len), None) => Arc::new(RepeatDataset::new(dataset, len)),
+            (None, Some(seed)) => Arc::new(ShuffledDataset::with_seed(dataset, seed)),
+            (None, None) => dataset,
         };
         let strategy = match self.strategy {
             Some(strategy) => strategy,
             None => Box::new(FixBatchStrategy::new(1)),
         };
         if let Some(num_threads) = self.num_threads {
-            return Arc::new(BatchDataLoader::multi_thread(
-                strategy,
-                dataset,
-                self.batcher,
-                num_threads,
-            ));
+            let mut dataloader =
+                BatchDataLoader::multi_thread(strategy, dataset, self.batcher, num_threads);
+
+            if let Some(callback) = self.progress {
+                dataloader = dataloader.on_progress(callback);
+            }
+
+            return match self.ordered {
+                true => Arc::new(dataloader.ordered()),
+                false => Arc::new(dataloader),
+            };
         }
 
         Arc::new(BatchDataLoader::new(strategy, dataset, self.batcher))
     }
 }
+
+impl<I, E, O> DataLoaderBuilder<Result<I, E>, O>
+where
+    I: Send + Sync + Clone + std::fmt::Debug + 'static,
+    E: Send + Sync + Clone + std::fmt::Debug + std::fmt::Display + 'static,
+    O: Send + Sync + Clone + std::fmt::Debug + 'static,
+{
+    /// Create a builder for a dataset whose items can fail to load, the failed items are skipped
+    /// with a warning instead of being given to the batcher.
+    ///
+    /// The batches containing failed items are smaller, use
+    /// [batch_size_refill](Self::batch_size_refill) to keep the batch size constant.
+    pub fn skip_errors(batcher: Arc<dyn Batcher<I, O>>) -> Self {
+        Self::new(Arc::new(SkipErrorsBatcher::new(batcher)))
+    }
+
+    /// Set the batch size, the failed items are replaced by the next items of the dataset so that
+    /// every batch but the last one is full.
+    pub fn batch_size_refill(mut self, batch_size: usize) -> Self {
+        self.strategy = Some(Box::new(SkipErrorsBatchStrategy::new(batch_size)));
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::dataloader::batcher::TestBatcher;
+    use crate::data::dataset::InMemDataset;
+    use crate::TestBackend;
+    use std::thread::ThreadId;
+
+    type Device = <TestBackend as Backend>::Device;
+
+    #[derive(Clone, Debug)]
+    struct TestBatch {
+        device: Option<Device>,
+        thread: Option<ThreadId>,
+    }
+
+    impl BatchToDevice<TestBackend> for TestBatch {
+        fn to_device(self, device: &Device) -> Self {
+            Self {
+                device: Some(*device),
+                thread: Some(std::thread::current().id()),
+            }
+        }
+    }
+
+    struct TestBatchBatcher;
+
+    impl Batcher<i32, TestBatch> for TestBatchBatcher {
+        fn batch(&self, _items: Vec<i32>) -> TestBatch {
+            TestBatch {
+                device: None,
+                thread: None,
+            }
+        }
+    }
+
+    #[test]
+    fn test_batches_should_arrive_on_the_requested_device() {
+        let device = Device::default();
+        let dataset = Arc::new(InMemDataset::new((0..10).collect::<Vec<i32>>()));
+        let dataloader = DataLoaderBuilder::new(Arc::new(TestBatchBatcher))
+            .batch_size(5)
+            .num_workers(2)
+            .device::<TestBackend>(device)
+            .build(dataset);
+
+        let batches: Vec<TestBatch> = dataloader.iter().collect();
+
+        assert_eq!(batches.len(), 2);
+        for batch in batches {
+            let device_batch = batch.device.expect("Batch should be moved to a device");
+            assert_eq!(format!("{device_batch:?}"), format!("{device:?}"));
+            assert_ne!(batch.thread, Some(std::thread::current().id()));
+        }
+    }
+
+    impl BatchToDevice<TestBackend> for Vec<i32> {
+        fn to_device(self, _device: &Device) -> Self {
+            self
+        }
+    }
+
+    #[test]
+    fn test_per_device_should_split_each_batch_between_the_devices() {
+        let dataset = Arc::new(InMemDataset::new((0..16).collect::<Vec<i32>>()));
+        let dataloader = DataLoaderBuilder::new(Arc::new(TestBatcher::new()))
+            .batch_size(8)
+            .per_device::<TestBackend>(vec![Device::default(), Device::default()])
+            .build(dataset);
+
+        let batches: Vec<Vec<Vec<i32>>> = dataloader.iter().collect();
+
+        assert_eq!(
+            batches,
+            vec![
+                vec![vec![0, 1, 2, 3], vec![4, 5, 6, 7]],
+                vec![vec![8, 9, 10, 11], vec![12, 13, 14, 15]],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_repeat_should_cycle_over_the_dataset() {
+        let dataset = Arc::new(InMemDataset::new(vec![0, 1, 2]));
+        let dataloader = DataLoaderBuilder::new(Arc::new(TestBatcher::new()))
+            .batch_size(4)
+            .repeat(10)
+            .build(dataset);
+
+        let batches: Vec<Vec<i32>> = dataloader.iter().collect();
+
+        assert_eq!(
+            batches,
+            vec![vec![0, 1, 2, 0], vec![1, 2, 0, 1], vec![2, 0]]
+        );
+    }
+
+    fn failing_dataset() -> Arc<InMemDataset<Result<i32, String>>> {
+        let items = (0..30)
+            .map(|item| match item % 3 {
+                2 => Err(format!("Can't decode item {item}")),
+                _ => Ok(item),
+            })
+            .collect();
+
+        Arc::new(InMemDataset::new(items))
+    }
+
+    #[test]
+    fn test_skip_errors_should_refill_batches() {
+        let dataloader = DataLoaderBuilder::skip_errors(Arc::new(TestBatcher::new()))
+            .batch_size_refill(4)
+            .build(failing_dataset());
+
+        let batches: Vec<Vec<i32>> = dataloader.iter().collect();
+
+        assert_eq!(batches.len(), 5);
+        for batch in batches.iter() {
+            assert_eq!(batch.len(), 4);
+            assert!(batch.iter().all(|item| item % 3 != 2));
+        }
+    }
+
+    #[test]
+    fn test_skip_errors_without_refill_should_shrink_batches() {
+        let dataloader = DataLoaderBuilder::skip_errors(Arc::new(TestBatcher::new()))
+            .batch_size(3)
+            .num_workers(2)
+            .build(failing_dataset());
+
+        let batches: Vec<Vec<i32>> = dataloader.iter().collect();
+
+        assert_eq!(batches.len(), 10);
+        assert!(batches.iter().all(|batch| batch.len() == 2));
+    }
+
+    #[test]
+    fn test_progress_callback_should_be_called_for_each_batch() {
+        let dataset = Arc::new(InMemDataset::new((0..12).collect::<Vec<i32>>()));
+        let progresses = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let progresses_cloned = progresses.clone();
+        let dataloader = DataLoaderBuilder::new(Arc::new(TestBatchBatcher))
+            .batch_size(3)
+            .num_workers(2)
+            .on_progress(move |progress| progresses_cloned.lock().unwrap().push(progress))
+            .build(dataset);
+
+        let num_batches = dataloader.iter().count();
+
+        let progresses = progresses.lock().unwrap();
+        let last = progresses.last().unwrap();
+        assert_eq!(num_batches, 4);
+        assert_eq!(progresses.len(), 4);
+        assert_eq!(last.items_processed, 12);
+        assert_eq!(last.items_total, 12);
+    }
+}
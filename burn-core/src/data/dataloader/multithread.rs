@@ -1,30 +1,62 @@
 use super::{DataLoader, DataLoaderIterator, Progress};
 use std::collections::HashMap;
-use std::sync::{mpsc, Arc};
+use std::sync::{mpsc, Arc, Mutex};
 use std::thread;
 
 static MAX_QUEUED_ITEMS: usize = 100;
 
+/// Callback receiving the loading progress of the dataset each time a batch is prepared.
+pub type ProgressCallback = Arc<dyn Fn(Progress) + Send + Sync>;
+
 pub struct MultiThreadDataLoader<O> {
     dataloaders: Vec<Arc<dyn DataLoader<O> + Send + Sync>>,
+    ordered: bool,
+    progress: Option<ProgressCallback>,
 }
 
 #[derive(Debug)]
 pub enum Message<O> {
     Batch(usize, O, Progress),
-    Done,
+    Done(usize),
 }
 
+/// The batches are received from a channel shared by the workers, or from a channel per worker
+/// when they are [ordered](MultiThreadDataLoader::ordered). The channels are then read worker after
+/// worker, which is the order of the dataset since each worker loads a contiguous partition.
 struct MultiThreadsDataloaderIterator<O> {
     num_done: usize,
     workers: Vec<thread::JoinHandle<()>>,
-    receiver: mpsc::Receiver<Message<O>>,
+    receivers: Vec<mpsc::Receiver<Message<O>>>,
+    current: usize,
     progresses: HashMap<usize, Progress>,
 }
 
 impl<O> MultiThreadDataLoader<O> {
     pub fn new(dataloaders: Vec<Arc<dyn DataLoader<O> + Send + Sync>>) -> Self {
-        Self { dataloaders }
+        Self {
+            dataloaders,
+            ordered: false,
+            progress: None,
+        }
+    }
+
+    /// Yield the batches in the order of the dataset instead of as soon as they are loaded.
+    ///
+    /// The batches loaded ahead by the other workers are kept in memory until their turn comes,
+    /// up to a bounded number per worker after which the worker waits.
+    pub fn ordered(mut self) -> Self {
+        self.ordered = true;
+        self
+    }
+
+    /// Call the given callback from the workers each time a batch is prepared, with the progress
+    /// of all the workers.
+    ///
+    /// Batches are prepared ahead of the iteration, so this reports the progress of the data
+    /// pipeline rather than the number of batches consumed.
+    pub fn on_progress(mut self, callback: ProgressCallback) -> Self {
+        self.progress = Some(callback);
+        self
     }
 }
 
@@ -33,7 +65,20 @@ where
     O: Send + 'static + std::fmt::Debug,
 {
     fn iter<'a>(&'a self) -> Box<dyn DataLoaderIterator<O> + 'a> {
-        let (sender, receiver) = mpsc::sync_channel::<Message<O>>(MAX_QUEUED_ITEMS);
+        let num_channels = match self.ordered {
+            true => self.dataloaders.len(),
+            false => 1,
+        };
+        let (senders, receivers): (Vec<_>, Vec<_>) = (0..num_channels)
+            .map(|_| mpsc::sync_channel::<Message<O>>(MAX_QUEUED_ITEMS))
+            .unzip();
+        let progresses = Arc::new(Mutex::new(vec![
+            Progress {
+                items_processed: 0,
+                items_total: 0,
+            };
+            self.dataloaders.len()
+        ]));
 
         let handlers: Vec<_> = self
             .dataloaders
@@ -42,49 +87,68 @@ where
             .enumerate()
             .map(|(index, dataloader)| {
                 let dataloader_cloned = dataloader;
-                let sender_cloned = sender.clone();
+                let sender_cloned = senders[index % num_channels].clone();
+                let progress_callback = self.progress.clone();
+                let progresses = progresses.clone();
 
                 thread::spawn(move || {
                     let mut iterator = dataloader_cloned.iter();
                     while let Some(item) = iterator.next() {
                         let progress = iterator.progress();
+
+                        if let Some(callback) = &progress_callback {
+                            let mut progresses = progresses.lock().unwrap();
+                            progresses[index] = progress.clone();
+                            callback(sum_progresses(&progresses));
+                        }
+
                         sender_cloned
                             .send(Message::Batch(index, item, progress))
                             .unwrap();
                     }
-                    sender_cloned.send(Message::Done).unwrap();
+                    sender_cloned.send(Message::Done(index)).unwrap();
                 })
             })
             .collect();
 
-        Box::new(MultiThreadsDataloaderIterator::new(receiver, handlers))
+        Box::new(MultiThreadsDataloaderIterator::new(receivers, handlers))
+    }
+}
+
+fn sum_progresses(progresses: &[Progress]) -> Progress {
+    let mut items_total = 0;
+    let mut items_processed = 0;
+
+    for progress in progresses {
+        items_total += progress.items_total;
+        items_processed += progress.items_processed;
+    }
+
+    Progress {
+        items_processed,
+        items_total,
     }
 }
 
 impl<O> MultiThreadsDataloaderIterator<O> {
-    pub fn new(receiver: mpsc::Receiver<Message<O>>, workers: Vec<thread::JoinHandle<()>>) -> Self {
+    pub fn new(
+        receivers: Vec<mpsc::Receiver<Message<O>>>,
+        workers: Vec<thread::JoinHandle<()>>,
+    ) -> Self {
         MultiThreadsDataloaderIterator {
             num_done: 0,
             workers,
-            receiver,
+            receivers,
+            current: 0,
             progresses: HashMap::new(),
         }
     }
 }
 impl<O: std::fmt::Debug> DataLoaderIterator<O> for MultiThreadsDataloaderIterator<O> {
     fn progress(&self) -> Progress {
-        let mut items_total = 0;
-        let mut items_processed = 0;
-
-        for progress in self.progresses.values() {
-            items_total += progress.items_total;
-            items_processed += progress.items_processed;
-        }
+        let progresses: Vec<_> = self.progresses.values().cloned().collect();
 
-        Progress {
-            items_processed,
-            items_total,
-        }
+        sum_progresses(&progresses)
     }
 }
 
@@ -97,7 +161,14 @@ impl<O: std::fmt::Debug> Iterator for MultiThreadsDataloaderIterator<O> {
         }
 
         loop {
-            let item = self.receiver.recv();
+            if self.num_done == self.workers.len() {
+                while let Some(worker) = self.workers.pop() {
+                    worker.join().unwrap();
+                }
+                return None;
+            }
+
+            let item = self.receivers[self.current].recv();
             let item = item.unwrap();
 
             match item {
@@ -105,17 +176,102 @@ impl<O: std::fmt::Debug> Iterator for MultiThreadsDataloaderIterator<O> {
                     self.progresses.insert(index, progress);
                     return Some(item);
                 }
-                Message::Done => {
+                Message::Done(_) => {
                     self.num_done += 1;
+
+                    // The channel of the next worker is read once the current one is done.
+                    if self.receivers.len() > 1 {
+                        self.current += 1;
+                    }
                 }
             };
+        }
+    }
+}
 
-            if self.num_done == self.workers.len() {
-                while let Some(worker) = self.workers.pop() {
-                    worker.join().unwrap();
-                }
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    /// Yield the given number of items once the gate is open, counting the loaded items.
+    struct GatedDataLoader {
+        num_items: usize,
+        loaded: Arc<AtomicUsize>,
+        gate: Arc<AtomicBool>,
+    }
+
+    struct GatedIterator<'a> {
+        dataloader: &'a GatedDataLoader,
+        remaining: usize,
+    }
+
+    impl DataLoader<usize> for GatedDataLoader {
+        fn iter<'a>(&'a self) -> Box<dyn DataLoaderIterator<usize> + 'a> {
+            Box::new(GatedIterator {
+                dataloader: self,
+                remaining: self.num_items,
+            })
+        }
+    }
+
+    impl<'a> Iterator for GatedIterator<'a> {
+        type Item = usize;
+
+        fn next(&mut self) -> Option<usize> {
+            while !self.dataloader.gate.load(Ordering::Relaxed) {
+                thread::sleep(Duration::from_millis(1));
+            }
+            if self.remaining == 0 {
                 return None;
             }
+
+            self.remaining -= 1;
+            self.dataloader.loaded.fetch_add(1, Ordering::Relaxed);
+            Some(self.remaining)
         }
     }
+
+    impl<'a> DataLoaderIterator<usize> for GatedIterator<'a> {
+        fn progress(&self) -> Progress {
+            Progress {
+                items_processed: self.dataloader.num_items - self.remaining,
+                items_total: self.dataloader.num_items,
+            }
+        }
+    }
+
+    #[test]
+    fn test_ordered_workers_should_wait_for_their_turn() {
+        let gate = Arc::new(AtomicBool::new(false));
+        let loaded = Arc::new(AtomicUsize::new(0));
+        let num_items = 3 * MAX_QUEUED_ITEMS;
+        let first = GatedDataLoader {
+            num_items,
+            loaded: Arc::new(AtomicUsize::new(0)),
+            gate: gate.clone(),
+        };
+        let second = GatedDataLoader {
+            num_items,
+            loaded: loaded.clone(),
+            gate: Arc::new(AtomicBool::new(true)),
+        };
+        let dataloader =
+            MultiThreadDataLoader::new(vec![Arc::new(first), Arc::new(second)]).ordered();
+
+        // The first worker is released while the iterator waits for its first batch.
+        let opener = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(100));
+            let loaded = loaded.load(Ordering::Relaxed);
+            gate.store(true, Ordering::Relaxed);
+            loaded
+        });
+        let items: Vec<usize> = dataloader.iter().collect();
+
+        // The second worker is blocked once its channel is full, plus the item being sent.
+        assert!(opener.join().unwrap() <= MAX_QUEUED_ITEMS + 1);
+        let expected: Vec<usize> = (0..num_items).rev().chain((0..num_items).rev()).collect();
+        assert_eq!(items, expected);
+    }
 }
@@ -42,3 +42,51 @@ impl<I: Send + Sync + 'static> BatchStrategy<I> for FixBatchStrategy<I> {
         Box::new(Self::new(self.batch_size))
     }
 }
+
+/// Batch strategy for datasets whose items can fail to load, where the failed items are skipped
+/// with a warning and replaced by the next ones, so that every batch but the last one is full.
+pub struct SkipErrorsBatchStrategy<I, E> {
+    items: Vec<Result<I, E>>,
+    batch_size: usize,
+}
+
+impl<I, E> SkipErrorsBatchStrategy<I, E> {
+    pub fn new(batch_size: usize) -> Self {
+        SkipErrorsBatchStrategy {
+            items: Vec::with_capacity(batch_size),
+            batch_size,
+        }
+    }
+}
+
+impl<I, E> BatchStrategy<Result<I, E>> for SkipErrorsBatchStrategy<I, E>
+where
+    I: Send + Sync + 'static,
+    E: core::fmt::Display + Send + Sync + 'static,
+{
+    fn add(&mut self, item: Result<I, E>) {
+        match item {
+            Ok(item) => self.items.push(Ok(item)),
+            Err(err) => log::warn!("Skipping an item that failed to load: {err}"),
+        }
+    }
+
+    fn batch(&mut self, force: bool) -> Option<Vec<Result<I, E>>> {
+        if self.items.len() < self.batch_size && !force {
+            return None;
+        }
+
+        let mut items = Vec::with_capacity(self.batch_size);
+        std::mem::swap(&mut items, &mut self.items);
+
+        if items.is_empty() {
+            return None;
+        }
+
+        Some(items)
+    }
+
+    fn new_like(&self) -> Box<dyn BatchStrategy<Result<I, E>>> {
+        Box::new(Self::new(self.batch_size))
+    }
+}
@@ -11,6 +11,7 @@ pub mod data;
 #[cfg(feature = "std")]
 pub mod optim;
 
+pub mod lr_scheduler;
 pub mod module;
 pub mod nn;
 pub mod tensor;
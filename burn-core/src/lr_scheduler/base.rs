@@ -0,0 +1,28 @@
+/// Type used to represent the learning rate.
+pub type LearningRate = f64;
+
+/// Learning rate scheduler defines how the learning rate will evolve during training.
+pub trait LrScheduler: Send + Sync {
+    /// Perform the scheduler step, returning the learning rate to use for the next optimizer
+    /// update.
+    fn step(&mut self) -> LearningRate;
+
+    /// Move the scheduler to the state reached after the given number of steps, used when
+    /// resuming a training from a checkpoint.
+    ///
+    /// By default the steps are replayed, schedulers tracking their step should set it directly.
+    fn resume(&mut self, num_steps: usize) {
+        for _ in 0..num_steps {
+            self.step();
+        }
+    }
+}
+
+/// A constant learning rate is the simplest scheduler.
+impl LrScheduler for LearningRate {
+    fn step(&mut self) -> LearningRate {
+        *self
+    }
+
+    fn resume(&mut self, _num_steps: usize) {}
+}
@@ -0,0 +1,171 @@
+use crate as burn;
+
+use super::{LearningRate, LrScheduler};
+use crate::config::Config;
+
+use core::f64::consts::PI;
+use libm::cos;
+
+/// Configuration to create a [cosine annealing with warm restarts](CosineWithRestarts) scheduler.
+#[derive(Config)]
+pub struct CosineWithRestartsConfig {
+    /// The learning rate at the start of each cycle.
+    pub base_lr: LearningRate,
+    /// The number of iterations in the first cycle.
+    pub t_0: usize,
+    /// Factor applied to the number of iterations after each cycle.
+    #[config(default = 1)]
+    pub t_mult: usize,
+    /// The learning rate reached at the end of each cycle.
+    #[config(default = 0.0)]
+    pub min_lr: LearningRate,
+}
+
+/// Cosine annealing with warm restarts as described in the paper
+/// [SGDR: Stochastic Gradient Descent with Warm Restarts](https://arxiv.org/abs/1608.03983).
+///
+/// The learning rate follows a cosine curve from `base_lr` down to `min_lr` during a cycle, then
+/// jumps back to `base_lr`. The first cycle lasts `t_0` iterations and each following cycle is
+/// `t_mult` times longer than the previous one.
+#[derive(Clone, Debug)]
+pub struct CosineWithRestarts {
+    base_lr: LearningRate,
+    min_lr: LearningRate,
+    t_0: usize,
+    t_mult: usize,
+    iteration: usize,
+    cycle_start: usize,
+    cycle_length: usize,
+}
+
+impl CosineWithRestarts {
+    /// Create the scheduler from the given configuration.
+    pub fn new(config: &CosineWithRestartsConfig) -> Self {
+        if config.t_0 == 0 || config.t_mult == 0 {
+            panic!("The cycle length and multiplier of the cosine scheduler must be positive");
+        }
+
+        Self {
+            base_lr: config.base_lr,
+            min_lr: config.min_lr,
+            t_0: config.t_0,
+            t_mult: config.t_mult,
+            iteration: 0,
+            cycle_start: 0,
+            cycle_length: config.t_0,
+        }
+    }
+
+    /// The learning rate at the given iteration.
+    pub fn learning_rate(&self, iteration: usize) -> LearningRate {
+        let (start, length) = self.cycle(iteration);
+
+        self.learning_rate_in_cycle(iteration - start, length)
+    }
+
+    fn learning_rate_in_cycle(&self, position: usize, length: usize) -> LearningRate {
+        let progress = position as f64 / length as f64;
+
+        self.min_lr + (self.base_lr - self.min_lr) * (1.0 + cos(PI * progress)) / 2.0
+    }
+
+    /// The start and the length of the cycle of the given iteration.
+    ///
+    /// Cycles of constant length are found directly, the others grow geometrically so there are
+    /// at most `log(iteration)` of them before the iteration. The length saturates instead of
+    /// overflowing, the last cycle then lasts until the end of the training.
+    fn cycle(&self, iteration: usize) -> (usize, usize) {
+        if self.t_mult == 1 {
+            return (iteration - iteration % self.t_0, self.t_0);
+        }
+
+        let mut start = 0;
+        let mut length = self.t_0;
+
+        while iteration - start >= length {
+            start += length;
+            length = length.saturating_mul(self.t_mult);
+        }
+
+        (start, length)
+    }
+}
+
+impl LrScheduler for CosineWithRestarts {
+    fn step(&mut self) -> LearningRate {
+        if self.iteration - self.cycle_start >= self.cycle_length {
+            self.cycle_start += self.cycle_length;
+            self.cycle_length = self.cycle_length.saturating_mul(self.t_mult);
+        }
+
+        let learning_rate =
+            self.learning_rate_in_cycle(self.iteration - self.cycle_start, self.cycle_length);
+        self.iteration += 1;
+
+        learning_rate
+    }
+
+    fn resume(&mut self, num_steps: usize) {
+        self.iteration = num_steps;
+        (self.cycle_start, self.cycle_length) = self.cycle(num_steps);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_restarts_with_cycle_multiplier() {
+        let config = CosineWithRestartsConfig::new(1.0, 2)
+            .with_t_mult(2)
+            .with_min_lr(0.1);
+        let mut scheduler = CosineWithRestarts::new(&config);
+
+        let lrs: Vec<LearningRate> = (0..15).map(|_| scheduler.step()).collect();
+
+        // Cycles of length 2, 4 and 8 start at iterations 0, 2 and 6.
+        for restart in [0, 2, 6, 14] {
+            assert!((lrs[restart] - 1.0).abs() < 1e-9, "{lrs:?}");
+        }
+        assert!((lrs[1] - 0.55).abs() < 1e-9, "{lrs:?}");
+        assert!((lrs[4] - 0.55).abs() < 1e-9, "{lrs:?}");
+        assert!((lrs[10] - 0.55).abs() < 1e-9, "{lrs:?}");
+        for (before, after) in lrs[6..13].iter().zip(lrs[7..14].iter()) {
+            assert!(after < before, "{lrs:?}");
+        }
+    }
+
+    #[test]
+    fn test_constant_cycle_length() {
+        let config = CosineWithRestartsConfig::new(0.5, 3);
+        let scheduler = CosineWithRestarts::new(&config);
+
+        for restart in [0, 3, 6, 9] {
+            assert!((scheduler.learning_rate(restart) - 0.5).abs() < 1e-9);
+        }
+        assert!((scheduler.learning_rate(2) - 0.125).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_step_should_match_learning_rate_after_resume() {
+        let config = CosineWithRestartsConfig::new(1.0, 3).with_t_mult(2);
+        let mut scheduler = CosineWithRestarts::new(&config);
+        scheduler.resume(20);
+
+        for iteration in 20..50 {
+            assert_eq!(scheduler.step(), scheduler.learning_rate(iteration));
+        }
+    }
+
+    #[test]
+    fn test_cycle_length_should_saturate() {
+        let config = CosineWithRestartsConfig::new(1.0, usize::MAX / 2).with_t_mult(4);
+        let scheduler = CosineWithRestarts::new(&config);
+
+        assert_eq!(
+            scheduler.cycle(usize::MAX - 1),
+            (usize::MAX / 2, usize::MAX)
+        );
+    }
+}
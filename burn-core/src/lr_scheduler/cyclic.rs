@@ -0,0 +1,109 @@
+use crate as burn;
+
+use super::{LearningRate, LrScheduler};
+use crate::config::Config;
+
+use libm::{fabs, floor, pow};
+
+/// Configuration to create a [cyclic learning rate scheduler](CyclicLrScheduler).
+#[derive(Config)]
+pub struct CyclicLrSchedulerConfig {
+    /// The lower bound of the learning rate, reached at the start of each cycle.
+    pub base_lr: LearningRate,
+    /// The upper bound of the learning rate, reached in the middle of each cycle.
+    pub max_lr: LearningRate,
+    /// The number of iterations in half a cycle.
+    pub step_size: usize,
+    /// Factor applied to the amplitude after each cycle, `1.0` keeps a constant amplitude while
+    /// `0.5` halves it every cycle.
+    #[config(default = 1.0)]
+    pub amplitude_decay: f64,
+}
+
+/// Triangular cyclical learning rate policy as described in the paper
+/// [Cyclical Learning Rates for Training Neural Networks](https://arxiv.org/abs/1506.01186).
+///
+/// The learning rate increases linearly from `base_lr` to `max_lr` during `step_size`
+/// iterations, then decreases back to `base_lr` during the same number of iterations.
+#[derive(Clone, Debug)]
+pub struct CyclicLrScheduler {
+    base_lr: LearningRate,
+    max_lr: LearningRate,
+    step_size: usize,
+    amplitude_decay: f64,
+    iteration: usize,
+}
+
+impl CyclicLrScheduler {
+    /// Create the scheduler from the given configuration.
+    pub fn new(config: &CyclicLrSchedulerConfig) -> Self {
+        if config.step_size == 0 {
+            panic!("The step size of the cyclic learning rate scheduler must be positive");
+        }
+
+        Self {
+            base_lr: config.base_lr,
+            max_lr: config.max_lr,
+            step_size: config.step_size,
+            amplitude_decay: config.amplitude_decay,
+            iteration: 0,
+        }
+    }
+
+    /// The learning rate at the given iteration.
+    pub fn learning_rate(&self, iteration: usize) -> LearningRate {
+        let step_size = self.step_size as f64;
+        let iteration = iteration as f64;
+
+        let cycle = floor(iteration / (2.0 * step_size));
+        let position = fabs(iteration / step_size - 2.0 * cycle - 1.0);
+        let amplitude = (self.max_lr - self.base_lr) * pow(self.amplitude_decay, cycle);
+
+        self.base_lr + amplitude * (1.0 - position)
+    }
+}
+
+impl LrScheduler for CyclicLrScheduler {
+    fn step(&mut self) -> LearningRate {
+        let learning_rate = self.learning_rate(self.iteration);
+        self.iteration += 1;
+
+        learning_rate
+    }
+
+    fn resume(&mut self, num_steps: usize) {
+        self.iteration = num_steps;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_peaks_and_troughs() {
+        let config = CyclicLrSchedulerConfig::new(0.1, 1.0, 4);
+        let mut scheduler = CyclicLrScheduler::new(&config);
+
+        let lrs: Vec<LearningRate> = (0..17).map(|_| scheduler.step()).collect();
+
+        for trough in [0, 8, 16] {
+            assert!((lrs[trough] - 0.1).abs() < 1e-9, "{lrs:?}");
+        }
+        for peak in [4, 12] {
+            assert!((lrs[peak] - 1.0).abs() < 1e-9, "{lrs:?}");
+        }
+        assert!((lrs[2] - 0.55).abs() < 1e-9, "{lrs:?}");
+        assert!((lrs[6] - 0.55).abs() < 1e-9, "{lrs:?}");
+    }
+
+    #[test]
+    fn test_amplitude_decay() {
+        let config = CyclicLrSchedulerConfig::new(0.0, 1.0, 2).with_amplitude_decay(0.5);
+        let scheduler = CyclicLrScheduler::new(&config);
+
+        assert!((scheduler.learning_rate(2) - 1.0).abs() < 1e-9);
+        assert!((scheduler.learning_rate(6) - 0.5).abs() < 1e-9);
+        assert!((scheduler.learning_rate(10) - 0.25).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,9 @@
+mod base;
+mod cosine;
+mod cyclic;
+mod noam;
+
+pub use base::*;
+pub use cosine::*;
+pub use cyclic::*;
+pub use noam::*;
@@ -0,0 +1,116 @@
+use crate as burn;
+
+use super::{LearningRate, LrScheduler};
+use crate::config::Config;
+
+use libm::{pow, sqrt};
+
+/// Configuration to create a [noam](NoamScheduler) learning rate scheduler.
+#[derive(Config)]
+pub struct NoamSchedulerConfig {
+    /// The size of the model embeddings.
+    pub d_model: usize,
+    /// The number of steps during which the learning rate increases linearly.
+    pub warmup_steps: usize,
+    /// Factor applied to the learning rate.
+    #[config(default = 1.0)]
+    pub factor: f64,
+}
+
+/// Learning rate schedule used to train the transformer in the paper
+/// [Attention Is All You Need](https://arxiv.org/abs/1706.03762).
+///
+/// `lr = factor * d_model^-0.5 * min(step^-0.5, step * warmup_steps^-1.5)`
+///
+/// Steps are counted from 1, the learning rate increases linearly during the warmup and then
+/// decreases proportionally to the inverse square root of the step.
+#[derive(Clone, Debug)]
+pub struct NoamScheduler {
+    d_model: usize,
+    warmup_steps: usize,
+    factor: f64,
+    step: usize,
+}
+
+impl NoamScheduler {
+    /// Create the scheduler from the given configuration.
+    pub fn new(config: &NoamSchedulerConfig) -> Self {
+        if config.d_model == 0 || config.warmup_steps == 0 {
+            panic!("The model size and warmup steps of the noam scheduler must be positive");
+        }
+
+        Self {
+            d_model: config.d_model,
+            warmup_steps: config.warmup_steps,
+            factor: config.factor,
+            step: 0,
+        }
+    }
+
+    /// The learning rate at the given step, starting from 1.
+    pub fn learning_rate(&self, step: usize) -> LearningRate {
+        let step = usize::max(step, 1) as f64;
+        let decay = 1.0 / sqrt(step);
+        let warmup = step * pow(self.warmup_steps as f64, -1.5);
+
+        self.factor / sqrt(self.d_model as f64) * f64::min(decay, warmup)
+    }
+}
+
+impl LrScheduler for NoamScheduler {
+    fn step(&mut self) -> LearningRate {
+        self.step += 1;
+
+        self.learning_rate(self.step)
+    }
+
+    fn resume(&mut self, num_steps: usize) {
+        self.step = num_steps;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resume_should_continue_the_schedule() {
+        let config = NoamSchedulerConfig::new(64, 10);
+        let mut scheduler = NoamScheduler::new(&config);
+        let mut resumed = NoamScheduler::new(&config);
+
+        (0..25).for_each(|_| {
+            scheduler.step();
+        });
+        resumed.resume(25);
+
+        assert_eq!(resumed.step(), scheduler.step());
+    }
+
+    #[test]
+    fn test_matches_formula() {
+        let config = NoamSchedulerConfig::new(512, 4000).with_factor(2.0);
+        let mut scheduler = NoamScheduler::new(&config);
+        let formula = |step: f64| {
+            2.0 * 512f64.powf(-0.5) * f64::min(step.powf(-0.5), step * 4000f64.powf(-1.5))
+        };
+
+        let lrs: Vec<LearningRate> = (0..10_000).map(|_| scheduler.step()).collect();
+
+        for step in [1, 2, 100, 3999, 4000, 4001, 10_000] {
+            let expected = formula(step as f64);
+            let lr = lrs[step - 1];
+            assert!((lr - expected).abs() < 1e-12, "{step}: {lr} != {expected}");
+        }
+    }
+
+    #[test]
+    fn test_peak_at_end_of_warmup() {
+        let scheduler = NoamScheduler::new(&NoamSchedulerConfig::new(64, 10));
+        let peak = scheduler.learning_rate(10);
+
+        assert!(scheduler.learning_rate(9) < peak);
+        assert!(scheduler.learning_rate(11) < peak);
+        assert!((peak - 1.0 / (8.0 * 10f64.sqrt())).abs() < 1e-12);
+    }
+}
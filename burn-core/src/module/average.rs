@@ -0,0 +1,148 @@
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+
+use super::{State, StateError, StateNamed};
+use crate::tensor::{DataSerialize, Element, ElementConversion};
+
+impl<E: Element> State<E> {
+    /// Average several states of the same module element-wise, like the checkpoints saved during
+    /// the last epochs of a training, returning a state that can be loaded into the module.
+    ///
+    /// The tensors are matched by their path in the states and the parameter ids of the first
+    /// state are kept. An error is returned when the states don't have the same parameters or
+    /// when the shapes of the tensors differ.
+    pub fn average(states: &[State<E>]) -> Result<Self, StateError> {
+        if states.is_empty() {
+            return Err(StateError::InvalidFormat(
+                "At least one state is required to compute an average".to_string(),
+            ));
+        }
+
+        average_states(&states.iter().collect::<Vec<_>>(), "")
+    }
+}
+
+/// Load the states saved in the given files and [average](State::average) them.
+#[cfg(feature = "std")]
+pub fn average_checkpoints<E, P>(paths: &[P]) -> Result<State<E>, StateError>
+where
+    E: Element + serde::de::DeserializeOwned + serde::Serialize,
+    P: AsRef<std::path::Path>,
+{
+    let states = paths
+        .iter()
+        .map(|path| State::load(&path.as_ref().to_string_lossy()))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    State::average(&states)
+}
+
+fn average_states<E: Element>(states: &[&State<E>], path: &str) -> Result<State<E>, StateError> {
+    let mismatch = || StateError::InvalidFormat(format!("Mismatched states at `{path}`"));
+
+    match states[0] {
+        State::StateNamed(named) => {
+            let mut averaged = StateNamed::new();
+
+            for state in states.iter().skip(1) {
+                match state {
+                    State::StateNamed(other) if other.values.len() == named.values.len() => {}
+                    _ => return Err(mismatch()),
+                }
+            }
+
+            for (name, value) in named.values.iter() {
+                let mut values = vec![value];
+                for state in states.iter().skip(1) {
+                    values.push(state.get(name).ok_or_else(mismatch)?);
+                }
+
+                let path: String = match path {
+                    "" => name.clone(),
+                    _ => format!("{path}.{name}"),
+                };
+                averaged.register_state(name, average_states(&values, &path)?);
+            }
+
+            Ok(State::StateNamed(averaged))
+        }
+        State::Data(data) => {
+            let mut sums: Vec<f64> = data.value.iter().map(|value| value.elem()).collect();
+
+            for state in states.iter().skip(1) {
+                match state {
+                    State::Data(other) if other.shape == data.shape => {
+                        for (sum, value) in sums.iter_mut().zip(other.value.iter()) {
+                            *sum += value.elem::<f64>();
+                        }
+                    }
+                    _ => return Err(mismatch()),
+                }
+            }
+
+            let num_states = states.len() as f64;
+            let value = sums
+                .into_iter()
+                .map(|sum| (sum / num_states).elem())
+                .collect();
+
+            Ok(State::Data(DataSerialize {
+                value,
+                shape: data.shape.clone(),
+            }))
+        }
+        State::ParamId(id) => {
+            if states
+                .iter()
+                .any(|state| !matches!(state, State::ParamId(_)))
+            {
+                return Err(mismatch());
+            }
+
+            Ok(State::ParamId(id.clone()))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::module::Module;
+    use crate::nn::{Linear, LinearConfig};
+    use crate::TestBackend;
+
+    #[test]
+    fn average_should_be_the_element_wise_mean_of_the_states() {
+        let linear_1 = Linear::<TestBackend>::new(&LinearConfig::new(3, 2));
+        let linear_2 = Linear::<TestBackend>::new(&LinearConfig::new(3, 2));
+
+        let state = State::average(&[linear_1.state(), linear_2.state()]).unwrap();
+        let averaged = linear_1.clone().load(&state).unwrap();
+
+        let expected_weight = (linear_1.weight.val() + linear_2.weight.val()).div_scalar(2.0);
+        let expected_bias =
+            (linear_1.bias.val().unwrap() + linear_2.bias.val().unwrap()).div_scalar(2.0);
+        averaged
+            .weight
+            .to_data()
+            .assert_approx_eq(&expected_weight.into_data(), 5);
+        averaged
+            .bias
+            .val()
+            .unwrap()
+            .to_data()
+            .assert_approx_eq(&expected_bias.into_data(), 5);
+    }
+
+    #[test]
+    fn average_should_fail_with_mismatched_params() {
+        let linear_1 = Linear::<TestBackend>::new(&LinearConfig::new(3, 2));
+        let linear_2 = Linear::<TestBackend>::new(&LinearConfig::new(3, 2).with_bias(false));
+
+        assert!(State::average(&[linear_1.state(), linear_2.state()]).is_err());
+    }
+}
@@ -4,6 +4,7 @@ use super::{ParamId, State};
 use crate::tensor::backend::{ADBackend, Backend};
 pub use burn_derive::Module;
 use burn_tensor::Tensor;
+use hashbrown::HashSet;
 
 /// Trait for all neural network modules.
 ///
@@ -54,19 +55,128 @@ pub trait Module: Clone + Send + Sync + core::fmt::Debug + core::fmt::Display {
     /// Detach the module from the graph.
     fn detach(self) -> Self;
     /// Get the number of parameters the module has, including all of its sub-modules.
-    fn num_params(&self) -> usize;
+    ///
+    /// Parameters shared by multiple sub-modules, having the same [id](ParamId), are counted
+    /// once.
+    fn num_params(&self) -> usize {
+        let mut counter = ParamCounter::default();
+        self.visit(&mut UniqueParamVisitor::new(&mut counter));
+        counter.num_params
+    }
     /// Visit each tensor in the module with a [visitor](ModuleVisitor).
     fn visit<V: ModuleVisitor<Self::Backend>>(&self, visitor: &mut V);
     /// Map each tensor in the module with a [mapper](ModuleMapper).
     fn map<M: ModuleMapper<Self::Backend>>(self, mapper: &mut M) -> Self;
+    /// Map each tensor parameter in the module with a closure, without implementing a
+    /// [mapper](ModuleMapper).
+    ///
+    /// The closure receives the parameter id with the flattened tensor, it must return a tensor
+    /// with the same number of elements, which is reshaped to the shape of the parameter.
+    fn map_params<F>(self, func: F) -> Self
+    where
+        F: FnMut(&ParamId, Tensor<Self::Backend, 1>) -> Tensor<Self::Backend, 1>,
+    {
+        self.map(&mut ClosureMapper { func })
+    }
 }
 
 pub trait ModuleVisitor<B: Backend> {
     fn visit<const D: usize>(&mut self, id: &ParamId, tensor: &Tensor<B, D>);
+
+    /// Called before visiting the sub-module with the given name, which is its key in the
+    /// [state](Module::state) of the parent module. Nothing is done by default.
+    fn enter_module(&mut self, _name: &str) {}
+
+    /// Called after visiting the sub-module [entered](Self::enter_module) with the given name.
+    fn exit_module(&mut self, _name: &str) {}
 }
 
 pub trait ModuleMapper<B: Backend> {
     fn map<const D: usize>(&mut self, id: &ParamId, tensor: Tensor<B, D>) -> Tensor<B, D>;
+
+    /// Map the tensor of a parameter, `trainable` being the state returned by
+    /// [trainable](Self::trainable) for that parameter. Running states, which aren't updated by
+    /// the optimizer but aren't frozen either, are always mapped as trainable.
+    ///
+    /// Modules call this method, which ignores the trainable state and calls
+    /// [map](Self::map) by default.
+    fn map_trainable<const D: usize>(
+        &mut self,
+        id: &ParamId,
+        tensor: Tensor<B, D>,
+        _trainable: bool,
+    ) -> Tensor<B, D> {
+        self.map(id, tensor)
+    }
+
+    /// If the given tensor parameter is [trainable](crate::module::Param::is_trainable) after the
+    /// mapping, the current value is kept by default.
+    fn trainable(&mut self, _id: &ParamId, trainable: bool) -> bool {
+        trainable
+    }
+}
+
+/// Wrap a [visitor](ModuleVisitor) so that it visits each parameter once, even when the
+/// parameter is shared by multiple sub-modules with the same [id](ParamId).
+///
+/// Visitors that aren't idempotent, like the ones scaling or averaging tensors keyed by id,
+/// should be wrapped when the module may share parameters.
+pub struct UniqueParamVisitor<'a, V> {
+    visitor: &'a mut V,
+    ids: HashSet<ParamId>,
+}
+
+impl<'a, V> UniqueParamVisitor<'a, V> {
+    pub fn new(visitor: &'a mut V) -> Self {
+        Self {
+            visitor,
+            ids: HashSet::new(),
+        }
+    }
+}
+
+impl<'a, B: Backend, V: ModuleVisitor<B>> ModuleVisitor<B> for UniqueParamVisitor<'a, V> {
+    fn visit<const D: usize>(&mut self, id: &ParamId, tensor: &Tensor<B, D>) {
+        if self.ids.insert(id.clone()) {
+            self.visitor.visit(id, tensor);
+        }
+    }
+
+    fn enter_module(&mut self, name: &str) {
+        self.visitor.enter_module(name);
+    }
+
+    fn exit_module(&mut self, name: &str) {
+        self.visitor.exit_module(name);
+    }
+}
+
+#[derive(Default)]
+struct ParamCounter {
+    num_params: usize,
+}
+
+impl<B: Backend> ModuleVisitor<B> for ParamCounter {
+    fn visit<const D: usize>(&mut self, _id: &ParamId, tensor: &Tensor<B, D>) {
+        self.num_params += tensor.shape().num_elements();
+    }
+}
+
+struct ClosureMapper<F> {
+    func: F,
+}
+
+impl<B, F> ModuleMapper<B> for ClosureMapper<F>
+where
+    B: Backend,
+    F: FnMut(&ParamId, Tensor<B, 1>) -> Tensor<B, 1>,
+{
+    fn map<const D: usize>(&mut self, id: &ParamId, tensor: Tensor<B, D>) -> Tensor<B, D> {
+        let shape = tensor.shape();
+        let tensor = tensor.reshape([shape.num_elements()]);
+
+        (self.func)(id, tensor).reshape(shape)
+    }
 }
 
 /// Module with auto-differentiation backend.
@@ -79,6 +189,17 @@ pub trait ADModule:
     /// Get the same module, but on the inner backend without auto-differentiation.
     fn inner(self) -> Self::InnerModule;
     fn from_inner(module: Self::InnerModule) -> Self;
+
+    /// Execute the given function with the module on the inner backend.
+    ///
+    /// No computation graph is recorded inside the function, which is useful for validation and
+    /// inference where gradients are never needed.
+    fn no_grad<F, O>(&self, func: F) -> O
+    where
+        F: FnOnce(&Self::InnerModule) -> O,
+    {
+        func(&self.clone().inner())
+    }
 }
 
 #[derive(new, Debug)]
@@ -95,3 +216,49 @@ impl core::fmt::Display for LoadingError {
 // TODO: Move from std to core after Error is core (see https://github.com/rust-lang/rust/issues/103765)
 #[cfg(feature = "std")]
 impl std::error::Error for LoadingError {}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::module::list_param_ids;
+    use crate::nn::{Linear, LinearConfig};
+    use crate::TestBackend;
+
+    struct Collector {
+        tensors: Vec<Tensor<TestBackend, 1>>,
+    }
+
+    impl ModuleVisitor<TestBackend> for Collector {
+        fn visit<const D: usize>(&mut self, _id: &ParamId, tensor: &Tensor<TestBackend, D>) {
+            let num_elements = tensor.shape().num_elements();
+            self.tensors.push(tensor.clone().reshape([num_elements]));
+        }
+    }
+
+    fn params<M: Module<Backend = TestBackend>>(module: &M) -> Vec<Tensor<TestBackend, 1>> {
+        let mut collector = Collector {
+            tensors: Vec::new(),
+        };
+        module.visit(&mut collector);
+        collector.tensors
+    }
+
+    #[test]
+    fn map_params_should_apply_the_closure_to_each_param() {
+        let linear = Linear::<TestBackend>::new(&LinearConfig::new(4, 3));
+
+        let mapped = linear
+            .clone()
+            .map_params(|_id, tensor| tensor.mul_scalar(0.5));
+
+        let (params, params_mapped) = (params(&linear), params(&mapped));
+        assert_eq!(params.len(), 2);
+        assert_eq!(params_mapped.len(), 2);
+        for (param, param_mapped) in params.into_iter().zip(params_mapped) {
+            param_mapped
+                .into_data()
+                .assert_approx_eq(&param.mul_scalar(0.5).into_data(), 5);
+        }
+        assert_eq!(list_param_ids(&mapped), list_param_ids(&linear));
+    }
+}
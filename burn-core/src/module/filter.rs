@@ -0,0 +1,137 @@
+use alloc::vec::Vec;
+
+use super::{list_param_names, Module, ModuleMapper, ModuleVisitor, ParamId};
+use crate::tensor::{backend::Backend, Tensor};
+
+/// Map only the tensor parameters whose [name](crate::module::list_param_names) matches the given
+/// filter, like `|name| name.contains("norm")`, the other parameters are unchanged.
+pub fn map_params_by_name<M, P, F>(module: M, filter: F, mapper: &mut P) -> M
+where
+    M: Module,
+    P: ModuleMapper<M::Backend>,
+    F: Fn(&str) -> bool,
+{
+    let param_ids = filter_param_ids(&module, filter);
+
+    module.map(&mut FilteredMapper {
+        param_ids: &param_ids,
+        mapper,
+    })
+}
+
+/// Visit only the tensor parameters whose [name](crate::module::list_param_names) matches the
+/// given filter.
+pub fn visit_params_by_name<M, V, F>(module: &M, filter: F, visitor: &mut V)
+where
+    M: Module,
+    V: ModuleVisitor<M::Backend>,
+    F: Fn(&str) -> bool,
+{
+    let param_ids = filter_param_ids(module, filter);
+
+    module.visit(&mut FilteredVisitor {
+        param_ids: &param_ids,
+        visitor,
+    })
+}
+
+fn filter_param_ids<M: Module, F: Fn(&str) -> bool>(module: &M, filter: F) -> Vec<ParamId> {
+    list_param_names(module)
+        .into_iter()
+        .filter(|(_, name)| filter(name))
+        .map(|(id, _)| id)
+        .collect()
+}
+
+struct FilteredMapper<'a, P> {
+    param_ids: &'a [ParamId],
+    mapper: &'a mut P,
+}
+
+struct FilteredVisitor<'a, V> {
+    param_ids: &'a [ParamId],
+    visitor: &'a mut V,
+}
+
+impl<'a, B: Backend, P: ModuleMapper<B>> ModuleMapper<B> for FilteredMapper<'a, P> {
+    fn map<const D: usize>(&mut self, id: &ParamId, tensor: Tensor<B, D>) -> Tensor<B, D> {
+        match self.param_ids.contains(id) {
+            true => self.mapper.map(id, tensor),
+            false => tensor,
+        }
+    }
+
+    fn map_trainable<const D: usize>(
+        &mut self,
+        id: &ParamId,
+        tensor: Tensor<B, D>,
+        trainable: bool,
+    ) -> Tensor<B, D> {
+        match self.param_ids.contains(id) {
+            true => self.mapper.map_trainable(id, tensor, trainable),
+            false => tensor,
+        }
+    }
+
+    fn trainable(&mut self, id: &ParamId, trainable: bool) -> bool {
+        match self.param_ids.contains(id) {
+            true => self.mapper.trainable(id, trainable),
+            false => trainable,
+        }
+    }
+}
+
+impl<'a, B: Backend, V: ModuleVisitor<B>> ModuleVisitor<B> for FilteredVisitor<'a, V> {
+    fn visit<const D: usize>(&mut self, id: &ParamId, tensor: &Tensor<B, D>) {
+        if self.param_ids.contains(id) {
+            self.visitor.visit(id, tensor);
+        }
+    }
+
+    fn enter_module(&mut self, name: &str) {
+        self.visitor.enter_module(name);
+    }
+
+    fn exit_module(&mut self, name: &str) {
+        self.visitor.exit_module(name);
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate as burn;
+    use crate::module::Param;
+    use crate::nn::attention::{MultiHeadAttention, MultiHeadAttentionConfig};
+    use crate::nn::{Linear, LinearConfig};
+    use crate::TestBackend;
+
+    #[derive(Module, Debug)]
+    struct Block<B: Backend> {
+        mha: Param<MultiHeadAttention<B>>,
+        linear: Param<Linear<B>>,
+    }
+
+    struct Scaler;
+
+    impl<B: Backend> ModuleMapper<B> for Scaler {
+        fn map<const D: usize>(&mut self, _id: &ParamId, tensor: Tensor<B, D>) -> Tensor<B, D> {
+            tensor.mul_scalar(2.0)
+        }
+    }
+
+    #[test]
+    fn map_params_by_name_should_only_map_the_matching_params() {
+        let config = MultiHeadAttentionConfig::new(8, 2);
+        let block = Block::<TestBackend> {
+            mha: Param::from(MultiHeadAttention::new(&config)),
+            linear: Param::from(Linear::new(&LinearConfig::new(8, 8))),
+        };
+
+        let mapped = map_params_by_name(block.clone(), |name| name.contains("mha"), &mut Scaler);
+
+        assert_eq!(mapped.linear.state(), block.linear.state());
+        let expected = block.mha.clone().map(&mut Scaler);
+        assert_eq!(mapped.mha.state(), expected.state());
+    }
+}
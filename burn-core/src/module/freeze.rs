@@ -0,0 +1,132 @@
+use super::{Module, ModuleMapper, ParamId};
+use crate::tensor::{backend::Backend, Tensor};
+use alloc::vec::Vec;
+
+/// Set whether the given tensor parameters of the module are
+/// [trainable](crate::module::Param::is_trainable), the other parameters are unchanged.
+///
+/// Frozen parameters are detached from the autodiff graph, so they don't receive gradients and
+/// aren't updated by the optimizer until they are made trainable again. The ids of a submodule
+/// can be collected with [list_param_ids](crate::module::list_param_ids).
+pub fn set_trainable<M: Module>(module: M, param_ids: &[ParamId], trainable: bool) -> M {
+    module.map(&mut TrainableSetter {
+        param_ids,
+        trainable,
+    })
+}
+
+struct TrainableSetter<'a> {
+    param_ids: &'a [ParamId],
+    trainable: bool,
+}
+
+impl<'a, B: Backend> ModuleMapper<B> for TrainableSetter<'a> {
+    fn map<const D: usize>(&mut self, id: &ParamId, tensor: Tensor<B, D>) -> Tensor<B, D> {
+        match self.param_ids.contains(id) {
+            true => tensor.detach(),
+            false => tensor,
+        }
+    }
+
+    fn trainable(&mut self, id: &ParamId, trainable: bool) -> bool {
+        match self.param_ids.contains(id) {
+            true => self.trainable,
+            false => trainable,
+        }
+    }
+}
+
+/// Move the [trainable](crate::module::Param::is_trainable) parameters of the module to the given
+/// device, the frozen parameters stay on their current device.
+///
+/// This keeps the large frozen parts of a model, like a pretrained backbone, out of the memory
+/// of the accelerator. Running states, like the statistics of a batch norm, are always moved.
+pub fn to_device_trainable<M: Module>(module: M, device: &<M::Backend as Backend>::Device) -> M {
+    map_trainable_to_device(module, device).0
+}
+
+/// Move the trainable parameters of the module to the given device, returning the ids of the
+/// tensors that were moved.
+fn map_trainable_to_device<M: Module>(
+    module: M,
+    device: &<M::Backend as Backend>::Device,
+) -> (M, Vec<ParamId>) {
+    let mut mapper = TrainableDeviceMapper {
+        device,
+        moved: Vec::new(),
+    };
+    let module = module.map(&mut mapper);
+
+    (module, mapper.moved)
+}
+
+struct TrainableDeviceMapper<'a, D> {
+    device: &'a D,
+    moved: Vec<ParamId>,
+}
+
+impl<'a, B: Backend> ModuleMapper<B> for TrainableDeviceMapper<'a, B::Device> {
+    fn map<const D: usize>(&mut self, id: &ParamId, tensor: Tensor<B, D>) -> Tensor<B, D> {
+        self.map_trainable(id, tensor, true)
+    }
+
+    fn map_trainable<const D: usize>(
+        &mut self,
+        id: &ParamId,
+        tensor: Tensor<B, D>,
+        trainable: bool,
+    ) -> Tensor<B, D> {
+        if !trainable {
+            return tensor;
+        }
+
+        self.moved.push(id.clone());
+        tensor.detach().to_device(self.device)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::module::list_param_ids;
+    use crate::nn::{Linear, LinearConfig};
+    use crate::optim::GradientsParams;
+    use crate::tensor::Distribution;
+    use crate::TestADBackend;
+
+    fn num_grads(linear: &Linear<TestADBackend>) -> usize {
+        let input = Tensor::random([2, 4], Distribution::Standard);
+        let loss = linear.forward(input).mean();
+
+        GradientsParams::from_grads(loss.backward(), linear).len()
+    }
+
+    #[test]
+    fn to_device_trainable_should_only_move_the_trainable_params() {
+        let linear = Linear::<TestADBackend>::new(&LinearConfig::new(4, 2));
+        let ids = list_param_ids(&linear);
+        let linear = set_trainable(linear, &ids[0..1], false);
+        let device = Default::default();
+
+        let (moved, moved_ids) = map_trainable_to_device(linear.clone(), &device);
+
+        assert_eq!(moved_ids, vec![linear.bias.id.clone()]);
+        assert_eq!(moved.bias.devices(), vec![device]);
+        assert!(!moved.weight.is_trainable());
+        assert!(moved.bias.is_trainable());
+        assert_eq!(moved.weight.to_data(), linear.weight.to_data());
+        assert_eq!(num_grads(&moved), 1);
+    }
+
+    #[test]
+    fn frozen_params_should_not_receive_gradients_until_unfrozen() {
+        let linear = Linear::<TestADBackend>::new(&LinearConfig::new(4, 2));
+        let ids = list_param_ids(&linear);
+
+        let linear = set_trainable(linear, &ids[0..1], false);
+        assert_eq!(num_grads(&linear), 1);
+
+        let linear = set_trainable(linear, &ids, true);
+        assert_eq!(num_grads(&linear), 2);
+    }
+}
@@ -1,7 +1,15 @@
+mod average;
 mod base;
+mod filter;
+mod freeze;
 mod param;
+mod prune;
 mod state;
 
+pub use average::*;
 pub use base::*;
+pub use filter::*;
+pub use freeze::*;
 pub use param::*;
+pub use prune::*;
 pub use state::*;
@@ -7,8 +7,9 @@ use crate::tensor::Element;
 /// Define a trainable parameter.
 #[derive(Debug, Clone)]
 pub struct Param<T> {
-    pub(super) id: ParamId,
-    pub(super) value: T,
+    pub(crate) id: ParamId,
+    pub(crate) value: T,
+    pub(crate) trainable: bool,
 }
 
 impl<T> core::fmt::Display for Param<T> {
@@ -17,6 +18,13 @@ impl<T> core::fmt::Display for Param<T> {
     }
 }
 
+impl<T> Param<T> {
+    /// If the parameter receives gradients and is updated by the optimizer.
+    pub fn is_trainable(&self) -> bool {
+        self.trainable
+    }
+}
+
 impl<T: Clone> Param<T> {
     pub fn val(&self) -> T {
         self.value.clone()
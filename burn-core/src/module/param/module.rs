@@ -11,6 +11,7 @@ impl<M: Module> From<M> for Param<M> {
         Param {
             id: ParamId::new(),
             value,
+            trainable: true,
         }
     }
 }
@@ -20,16 +21,13 @@ impl<M: Module> From<Vec<M>> for Param<Vec<M>> {
         Param {
             id: ParamId::new(),
             value,
+            trainable: true,
         }
     }
 }
 impl<M: Module> Module for Param<M> {
     type Backend = M::Backend;
 
-    fn num_params(&self) -> usize {
-        self.value.num_params()
-    }
-
     fn devices(&self) -> Vec<<M::Backend as Backend>::Device> {
         self.value.devices()
     }
@@ -38,6 +36,7 @@ impl<M: Module> Module for Param<M> {
         Param {
             id: self.id,
             value: self.value.to_device(device),
+            trainable: self.trainable,
         }
     }
 
@@ -53,6 +52,7 @@ impl<M: Module> Module for Param<M> {
         Ok(Self {
             id: id.clone(),
             value: self.value.load(state)?,
+            trainable: self.trainable,
         })
     }
 
@@ -60,6 +60,7 @@ impl<M: Module> Module for Param<M> {
         Param {
             id: self.id,
             value: self.value.detach(),
+            trainable: self.trainable,
         }
     }
 
@@ -71,6 +72,7 @@ impl<M: Module> Module for Param<M> {
         Self {
             id: self.id,
             value: self.value.map(mapper),
+            trainable: self.trainable,
         }
     }
 }
@@ -78,15 +80,6 @@ impl<M: Module> Module for Param<M> {
 impl<M: Module> Module for Param<Vec<M>> {
     type Backend = M::Backend;
 
-    fn num_params(&self) -> usize {
-        let mut num_params = 0;
-        for module in self.value.iter() {
-            num_params += module.num_params();
-        }
-
-        num_params
-    }
-
     fn devices(&self) -> Vec<<M::Backend as Backend>::Device> {
         let mut devices = Vec::new();
         for module in self.value.iter() {
@@ -103,6 +96,7 @@ impl<M: Module> Module for Param<Vec<M>> {
                 .into_iter()
                 .map(|val| val.to_device(device))
                 .collect(),
+            trainable: self.trainable,
         }
     }
 
@@ -137,19 +131,28 @@ impl<M: Module> Module for Param<Vec<M>> {
             modules.push(module);
         }
 
-        Ok(Self { id, value: modules })
+        Ok(Self {
+            id,
+            value: modules,
+            trainable: self.trainable,
+        })
     }
 
     fn detach(self) -> Self {
         Param {
             id: self.id,
             value: self.value.into_iter().map(|val| val.detach()).collect(),
+            trainable: self.trainable,
         }
     }
 
     fn visit<V: ModuleVisitor<Self::Backend>>(&self, visitor: &mut V) {
-        for module in self.value.iter() {
+        for (i, module) in self.value.iter().enumerate() {
+            let name = format!("mod-{i}");
+
+            visitor.enter_module(&name);
             module.visit(visitor);
+            visitor.exit_module(&name);
         }
     }
 
@@ -157,6 +160,7 @@ impl<M: Module> Module for Param<Vec<M>> {
         Self {
             id: self.id,
             value: self.value.into_iter().map(|val| val.map(mapper)).collect(),
+            trainable: self.trainable,
         }
     }
 }
@@ -167,18 +171,18 @@ impl<M: ADModule> ADModule for Param<Vec<M>> {
     type InnerModule = Param<Vec<M::InnerModule>>;
 
     fn inner(self) -> Self::InnerModule {
-        Param::from(
-            self.value
-                .into_iter()
-                .map(|v| v.inner())
-                .collect::<Vec<_>>(),
-        )
+        Param {
+            id: self.id,
+            value: self.value.into_iter().map(|v| v.inner()).collect(),
+            trainable: self.trainable,
+        }
     }
 
     fn from_inner(module: Self::InnerModule) -> Self {
         Param {
             id: module.id,
             value: module.value.into_iter().map(ADModule::from_inner).collect(),
+            trainable: module.trainable,
         }
     }
 }
@@ -189,13 +193,18 @@ impl<M: ADModule> ADModule for Param<M> {
     type InnerModule = Param<M::InnerModule>;
 
     fn inner(self) -> Self::InnerModule {
-        Param::from(self.value.inner())
+        Param {
+            id: self.id,
+            value: self.value.inner(),
+            trainable: self.trainable,
+        }
     }
 
     fn from_inner(module: Self::InnerModule) -> Self {
         Param {
             id: module.id,
             value: ADModule::from_inner(module.value),
+            trainable: module.trainable,
         }
     }
 }
@@ -51,6 +51,7 @@ impl<B: Backend, const D: usize> From<RunningState<Tensor<B, D>>>
         Param {
             id: ParamId::new(),
             value,
+            trainable: false,
         }
     }
 }
@@ -58,11 +59,6 @@ impl<B: Backend, const D: usize> From<RunningState<Tensor<B, D>>>
 impl<const D: usize, B: Backend> Module for Param<RunningState<Tensor<B, D>>> {
     type Backend = B;
 
-    fn num_params(&self) -> usize {
-        let tensor = self.value.value.read().unwrap();
-        tensor.shape().num_elements()
-    }
-
     fn devices(&self) -> Vec<B::Device> {
         let tensor = self.value.value.read().unwrap();
         vec![tensor.device()]
@@ -121,7 +117,7 @@ impl<const D: usize, B: Backend> Module for Param<RunningState<Tensor<B, D>>> {
 
     fn map<M: ModuleMapper<Self::Backend>>(self, mapper: &mut M) -> Self {
         let mut tensor = self.value.value.write().unwrap();
-        let tensor_out = mapper.map(&self.id, tensor.clone());
+        let tensor_out = mapper.map_trainable(&self.id, tensor.clone(), true);
 
         *tensor = tensor_out;
         core::mem::drop(tensor);
@@ -220,6 +216,7 @@ impl<const D: usize, B: ADBackend> ADModule for Param<RunningState<Tensor<B, D>>
         Param {
             id: self.id,
             value: RunningState::new(value.inner()),
+            trainable: self.trainable,
         }
     }
 
@@ -231,6 +228,7 @@ impl<const D: usize, B: ADBackend> ADModule for Param<RunningState<Tensor<B, D>>
         Param {
             id: module.id,
             value: RunningState::new(Tensor::from_inner(value)),
+            trainable: module.trainable,
         }
     }
 }
@@ -11,18 +11,46 @@ use crate::tensor::{
 
 impl<B: Backend, const D: usize> From<Tensor<B, D>> for Param<Tensor<B, D>> {
     fn from(value: Tensor<B, D>) -> Self {
+        Self::from_tensor(value, true)
+    }
+}
+
+impl<B: Backend, const D: usize> Param<Tensor<B, D>> {
+    /// Create a parameter from the given tensor.
+    ///
+    /// A non-trainable parameter is still part of the module [state](Module::state), but never
+    /// receives gradients, so it isn't updated by the optimizer. This is useful for buffers such
+    /// as positional tables or pretrained weights that should stay frozen.
+    pub fn from_tensor(tensor: Tensor<B, D>, trainable: bool) -> Self {
         Param {
             id: ParamId::new(),
-            value: value.require_grad(),
+            value: require_grad(tensor, trainable),
+            trainable,
         }
     }
 }
 
+fn require_grad<B: Backend, const D: usize>(tensor: Tensor<B, D>, trainable: bool) -> Tensor<B, D> {
+    match trainable {
+        true => tensor.require_grad(),
+        false => tensor,
+    }
+}
+
 impl<B: Backend, const D: usize> From<Option<Tensor<B, D>>> for Param<Option<Tensor<B, D>>> {
     fn from(value: Option<Tensor<B, D>>) -> Self {
+        Self::from_optional_tensor(value, true)
+    }
+}
+
+impl<B: Backend, const D: usize> Param<Option<Tensor<B, D>>> {
+    /// Create an optional parameter from the given tensor, see
+    /// [from_tensor](Param::<Tensor<B, D>>::from_tensor).
+    pub fn from_optional_tensor(tensor: Option<Tensor<B, D>>, trainable: bool) -> Self {
         Param {
             id: ParamId::new(),
-            value: value.map(|tensor| tensor.require_grad()),
+            value: tensor.map(|tensor| require_grad(tensor, trainable)),
+            trainable,
         }
     }
 }
@@ -30,10 +58,6 @@ impl<B: Backend, const D: usize> From<Option<Tensor<B, D>>> for Param<Option<Ten
 impl<const D: usize, B: Backend> Module for Param<Tensor<B, D>> {
     type Backend = B;
 
-    fn num_params(&self) -> usize {
-        self.value.shape().num_elements()
-    }
-
     fn devices(&self) -> Vec<B::Device> {
         vec![self.value.device()]
     }
@@ -41,7 +65,8 @@ impl<const D: usize, B: Backend> Module for Param<Tensor<B, D>> {
     fn to_device(self, device: &B::Device) -> Self {
         Self {
             id: self.id,
-            value: self.value.to_device(device).require_grad(),
+            value: require_grad(self.value.to_device(device), self.trainable),
+            trainable: self.trainable,
         }
     }
 
@@ -56,19 +81,25 @@ impl<const D: usize, B: Backend> Module for Param<Tensor<B, D>> {
         let id = id.clone();
 
         let tensor = match state {
-            State::Data(data) => {
-                Tensor::from_data_device(Data::from(data), &self.value.device()).require_grad()
-            }
+            State::Data(data) => require_grad(
+                Tensor::from_data_device(Data::from(data), &self.value.device()),
+                self.trainable,
+            ),
             _ => return Err(LoadingError::new("Can't load tensor".to_string())),
         };
 
-        Ok(Self { id, value: tensor })
+        Ok(Self {
+            id,
+            value: tensor,
+            trainable: self.trainable,
+        })
     }
 
     fn detach(self) -> Self {
         Self {
             id: self.id,
-            value: self.value.detach().require_grad(),
+            value: require_grad(self.value.detach(), self.trainable),
+            trainable: self.trainable,
         }
     }
 
@@ -77,22 +108,22 @@ impl<const D: usize, B: Backend> Module for Param<Tensor<B, D>> {
     }
 
     fn map<M: ModuleMapper<Self::Backend>>(self, mapper: &mut M) -> Self {
-        let value = mapper.map(&self.id, self.value).require_grad();
-        Self { id: self.id, value }
+        let trainable = mapper.trainable(&self.id, self.trainable);
+        let value = require_grad(
+            mapper.map_trainable(&self.id, self.value, trainable),
+            trainable,
+        );
+        Self {
+            id: self.id,
+            value,
+            trainable,
+        }
     }
 }
 
 impl<const D: usize, B: Backend> Module for Param<Option<Tensor<B, D>>> {
     type Backend = B;
 
-    fn num_params(&self) -> usize {
-        if let Some(value) = &self.value {
-            return value.shape().num_elements();
-        }
-
-        0
-    }
-
     fn devices(&self) -> Vec<B::Device> {
         if let Some(value) = &self.value {
             return vec![value.device()];
@@ -106,7 +137,8 @@ impl<const D: usize, B: Backend> Module for Param<Option<Tensor<B, D>>> {
             id: self.id,
             value: self
                 .value
-                .map(|value| value.to_device(device).require_grad()),
+                .map(|value| require_grad(value.to_device(device), self.trainable)),
+            trainable: self.trainable,
         }
     }
 
@@ -133,18 +165,28 @@ impl<const D: usize, B: Backend> Module for Param<Option<Tensor<B, D>>> {
                 }
             };
 
-            Some(Tensor::from_data_device(Data::from(data), &tensor.device()).require_grad())
+            Some(require_grad(
+                Tensor::from_data_device(Data::from(data), &tensor.device()),
+                self.trainable,
+            ))
         } else {
             None
         };
 
-        Ok(Self { id, value: tensor })
+        Ok(Self {
+            id,
+            value: tensor,
+            trainable: self.trainable,
+        })
     }
 
     fn detach(self) -> Self {
         Self {
             id: self.id,
-            value: self.value.map(|value| value.detach().require_grad()),
+            value: self
+                .value
+                .map(|value| require_grad(value.detach(), self.trainable)),
+            trainable: self.trainable,
         }
     }
 
@@ -155,10 +197,15 @@ impl<const D: usize, B: Backend> Module for Param<Option<Tensor<B, D>>> {
     }
 
     fn map<M: ModuleMapper<Self::Backend>>(self, mapper: &mut M) -> Self {
+        let trainable = mapper.trainable(&self.id, self.trainable);
         let value = self
             .value
-            .map(|value| mapper.map(&self.id, value).require_grad());
-        Self { id: self.id, value }
+            .map(|value| require_grad(mapper.map_trainable(&self.id, value, trainable), trainable));
+        Self {
+            id: self.id,
+            value,
+            trainable,
+        }
     }
 }
 
@@ -171,13 +218,15 @@ impl<const D: usize, B: ADBackend> ADModule for Param<Tensor<B, D>> {
         Param {
             id: self.id,
             value: self.value.inner(),
+            trainable: self.trainable,
         }
     }
 
     fn from_inner(module: Self::InnerModule) -> Self {
         Param {
             id: module.id,
-            value: Tensor::from_inner(module.value).require_grad(),
+            value: require_grad(Tensor::from_inner(module.value), module.trainable),
+            trainable: module.trainable,
         }
     }
 }
@@ -191,6 +240,7 @@ impl<const D: usize, B: ADBackend> ADModule for Param<Option<Tensor<B, D>>> {
         Param {
             id: self.id,
             value: self.value.map(|val| val.inner()),
+            trainable: self.trainable,
         }
     }
 
@@ -199,7 +249,8 @@ impl<const D: usize, B: ADBackend> ADModule for Param<Option<Tensor<B, D>>> {
             id: module.id,
             value: module
                 .value
-                .map(|val| Tensor::from_inner(val).require_grad()),
+                .map(|val| require_grad(Tensor::from_inner(val), module.trainable)),
+            trainable: module.trainable,
         }
     }
 }
@@ -1,4 +1,9 @@
-use alloc::vec::Vec;
+use alloc::{
+    collections::BTreeMap,
+    string::{String, ToString},
+    vec::Vec,
+};
+use hashbrown::HashMap;
 
 use super::ParamId;
 use crate::module::{Module, ModuleVisitor};
@@ -23,3 +28,152 @@ pub fn list_param_ids<M: Module>(module: &M) -> Vec<ParamId> {
 
     params_ids
 }
+
+#[derive(new)]
+struct ParamSizeCollector<'a> {
+    sizes: &'a mut HashMap<ParamId, usize>,
+}
+
+impl<'a, B: Backend> ModuleVisitor<B> for ParamSizeCollector<'a> {
+    fn visit<const D: usize>(&mut self, id: &ParamId, tensor: &Tensor<B, D>) {
+        self.sizes.insert(id.clone(), tensor.shape().num_elements());
+    }
+}
+
+/// Count the parameters of each top-level sub-module, keyed by the first segment of the
+/// [name](list_param_names) of the parameters, like `embedding` or `encoder`.
+///
+/// The counts add up to the [number of parameters](Module::num_params) of the module, a
+/// parameter shared by multiple sub-modules is counted in the first one by name.
+pub fn num_params_by_submodule<M: Module>(module: &M) -> BTreeMap<String, usize> {
+    let mut sizes = HashMap::new();
+    module.visit(&mut ParamSizeCollector::new(&mut sizes));
+
+    let mut counts = BTreeMap::new();
+    for (id, name) in list_param_names(module) {
+        if let Some(size) = sizes.remove(&id) {
+            let submodule = name.split('.').next().unwrap_or_default();
+            *counts.entry(submodule.to_string()).or_insert(0) += size;
+        }
+    }
+
+    counts
+}
+
+#[derive(new)]
+struct ParamNameCollector<'a> {
+    names: &'a mut Vec<(ParamId, String)>,
+    #[new(default)]
+    path: Vec<String>,
+}
+
+impl<'a, B: Backend> ModuleVisitor<B> for ParamNameCollector<'a> {
+    fn visit<const D: usize>(&mut self, id: &ParamId, _tensor: &Tensor<B, D>) {
+        self.names.push((id.clone(), self.path.join(".")));
+    }
+
+    fn enter_module(&mut self, name: &str) {
+        self.path.push(name.to_string());
+    }
+
+    fn exit_module(&mut self, _name: &str) {
+        self.path.pop();
+    }
+}
+
+/// List the name of each tensor parameter in a module, which is the path of the parameter in the
+/// [state](Module::state) of the module joined with dots, like `layers.mod-0.weight`.
+///
+/// The names are sorted, they are collected by [visiting](Module::visit) the module, so the
+/// tensors aren't read.
+pub fn list_param_names<M: Module>(module: &M) -> Vec<(ParamId, String)> {
+    let mut names = Vec::new();
+    module.visit(&mut ParamNameCollector::new(&mut names));
+    names.sort_by(|(_, name_1), (_, name_2)| name_1.cmp(name_2));
+
+    names
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate as burn;
+    use crate::module::{Param, State};
+    use crate::nn::transformer::{TransformerEncoder, TransformerEncoderConfig};
+    use crate::nn::{Embedding, EmbeddingConfig, Linear, LinearConfig};
+    use crate::TestBackend;
+
+    #[derive(Module, Debug)]
+    struct Model<B: Backend> {
+        embedding: Param<Embedding<B>>,
+        encoder: Param<TransformerEncoder<B>>,
+        head: Param<Linear<B>>,
+    }
+
+    #[test]
+    fn num_params_by_submodule_should_add_up_to_the_total() {
+        let model = Model::<TestBackend> {
+            embedding: Param::from(Embedding::new(&EmbeddingConfig::new(10, 8))),
+            encoder: Param::from(TransformerEncoder::new(&TransformerEncoderConfig::new(
+                8, 16, 2, 2,
+            ))),
+            head: Param::from(Linear::new(&LinearConfig::new(8, 3))),
+        };
+
+        let counts = num_params_by_submodule(&model);
+
+        assert_eq!(
+            counts.keys().collect::<Vec<_>>(),
+            vec!["embedding", "encoder", "head"]
+        );
+        assert_eq!(counts["embedding"], model.embedding.num_params());
+        assert_eq!(counts["encoder"], model.encoder.num_params());
+        assert_eq!(counts["head"], 8 * 3 + 3);
+        assert_eq!(counts.values().sum::<usize>(), model.num_params());
+    }
+
+    /// The id of the parameter at the given path of the state, skipping the id wrappers.
+    fn state_param_id(state: &State<f32>, name: &str) -> Option<ParamId> {
+        let mut state = state;
+        for segment in name.split('.') {
+            state = match state {
+                State::StateNamed(named) => match named.get("data") {
+                    Some(data) if named.get(segment).is_none() => data,
+                    _ => state,
+                },
+                _ => return None,
+            };
+            state = match state {
+                State::StateNamed(named) => named.get(segment)?,
+                _ => return None,
+            };
+        }
+
+        match state {
+            State::StateNamed(named) => match named.get("id") {
+                Some(State::ParamId(id)) => Some(id.clone()),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn param_names_should_be_the_paths_in_the_state() {
+        let config = TransformerEncoderConfig::new(8, 16, 2, 2).with_shared_norm(true);
+        let model = Model::<TestBackend> {
+            embedding: Param::from(Embedding::new(&EmbeddingConfig::new(10, 8))),
+            encoder: Param::from(TransformerEncoder::new(&config)),
+            head: Param::from(Linear::new(&LinearConfig::new(8, 3))),
+        };
+        let state = model.state();
+
+        let names = list_param_names(&model);
+
+        assert_eq!(names.len(), list_param_ids(&model).len());
+        assert!(names.iter().any(|(_, name)| name == "head.weight"));
+        for (id, name) in names {
+            assert_eq!(state_param_id(&state, &name), Some(id), "{name}");
+        }
+    }
+}
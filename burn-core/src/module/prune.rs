@@ -0,0 +1,327 @@
+use alloc::{vec, vec::Vec};
+
+use libm::fabsf;
+
+use super::{Module, ModuleMapper, ModuleVisitor, ParamId};
+use crate::tensor::{backend::Backend, container::TensorContainer, Data, Tensor};
+
+/// Masks of the parameters pruned by [prune_structured].
+///
+/// The optimizer doesn't know about pruning, so the masks should be [applied](Self::apply) after
+/// each update to keep the pruned weights at zero.
+#[derive(Default)]
+pub struct PruningMasks {
+    masks: TensorContainer<ParamId>,
+}
+
+impl PruningMasks {
+    /// Set to zero the pruned weights of the given module.
+    pub fn apply<M: Module>(&self, module: M) -> M {
+        module.map(&mut PruningMaskApplier { masks: self })
+    }
+
+    /// Get the mask of the given parameter, `1` for the weights kept and `0` for the pruned ones.
+    pub fn get<B: Backend, const D: usize>(&self, id: &ParamId) -> Option<Tensor<B, D>> {
+        self.masks.get(id)
+    }
+
+    /// The number of pruned parameters.
+    pub fn len(&self) -> usize {
+        self.masks.len()
+    }
+
+    /// If no parameter is pruned.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Prune the given fraction of the output units with the lowest L2 norm in each weight of the
+/// module, returning the pruned module along with the [masks](PruningMasks) used.
+///
+/// The output units are the columns of matrices, following the `[d_input, d_output]` layout of
+/// [linear](crate::nn::Linear) weights, and the first dimension of higher rank tensors, following
+/// the `[channels_out, channels_in, ...]` layout of convolution weights. Tensors of rank 1, such
+/// as biases, aren't pruned.
+///
+/// The pruned units are set to zero, so the size of the module is unchanged.
+pub fn prune_structured<M: Module>(module: M, amount: f64) -> (M, PruningMasks) {
+    if !(0.0..=1.0).contains(&amount) {
+        panic!("The amount of pruned units must be between 0 and 1, got {amount}");
+    }
+
+    let mut masks = PruningMasks::default();
+    let module = module.map(&mut StructuredPruner {
+        masks: &mut masks,
+        amount,
+    });
+
+    (module, masks)
+}
+
+/// Prune the given fraction of the weights with the lowest magnitude across the whole module,
+/// returning the pruned module along with the [masks](PruningMasks) used.
+///
+/// A single threshold is computed over all the weights, so some parameters end up sparser than
+/// others. Like with [structured pruning](prune_structured), tensors of rank 1 aren't pruned.
+/// NaN weights are never pruned and don't count in the sparsity.
+///
+/// To keep the pruned weights at zero during training, wrap the optimizer with a
+/// [masked optimizer](crate::optim::MaskedOptimizer).
+pub fn prune_unstructured<M: Module>(module: M, sparsity: f64) -> (M, PruningMasks) {
+    if !(0.0..=1.0).contains(&sparsity) {
+        panic!("The sparsity must be between 0 and 1, got {sparsity}");
+    }
+
+    let mut magnitudes = Vec::new();
+    module.visit(&mut MagnitudeCollector {
+        magnitudes: &mut magnitudes,
+    });
+    magnitudes.sort_by(|a, b| a.total_cmp(b));
+
+    let num_pruned = (sparsity * magnitudes.len() as f64) as usize;
+    let threshold = match num_pruned {
+        0 => None,
+        _ => Some(magnitudes[num_pruned - 1]),
+    };
+
+    let mut masks = PruningMasks::default();
+    let module = module.map(&mut UnstructuredPruner {
+        masks: &mut masks,
+        threshold,
+    });
+
+    (module, masks)
+}
+
+struct StructuredPruner<'a> {
+    masks: &'a mut PruningMasks,
+    amount: f64,
+}
+
+struct MagnitudeCollector<'a> {
+    magnitudes: &'a mut Vec<f32>,
+}
+
+struct UnstructuredPruner<'a> {
+    masks: &'a mut PruningMasks,
+    threshold: Option<f32>,
+}
+
+struct PruningMaskApplier<'a> {
+    masks: &'a PruningMasks,
+}
+
+impl<'a, B: Backend> ModuleMapper<B> for StructuredPruner<'a> {
+    fn map<const D: usize>(&mut self, id: &ParamId, tensor: Tensor<B, D>) -> Tensor<B, D> {
+        if D < 2 {
+            return tensor;
+        }
+
+        let mask = structured_mask(&tensor, self.amount);
+        self.masks.masks.register(id.clone(), mask.clone());
+
+        tensor.mul(mask).detach()
+    }
+}
+
+impl<'a, B: Backend> ModuleVisitor<B> for MagnitudeCollector<'a> {
+    fn visit<const D: usize>(&mut self, _id: &ParamId, tensor: &Tensor<B, D>) {
+        if D < 2 {
+            return;
+        }
+
+        let values = tensor.to_data().convert::<f32>().value;
+        self.magnitudes.extend(
+            values
+                .into_iter()
+                .filter(|value| !value.is_nan())
+                .map(fabsf),
+        );
+    }
+}
+
+impl<'a, B: Backend> ModuleMapper<B> for UnstructuredPruner<'a> {
+    fn map<const D: usize>(&mut self, id: &ParamId, tensor: Tensor<B, D>) -> Tensor<B, D> {
+        let threshold = match self.threshold {
+            Some(threshold) if D >= 2 => threshold,
+            _ => return tensor,
+        };
+
+        let mask = tensor
+            .to_data()
+            .convert::<f32>()
+            .value
+            .into_iter()
+            .map(|value| match fabsf(value) <= threshold {
+                true => 0.0,
+                false => 1.0,
+            })
+            .collect();
+        let mask =
+            Tensor::from_data_device(Data::new(mask, tensor.shape()).convert(), &tensor.device());
+        self.masks.masks.register(id.clone(), mask.clone());
+
+        tensor.mul(mask).detach()
+    }
+}
+
+impl<'a, B: Backend> ModuleMapper<B> for PruningMaskApplier<'a> {
+    fn map<const D: usize>(&mut self, id: &ParamId, tensor: Tensor<B, D>) -> Tensor<B, D> {
+        match self.masks.masks.get::<B, D>(id) {
+            Some(mask) => tensor.mul(mask).detach(),
+            None => tensor,
+        }
+    }
+}
+
+fn structured_mask<B: Backend, const D: usize>(tensor: &Tensor<B, D>, amount: f64) -> Tensor<B, D> {
+    let shape = tensor.shape();
+    let num_elements = shape.num_elements();
+    let (num_units, unit_of): (usize, fn(usize, usize, usize) -> usize) = match D {
+        2 => (shape.dims[1], |index, num_units, _| index % num_units),
+        _ => (shape.dims[0], |index, num_units, num_elements| {
+            index / (num_elements / num_units)
+        }),
+    };
+
+    let values = tensor.to_data().convert::<f32>().value;
+    let mut norms = vec![0.0; num_units];
+    for (index, value) in values.iter().enumerate() {
+        norms[unit_of(index, num_units, num_elements)] += (*value as f64) * (*value as f64);
+    }
+
+    let mut units: Vec<usize> = (0..num_units).collect();
+    // The units with a NaN norm are ordered last, the units with finite norms are pruned first.
+    units.sort_by(|a, b| {
+        let (a, b) = (norms[*a], norms[*b]);
+        a.is_nan().cmp(&b.is_nan()).then(a.total_cmp(&b))
+    });
+    let num_pruned = (amount * num_units as f64) as usize;
+    let mut pruned = vec![false; num_units];
+    for unit in units.into_iter().take(num_pruned) {
+        pruned[unit] = true;
+    }
+
+    let mask = (0..num_elements)
+        .map(
+            |index| match pruned[unit_of(index, num_units, num_elements)] {
+                true => 0.0,
+                false => 1.0,
+            },
+        )
+        .collect();
+
+    Tensor::from_data_device(Data::new(mask, shape).convert(), &tensor.device())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate as burn;
+    use crate::module::Param;
+    use crate::nn::{Linear, LinearConfig};
+    use crate::TestBackend;
+
+    #[test]
+    fn should_prune_half_of_linear_output_neurons() {
+        let linear = Linear::<TestBackend>::new(&LinearConfig::new(8, 6));
+        let bias = linear.bias.val().unwrap().to_data();
+
+        let (linear, masks) = prune_structured(linear, 0.5);
+
+        let weight = linear.weight.to_data();
+        let num_zero_neurons = (0..6)
+            .filter(|neuron| (0..8).all(|input| weight.value[input * 6 + neuron] == 0.0))
+            .count();
+        assert_eq!(num_zero_neurons, 3);
+        assert_eq!(masks.len(), 1);
+        assert_eq!(linear.bias.val().unwrap().to_data(), bias);
+    }
+
+    #[test]
+    fn should_prune_convolution_filters_with_lowest_norm() {
+        // Filters of shape [channels_in, kernel_size] with increasing norms.
+        let weight = Tensor::<TestBackend, 4>::ones([4, 2, 3, 3])
+            * Tensor::from_floats([1.0, 4.0, 2.0, 3.0]).reshape([4, 1, 1, 1]);
+
+        let (weight, _masks) = prune_structured(Param::from(weight), 0.5);
+
+        let weight = weight.val().sum_dim(3).sum_dim(2).sum_dim(1).reshape([4]);
+        assert_eq!(weight.into_data(), Data::from([0.0, 72.0, 0.0, 54.0]));
+    }
+
+    #[test]
+    fn structured_pruning_should_keep_the_units_with_nan_norms() {
+        let weight = Tensor::<TestBackend, 2>::from_floats([
+            [1.0, f32::NAN, 3.0, 4.0],
+            [1.0, 2.0, 3.0, 4.0],
+        ]);
+
+        let (weight, _masks) = prune_structured(Param::from(weight), 0.5);
+
+        // The columns are the units, the ones with the lowest finite norms are pruned.
+        let weight = weight.val().into_data().value;
+        assert_eq!([weight[0], weight[2], weight[4], weight[6]], [0.0; 4]);
+        assert_eq!([weight[3], weight[5], weight[7]], [4.0, 2.0, 4.0]);
+        assert!(weight[1].is_nan());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn pruned_weights_should_stay_zero_after_update_with_masks() {
+        use crate::optim::{GradientsParams, Optimizer, Sgd, SgdConfig};
+        use crate::TestADBackend;
+        use burn_tensor::Distribution;
+
+        let linear = Linear::<TestADBackend>::new(&LinearConfig::new(8, 6));
+        let (linear, masks) = prune_structured(linear, 0.5);
+        let zeros_before = num_zeros(&linear.weight.to_data().value);
+        let mut optim = Sgd::new(&SgdConfig::new(0.1));
+        let input = Tensor::<TestADBackend, 2>::random([2, 8], Distribution::Standard);
+
+        let grads = GradientsParams::from_grads(linear.forward(input).sum().backward(), &linear);
+        let linear = optim.update_module(linear, grads);
+        assert!(num_zeros(&linear.weight.to_data().value) < zeros_before);
+
+        let linear = masks.apply(linear);
+        assert_eq!(num_zeros(&linear.weight.to_data().value), zeros_before);
+    }
+
+    #[test]
+    fn unstructured_pruning_should_reach_global_sparsity() {
+        let model = TwoLayers {
+            first: Param::from(Linear::<TestBackend>::new(&LinearConfig::new(10, 20))),
+            second: Param::from(Linear::<TestBackend>::new(&LinearConfig::new(20, 5))),
+        };
+
+        let (model, masks) = prune_unstructured(model, 0.3);
+
+        let first = model.first.weight.to_data().value;
+        let second = model.second.weight.to_data().value;
+        let num_weights = first.len() + second.len();
+        assert_eq!(num_zeros(&first) + num_zeros(&second), num_weights * 3 / 10);
+        assert_eq!(masks.len(), 2);
+    }
+
+    #[test]
+    fn unstructured_pruning_should_ignore_nan_weights() {
+        let weight = Tensor::<TestBackend, 2>::from_floats([[1.0, f32::NAN], [3.0, 2.0]]);
+
+        let (weight, _masks) = prune_unstructured(Param::from(weight), 1.0);
+
+        let weight = weight.val().into_data().value;
+        assert_eq!([weight[0], weight[2], weight[3]], [0.0; 3]);
+        assert!(weight[1].is_nan());
+    }
+
+    #[derive(Module, Debug)]
+    struct TwoLayers<B: Backend> {
+        first: Param<Linear<B>>,
+        second: Param<Linear<B>>,
+    }
+
+    fn num_zeros(values: &[f32]) -> usize {
+        values.iter().filter(|value| **value == 0.0).count()
+    }
+}
@@ -9,7 +9,12 @@ use crate::tensor::{DataSerialize, Element};
 use serde::{Deserialize, Serialize};
 
 #[cfg(feature = "std")]
-use std::{collections::HashMap, fs::File, path::Path};
+use std::{
+    collections::HashMap,
+    fs::File,
+    io::{BufRead, BufReader},
+    path::Path,
+};
 
 #[cfg(feature = "std")]
 use flate2::{read::GzDecoder, write::GzEncoder, Compression};
@@ -17,11 +22,31 @@ use flate2::{read::GzDecoder, write::GzEncoder, Compression};
 #[cfg(not(feature = "std"))]
 use hashbrown::HashMap;
 
+#[cfg(feature = "std")]
+const GZIP_MAGIC_NUMBER: [u8; 2] = [0x1f, 0x8b];
+
 #[derive(Debug, PartialEq, Eq, Clone, Default, Serialize, Deserialize)]
 pub struct StateNamed<E> {
     pub values: HashMap<String, State<E>>,
 }
 
+/// The state of a [module](crate::module::Module), holding the parameters as plain data.
+///
+/// The state only depends on the element type of the backend, not on the backend itself nor its
+/// devices, so a state saved with a backend can be loaded by a module of any other backend. This
+/// is how a model trained on a GPU with `burn-tch` can run its inference with `burn-ndarray`:
+///
+/// ```rust,ignore
+/// let state = model_tch.state();
+/// state.save("model.json.gz")?;
+///
+/// // Possibly in another program, without libtorch.
+/// let state = State::<f32>::load("model.json.gz")?;
+/// let model_ndarray = Model::<NdArrayBackend<f32>>::new(&config).load(&state)?;
+/// ```
+///
+/// When the backends use different float element types, the state should be
+/// [converted](State::convert) first.
 #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
 pub enum State<E> {
     StateNamed(StateNamed<E>),
@@ -104,6 +129,8 @@ impl<E: Element> State<E> {
         }
     }
 
+    /// Convert the data of the state to another element type, to load the state in a module of
+    /// a backend using a different precision.
     pub fn convert<O: Element>(self) -> State<O> {
         match self {
             State::StateNamed(named) => State::StateNamed(named.convert()),
@@ -119,26 +146,37 @@ where
     E: serde::de::DeserializeOwned,
     E: serde::Serialize,
 {
+    /// Save the state to a gzip compressed JSON file.
     pub fn save(self, file: &str) -> std::io::Result<()> {
-        let path = Path::new(file);
-        if path.exists() {
-            log::info!("File exists, replacing");
-            std::fs::remove_file(path).unwrap();
-        }
-
-        let writer = File::create(path)?;
+        let writer = Self::create_file(file)?;
         let writer = GzEncoder::new(writer, Compression::default());
         serde_json::to_writer(writer, &self).unwrap();
 
         Ok(())
     }
 
+    /// Save the state to a plain JSON file, trading disk space for faster saving and loading.
+    pub fn save_uncompressed(self, file: &str) -> std::io::Result<()> {
+        let writer = Self::create_file(file)?;
+        serde_json::to_writer(writer, &self).unwrap();
+
+        Ok(())
+    }
+
+    /// Load the state from a JSON file, decompressing it if it was saved with gzip.
     pub fn load(file: &str) -> Result<Self, StateError> {
         let path = Path::new(file);
         let reader =
             File::open(path).map_err(|err| StateError::FileNotFound(format!("{err:?}")))?;
-        let reader = GzDecoder::new(reader);
-        let state = serde_json::from_reader(reader).unwrap();
+        let mut reader = BufReader::new(reader);
+        let header = reader
+            .fill_buf()
+            .map_err(|err| StateError::FileNotFound(format!("{err:?}")))?;
+
+        let state = match header.starts_with(&GZIP_MAGIC_NUMBER) {
+            true => serde_json::from_reader(GzDecoder::new(reader)).unwrap(),
+            false => serde_json::from_reader(reader).unwrap(),
+        };
 
         Ok(state)
     }
@@ -149,6 +187,16 @@ where
 
         Ok(state)
     }
+
+    fn create_file(file: &str) -> std::io::Result<File> {
+        let path = Path::new(file);
+        if path.exists() {
+            log::info!("File exists, replacing");
+            std::fs::remove_file(path).unwrap();
+        }
+
+        File::create(path)
+    }
 }
 
 #[cfg(test)]
@@ -185,6 +233,20 @@ mod tests {
         assert_eq!(state_before, state_after);
     }
 
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_can_save_uncompressed_and_load_from_file() {
+        let state_before = create_model().state();
+        state_before
+            .clone()
+            .save_uncompressed("/tmp/test-uncompressed.json")
+            .unwrap();
+
+        let state_after = State::load("/tmp/test-uncompressed.json").unwrap();
+
+        assert_eq!(state_before, state_after);
+    }
+
     #[test]
     fn test_parameter_ids_are_loaded() {
         let model_1 = create_model();
@@ -226,6 +288,40 @@ mod tests {
         assert_eq!(params_before_1, params_after_2);
     }
 
+    #[test]
+    fn test_state_saved_with_tch_should_load_in_ndarray() {
+        type TchBackend = burn_tch::TchBackend<f32>;
+        type NdArrayBackend = burn_ndarray::NdArrayBackend<f32>;
+        type NdArrayBackendF64 = burn_ndarray::NdArrayBackend<f64>;
+
+        let config = nn::LinearConfig::new(8, 4).with_bias(true);
+        let model_tch = nn::Linear::<TchBackend>::new(&config);
+        let bytes = serde_json::to_vec(&model_tch.state()).unwrap();
+        let state: State<f32> = serde_json::from_slice(&bytes).unwrap();
+
+        let model_ndarray = nn::Linear::<NdArrayBackend>::new(&config)
+            .load(&state)
+            .unwrap();
+        let model_ndarray_f64 = nn::Linear::<NdArrayBackendF64>::new(&config)
+            .load(&state.convert::<f64>())
+            .unwrap();
+
+        let weight = model_tch.weight.to_data();
+        let bias = model_tch.bias.val().unwrap().to_data();
+        assert_eq!(model_ndarray.weight.to_data(), weight);
+        assert_eq!(model_ndarray.bias.val().unwrap().to_data(), bias);
+        assert_eq!(model_ndarray_f64.weight.to_data().convert::<f32>(), weight);
+        assert_eq!(
+            model_ndarray_f64
+                .bias
+                .val()
+                .unwrap()
+                .to_data()
+                .convert::<f32>(),
+            bias
+        );
+    }
+
     fn create_model() -> nn::Linear<TestBackend> {
         nn::Linear::<crate::TestBackend>::new(&nn::LinearConfig::new(32, 32).with_bias(true))
     }
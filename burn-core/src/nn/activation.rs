@@ -0,0 +1,47 @@
+use crate as burn;
+
+use crate::config::Config;
+use crate::tensor::backend::Backend;
+use crate::tensor::{activation, Tensor};
+
+use super::{LeakyReLU, LeakyReLUConfig};
+
+/// Activation function applied element-wise, used to configure the activation fused into a
+/// layer such as [Linear](super::Linear).
+#[derive(Config, Debug, PartialEq)]
+pub enum Activation {
+    /// Rectified linear unit: `max(0, x)`.
+    Relu,
+    /// Leaky rectified linear unit with the given negative slope, see [LeakyReLU].
+    LeakyRelu(f64),
+    /// Gaussian error linear unit, with the exact formulation.
+    Gelu,
+    /// Gaussian error linear unit, with the tanh approximation.
+    GeluTanh,
+    /// Logistic sigmoid: `1 / (1 + exp(-x))`.
+    Sigmoid,
+    /// Hyperbolic tangent.
+    Tanh,
+}
+
+impl Activation {
+    /// Applies the activation function on the input tensor.
+    ///
+    /// # Shapes
+    ///
+    /// - input: `[..., any]`
+    /// - output: `[..., any]`
+    pub fn forward<B: Backend, const D: usize>(&self, input: Tensor<B, D>) -> Tensor<B, D> {
+        match self {
+            Self::Relu => activation::relu(input),
+            Self::LeakyRelu(negative_slope) => {
+                LeakyReLU::new(&LeakyReLUConfig::new().with_negative_slope(*negative_slope))
+                    .forward(input)
+            }
+            Self::Gelu => activation::gelu(input),
+            Self::GeluTanh => activation::gelu_tanh(input),
+            Self::Sigmoid => activation::sigmoid(input),
+            Self::Tanh => input.tanh(),
+        }
+    }
+}
@@ -0,0 +1,96 @@
+use crate as burn;
+use crate::config::Config;
+use crate::tensor::backend::Backend;
+use crate::tensor::{Distribution, Tensor};
+
+use libm::sqrt;
+
+/// The `alpha` constant of the SELU activation function.
+const SELU_ALPHA: f64 = 1.673_263_242_354_377_3;
+/// The `scale` constant of the SELU activation function.
+const SELU_SCALE: f64 = 1.050_700_987_355_480_5;
+
+/// Configuration to create an [AlphaDropout](AlphaDropout) layer.
+#[derive(Config)]
+pub struct AlphaDropoutConfig {
+    /// The probability of randomly setting some elements of the input tensor to the SELU
+    /// saturation value during training.
+    pub prob: f64,
+}
+
+/// Set at random some elements of the input tensor to the negative saturation value of the SELU
+/// activation during training, keeping the mean and the variance of the input unchanged.
+///
+/// This is the dropout variant used by self-normalizing networks as describe in the paper
+/// [Self-Normalizing Neural Networks](https://arxiv.org/abs/1706.02515).
+#[derive(Clone, Debug)]
+pub struct AlphaDropout {
+    prob: f64,
+}
+
+impl AlphaDropout {
+    /// Create the module from the given configuration.
+    pub fn new(config: &AlphaDropoutConfig) -> Self {
+        Self { prob: config.prob }
+    }
+
+    /// Applies the forward pass on the input tensor.
+    ///
+    /// # Shapes
+    ///
+    /// - input: `[..., any]`
+    /// - output: `[..., any]`
+    pub fn forward<B: Backend, const D: usize>(&self, input: Tensor<B, D>) -> Tensor<B, D> {
+        if !B::ad_enabled() || self.prob == 0.0 {
+            return input;
+        }
+
+        let alpha = -SELU_SCALE * SELU_ALPHA;
+        let prob_keep = 1.0 - self.prob;
+        let a = 1.0 / sqrt(prob_keep + alpha * alpha * prob_keep * self.prob);
+        let b = -a * alpha * self.prob;
+
+        let mask = input.random_like(Distribution::Bernoulli(prob_keep));
+        let dropped = mask.clone().neg().add_scalar(1.0).mul_scalar(alpha);
+        let x = input * mask + dropped;
+
+        x.mul_scalar(a).add_scalar(b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tensor::Shape;
+    use crate::TestBackend;
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn with_ad_backend_should_preserve_mean_and_variance() {
+        use crate::TestADBackend;
+
+        let tensor = Tensor::<TestADBackend, 2>::random([200, 200], Distribution::Normal(0.0, 1.0));
+        let dropout = AlphaDropout::new(&AlphaDropoutConfig::new(0.2));
+
+        let output = dropout.forward(tensor.clone());
+
+        assert_ne!(tensor.to_data(), output.to_data());
+        let values = output.into_data().value;
+        let num_elems = values.len() as f32;
+        let mean = values.iter().sum::<f32>() / num_elems;
+        let variance = values.iter().map(|v| (v - mean).powi(2)).sum::<f32>() / num_elems;
+
+        assert!(mean.abs() < 0.05, "mean {mean}");
+        assert!((variance - 1.0).abs() < 0.05, "variance {variance}");
+    }
+
+    #[test]
+    fn without_ad_backend_should_not_change_input() {
+        let tensor = Tensor::<TestBackend, 2>::ones(Shape::new([100, 100]));
+        let dropout = AlphaDropout::new(&AlphaDropoutConfig::new(0.5));
+
+        let output = dropout.forward(tensor.clone());
+
+        assert_eq!(tensor.to_data(), output.to_data());
+    }
+}
@@ -5,6 +5,8 @@ use burn_tensor::{backend::Backend, Bool, Data, ElementConversion, Int, Shape, T
 /// Generate an autoregressive attention mask.
 ///
 /// The mask can be used in Transformer modules to train models to generate tensors sequentially.
+/// A batch size of 1 creates a single mask that is broadcast across the batch by the attention
+/// modules.
 pub fn generate_autoregressive_mask<B: Backend>(
     batch_size: usize,
     seq_length: usize,
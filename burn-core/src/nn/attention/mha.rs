@@ -1,16 +1,23 @@
-use alloc::{format, vec::Vec};
+use alloc::{format, string::ToString, vec::Vec};
 
 use crate as burn;
 
+use super::qkv::QkvProjection;
 use crate::nn::cache::TensorCache;
 use crate::{
     config::Config,
-    module::{Module, Param},
+    module::{
+        ADModule, LoadingError, Module, ModuleMapper, ModuleVisitor, Param, State, StateNamed,
+    },
     nn,
-    tensor::{activation, backend::Backend, Bool, Tensor},
+    tensor::{
+        activation,
+        backend::{ADBackend, Backend},
+        Bool, Tensor,
+    },
 };
 
-use libm::sqrtf;
+use libm::sqrt;
 
 /// Configuration to create a [Multi Head Attention](MultiHeadAttention) layer.
 #[derive(Config)]
@@ -22,11 +29,23 @@ pub struct MultiHeadAttentionConfig {
     /// The dropout rate. Default: 0.1
     #[config(default = 0.1)]
     dropout: f64,
-    /// The minimum value a float can take. Default: -1.0e4
-    /// This is used to mask attention scores before calculating attention weights.
-    /// A value too low might result in NaN.
-    #[config(default = -1.0e4)]
-    min_float: f64,
+    /// The factor multiplying the attention scores, `1 / sqrt(d_model / n_heads)` by default.
+    scale: Option<f64>,
+    /// If the queries and keys should be L2 normalized before the dot product. Default: false
+    /// The attention scores are then cosine similarities bounded by the scale, so a larger
+    /// [scale](MultiHeadAttentionConfig::scale) than the default is usually needed.
+    #[config(default = false)]
+    qk_norm: bool,
+    /// If the query, key and value projections should be fused in a single linear layer with
+    /// `3 * d_model` output features. Default: false
+    /// The self-attention projections are then computed with a single matrix multiplication.
+    #[config(default = false)]
+    fused_qkv: bool,
+    /// The factor scaling the initial weights of the output projection. Default: 1.0
+    /// A factor smaller than one, like `1 / sqrt(2 * n_layers)` in GPT-2, makes the residual
+    /// branches of deep transformers start small.
+    #[config(default = 1.0)]
+    output_proj_scale: f64,
 }
 
 /// The multihead attention module as describe in the paper [Attention Is All You Need](https://arxiv.org/abs/1706.03762).
@@ -37,17 +56,24 @@ pub struct MultiHeadAttentionConfig {
 /// - key: [Linear](nn::Linear) layer with `d_model` input and output features.
 /// - value: [Linear](nn::Linear) layer with `d_model` input and output features.
 /// - output: [Linear](nn::Linear) layer with `d_model` input and output features.
-#[derive(Module, Debug)]
+///
+/// The query, key and value layers can be [fused](MultiHeadAttentionConfig::fused_qkv) in a
+/// single layer with `3 * d_model` output features. The fused layer is saved as the separate
+/// layers, so the states of both layouts are interchangeable.
+///
+/// The module is implemented manually to save the query, key and value layers at the top level
+/// of the state, like the output layer, which keeps loading the states saved before the layers
+/// could be fused.
+#[derive(Debug, Clone)]
 pub struct MultiHeadAttention<B: Backend> {
-    query: Param<nn::Linear<B>>,
-    key: Param<nn::Linear<B>>,
-    value: Param<nn::Linear<B>>,
+    qkv: QkvProjection<B>,
     output: Param<nn::Linear<B>>,
     dropout: nn::Dropout,
     activation: nn::GELU,
     n_heads: usize,
     d_k: usize,
-    min_float: f64,
+    scale: f64,
+    qk_norm: bool,
 }
 
 /// [Multihead attention](MultiHeadAttention) forward pass input argument.
@@ -58,6 +84,7 @@ pub struct MhaInput<B: Backend> {
     value: Tensor<B, 3>,
     mask_pad: Option<Tensor<B, 2, Bool>>,
     mask_attn: Option<Tensor<B, 3, Bool>>,
+    self_attn: bool,
 }
 
 impl<B: Backend> MhaInput<B> {
@@ -70,9 +97,18 @@ impl<B: Backend> MhaInput<B> {
             value: tensor,
             mask_pad: None,
             mask_attn: None,
+            self_attn: true,
         }
     }
 
+    /// Create a [multihead attention](MultiHeadAttention) input argument where the query attends
+    /// to a memory, like the output of an encoder, given as the key and the value.
+    ///
+    /// The [padding mask](Self::mask_pad) then applies to the positions of the memory.
+    pub fn cross_attn(query: Tensor<B, 3>, key: Tensor<B, 3>, value: Tensor<B, 3>) -> Self {
+        Self::new(query, key, value)
+    }
+
     /// Create a [multihead attention](MultiHeadAttention) input argument.
     pub fn new(query: Tensor<B, 3>, key: Tensor<B, 3>, value: Tensor<B, 3>) -> Self {
         Self {
@@ -81,16 +117,24 @@ impl<B: Backend> MhaInput<B> {
             value,
             mask_pad: None,
             mask_attn: None,
+            self_attn: false,
         }
     }
 
     /// Register the padding mask.
+    ///
+    /// The mask has the shape `[batch_size, seq_length_2]`, masking the positions of the key and
+    /// the value.
     pub fn mask_pad(mut self, mask_pad: Tensor<B, 2, Bool>) -> Self {
         self.mask_pad = Some(mask_pad);
         self
     }
 
     /// Register the attention mask.
+    ///
+    /// The mask has the shape `[batch_size, seq_length_1, seq_length_2]`, a batch size of 1 can be
+    /// used to share the same mask, like a causal mask, across every item of the batch without
+    /// tiling it.
     pub fn mask_attn(mut self, mask_attn: Tensor<B, 3, Bool>) -> Self {
         self.mask_attn = Some(mask_attn);
         self
@@ -109,23 +153,22 @@ pub struct MhaOutput<B: Backend> {
 impl<B: Backend> MultiHeadAttention<B> {
     /// Create the module from the given configuration.
     pub fn new(config: &MultiHeadAttentionConfig) -> Self {
-        let linear = |config: &MultiHeadAttentionConfig| {
-            Param::from(nn::Linear::new(&nn::LinearConfig::new(
-                config.d_model,
-                config.d_model,
-            )))
-        };
+        let d_k = config.d_model / config.n_heads;
+        let output = nn::Linear::new(
+            &nn::LinearConfig::new(config.d_model, config.d_model).with_initializer(
+                nn::Initializer::scaled_uniform_default(config.d_model, config.output_proj_scale),
+            ),
+        );
 
         Self {
-            query: linear(config),
-            key: linear(config),
-            value: linear(config),
-            output: linear(config),
+            qkv: QkvProjection::new(config.d_model, config.fused_qkv),
+            output: Param::from(output),
             dropout: nn::Dropout::new(&nn::DropoutConfig::new(config.dropout)),
             activation: nn::GELU::new(),
             n_heads: config.n_heads,
-            d_k: config.d_model / config.n_heads,
-            min_float: config.min_float,
+            d_k,
+            scale: config.scale.unwrap_or(1.0 / sqrt(d_k as f64)),
+            qk_norm: config.qk_norm,
         }
     }
 
@@ -140,11 +183,19 @@ impl<B: Backend> MultiHeadAttention<B> {
     pub fn forward(&self, input: MhaInput<B>) -> MhaOutput<B> {
         let [batch_size, seq_length_1, d_model] = input.query.dims();
 
-        let query = self.attention_linear(input.query, &self.query);
-        let key = self.attention_linear(input.key, &self.key);
-        let value = self.attention_linear(input.value, &self.value);
+        // The fused projections are computed with a single matrix multiplication when the
+        // query, the key and the value are the same tensor.
+        let projections = match input.self_attn {
+            true => self.qkv.forward_all(input.query),
+            false => [
+                self.qkv.forward(input.query, 0),
+                self.qkv.forward(input.key, 1),
+                self.qkv.forward(input.value, 2),
+            ],
+        };
+        let [query, key, value] = projections.map(|x| self.split_heads(x));
 
-        let attn_scores = self.attn_scores(query, key);
+        let attn_scores = self.dropout.forward(self.attn_scores(query, key));
         let weights = self.attn_weights(attn_scores, input.mask_pad, input.mask_attn);
 
         let context = weights.clone().matmul(value);
@@ -158,6 +209,11 @@ impl<B: Backend> MultiHeadAttention<B> {
 
     /// Applies the forward pass on the input tensors using an autoregressive cache.
     ///
+    /// With a [windowed](Self::new_autoregressive_cache_windowed) cache, only the most recent
+    /// positions are attended and returned, the masks are cropped to their last positions.
+    ///
+    /// Dropout is never applied, so the output is deterministic even with an autodiff backend.
+    ///
     /// # Shapes
     ///
     /// - query: `[batch_size, seq_length_1, d_model]`
@@ -169,20 +225,32 @@ impl<B: Backend> MultiHeadAttention<B> {
         input: MhaInput<B>,
         cache: &mut MHAAutoregressiveCache<B>,
     ) -> MhaOutput<B> {
-        let [batch_size, seq_length_1, d_model] = input.query.dims();
+        let [batch_size, _, d_model] = input.query.dims();
 
-        let attention_linear = |cache: &mut TensorCache<B, 4>,
-                                tensor: Tensor<B, 3>,
-                                param: &Param<nn::Linear<B>>| {
-            cache.forward_autoregressive(tensor, 2, |tensor| self.attention_linear(tensor, param))
+        let attention_linear = |cache: &mut TensorCache<B, 4>, tensor: Tensor<B, 3>, index| {
+            cache.forward_autoregressive(tensor, 2, |tensor| self.attention_linear(tensor, index))
         };
 
-        let query = attention_linear(&mut cache.query, input.query, &self.query);
-        let key = attention_linear(&mut cache.key, input.key, &self.key);
-        let value = attention_linear(&mut cache.value, input.value, &self.value);
+        let query = attention_linear(&mut cache.query, input.query, 0);
+        let key = attention_linear(&mut cache.key, input.key, 1);
+        let value = attention_linear(&mut cache.value, input.value, 2);
+
+        let [_, _, seq_length_1, _] = query.dims();
+        let [_, _, seq_length_2, _] = key.dims();
+        let mask_pad = input.mask_pad.map(|mask| last_columns(mask, seq_length_2));
+        let mask_attn = input.mask_attn.map(|mask| {
+            let [batch_size, seq_length_mask, _] = mask.dims();
+            let mask = last_columns(mask, seq_length_2);
+
+            mask.index([
+                0..batch_size,
+                seq_length_mask - seq_length_1..seq_length_mask,
+                0..seq_length_2,
+            ])
+        });
 
         let attn_scores = self.attn_scores(query, key);
-        let weights = self.attn_weights(attn_scores, input.mask_pad, input.mask_attn);
+        let weights = self.attn_weights(attn_scores, mask_pad, mask_attn);
 
         let context = weights.clone().matmul(value);
         let context = context
@@ -201,54 +269,205 @@ impl<B: Backend> MultiHeadAttention<B> {
         MHAAutoregressiveCache::default()
     }
 
+    /// Create an autoregressive cache preallocated for sequences of at most `max_seq_length`
+    /// tokens, avoiding reallocations at each decoding step.
+    pub fn new_autoregressive_cache_preallocated(
+        &self,
+        max_seq_length: usize,
+    ) -> MHAAutoregressiveCache<B> {
+        MHAAutoregressiveCache::preallocated(max_seq_length)
+    }
+
+    /// Create an autoregressive cache keeping only the last `window` positions, implementing a
+    /// sliding window attention with a bounded memory usage.
+    pub fn new_autoregressive_cache_windowed(&self, window: usize) -> MHAAutoregressiveCache<B> {
+        MHAAutoregressiveCache::windowed(window)
+    }
+
     fn attn_scores(&self, query: Tensor<B, 4>, key: Tensor<B, 4>) -> Tensor<B, 4> {
-        let attn_scores = query
-            .matmul(key.transpose())
-            .div_scalar(sqrtf(self.d_k as f32));
+        let (query, key) = match self.qk_norm {
+            true => (l2_normalize(query), l2_normalize(key)),
+            false => (query, key),
+        };
 
-        self.dropout.forward(attn_scores)
+        query.matmul(key.transpose()).mul_scalar(self.scale)
     }
 
     fn attn_weights(
         &self,
-        mut attn_scores: Tensor<B, 4>,
+        attn_scores: Tensor<B, 4>,
         mask_pad: Option<Tensor<B, 2, Bool>>,
         mask_attn: Option<Tensor<B, 3, Bool>>,
     ) -> Tensor<B, 4> {
+        let [_, _, seq_length_scores_1, seq_length_scores_2] = attn_scores.dims();
+        let mut mask: Option<Tensor<B, 4, Bool>> = None;
+
         if let Some(mask_pad) = mask_pad {
             let [batch_size, seq_length] = mask_pad.dims();
 
-            attn_scores = attn_scores.mask_fill(
-                mask_pad.reshape([batch_size, 1, 1, seq_length]),
-                self.min_float,
-            );
+            if seq_length != seq_length_scores_2 {
+                panic!(
+                    "The padding mask length should be the key length {}, got {}",
+                    seq_length_scores_2, seq_length
+                );
+            }
+
+            mask = Some(mask_pad.reshape([batch_size, 1, 1, seq_length]));
         }
 
         if let Some(mask_attn) = mask_attn {
             let [batch_size, seq_length_1, seq_length_2] = mask_attn.dims();
+            let [batch_size_scores, _, _, _] = attn_scores.dims();
+
+            if batch_size != 1 && batch_size != batch_size_scores {
+                panic!(
+                    "The attention mask batch size should be 1 or {}, got {}",
+                    batch_size_scores, batch_size
+                );
+            }
+
+            if [seq_length_1, seq_length_2] != [seq_length_scores_1, seq_length_scores_2] {
+                panic!(
+                    "The attention mask should have the shape [_, {}, {}], got [_, {}, {}]",
+                    seq_length_scores_1, seq_length_scores_2, seq_length_1, seq_length_2
+                );
+            }
+
+            let mask_attn = mask_attn.reshape([batch_size, 1, seq_length_1, seq_length_2]);
+            mask = Some(match mask {
+                Some(mask) => mask.into_int().add(mask_attn.into_int()).greater_elem(0),
+                None => mask_attn,
+            });
+        }
 
-            attn_scores = attn_scores.mask_fill(
-                mask_attn.reshape([batch_size, 1, seq_length_1, seq_length_2]),
-                self.min_float,
-            );
+        // Rows where every key is masked get null weights instead of NaN values.
+        match mask {
+            Some(mask) => activation::masked_softmax(attn_scores, mask, 3),
+            None => activation::softmax(attn_scores, 3),
         }
+    }
 
-        activation::softmax(attn_scores, 3)
+    fn attention_linear(&self, x: Tensor<B, 3>, index: usize) -> Tensor<B, 4> {
+        self.split_heads(self.qkv.forward(x, index))
     }
 
-    fn attention_linear(&self, x: Tensor<B, 3>, linear: &Param<nn::Linear<B>>) -> Tensor<B, 4> {
+    fn split_heads(&self, x: Tensor<B, 3>) -> Tensor<B, 4> {
         let [batch_size, seq_length, _d_model] = x.dims();
-        linear
-            .forward(x)
-            .reshape([batch_size, seq_length, self.n_heads, self.d_k])
+
+        x.reshape([batch_size, seq_length, self.n_heads, self.d_k])
             .swap_dims(1, 2)
     }
 }
 
+impl<B: Backend> Module for MultiHeadAttention<B> {
+    type Backend = B;
+
+    fn devices(&self) -> Vec<B::Device> {
+        let mut devices = self.qkv.devices();
+        devices.append(&mut self.output.devices());
+        devices
+    }
+
+    fn to_device(self, device: &B::Device) -> Self {
+        Self {
+            qkv: self.qkv.to_device(device),
+            output: self.output.to_device(device),
+            ..self
+        }
+    }
+
+    fn state(&self) -> State<B::FloatElem> {
+        let mut state = StateNamed::new();
+        self.qkv.register_state(&mut state);
+        state.register_state("output", self.output.state());
+
+        State::StateNamed(state)
+    }
+
+    fn load(self, state: &State<B::FloatElem>) -> Result<Self, LoadingError> {
+        // The query, key and value layers are read from the top level of the state.
+        let qkv = self.qkv.load(state)?;
+        let state_output = state
+            .get("output")
+            .ok_or_else(|| LoadingError::new("Missing module 'output' from state".to_string()))?;
+        let output = self
+            .output
+            .load(state_output)
+            .map_err(|err| LoadingError::new(format!("Can't load module output: {err}")))?;
+
+        Ok(Self {
+            qkv,
+            output,
+            ..self
+        })
+    }
+
+    fn detach(self) -> Self {
+        Self {
+            qkv: self.qkv.detach(),
+            output: self.output.detach(),
+            ..self
+        }
+    }
+
+    fn visit<V: ModuleVisitor<Self::Backend>>(&self, visitor: &mut V) {
+        // The query, key and value layers are at the top level of the state.
+        self.qkv.visit(visitor);
+        visitor.enter_module("output");
+        self.output.visit(visitor);
+        visitor.exit_module("output");
+    }
+
+    fn map<M: ModuleMapper<Self::Backend>>(self, mapper: &mut M) -> Self {
+        Self {
+            qkv: self.qkv.map(mapper),
+            output: self.output.map(mapper),
+            ..self
+        }
+    }
+}
+
+impl<B: ADBackend> ADModule for MultiHeadAttention<B> {
+    type ADBackend = B;
+    type InnerModule = MultiHeadAttention<B::InnerBackend>;
+
+    fn inner(self) -> Self::InnerModule {
+        MultiHeadAttention {
+            qkv: self.qkv.inner(),
+            output: self.output.inner(),
+            dropout: self.dropout,
+            activation: self.activation,
+            n_heads: self.n_heads,
+            d_k: self.d_k,
+            scale: self.scale,
+            qk_norm: self.qk_norm,
+        }
+    }
+
+    fn from_inner(module: Self::InnerModule) -> Self {
+        Self {
+            qkv: ADModule::from_inner(module.qkv),
+            output: ADModule::from_inner(module.output),
+            dropout: module.dropout,
+            activation: module.activation,
+            n_heads: module.n_heads,
+            d_k: module.d_k,
+            scale: module.scale,
+            qk_norm: module.qk_norm,
+        }
+    }
+}
+
+impl<B: Backend> core::fmt::Display for MultiHeadAttention<B> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "MultiHeadAttention[num_params={}]", self.num_params())
+    }
+}
+
 /// Autoregressive cache for the [Multi Head Attention](MultiHeadAttention) layer.
 ///
 /// To be used during inference when decoding tokens.
-#[derive(Default)]
+#[derive(Default, Clone)]
 pub struct MHAAutoregressiveCache<B: Backend> {
     query: TensorCache<B, 4>,
     key: TensorCache<B, 4>,
@@ -256,6 +475,43 @@ pub struct MHAAutoregressiveCache<B: Backend> {
     output: TensorCache<B, 3>,
 }
 
+fn last_columns<B: Backend, const D: usize>(
+    mask: Tensor<B, D, Bool>,
+    num_columns: usize,
+) -> Tensor<B, D, Bool> {
+    let dims = mask.dims();
+    let mut indexes = dims.map(|dim| 0..dim);
+    indexes[D - 1] = dims[D - 1] - num_columns..dims[D - 1];
+
+    mask.index(indexes)
+}
+
+fn l2_normalize<B: Backend>(tensor: Tensor<B, 4>) -> Tensor<B, 4> {
+    let norm = tensor.clone().powf(2.0).sum_dim(3).add_scalar(1e-12).sqrt();
+
+    tensor.div(norm)
+}
+
+impl<B: Backend> MHAAutoregressiveCache<B> {
+    pub(crate) fn preallocated(max_seq_length: usize) -> Self {
+        Self {
+            query: TensorCache::preallocated(max_seq_length),
+            key: TensorCache::preallocated(max_seq_length),
+            value: TensorCache::preallocated(max_seq_length),
+            output: TensorCache::preallocated(max_seq_length),
+        }
+    }
+
+    pub(crate) fn windowed(window: usize) -> Self {
+        Self {
+            query: TensorCache::windowed(window),
+            key: TensorCache::windowed(window),
+            value: TensorCache::windowed(window),
+            output: TensorCache::windowed(window),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -314,6 +570,25 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_output_proj_scale_should_reduce_the_initial_variance() {
+        let [d_model, n_heads, n_layers] = [64, 4, 8];
+        let scale = 1.0 / sqrt(2.0 * n_layers as f64);
+        let config = MultiHeadAttentionConfig::new(d_model, n_heads).with_output_proj_scale(scale);
+        let mha = MultiHeadAttention::<TestBackend>::new(&config);
+
+        let weights = mha.output.weight.to_data().convert::<f64>().value;
+        let variance =
+            weights.iter().map(|weight| weight * weight).sum::<f64>() / weights.len() as f64;
+
+        // The variance of U(-k, k) is k^2 / 3, with k = scale * sqrt(1 / d_model).
+        let expected = scale * scale / (3.0 * d_model as f64);
+        assert!(
+            (variance / expected - 1.0).abs() < 0.1,
+            "The variance {variance} should be close to {expected}"
+        );
+    }
+
     #[test]
     fn test_self_attention_mask_pad() {
         let [batch_size, seq_length, d_model, n_heads, num_padded] = [3, 6, 32, 2, 2];
@@ -394,4 +669,323 @@ mod tests {
             .into_data()
             .assert_approx_eq(&output_2.into_data(), 3);
     }
+
+    #[test]
+    fn test_broadcast_mask_attn_should_match_tiled_mask() {
+        let [batch_size, seq_length, d_model, n_heads] = [3, 5, 12, 2];
+        let mha = MultiHeadAttention::new(&MultiHeadAttentionConfig::new(d_model, n_heads));
+        let tensor = Tensor::<TestBackend, 3>::random(
+            [batch_size, seq_length, d_model],
+            Distribution::Standard,
+        );
+        let mask_shared = generate_autoregressive_mask(1, seq_length, &tensor.device());
+        let mask_tiled = generate_autoregressive_mask(batch_size, seq_length, &tensor.device());
+
+        let output_shared = mha.forward(MhaInput::self_attn(tensor.clone()).mask_attn(mask_shared));
+        let output_tiled = mha.forward(MhaInput::self_attn(tensor).mask_attn(mask_tiled));
+
+        output_shared
+            .context
+            .into_data()
+            .assert_approx_eq(&output_tiled.context.into_data(), 3);
+        output_shared
+            .weights
+            .into_data()
+            .assert_approx_eq(&output_tiled.weights.into_data(), 3);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_mask_attn_with_mismatched_batch_size_should_panic() {
+        let [batch_size, seq_length, d_model, n_heads] = [3, 5, 12, 2];
+        let mha = MultiHeadAttention::new(&MultiHeadAttentionConfig::new(d_model, n_heads));
+        let tensor = Tensor::<TestBackend, 3>::random(
+            [batch_size, seq_length, d_model],
+            Distribution::Standard,
+        );
+        let mask = generate_autoregressive_mask(2, seq_length, &tensor.device());
+
+        mha.forward(MhaInput::self_attn(tensor).mask_attn(mask));
+    }
+
+    #[test]
+    fn test_custom_scale_should_sharpen_attention_weights() {
+        let [batch_size, seq_length, d_model, n_heads] = [2, 5, 12, 3];
+        let mha = MultiHeadAttention::<TestBackend>::new(&MultiHeadAttentionConfig::new(
+            d_model, n_heads,
+        ));
+        let mut mha_sharp = mha.clone();
+        mha_sharp.scale = 2.0 * mha.scale;
+        let tensor = Tensor::<TestBackend, 3>::random(
+            [batch_size, seq_length, d_model],
+            Distribution::Standard,
+        );
+
+        let weights = mha.forward(MhaInput::self_attn(tensor.clone())).weights;
+        let weights_sharp = mha_sharp.forward(MhaInput::self_attn(tensor)).weights;
+
+        // Doubling the scale squares the unnormalized attention weights.
+        let weights_squared = weights.powf(2.0);
+        let expected = weights_squared.clone() / weights_squared.sum_dim(3);
+        weights_sharp
+            .into_data()
+            .assert_approx_eq(&expected.into_data(), 3);
+    }
+
+    #[test]
+    fn test_zero_scale_should_give_uniform_attention_weights() {
+        let [batch_size, seq_length, d_model, n_heads] = [2, 4, 12, 3];
+        let config = MultiHeadAttentionConfig::new(d_model, n_heads).with_scale(Some(0.0));
+        let mha = MultiHeadAttention::<TestBackend>::new(&config);
+        let tensor = Tensor::<TestBackend, 3>::random(
+            [batch_size, seq_length, d_model],
+            Distribution::Standard,
+        );
+
+        let weights = mha.forward(MhaInput::self_attn(tensor)).weights;
+
+        weights.into_data().assert_approx_eq(
+            &Tensor::<TestBackend, 4>::ones([batch_size, n_heads, seq_length, seq_length])
+                .div_scalar(seq_length as f32)
+                .into_data(),
+            3,
+        );
+    }
+
+    #[test]
+    fn test_qk_norm_should_bound_attention_scores() {
+        let [batch_size, seq_length, d_model, n_heads] = [2, 6, 12, 3];
+        let scale = 2.0;
+        let config = MultiHeadAttentionConfig::new(d_model, n_heads)
+            .with_scale(Some(scale))
+            .with_qk_norm(true);
+        let mha = MultiHeadAttention::<TestBackend>::new(&config);
+        let tensor = Tensor::<TestBackend, 3>::random(
+            [batch_size, seq_length, d_model],
+            Distribution::Normal(0.0, 10.0),
+        );
+
+        let weights = mha.forward(MhaInput::self_attn(tensor)).weights;
+
+        // Cosine similarities are in [-1, 1], so the weights of a row can't differ by more than
+        // a factor of exp(2 * scale).
+        let bound = (2.0 * scale as f32).exp() + 1e-3;
+        let values = weights.into_data().value;
+        for row in values.chunks(seq_length) {
+            let max = row.iter().cloned().fold(f32::MIN, f32::max);
+            let min = row.iter().cloned().fold(f32::MAX, f32::min);
+            assert!(max / min <= bound);
+        }
+    }
+
+    #[test]
+    fn test_fused_qkv_should_match_separate_projections() {
+        let [batch_size, seq_length_1, seq_length_2, d_model, n_heads] = [2, 4, 5, 12, 3];
+        let config = MultiHeadAttentionConfig::new(d_model, n_heads);
+        let mha = MultiHeadAttention::<TestBackend>::new(&config);
+        let mha_fused = MultiHeadAttention::<TestBackend>::new(&config.with_fused_qkv(true))
+            .load(&mha.state())
+            .unwrap();
+        let query = Tensor::<TestBackend, 3>::random(
+            [batch_size, seq_length_1, d_model],
+            Distribution::Standard,
+        );
+        let key = Tensor::<TestBackend, 3>::random(
+            [batch_size, seq_length_2, d_model],
+            Distribution::Standard,
+        );
+
+        let self_attn = MhaInput::self_attn(query.clone());
+        let cross_attn = MhaInput::new(query, key.clone(), key);
+
+        for input in [self_attn, cross_attn] {
+            let output = mha.forward(input.clone());
+            let output_fused = mha_fused.forward(input);
+
+            output
+                .context
+                .into_data()
+                .assert_approx_eq(&output_fused.context.into_data(), 3);
+        }
+    }
+
+    #[test]
+    fn test_fused_qkv_state_should_load_in_separate_projections() {
+        let [batch_size, seq_length, d_model, n_heads] = [2, 4, 12, 3];
+        let config = MultiHeadAttentionConfig::new(d_model, n_heads);
+        let mha_fused =
+            MultiHeadAttention::<TestBackend>::new(&config.clone().with_fused_qkv(true));
+        let mha = MultiHeadAttention::<TestBackend>::new(&config)
+            .load(&mha_fused.state())
+            .unwrap();
+        let tensor = Tensor::<TestBackend, 3>::random(
+            [batch_size, seq_length, d_model],
+            Distribution::Standard,
+        );
+
+        let output = mha.forward(MhaInput::self_attn(tensor.clone()));
+        let output_fused = mha_fused.forward(MhaInput::self_attn(tensor));
+
+        assert_eq!(mha.num_params(), mha_fused.num_params());
+        assert_eq!(mha.state(), mha_fused.state());
+        output
+            .context
+            .into_data()
+            .assert_approx_eq(&output_fused.context.into_data(), 3);
+    }
+
+    #[test]
+    fn test_should_load_the_state_of_separate_top_level_projections() {
+        #[derive(Module, Debug)]
+        struct MhaSeparate<B: Backend> {
+            query: Param<nn::Linear<B>>,
+            key: Param<nn::Linear<B>>,
+            value: Param<nn::Linear<B>>,
+            output: Param<nn::Linear<B>>,
+        }
+
+        let [batch_size, seq_length, d_model, n_heads] = [2, 4, 12, 3];
+        let linear = || Param::from(nn::Linear::new(&nn::LinearConfig::new(d_model, d_model)));
+        let mha_separate = MhaSeparate::<TestBackend> {
+            query: linear(),
+            key: linear(),
+            value: linear(),
+            output: linear(),
+        };
+        let config = MultiHeadAttentionConfig::new(d_model, n_heads);
+        let mha = MultiHeadAttention::<TestBackend>::new(&config)
+            .load(&mha_separate.state())
+            .unwrap();
+        let mha_fused = MultiHeadAttention::<TestBackend>::new(&config.with_fused_qkv(true))
+            .load(&mha_separate.state())
+            .unwrap();
+        let tensor = Tensor::<TestBackend, 3>::random(
+            [batch_size, seq_length, d_model],
+            Distribution::Standard,
+        );
+
+        let output = mha.forward(MhaInput::self_attn(tensor.clone()));
+        let output_fused = mha_fused.forward(MhaInput::self_attn(tensor));
+
+        assert_eq!(mha.state(), mha_separate.state());
+        output
+            .context
+            .into_data()
+            .assert_approx_eq(&output_fused.context.into_data(), 3);
+    }
+
+    #[test]
+    fn test_cross_attention_with_same_tensors_should_match_self_attention() {
+        let [batch_size, seq_length, d_model, n_heads] = [2, 5, 12, 3];
+        let mha = MultiHeadAttention::<TestBackend>::new(&MultiHeadAttentionConfig::new(
+            d_model, n_heads,
+        ));
+        let tensor = Tensor::<TestBackend, 3>::random(
+            [batch_size, seq_length, d_model],
+            Distribution::Standard,
+        );
+
+        let output_self = mha.forward(MhaInput::self_attn(tensor.clone()));
+        let output_cross =
+            mha.forward(MhaInput::cross_attn(tensor.clone(), tensor.clone(), tensor));
+
+        output_cross
+            .context
+            .into_data()
+            .assert_approx_eq(&output_self.context.into_data(), 3);
+    }
+
+    #[test]
+    fn test_cross_attention_mask_pad_should_ignore_padded_memory() {
+        let [batch_size, seq_length_1, seq_length_2, d_model, n_heads, num_padded] =
+            [2, 3, 6, 12, 3, 2];
+        let mha = MultiHeadAttention::new(&MultiHeadAttentionConfig::new(d_model, n_heads));
+        let mask_pad: Tensor<TestBackend, 2, Int> = Tensor::zeros([batch_size, seq_length_2]);
+        let mask_pad = mask_pad
+            .index_assign(
+                [0..batch_size, seq_length_2 - num_padded..seq_length_2],
+                Tensor::ones([batch_size, num_padded]),
+            )
+            .equal_elem(1);
+
+        let query = Tensor::<TestBackend, 3>::random(
+            [batch_size, seq_length_1, d_model],
+            Distribution::Standard,
+        );
+        let memory_1 = Tensor::<TestBackend, 3>::random(
+            [batch_size, seq_length_2, d_model],
+            Distribution::Standard,
+        );
+        // Change the padded positions of the memory.
+        let memory_2 = memory_1.clone().index_assign(
+            [
+                0..batch_size,
+                seq_length_2 - num_padded..seq_length_2,
+                0..d_model,
+            ],
+            Tensor::random([batch_size, num_padded, d_model], Distribution::Standard),
+        );
+
+        let output_1 = mha.forward(
+            MhaInput::cross_attn(query.clone(), memory_1.clone(), memory_1)
+                .mask_pad(mask_pad.clone()),
+        );
+        let output_2 =
+            mha.forward(MhaInput::cross_attn(query, memory_2.clone(), memory_2).mask_pad(mask_pad));
+
+        output_1
+            .context
+            .into_data()
+            .assert_approx_eq(&output_2.context.into_data(), 3);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_mask_pad_with_query_length_should_panic() {
+        let [batch_size, seq_length_1, seq_length_2, d_model, n_heads] = [2, 3, 6, 12, 3];
+        let mha = MultiHeadAttention::new(&MultiHeadAttentionConfig::new(d_model, n_heads));
+        let query = Tensor::<TestBackend, 3>::random(
+            [batch_size, seq_length_1, d_model],
+            Distribution::Standard,
+        );
+        let memory = Tensor::<TestBackend, 3>::random(
+            [batch_size, seq_length_2, d_model],
+            Distribution::Standard,
+        );
+        let mask_pad =
+            Tensor::<TestBackend, 2, Int>::zeros([batch_size, seq_length_1]).equal_elem(1);
+
+        mha.forward(MhaInput::cross_attn(query, memory.clone(), memory).mask_pad(mask_pad));
+    }
+
+    #[test]
+    fn test_fully_padded_item_should_have_null_weights_without_nan() {
+        let [batch_size, seq_length, d_model, n_heads] = [2, 4, 12, 3];
+        let mha = MultiHeadAttention::new(&MultiHeadAttentionConfig::new(d_model, n_heads));
+        let tensor = Tensor::<TestBackend, 3>::random(
+            [batch_size, seq_length, d_model],
+            Distribution::Standard,
+        );
+        let mask_pad = Tensor::<TestBackend, 2, Int>::zeros([batch_size, seq_length])
+            .index_assign([1..2, 0..seq_length], Tensor::ones([1, seq_length]))
+            .equal_elem(1);
+        let mask_attn = generate_autoregressive_mask(batch_size, seq_length, &tensor.device());
+
+        let output = mha.forward(
+            MhaInput::self_attn(tensor)
+                .mask_pad(mask_pad)
+                .mask_attn(mask_attn),
+        );
+
+        let context = output.context.into_data();
+        assert!(context.value.iter().all(|value| !value.is_nan()));
+        output
+            .weights
+            .index([1..2, 0..n_heads, 0..seq_length, 0..seq_length])
+            .into_data()
+            .assert_approx_eq(
+                &Tensor::<TestBackend, 4>::zeros([1, n_heads, seq_length, seq_length]).into_data(),
+                3,
+            );
+    }
 }
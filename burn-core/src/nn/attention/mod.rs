@@ -1,5 +1,6 @@
 mod mask;
 mod mha;
+mod qkv;
 
 pub use mask::*;
 pub use mha::*;
@@ -0,0 +1,299 @@
+use alloc::{format, string::ToString, vec, vec::Vec};
+
+use crate::module::{
+    ADModule, LoadingError, Module, ModuleMapper, ModuleVisitor, Param, ParamId, State, StateNamed,
+};
+use crate::nn::{Linear, LinearConfig};
+use crate::tensor::{
+    backend::{ADBackend, Backend},
+    Tensor,
+};
+
+const PROJECTIONS: [&str; 3] = ["query", "key", "value"];
+
+/// The query, key and value projections of the [multihead attention](super::MultiHeadAttention),
+/// either as three linear layers or as a single fused one computing the three projections with
+/// one matrix multiplication.
+///
+/// The state always has the layout of the separate projections, so both variants can load the
+/// state saved by the other one. The fused parameters keep the ids of the query projection.
+#[derive(Debug, Clone)]
+pub(super) enum QkvProjection<B: Backend> {
+    Separate([Param<Linear<B>>; 3]),
+    Fused {
+        qkv: Param<Linear<B>>,
+        d_model: usize,
+    },
+}
+
+impl<B: Backend> QkvProjection<B> {
+    pub(super) fn new(d_model: usize, fused: bool) -> Self {
+        let linear = || Linear::new(&LinearConfig::new(d_model, d_model));
+
+        let projections = [linear(), linear(), linear()].map(Param::from);
+
+        match fused {
+            true => Self::fuse(projections),
+            false => Self::Separate(projections),
+        }
+    }
+
+    /// Compute the query, key and value projections of the same input.
+    pub(super) fn forward_all(&self, x: Tensor<B, 3>) -> [Tensor<B, 3>; 3] {
+        match self {
+            Self::Separate(projections) => {
+                [0, 1, 2].map(|index| projections[index].forward(x.clone()))
+            }
+            Self::Fused { qkv, .. } => qkv.forward(x).chunk(3, 2).try_into().unwrap(),
+        }
+    }
+
+    /// Compute a single projection, where the index is 0 for the query, 1 for the key and 2 for
+    /// the value.
+    pub(super) fn forward(&self, x: Tensor<B, 3>, index: usize) -> Tensor<B, 3> {
+        match self {
+            Self::Separate(projections) => projections[index].forward(x),
+            Self::Fused { qkv, d_model } => {
+                let range = index * d_model..(index + 1) * d_model;
+                let weight = qkv.weight.val().index([0..*d_model, range.clone()]);
+                let output = x.matmul(weight.unsqueeze());
+
+                match qkv.bias.val() {
+                    Some(bias) => output + bias.index([range]).unsqueeze(),
+                    None => output,
+                }
+            }
+        }
+    }
+
+    fn fuse(projections: [Param<Linear<B>>; 3]) -> Self {
+        let [d_model, _] = projections[0].weight.shape().dims;
+        let [query, key, value] = projections;
+
+        let weight = Tensor::cat(
+            [&query, &key, &value]
+                .map(|linear| linear.weight.val())
+                .to_vec(),
+            1,
+        );
+        let bias = match [&query, &key, &value].map(|linear| linear.bias.val()) {
+            [Some(query), Some(key), Some(value)] => Some(Tensor::cat(vec![query, key, value], 0)),
+            _ => None,
+        };
+
+        let qkv = Linear {
+            weight: with_tensor(&query.weight, weight),
+            bias: Param {
+                id: query.bias.id.clone(),
+                value: bias.map(|bias| require_grad(bias.detach(), query.bias.trainable)),
+                trainable: query.bias.trainable,
+            },
+            activation: query.activation.clone(),
+        };
+
+        Self::Fused {
+            qkv: Param {
+                id: query.id.clone(),
+                value: qkv,
+                trainable: query.trainable,
+            },
+            d_model,
+        }
+    }
+
+    /// Split the fused projection into separate ones, the key and the value projections get ids
+    /// derived from the fused ones.
+    fn split(qkv: &Param<Linear<B>>, d_model: usize) -> [Param<Linear<B>>; 3] {
+        [0, 1, 2].map(|index| {
+            let range = index * d_model..(index + 1) * d_model;
+            let id = |id: &ParamId| match index {
+                0 => id.clone(),
+                _ => ParamId::from(format!("{}-{}", id, PROJECTIONS[index]).as_str()),
+            };
+
+            let weight = qkv.weight.val().index([0..d_model, range.clone()]);
+            let bias = qkv.bias.val().map(|bias| bias.index([range]));
+            let linear = Linear {
+                weight: Param {
+                    id: id(&qkv.weight.id),
+                    ..with_tensor(&qkv.weight, weight)
+                },
+                bias: Param {
+                    id: id(&qkv.bias.id),
+                    value: bias.map(|bias| require_grad(bias.detach(), qkv.bias.trainable)),
+                    trainable: qkv.bias.trainable,
+                },
+                activation: qkv.activation.clone(),
+            };
+
+            Param {
+                id: id(&qkv.id),
+                value: linear,
+                trainable: qkv.trainable,
+            }
+        })
+    }
+
+    /// Register the state of the query, key and value projections in the given state.
+    pub(super) fn register_state(&self, state: &mut StateNamed<B::FloatElem>) {
+        let projections = match self {
+            Self::Separate(projections) => projections.clone(),
+            Self::Fused { qkv, d_model } => Self::split(qkv, *d_model),
+        };
+
+        for (projection, name) in projections.iter().zip(PROJECTIONS) {
+            state.register_state(name, projection.state());
+        }
+    }
+
+    fn projections(&self) -> Vec<&Param<Linear<B>>> {
+        match self {
+            Self::Separate(projections) => projections.iter().collect(),
+            Self::Fused { qkv, .. } => vec![qkv],
+        }
+    }
+}
+
+fn with_tensor<B: Backend, const D: usize>(
+    param: &Param<Tensor<B, D>>,
+    tensor: Tensor<B, D>,
+) -> Param<Tensor<B, D>> {
+    Param {
+        id: param.id.clone(),
+        value: require_grad(tensor.detach(), param.trainable),
+        trainable: param.trainable,
+    }
+}
+
+fn require_grad<B: Backend, const D: usize>(tensor: Tensor<B, D>, trainable: bool) -> Tensor<B, D> {
+    match trainable {
+        true => tensor.require_grad(),
+        false => tensor,
+    }
+}
+
+impl<B: Backend> Module for QkvProjection<B> {
+    type Backend = B;
+
+    fn devices(&self) -> Vec<B::Device> {
+        self.projections()
+            .into_iter()
+            .flat_map(|projection| projection.devices())
+            .collect()
+    }
+
+    fn to_device(self, device: &B::Device) -> Self {
+        match self {
+            Self::Separate(projections) => {
+                Self::Separate(projections.map(|projection| projection.to_device(device)))
+            }
+            Self::Fused { qkv, d_model } => Self::Fused {
+                qkv: qkv.to_device(device),
+                d_model,
+            },
+        }
+    }
+
+    fn load(self, state: &State<B::FloatElem>) -> Result<Self, LoadingError> {
+        let projections = match &self {
+            Self::Separate(projections) => projections.clone(),
+            Self::Fused { qkv, d_model } => Self::split(qkv, *d_model),
+        };
+
+        let mut loaded = Vec::with_capacity(3);
+        for (projection, name) in projections.into_iter().zip(PROJECTIONS) {
+            let state = state
+                .get(name)
+                .ok_or_else(|| LoadingError::new(format!("Missing module '{name}' from state")))?;
+            let projection = projection
+                .load(state)
+                .map_err(|err| LoadingError::new(format!("Can't load module {name}: {err}")))?;
+
+            loaded.push(projection);
+        }
+
+        let projections: [Param<Linear<B>>; 3] = loaded
+            .try_into()
+            .map_err(|_| LoadingError::new("Can't load the projections".to_string()))?;
+
+        Ok(match self {
+            Self::Separate(_) => Self::Separate(projections),
+            Self::Fused { .. } => Self::fuse(projections),
+        })
+    }
+
+    fn state(&self) -> State<B::FloatElem> {
+        let mut state = StateNamed::new();
+        self.register_state(&mut state);
+
+        State::StateNamed(state)
+    }
+
+    fn detach(self) -> Self {
+        match self {
+            Self::Separate(projections) => {
+                Self::Separate(projections.map(|projection| projection.detach()))
+            }
+            Self::Fused { qkv, d_model } => Self::Fused {
+                qkv: qkv.detach(),
+                d_model,
+            },
+        }
+    }
+
+    /// The fused projection is visited as the query projection, whose ids it keeps.
+    fn visit<V: ModuleVisitor<Self::Backend>>(&self, visitor: &mut V) {
+        for (projection, name) in self.projections().into_iter().zip(PROJECTIONS) {
+            visitor.enter_module(name);
+            projection.visit(visitor);
+            visitor.exit_module(name);
+        }
+    }
+
+    fn map<M: ModuleMapper<Self::Backend>>(self, mapper: &mut M) -> Self {
+        match self {
+            Self::Separate(projections) => {
+                Self::Separate(projections.map(|projection| projection.map(mapper)))
+            }
+            Self::Fused { qkv, d_model } => Self::Fused {
+                qkv: qkv.map(mapper),
+                d_model,
+            },
+        }
+    }
+}
+
+impl<B: ADBackend> ADModule for QkvProjection<B> {
+    type ADBackend = B;
+    type InnerModule = QkvProjection<B::InnerBackend>;
+
+    fn inner(self) -> Self::InnerModule {
+        match self {
+            Self::Separate(projections) => {
+                QkvProjection::Separate(projections.map(|projection| projection.inner()))
+            }
+            Self::Fused { qkv, d_model } => QkvProjection::Fused {
+                qkv: qkv.inner(),
+                d_model,
+            },
+        }
+    }
+
+    fn from_inner(module: Self::InnerModule) -> Self {
+        match module {
+            QkvProjection::Separate(projections) => {
+                Self::Separate(projections.map(ADModule::from_inner))
+            }
+            QkvProjection::Fused { qkv, d_model } => Self::Fused {
+                qkv: ADModule::from_inner(qkv),
+                d_model,
+            },
+        }
+    }
+}
+
+impl<B: Backend> core::fmt::Display for QkvProjection<B> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "QkvProjection[num_params={}]", self.num_params())
+    }
+}
@@ -0,0 +1,213 @@
+use alloc::{vec, vec::Vec};
+
+use crate as burn;
+
+use crate::config::Config;
+use crate::tensor::{activation::log_softmax, backend::Backend, ElementConversion, Tensor};
+use libm::pow;
+
+/// Configuration of the [beam search](beam_search) decoder.
+#[derive(Config)]
+pub struct BeamSearchConfig {
+    /// The number of hypotheses kept at each step.
+    pub beam_width: usize,
+    /// The maximum number of tokens generated after the prompt.
+    pub max_length: usize,
+    /// The token ending a sequence.
+    pub eos_id: usize,
+    /// The exponent applied to the generated length when normalizing the score of a hypothesis,
+    /// values higher than zero favor longer sequences. Default: 1.0
+    #[config(default = 1.0)]
+    pub length_penalty: f64,
+}
+
+/// A sequence generated by the [beam search](beam_search) decoder.
+#[derive(Debug, Clone)]
+pub struct BeamHypothesis {
+    /// The prompt followed by the generated tokens, including the end of sequence token when
+    /// reached.
+    pub tokens: Vec<usize>,
+    /// The sum of the log probabilities of the generated tokens.
+    pub log_prob: f64,
+    /// The log probability normalized by the generated length.
+    pub score: f64,
+}
+
+struct Beam<C> {
+    tokens: Vec<usize>,
+    log_prob: f64,
+    cache: C,
+    finished: bool,
+}
+
+/// Generate sequences starting from the prompt using beam search.
+///
+/// The `step` function receives the tokens of a hypothesis with its own cache, which is usually an
+/// [autoregressive cache](crate::nn::transformer::TransformerEncoderAutoregressiveCache), and
+/// returns the logits of the next token with shape `[vocab_size]`. The cache of a hypothesis is
+/// cloned each time it is expanded into multiple hypotheses.
+///
+/// The hypotheses are returned from the best to the worst score.
+pub fn beam_search<B, C, F>(
+    config: &BeamSearchConfig,
+    prompt: Vec<usize>,
+    cache: C,
+    mut step: F,
+) -> Vec<BeamHypothesis>
+where
+    B: Backend,
+    C: Clone,
+    F: FnMut(&[usize], &mut C) -> Tensor<B, 1>,
+{
+    let prompt_length = prompt.len();
+    let score = |beam: &Beam<C>| {
+        let length = usize::max(beam.tokens.len() - prompt_length, 1);
+        beam.log_prob / pow(length as f64, config.length_penalty)
+    };
+
+    let mut beams = vec![Beam {
+        tokens: prompt,
+        log_prob: 0.0,
+        cache,
+        finished: false,
+    }];
+
+    for _ in 0..config.max_length {
+        if beams.iter().all(|beam| beam.finished) {
+            break;
+        }
+
+        // Candidates are referenced by the index of their parent beam and their next token.
+        let mut candidates = Vec::new();
+
+        for (index, beam) in beams.iter_mut().enumerate() {
+            if beam.finished {
+                candidates.push((index, None, beam.log_prob));
+                continue;
+            }
+
+            let logits = step(&beam.tokens, &mut beam.cache);
+            let log_probs = log_softmax(logits, 0).into_data().value;
+            let mut log_probs = log_probs
+                .into_iter()
+                .map(|log_prob| log_prob.elem::<f64>())
+                .enumerate()
+                .collect::<Vec<_>>();
+
+            log_probs.sort_by(|(_, a), (_, b)| b.partial_cmp(a).unwrap());
+            log_probs.truncate(config.beam_width);
+
+            for (token, log_prob) in log_probs {
+                candidates.push((index, Some(token), beam.log_prob + log_prob));
+            }
+        }
+
+        let mut candidates = candidates
+            .into_iter()
+            .map(|(index, token, log_prob)| {
+                let parent = &beams[index];
+                let mut tokens = parent.tokens.clone();
+
+                if let Some(token) = token {
+                    tokens.push(token);
+                }
+
+                let beam = Beam {
+                    finished: token.map(|token| token == config.eos_id).unwrap_or(true),
+                    tokens,
+                    log_prob,
+                    cache: parent.cache.clone(),
+                };
+                (score(&beam), beam)
+            })
+            .collect::<Vec<_>>();
+
+        candidates.sort_by(|(a, _), (b, _)| b.partial_cmp(a).unwrap());
+        candidates.truncate(config.beam_width);
+
+        beams = candidates.into_iter().map(|(_, beam)| beam).collect();
+    }
+
+    let mut hypotheses = beams
+        .into_iter()
+        .map(|beam| BeamHypothesis {
+            score: score(&beam),
+            tokens: beam.tokens,
+            log_prob: beam.log_prob,
+        })
+        .collect::<Vec<_>>();
+
+    hypotheses.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap());
+    hypotheses
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TestBackend;
+    use libm::log;
+
+    const EOS: usize = 0;
+
+    /// The next token probabilities only depend on the last token, greedy decoding picks the
+    /// token 1 first, but the sequence `[2, EOS]` is more probable.
+    fn probs(last_token: usize) -> [f64; 3] {
+        match last_token {
+            1 => [0.34, 0.33, 0.33],
+            2 => [0.98, 0.01, 0.01],
+            _ => [0.0001, 0.5999, 0.4],
+        }
+    }
+
+    fn sequence_log_prob(tokens: &[usize]) -> f64 {
+        let mut last_token = usize::MAX;
+        let mut log_prob = 0.0;
+
+        for token in tokens {
+            log_prob += log(probs(last_token)[*token]);
+            last_token = *token;
+        }
+
+        log_prob
+    }
+
+    #[test]
+    fn beam_search_should_return_the_most_probable_sequence() {
+        let config = BeamSearchConfig::new(2, 3, EOS).with_length_penalty(0.0);
+
+        let hypotheses = beam_search(&config, vec![], (), |tokens: &[usize], _cache| {
+            let probs = probs(tokens.last().cloned().unwrap_or(usize::MAX));
+            Tensor::<TestBackend, 1>::from_floats(probs.map(|prob| log(prob) as f32))
+        });
+
+        // Every sequence of at most 3 tokens.
+        let mut sequences: Vec<Vec<usize>> = vec![vec![]];
+        for _ in 0..3 {
+            let mut sequences_next = Vec::new();
+            for sequence in sequences.iter() {
+                if sequence.last() == Some(&EOS) {
+                    sequences_next.push(sequence.clone());
+                    continue;
+                }
+                for token in 0..3 {
+                    let mut sequence = sequence.clone();
+                    sequence.push(token);
+                    sequences_next.push(sequence);
+                }
+            }
+            sequences = sequences_next;
+        }
+        let best = sequences
+            .into_iter()
+            .max_by(|a, b| {
+                sequence_log_prob(a)
+                    .partial_cmp(&sequence_log_prob(b))
+                    .unwrap()
+            })
+            .unwrap();
+
+        assert_eq!(best, vec![2, EOS]);
+        assert_eq!(hypotheses[0].tokens, best);
+        assert!((hypotheses[0].log_prob - sequence_log_prob(&best)).abs() < 1e-4);
+    }
+}
@@ -14,22 +14,95 @@ impl<B: Backend, const D: usize> TensorCache<B, D> {
     where
         F: Fn(Tensor<B, 3>) -> Tensor<B, D>,
     {
+        if let Some(max_length) = self.max_length {
+            return self.forward_autoregressive_preallocated(tensor, dim_cat, max_length, func);
+        }
+
         let mut tensor_old = None;
         core::mem::swap(&mut self.state, &mut tensor_old);
 
         let tensor_new = match tensor_old {
             Some(tensor_old) => {
-                let [batch_size, seq_length, d_model] = tensor.dims();
-                let next_seq_token =
-                    tensor.index([0..batch_size, (seq_length - 1)..seq_length, 0..d_model]);
-                let next_seq_token = func(next_seq_token);
+                let next_seq_token = func(next_seq_token(tensor));
 
                 Tensor::cat(vec![tensor_old, next_seq_token], dim_cat)
             }
             None => func(tensor),
         };
+        let tensor_new = match self.window {
+            Some(window) => evict_oldest(tensor_new, dim_cat, window),
+            None => tensor_new,
+        };
 
         self.state = Some(tensor_new.clone());
         tensor_new
     }
+
+    fn forward_autoregressive_preallocated<F>(
+        &mut self,
+        tensor: Tensor<B, 3>,
+        dim_cat: usize,
+        max_length: usize,
+        func: F,
+    ) -> Tensor<B, D>
+    where
+        F: Fn(Tensor<B, 3>) -> Tensor<B, D>,
+    {
+        let mut buffer = None;
+        core::mem::swap(&mut self.state, &mut buffer);
+
+        let (buffer, tensor_new) = match buffer {
+            Some(buffer) => (buffer, func(next_seq_token(tensor))),
+            None => {
+                let tensor_new = func(tensor);
+                let mut dims = tensor_new.dims();
+                dims[dim_cat] = max_length;
+
+                (Tensor::zeros_device(dims, &tensor_new.device()), tensor_new)
+            }
+        };
+
+        let dims = tensor_new.dims();
+        let length = self.length + dims[dim_cat];
+
+        if length > max_length {
+            panic!("The autoregressive cache is full, the maximum length is {max_length}");
+        }
+
+        let mut indexes = dims.map(|dim| 0..dim);
+        indexes[dim_cat] = self.length..length;
+        let buffer = buffer.index_assign(indexes, tensor_new);
+
+        let mut indexes = buffer.dims().map(|dim| 0..dim);
+        indexes[dim_cat] = 0..length;
+        let output = buffer.clone().index(indexes);
+
+        self.state = Some(buffer);
+        self.length = length;
+
+        output
+    }
+}
+
+fn evict_oldest<B: Backend, const D: usize>(
+    tensor: Tensor<B, D>,
+    dim: usize,
+    window: usize,
+) -> Tensor<B, D> {
+    let dims = tensor.dims();
+
+    if dims[dim] <= window {
+        return tensor;
+    }
+
+    let mut indexes = dims.map(|dim| 0..dim);
+    indexes[dim] = dims[dim] - window..dims[dim];
+
+    tensor.index(indexes)
+}
+
+fn next_seq_token<B: Backend>(tensor: Tensor<B, 3>) -> Tensor<B, 3> {
+    let [batch_size, seq_length, d_model] = tensor.dims();
+
+    tensor.index([0..batch_size, (seq_length - 1)..seq_length, 0..d_model])
 }
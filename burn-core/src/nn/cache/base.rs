@@ -1,13 +1,41 @@
 use crate::tensor::backend::Backend;
 use crate::tensor::Tensor;
 
-#[derive(Default)]
+#[derive(Default, Clone)]
 pub struct TensorCache<B: Backend, const D: usize> {
     pub(crate) state: Option<Tensor<B, D>>,
+    /// When set, the state is a buffer preallocated to this length along the cached dimension.
+    pub(crate) max_length: Option<usize>,
+    /// The number of valid entries in the preallocated buffer.
+    pub(crate) length: usize,
+    /// When set, only the most recent entries up to this length are kept.
+    pub(crate) window: Option<usize>,
 }
 
 impl<B: Backend, const D: usize> TensorCache<B, D> {
     pub fn new() -> Self {
         Self::default()
     }
+
+    /// Create a cache writing into a buffer of `max_length` entries instead of concatenating new
+    /// entries at each step.
+    pub fn preallocated(max_length: usize) -> Self {
+        Self {
+            state: None,
+            max_length: Some(max_length),
+            length: 0,
+            window: None,
+        }
+    }
+
+    /// Create a cache keeping only the last `window` entries, the oldest entries are evicted when
+    /// new ones are added.
+    pub fn windowed(window: usize) -> Self {
+        Self {
+            state: None,
+            max_length: None,
+            length: 0,
+            window: Some(window),
+        }
+    }
 }
@@ -8,8 +8,8 @@ use crate::module::Param;
 use crate::nn::Initializer;
 use crate::tensor::backend::Backend;
 use crate::tensor::Tensor;
-use burn_tensor::module::conv2d;
-use burn_tensor::ops::conv::calculate_padding;
+use burn_tensor::module::{conv2d, memory_format};
+use burn_tensor::ops::{conv::calculate_padding, MemoryFormat};
 
 use libm::sqrt;
 
@@ -29,6 +29,10 @@ pub struct Conv2dConfig {
     /// The type of function used to initialize neural network parameters
     #[config(default = "Initializer::UniformDefault")]
     pub initializer: Initializer,
+    /// If the convolution should use the [channels-last](MemoryFormat::ChannelsLast) memory
+    /// format internally, the input and the output keep the default format. Default: false
+    #[config(default = false)]
+    pub channels_last: bool,
 }
 
 /// Padding configuration for 2D convolution [config](Conv2dConfig).
@@ -54,11 +58,12 @@ pub enum Conv2dPaddingConfig {
 ///     where `k = sqrt(1 / channels_in * kernel_size_1 * kernel_size_2)`
 #[derive(Module, Debug)]
 pub struct Conv2d<B: Backend> {
-    weight: Param<Tensor<B, 4>>,
-    bias: Param<Option<Tensor<B, 1>>>,
+    pub(crate) weight: Param<Tensor<B, 4>>,
+    pub(crate) bias: Param<Option<Tensor<B, 1>>>,
     stride: [usize; 2],
     kernel_size: [usize; 2],
     padding: Conv2dPaddingConfig,
+    channels_last: bool,
 }
 
 impl<B: Backend> Conv2d<B> {
@@ -92,6 +97,7 @@ impl<B: Backend> Conv2d<B> {
             stride: [1, 1], // TODO: Add the stride to the configuration when properly supported.
             kernel_size: config.kernel_size,
             padding: config.padding.clone(),
+            channels_last: config.channels_last,
         }
     }
 
@@ -102,17 +108,34 @@ impl<B: Backend> Conv2d<B> {
     /// - input: [batch_size, channels_in, height_in, width_in],
     /// - output: [batch_size, channels_out, height_out, width_out],
     pub fn forward(&self, input: Tensor<B, 4>) -> Tensor<B, 4> {
+        self.forward_with_weight(input, self.weight.val())
+    }
+
+    /// Applies the forward pass with the given weight in place of the module's weight, it must
+    /// have the same shape.
+    pub(crate) fn forward_with_weight(
+        &self,
+        input: Tensor<B, 4>,
+        weight: Tensor<B, 4>,
+    ) -> Tensor<B, 4> {
         let [_batch_size, _channels_in, height_in, width_in] = input.dims();
         let padding =
             self.padding
                 .calculate_padding_2d(height_in, width_in, &self.kernel_size, &self.stride);
-        conv2d(
-            input,
-            self.weight.val(),
+
+        if !self.channels_last {
+            return conv2d(input, weight, self.bias.val(), self.stride, padding);
+        }
+
+        let output = conv2d(
+            memory_format(input, MemoryFormat::ChannelsLast),
+            memory_format(weight, MemoryFormat::ChannelsLast),
             self.bias.val(),
             self.stride,
             padding,
-        )
+        );
+
+        memory_format(output, MemoryFormat::Contiguous)
     }
 }
 
@@ -169,4 +192,22 @@ mod tests {
             assert_eq!(*item, 0.0f32);
         }
     }
+
+    #[test]
+    fn channels_last_should_match_default_format() {
+        let config = Conv2dConfig::new([3, 4], [3, 3]).with_padding(Conv2dPaddingConfig::Same);
+        let conv: Conv2d<TB> = Conv2d::new(&config);
+        let conv_channels_last = Conv2d {
+            channels_last: true,
+            ..conv.clone()
+        };
+        let input = Tensor::<TB, 4>::random([2, 3, 5, 5], burn_tensor::Distribution::Standard);
+
+        let output = conv.forward(input.clone());
+        let output_channels_last = conv_channels_last.forward(input);
+
+        output
+            .into_data()
+            .assert_approx_eq(&output_channels_last.into_data(), 3);
+    }
 }
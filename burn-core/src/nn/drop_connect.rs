@@ -0,0 +1,126 @@
+use alloc::{format, vec::Vec};
+
+use crate as burn;
+
+use crate::config::Config;
+use crate::module::Module;
+use crate::module::Param;
+use crate::tensor::{backend::Backend, Distribution, Tensor};
+
+use super::{Linear, LinearConfig};
+
+/// Configuration to create a [DropConnect linear](DropConnectLinear) layer.
+#[derive(Config)]
+pub struct DropConnectLinearConfig {
+    /// The size of the input features.
+    pub d_input: usize,
+    /// The size of the output features.
+    pub d_output: usize,
+    /// The probability of randomly zeroes some weights during training.
+    pub prob: f64,
+    /// If a bias should be applied during the linear transformation.
+    #[config(default = true)]
+    pub bias: bool,
+}
+
+/// Applies a linear transformation to the input tensor, setting at random some elements of the
+/// weight matrix to zero during training.
+///
+/// This is the regularization technique described in the paper
+/// [Regularization of Neural Networks using DropConnect](https://proceedings.mlr.press/v28/wan13.html).
+///
+/// A new mask is sampled for each forward pass, and the kept weights are scaled to
+/// `1 / (1 - prob)`. Outside of training, the layer behaves like a [linear](Linear) layer.
+#[derive(Module, Debug)]
+pub struct DropConnectLinear<B: Backend> {
+    linear: Param<Linear<B>>,
+    prob: f64,
+}
+
+impl<B: Backend> DropConnectLinear<B> {
+    /// Create the module from the given configuration.
+    pub fn new(config: &DropConnectLinearConfig) -> Self {
+        let linear =
+            Linear::new(&LinearConfig::new(config.d_input, config.d_output).with_bias(config.bias));
+
+        Self {
+            linear: Param::from(linear),
+            prob: config.prob,
+        }
+    }
+
+    /// Applies the forward pass on the input tensor.
+    ///
+    /// # Shapes
+    ///
+    /// - input: `[..., any, d_input]`
+    /// - output: `[..., any, d_output]`
+    pub fn forward<const D: usize>(&self, input: Tensor<B, D>) -> Tensor<B, D> {
+        if !B::ad_enabled() || self.prob == 0.0 {
+            return self.linear.forward(input);
+        }
+
+        let output = input.matmul(self.masked_weight().unsqueeze());
+
+        match self.linear.bias.val() {
+            Some(bias) => output + bias.unsqueeze(),
+            None => output,
+        }
+    }
+
+    fn masked_weight(&self) -> Tensor<B, 2> {
+        let prob_keep = 1.0 - self.prob;
+        let weight = self.linear.weight.val();
+        let mask = weight.random_like(Distribution::Bernoulli(prob_keep));
+
+        (weight * mask) * (1.0 / prob_keep)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TestBackend;
+
+    #[test]
+    fn with_fixed_seed_should_zero_expected_fraction_of_weights() {
+        TestBackend::seed(0);
+        let layer =
+            DropConnectLinear::<TestBackend>::new(&DropConnectLinearConfig::new(100, 100, 0.3));
+
+        let weight = layer.masked_weight().into_data();
+        let zeros = weight.value.iter().filter(|value| **value == 0.0).count();
+        let fraction = zeros as f64 / weight.value.len() as f64;
+
+        assert!(
+            (fraction - 0.3).abs() < 0.02,
+            "fraction of zeros: {fraction}"
+        );
+    }
+
+    #[test]
+    fn without_ad_backend_should_equal_linear_forward() {
+        let layer = DropConnectLinear::<TestBackend>::new(&DropConnectLinearConfig::new(5, 3, 0.5));
+        let input = Tensor::<TestBackend, 2>::random([2, 5], Distribution::Standard);
+
+        let output = layer.forward(input.clone());
+        let expected = layer.linear.forward(input);
+
+        assert_eq!(output.into_data(), expected.into_data());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn with_ad_backend_should_drop_weights() {
+        use crate::TestADBackend;
+
+        let layer =
+            DropConnectLinear::<TestADBackend>::new(&DropConnectLinearConfig::new(20, 20, 0.5));
+        let input = Tensor::<TestADBackend, 2>::ones([1, 20]);
+
+        let output = layer.forward(input.clone());
+        let expected = layer.linear.forward(input);
+
+        assert_ne!(output.into_data(), expected.into_data());
+    }
+}
@@ -1,7 +1,10 @@
+use alloc::vec::Vec;
+
 use crate as burn;
 use crate::config::Config;
 use crate::tensor::backend::Backend;
-use crate::tensor::{Distribution, Tensor};
+use crate::tensor::{Data, Distribution, ElementConversion, Tensor};
+use rand::{rngs::StdRng, Rng};
 
 /// Configuration to create a [Dropout](Dropout) layer.
 #[derive(Config)]
@@ -40,7 +43,48 @@ impl Dropout {
 
         let prob_keep = 1.0 - self.prob;
         let random = input.random_like(Distribution::Bernoulli(prob_keep));
-        let x = input * random;
+
+        Self::apply_mask(input, random, prob_keep)
+    }
+
+    /// Applies the forward pass on the input tensor, sampling the dropped elements with the given
+    /// random number generator instead of the backend one.
+    ///
+    /// The same seeded generator always drops the same elements, making the forward pass
+    /// reproducible regardless of the global state of the backend.
+    ///
+    /// # Shapes
+    ///
+    /// - input: `[..., any]`
+    /// - output: `[..., any]`
+    pub fn forward_with_rng<B: Backend, const D: usize>(
+        &self,
+        input: Tensor<B, D>,
+        rng: &mut StdRng,
+    ) -> Tensor<B, D> {
+        if !B::ad_enabled() || self.prob == 0.0 {
+            return input;
+        }
+
+        let prob_keep = 1.0 - self.prob;
+        let shape = input.shape();
+        let values: Vec<B::FloatElem> = (0..shape.num_elements())
+            .map(|_| match rng.gen_bool(prob_keep) {
+                true => 1.0.elem(),
+                false => 0.0.elem(),
+            })
+            .collect();
+        let random = Tensor::from_data_device(Data::new(values, shape), &input.device());
+
+        Self::apply_mask(input, random, prob_keep)
+    }
+
+    fn apply_mask<B: Backend, const D: usize>(
+        input: Tensor<B, D>,
+        mask: Tensor<B, D>,
+        prob_keep: f64,
+    ) -> Tensor<B, D> {
+        let x = input * mask;
 
         x * (1.0 / prob_keep)
     }
@@ -77,4 +121,22 @@ mod tests {
 
         assert_eq!(tensor.to_data(), output.to_data());
     }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn with_same_seed_should_drop_same_elements() {
+        use rand::SeedableRng;
+
+        let tensor = Tensor::<TestADBackend, 2>::ones(Shape::new([20, 20]));
+        let dropout = Dropout::new(&DropoutConfig { prob: 0.5 });
+
+        let output_1 = dropout.forward_with_rng(tensor.clone(), &mut StdRng::seed_from_u64(42));
+        TestADBackend::seed(7);
+        let output_2 = dropout.forward_with_rng(tensor.clone(), &mut StdRng::seed_from_u64(42));
+        let output_3 = dropout.forward_with_rng(tensor.clone(), &mut StdRng::seed_from_u64(43));
+
+        assert_ne!(tensor.to_data(), output_1.to_data());
+        assert_eq!(output_1.to_data(), output_2.to_data());
+        assert_ne!(output_1.to_data(), output_3.to_data());
+    }
 }
@@ -0,0 +1,106 @@
+use crate as burn;
+use crate::config::Config;
+use crate::tensor::backend::Backend;
+use crate::tensor::Tensor;
+
+/// Configuration to create a [Flatten](Flatten) layer.
+#[derive(Config)]
+pub struct FlattenConfig {
+    /// The first dimension to flatten.
+    #[config(default = 1)]
+    pub start_dim: usize,
+    /// The last dimension to flatten (inclusive), the last dimension of the input by default.
+    pub end_dim: Option<usize>,
+}
+
+/// Flatten a contiguous range of dimensions into a single one.
+///
+/// With the default configuration, an input of shape `[batch, channels, height, width]` is
+/// reshaped to `[batch, channels * height * width]`, which is useful between convolutional and
+/// linear layers.
+#[derive(Clone, Debug)]
+pub struct Flatten {
+    start_dim: usize,
+    end_dim: Option<usize>,
+}
+
+impl Flatten {
+    /// Create the module from the given configuration.
+    pub fn new(config: &FlattenConfig) -> Self {
+        Self {
+            start_dim: config.start_dim,
+            end_dim: config.end_dim,
+        }
+    }
+
+    /// Applies the forward pass on the input tensor.
+    ///
+    /// # Shapes
+    ///
+    /// - input: `[d_0, ..., d_start, ..., d_end, ..., d_n]`
+    /// - output: `[d_0, ..., d_start * ... * d_end, ..., d_n]`
+    ///
+    /// # Panics
+    ///
+    /// If the dimensions are out of range or if `D2` doesn't match the number of dimensions after
+    /// flattening.
+    pub fn forward<B: Backend, const D: usize, const D2: usize>(
+        &self,
+        input: Tensor<B, D>,
+    ) -> Tensor<B, D2> {
+        let end_dim = self.end_dim.unwrap_or(D - 1);
+
+        if self.start_dim > end_dim || end_dim >= D {
+            panic!(
+                "Can't flatten dimensions {}..={} of a tensor with {} dimensions",
+                self.start_dim, end_dim, D
+            );
+        }
+        if D2 != D - (end_dim - self.start_dim) {
+            panic!(
+                "Flattening dimensions {}..={} of a {}D tensor produces a {}D tensor, got {}D",
+                self.start_dim,
+                end_dim,
+                D,
+                D - (end_dim - self.start_dim),
+                D2
+            );
+        }
+
+        let dims = input.dims();
+        let mut dims_new = [0; D2];
+
+        dims_new[..self.start_dim].copy_from_slice(&dims[..self.start_dim]);
+        dims_new[self.start_dim] = dims[self.start_dim..=end_dim].iter().product();
+        dims_new[self.start_dim + 1..].copy_from_slice(&dims[end_dim + 1..]);
+
+        input.reshape(dims_new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tensor::Shape;
+    use crate::TestBackend;
+
+    #[test]
+    fn flatten_should_merge_the_last_three_dims() {
+        let tensor = Tensor::<TestBackend, 4>::ones(Shape::new([2, 3, 4, 5]));
+        let flatten = Flatten::new(&FlattenConfig::new());
+
+        let output = flatten.forward::<_, 4, 2>(tensor);
+
+        assert_eq!(output.shape(), Shape::new([2, 60]));
+    }
+
+    #[test]
+    fn flatten_should_merge_the_given_dims() {
+        let tensor = Tensor::<TestBackend, 4>::ones(Shape::new([2, 3, 4, 5]));
+        let flatten = Flatten::new(&FlattenConfig::new().with_start_dim(1).with_end_dim(Some(2)));
+
+        let output = flatten.forward::<_, 4, 3>(tensor);
+
+        assert_eq!(output.shape(), Shape::new([2, 12, 5]));
+    }
+}
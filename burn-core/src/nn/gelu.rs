@@ -2,13 +2,24 @@ use crate::tensor::backend::Backend;
 use crate::tensor::Tensor;
 
 /// Applies the Gaussian Error Linear Units function element-wise.
+///
+/// The exact formulation is used by default, the [tanh approximation](GELU::with_approximate) can
+/// be used instead to match models trained with it.
 #[derive(Clone, Debug, Default)]
-pub struct GELU {}
+pub struct GELU {
+    approximate: bool,
+}
 
 impl GELU {
     /// Create the module from the given configuration.
     pub fn new() -> Self {
-        Self {}
+        Self { approximate: false }
+    }
+
+    /// Use the tanh approximation instead of the exact formulation.
+    pub fn with_approximate(mut self, approximate: bool) -> Self {
+        self.approximate = approximate;
+        self
     }
 
     /// Applies the forward pass on the input tensor.
@@ -18,6 +29,29 @@ impl GELU {
     /// - input: `[..., any]`
     /// - output: `[..., any]`
     pub fn forward<B: Backend, const D: usize>(&self, input: Tensor<B, D>) -> Tensor<B, D> {
-        crate::tensor::activation::gelu(input)
+        match self.approximate {
+            true => crate::tensor::activation::gelu_tanh(input),
+            false => crate::tensor::activation::gelu(input),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TestBackend;
+
+    #[test]
+    fn approximate_should_be_close_but_not_equal_to_exact() {
+        let input = Tensor::<TestBackend, 1>::from_floats([-3.0, -1.0, -0.5, 0.5, 1.0, 3.0]);
+
+        let output_exact = GELU::new().forward(input.clone()).into_data();
+        let output_approximate = GELU::new()
+            .with_approximate(true)
+            .forward(input)
+            .into_data();
+
+        assert_ne!(output_exact, output_approximate);
+        output_exact.assert_approx_eq(&output_approximate, 2);
     }
 }
@@ -6,6 +6,8 @@ use crate::tensor::{Distribution, ElementConversion, Tensor};
 
 use crate as burn;
 
+use libm::sqrt;
+
 #[derive(Config, Debug, PartialEq)]
 pub enum Initializer {
     Uniform(f64, f64),
@@ -18,6 +20,16 @@ pub enum Initializer {
 }
 
 impl Initializer {
+    /// The default uniform distribution of a layer with `d_input` input features,
+    /// `U(-k, k)` where `k = sqrt(1 / d_input)`, with its bounds multiplied by `scale`.
+    ///
+    /// The variance of the initial weights is then multiplied by `scale^2`.
+    pub fn scaled_uniform_default(d_input: usize, scale: f64) -> Self {
+        let k = sqrt(1.0 / d_input as f64) * scale;
+
+        Self::Uniform(-k, k)
+    }
+
     pub fn init<B: Backend, const D: usize, S: Into<Shape<D>>>(&self, shape: S) -> Tensor<B, D> {
         match self {
             Self::Uniform(a, b) => Tensor::<B, D>::random(
@@ -0,0 +1,61 @@
+use crate as burn;
+use crate::config::Config;
+use crate::tensor::backend::Backend;
+use crate::tensor::Tensor;
+
+/// Configuration to create a [LeakyReLU](LeakyReLU) layer.
+#[derive(Config)]
+pub struct LeakyReLUConfig {
+    /// The slope applied to negative inputs. Default: 0.01
+    #[config(default = 0.01)]
+    pub negative_slope: f64,
+}
+
+/// Applies the leaky rectified linear unit function element-wise:
+///
+/// `y = max(0, x) + negative_slope * min(0, x)`
+#[derive(Clone, Debug)]
+pub struct LeakyReLU {
+    negative_slope: f64,
+}
+
+impl LeakyReLU {
+    /// Create the module from the given configuration.
+    pub fn new(config: &LeakyReLUConfig) -> Self {
+        Self {
+            negative_slope: config.negative_slope,
+        }
+    }
+
+    /// Applies the forward pass on the input tensor.
+    ///
+    /// # Shapes
+    ///
+    /// - input: `[..., any]`
+    /// - output: `[..., any]`
+    pub fn forward<B: Backend, const D: usize>(&self, input: Tensor<B, D>) -> Tensor<B, D> {
+        let positive = crate::tensor::activation::relu(input.clone());
+        let negative = crate::tensor::activation::relu(input.neg());
+
+        positive.sub(negative.mul_scalar(self.negative_slope))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TestBackend;
+    use burn_tensor::Data;
+
+    #[test]
+    fn leaky_relu_should_scale_negative_inputs() {
+        let input = Tensor::<TestBackend, 2>::from_floats([[-2.0, -0.5], [0.0, 3.0]]);
+        let leaky_relu = LeakyReLU::new(&LeakyReLUConfig::new().with_negative_slope(0.1));
+
+        let output = leaky_relu.forward(input);
+
+        output
+            .into_data()
+            .assert_approx_eq(&Data::from([[-0.2, -0.05], [0.0, 3.0]]), 5);
+    }
+}
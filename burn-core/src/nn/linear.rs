@@ -7,9 +7,7 @@ use crate::module::Module;
 use crate::module::Param;
 use crate::tensor::{backend::Backend, Tensor};
 
-use libm::sqrt;
-
-use super::Initializer;
+use super::{Activation, Initializer};
 
 /// Configuration to create a [Linear](Linear) layer.
 #[derive(Config)]
@@ -24,12 +22,16 @@ pub struct LinearConfig {
     /// The type of function used to initialize neural network parameters
     #[config(default = "Initializer::UniformDefault")]
     pub initializer: Initializer,
+    /// The activation function applied after the linear transformation, if any.
+    pub activation: Option<Activation>,
 }
 
 /// Applies a linear transformation to the input tensor:
 ///
 /// `O = IW + b`
 ///
+/// followed by the configured [activation](Activation), if any.
+///
 /// # Params
 ///
 /// - weight: Matrix of shape `[d_input, d_output]` initialized from a uniform distribution:
@@ -39,17 +41,16 @@ pub struct LinearConfig {
 ///     `U(-k, k)`, where `k = sqrt(1 / d_input)`
 #[derive(Module, Debug)]
 pub struct Linear<B: Backend> {
-    weight: Param<Tensor<B, 2>>,
-    bias: Param<Option<Tensor<B, 1>>>,
+    pub(crate) weight: Param<Tensor<B, 2>>,
+    pub(crate) bias: Param<Option<Tensor<B, 1>>>,
+    pub(crate) activation: Option<Activation>,
 }
 
 impl<B: Backend> Linear<B> {
     /// Create the module from the given configuration.
     pub fn new(config: &LinearConfig) -> Self {
-        let k = sqrt(1.0 / config.d_input as f64);
-
         let initializer = if let Initializer::UniformDefault = config.initializer {
-            Initializer::Uniform(-k, k)
+            Initializer::scaled_uniform_default(config.d_input, 1.0)
         } else {
             config.initializer.clone()
         };
@@ -65,28 +66,55 @@ impl<B: Backend> Linear<B> {
         Self {
             weight: Param::from(weight),
             bias: Param::from(bias),
+            activation: config.activation.clone(),
         }
     }
 
     /// Applies the forward pass on the input tensor.
     ///
+    /// All the leading dimensions of the input, such as the batch and the sequence dimensions
+    /// of `[batch_size, seq_length, d_input]` inputs, are flattened before the transformation
+    /// and restored afterward.
+    ///
     /// # Shapes
     ///
     /// - input: `[..., any, d_input]`
     /// - output: `[..., any, d_output]`
+    ///
+    /// # Panics
+    ///
+    /// If the last dimension of the input isn't `d_input`.
     pub fn forward<const D: usize>(&self, input: Tensor<B, D>) -> Tensor<B, D> {
-        let output = input.matmul(self.weight.val().unsqueeze());
+        let [d_input, d_output] = self.weight.shape().dims;
+        let mut dims = input.shape().dims;
+        if dims[D - 1] != d_input {
+            panic!(
+                "The last dimension of the input should be {}, got shape {:?}",
+                d_input, dims
+            );
+        }
 
-        match self.bias.val() {
+        let num_rows = dims[..D - 1].iter().product();
+        let output = input.reshape([num_rows, d_input]).matmul(self.weight.val());
+        let output = match self.bias.val() {
             Some(bias) => output + bias.unsqueeze(),
             None => output,
-        }
+        };
+
+        let output = match &self.activation {
+            Some(activation) => activation.forward(output),
+            None => output,
+        };
+
+        dims[D - 1] = d_output;
+        output.reshape(dims)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use libm::sqrt;
     pub type TB = burn_ndarray::NdArrayBackend<f32>;
 
     #[test]
@@ -114,4 +142,94 @@ mod tests {
             assert_eq!(*item, 0.0f32);
         }
     }
+
+    #[test]
+    fn forward_3d_should_match_forward_on_each_position() {
+        use burn_tensor::Distribution;
+
+        let linear = Linear::<TB>::new(&LinearConfig::new(4, 3));
+        let input = Tensor::<TB, 3>::random([2, 5, 4], Distribution::Standard);
+
+        let output = linear.forward(input.clone());
+
+        assert_eq!(output.shape().dims, [2, 5, 3]);
+        for position in 0..5 {
+            let input = input.clone().index([0..2, position..position + 1, 0..4]);
+            let expected = linear.forward(input.reshape([2, 4]));
+            let output = output.clone().index([0..2, position..position + 1, 0..3]);
+
+            output
+                .reshape([2, 3])
+                .into_data()
+                .assert_approx_eq(&expected.into_data(), 5);
+        }
+    }
+
+    #[test]
+    fn relu_activation_should_zero_negative_pre_activations() {
+        let config = LinearConfig::new(2, 3)
+            .with_initializer(Initializer::Ones)
+            .with_bias(false)
+            .with_activation(Some(Activation::Relu));
+        let linear = Linear::<TB>::new(&config);
+        let input = Tensor::<TB, 2>::from_floats([[1.0, 2.0], [-1.0, -3.0], [2.0, -1.0]]);
+
+        let output = linear.forward(input);
+
+        output.into_data().assert_approx_eq(
+            &burn_tensor::Data::from([[3.0, 3.0, 3.0], [0.0, 0.0, 0.0], [1.0, 1.0, 1.0]]),
+            5,
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn forward_should_panic_when_last_dim_is_not_d_input() {
+        let linear = Linear::<TB>::new(&LinearConfig::new(4, 3));
+
+        linear.forward(Tensor::<TB, 3>::zeros([2, 4, 5]));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn forward_3d_gradients_should_match_flattened_input() {
+        use crate::TestADBackend;
+        use burn_tensor::Distribution;
+
+        let linear = Linear::<TestADBackend>::new(&LinearConfig::new(4, 3));
+        let input = Tensor::<TestADBackend, 3>::random([2, 5, 4], Distribution::Standard);
+
+        let grads_3d = linear.forward(input.clone()).sum().backward();
+        let grads_2d = linear.forward(input.reshape([10, 4])).sum().backward();
+
+        let weight = linear.weight.val();
+        let bias = linear.bias.val().unwrap();
+        weight
+            .grad(&grads_3d)
+            .unwrap()
+            .into_data()
+            .assert_approx_eq(&weight.grad(&grads_2d).unwrap().into_data(), 5);
+        bias.grad(&grads_3d)
+            .unwrap()
+            .into_data()
+            .assert_approx_eq(&bias.grad(&grads_2d).unwrap().into_data(), 5);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn forward_no_grad_should_equal_detached_forward() {
+        use crate::{module::ADModule, TestADBackend};
+        use burn_tensor::Distribution;
+
+        let config = LinearConfig::new(5, 3);
+        let linear = Linear::<TestADBackend>::new(&config);
+        let input = Tensor::<TestADBackend, 2>::random([2, 5], Distribution::Standard);
+
+        let output_ad = linear.forward(input.clone()).detach();
+        let output_inner = linear.no_grad(|linear| linear.forward(input.inner()));
+
+        output_ad
+            .into_data()
+            .assert_approx_eq(&output_inner.into_data(), 3);
+    }
 }
@@ -0,0 +1,205 @@
+use alloc::{format, vec::Vec};
+
+use crate as burn;
+
+use crate::config::Config;
+use crate::module::Module;
+use crate::module::Param;
+use crate::tensor::{backend::Backend, Tensor};
+
+use libm::sqrt;
+
+use super::{Activation, Initializer, Linear, LinearConfig};
+
+/// Configuration to create a [LoRA linear](LoraLinear) layer.
+#[derive(Config)]
+pub struct LoraLinearConfig {
+    /// The size of the input features.
+    pub d_input: usize,
+    /// The size of the output features.
+    pub d_output: usize,
+    /// The rank of the low-rank adapter matrices.
+    pub rank: usize,
+    /// The scaling numerator, the adapter output is multiplied by `alpha / rank`.
+    #[config(default = 1.0)]
+    pub alpha: f64,
+    /// If a bias should be applied by the base linear transformation.
+    #[config(default = true)]
+    pub bias: bool,
+}
+
+/// Applies a linear transformation with a low-rank adapter to the input tensor:
+///
+/// `O = IW + b + (alpha / rank) * IAB`
+///
+/// # Params
+///
+/// - weight: Frozen matrix of shape `[d_input, d_output]`.
+///
+/// - bias (optional): Frozen vector of size `d_output`.
+///
+/// - lora_a: Matrix of shape `[d_input, rank]` initialized from a uniform distribution:
+///   `U(-k, k)`, where `k = sqrt(1 / d_input)`
+///
+/// - lora_b: Matrix of shape `[rank, d_output]` initialized with zeros, so that the layer starts
+///   as the base linear transformation.
+///
+/// Only `lora_a` and `lora_b` receive gradients.
+#[derive(Module, Debug)]
+pub struct LoraLinear<B: Backend> {
+    weight: Param<Tensor<B, 2>>,
+    bias: Param<Option<Tensor<B, 1>>>,
+    lora_a: Param<Tensor<B, 2>>,
+    lora_b: Param<Tensor<B, 2>>,
+    scaling: f64,
+    activation: Option<Activation>,
+}
+
+impl<B: Backend> LoraLinear<B> {
+    /// Create the module from the given configuration with a randomly initialized base.
+    pub fn new(config: &LoraLinearConfig) -> Self {
+        let linear =
+            Linear::new(&LinearConfig::new(config.d_input, config.d_output).with_bias(config.bias));
+
+        Self::from_linear(config, linear)
+    }
+
+    /// Create the module from the given configuration, freezing the weights of an existing
+    /// [linear](Linear) layer as the base.
+    ///
+    /// The bias and the activation of the linear layer are kept as is, the `bias` field of the
+    /// configuration is ignored.
+    pub fn from_linear(config: &LoraLinearConfig, linear: Linear<B>) -> Self {
+        let [d_input, d_output] = linear.weight.shape().dims;
+        if d_input != config.d_input || d_output != config.d_output {
+            panic!(
+                "Can't create a LoRA adapter of shape [{}, {}] for a linear layer of shape [{}, {}]",
+                config.d_input, config.d_output, d_input, d_output
+            );
+        }
+
+        let k = sqrt(1.0 / config.d_input as f64);
+        let lora_a = Initializer::Uniform(-k, k).init([config.d_input, config.rank]);
+        let lora_b = Initializer::Zeros.init([config.rank, config.d_output]);
+
+        Self {
+            weight: Param::from_tensor(linear.weight.val().detach(), false),
+            bias: Param::from_optional_tensor(linear.bias.val().map(|bias| bias.detach()), false),
+            lora_a: Param::from(lora_a),
+            lora_b: Param::from(lora_b),
+            scaling: config.alpha / config.rank as f64,
+            activation: linear.activation,
+        }
+    }
+
+    /// Applies the forward pass on the input tensor.
+    ///
+    /// # Shapes
+    ///
+    /// - input: `[..., any, d_input]`
+    /// - output: `[..., any, d_output]`
+    pub fn forward<const D: usize>(&self, input: Tensor<B, D>) -> Tensor<B, D> {
+        let output = input.clone().matmul(self.weight.val().unsqueeze());
+        let output = match self.bias.val() {
+            Some(bias) => output + bias.unsqueeze(),
+            None => output,
+        };
+
+        let adapter = input
+            .matmul(self.lora_a.val().unsqueeze())
+            .matmul(self.lora_b.val().unsqueeze());
+
+        let output = output + adapter.mul_scalar(self.scaling);
+
+        match &self.activation {
+            Some(activation) => activation.forward(output),
+            None => output,
+        }
+    }
+
+    /// Fold the adapter into the base weights, returning a plain [linear](Linear) layer computing
+    /// the same transformation without the runtime overhead of the adapter.
+    ///
+    /// `W' = W + (alpha / rank) * AB`
+    pub fn merge(self) -> Linear<B> {
+        let update = self
+            .lora_a
+            .val()
+            .matmul(self.lora_b.val())
+            .mul_scalar(self.scaling);
+        let weight = self.weight.val() + update;
+
+        Linear {
+            weight: Param::from(weight.detach()),
+            bias: Param::from(self.bias.val().map(|bias| bias.detach())),
+            activation: self.activation,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TestBackend;
+    use burn_tensor::{Data, Distribution};
+
+    #[test]
+    fn full_rank_adapter_should_represent_arbitrary_update() {
+        let config = LoraLinearConfig::new(4, 3, 4).with_alpha(2.0);
+        let mut lora = LoraLinear::<TestBackend>::new(&config);
+        let update = Tensor::<TestBackend, 2>::random([4, 3], Distribution::Standard);
+        let input = Tensor::<TestBackend, 2>::random([2, 4], Distribution::Standard);
+
+        lora.lora_a = Param::from(Tensor::from_data(Data::from([
+            [1.0, 0.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0, 0.0],
+            [0.0, 0.0, 1.0, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ])));
+        lora.lora_b = Param::from(update.clone().div_scalar(lora.scaling));
+
+        let weight = lora.weight.val() + update;
+        let expected = input.clone().matmul(weight) + lora.bias.val().unwrap().unsqueeze();
+
+        lora.forward(input)
+            .into_data()
+            .assert_approx_eq(&expected.into_data(), 3);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn optimizer_step_should_only_update_adapter() {
+        use crate::optim::{GradientsParams, Optimizer, Sgd, SgdConfig};
+        use crate::TestADBackend;
+
+        let lora = LoraLinear::<TestADBackend>::new(&LoraLinearConfig::new(5, 3, 2));
+        let mut optim = Sgd::new(&SgdConfig::new(0.1));
+        let input = Tensor::<TestADBackend, 2>::random([2, 5], Distribution::Standard);
+        let weight = lora.weight.to_data();
+        let bias = lora.bias.val().unwrap().to_data();
+        let lora_b = lora.lora_b.to_data();
+
+        let loss = lora.forward(input).sum();
+        let grads = GradientsParams::from_grads(loss.backward(), &lora);
+        let lora = optim.update_module(lora, grads);
+
+        assert_eq!(lora.weight.to_data(), weight);
+        assert_eq!(lora.bias.val().unwrap().to_data(), bias);
+        assert_ne!(lora.lora_b.to_data(), lora_b);
+    }
+
+    #[test]
+    fn merged_linear_should_match_lora_forward() {
+        let mut lora =
+            LoraLinear::<TestBackend>::new(&LoraLinearConfig::new(6, 4, 2).with_alpha(4.0));
+        lora.lora_b = Param::from(Tensor::random([2, 4], Distribution::Standard));
+        let input = Tensor::<TestBackend, 3>::random([2, 3, 6], Distribution::Standard);
+
+        let output = lora.forward(input.clone());
+        let output_merged = lora.merge().forward(input);
+
+        output
+            .into_data()
+            .assert_approx_eq(&output_merged.into_data(), 3);
+    }
+}
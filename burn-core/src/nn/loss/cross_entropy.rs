@@ -2,10 +2,15 @@ use core::marker::PhantomData;
 
 use burn_tensor::{activation, backend::Backend, Bool, Int, Tensor};
 
+use super::Reduction;
+
 /// Calculate the cross entropy loss from the input logits and the targets.
+///
+/// The losses of the samples are averaged by default, see [with_reduction](Self::with_reduction).
 #[derive(Clone, Debug)]
 pub struct CrossEntropyLoss<B: Backend> {
     pad_index: Option<usize>,
+    reduction: Reduction,
     backend: PhantomData<B>,
 }
 
@@ -14,16 +19,24 @@ impl<B: Backend> CrossEntropyLoss<B> {
     pub fn new(pad_index: Option<usize>) -> Self {
         Self {
             pad_index,
+            reduction: Reduction::Mean,
             backend: PhantomData::default(),
         }
     }
 
+    /// Set how the losses of the samples are reduced, padded samples have a loss of zero.
+    pub fn with_reduction(mut self, reduction: Reduction) -> Self {
+        self.reduction = reduction;
+        self
+    }
+
     /// Compute the criterion on the input tensor.
     ///
     /// # Shapes
     ///
     /// - logits: [batch_size, num_targets]
     /// - targets: [batch_size]
+    /// - output: [batch_size] with [Reduction::None], [1] otherwise
     pub fn forward(&self, logits: Tensor<B, 2>, targets: Tensor<B, 1, Int>) -> Tensor<B, 1> {
         let [batch_size] = targets.dims();
 
@@ -32,7 +45,7 @@ impl<B: Backend> CrossEntropyLoss<B> {
         let tensor = tensor.index_select(targets.reshape([batch_size, 1]));
         let tensor = self.apply_mask(tensor.reshape([batch_size]), mask);
 
-        tensor.mean().neg()
+        self.reduction.apply(tensor.neg())
     }
 
     fn padding_mask(&self, targets: &Tensor<B, 1, Int>) -> Option<Tensor<B, 1, Bool>> {
@@ -105,4 +118,25 @@ mod tests {
 
         loss_1.into_data().assert_approx_eq(&loss_2.into_data(), 3);
     }
+
+    #[test]
+    fn test_cross_entropy_loss_without_reduction() {
+        let [batch_size, num_targets] = [4, 5];
+        let logits = Tensor::<TestBackend, 2>::random(
+            [batch_size, num_targets],
+            Distribution::Normal(0., 1.0),
+        );
+        let targets = Tensor::<TestBackend, 1, Int>::from_data(Data::from([2, 0, 4, 1]));
+
+        let losses = CrossEntropyLoss::new(None)
+            .with_reduction(Reduction::None)
+            .forward(logits.clone(), targets.clone());
+        let loss = CrossEntropyLoss::new(None).forward(logits, targets);
+
+        assert_eq!(losses.dims(), [batch_size]);
+        losses
+            .mean()
+            .into_data()
+            .assert_approx_eq(&loss.into_data(), 3);
+    }
 }
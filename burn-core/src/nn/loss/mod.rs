@@ -1,3 +1,5 @@
 mod cross_entropy;
+mod reduction;
 
 pub use cross_entropy::*;
+pub use reduction::*;
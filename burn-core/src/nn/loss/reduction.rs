@@ -0,0 +1,71 @@
+use alloc::vec::Vec;
+
+use burn_tensor::{backend::Backend, Data, ElementConversion, Int, Shape, Tensor};
+
+/// How the loss of each sample is reduced to the output of a criterion.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Reduction {
+    /// The mean of the losses, returned as a tensor of shape `[1]`.
+    #[default]
+    Mean,
+    /// The sum of the losses, returned as a tensor of shape `[1]`.
+    Sum,
+    /// No reduction, the loss of each sample is returned as a tensor of shape `[batch_size]`.
+    None,
+}
+
+impl Reduction {
+    /// Reduce the loss of each sample.
+    pub fn apply<B: Backend>(&self, losses: Tensor<B, 1>) -> Tensor<B, 1> {
+        match self {
+            Reduction::Mean => losses.mean(),
+            Reduction::Sum => losses.sum(),
+            Reduction::None => losses,
+        }
+    }
+}
+
+/// Select the `k` highest losses of a batch, also known as online hard example mining.
+///
+/// The losses must be computed with [Reduction::None], the mean of the selected losses is
+/// returned so that only the hardest samples are backpropagated.
+pub fn hard_example_mining<B: Backend>(losses: Tensor<B, 1>, k: usize) -> Tensor<B, 1> {
+    let device = losses.device();
+    let [batch_size] = losses.dims();
+    let data = losses.to_data();
+
+    let mut indices = (0..batch_size).collect::<Vec<_>>();
+    indices.sort_by(|a, b| {
+        let a = data.value[*a].elem::<f64>();
+        let b = data.value[*b].elem::<f64>();
+        b.partial_cmp(&a).unwrap()
+    });
+    indices.truncate(usize::max(k, 1));
+
+    let num_selected = indices.len();
+    let indices = indices
+        .into_iter()
+        .map(B::IntElem::from_elem)
+        .collect();
+    let indices = Tensor::<B, 1, Int>::from_data_device(
+        Data::new(indices, Shape::new([num_selected])),
+        &device,
+    );
+
+    losses.index_select_dim(0, indices).mean()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TestBackend;
+
+    #[test]
+    fn hard_example_mining_should_keep_the_highest_losses() {
+        let losses = Tensor::<TestBackend, 1>::from_floats([0.5, 3.0, 1.0, 2.0]);
+
+        let loss = hard_example_mining(losses, 2);
+
+        loss.into_data().assert_approx_eq(&Data::from([2.5]), 3);
+    }
+}
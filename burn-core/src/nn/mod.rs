@@ -3,20 +3,47 @@ pub mod cache;
 pub mod conv;
 pub mod loss;
 pub mod pool;
+pub mod sampling;
 pub mod transformer;
 
+mod activation;
+mod alpha_dropout;
+mod beam_search;
+mod drop_connect;
 mod dropout;
 mod embedding;
+mod flatten;
 mod gelu;
 mod initializer;
+mod leaky_relu;
 mod linear;
+mod lora;
 mod norm;
+mod positional_embedding;
+mod prelu;
+mod quantization;
 mod relu;
+mod segment_embedding;
+mod spectral_norm;
+mod weight_norm;
 
+pub use activation::*;
+pub use alpha_dropout::*;
+pub use beam_search::*;
+pub use drop_connect::*;
 pub use dropout::*;
 pub use embedding::*;
+pub use flatten::*;
 pub use gelu::*;
 pub use initializer::*;
+pub use leaky_relu::*;
 pub use linear::*;
+pub use lora::*;
 pub use norm::*;
+pub use positional_embedding::*;
+pub use prelu::*;
+pub use quantization::*;
 pub use relu::*;
+pub use segment_embedding::*;
+pub use spectral_norm::*;
+pub use weight_norm::*;
@@ -19,6 +19,11 @@ pub struct BatchNorm2dConfig {
     /// Momentum used to update the metrics. Default: 0.1
     #[config(default = 0.1)]
     pub momentum: f64,
+    /// Number of training steps over which the momentum is linearly ramped up to its configured
+    /// value. The step is given to [forward_with_step](BatchNorm2d::forward_with_step).
+    /// Default: 0 (no warmup)
+    #[config(default = 0)]
+    pub momentum_warmup: usize,
 }
 
 /// Applies Batch Normalization over a 4D tensor as described in the paper [Batch Normalization](https://arxiv.org/abs/1502.03167)
@@ -31,6 +36,7 @@ pub struct BatchNorm2d<B: Backend> {
     running_mean: Param<RunningState<Tensor<B, 1>>>,
     running_var: Param<RunningState<Tensor<B, 1>>>,
     momentum: f64,
+    momentum_warmup: usize,
     epsilon: f64,
 }
 
@@ -49,6 +55,7 @@ impl<B: Backend> BatchNorm2d<B> {
             running_mean: Param::from(RunningState::new(running_mean)),
             running_var: Param::from(RunningState::new(running_var)),
             momentum: config.momentum,
+            momentum_warmup: config.momentum_warmup,
             epsilon: config.epsilon,
         }
     }
@@ -61,11 +68,61 @@ impl<B: Backend> BatchNorm2d<B> {
     /// - output: `[batch_size, channels, height, width]`
     pub fn forward(&self, input: Tensor<B, 4>) -> Tensor<B, 4> {
         match B::ad_enabled() {
-            true => self.forward_train(input),
+            true => self.forward_train(input, self.momentum),
             false => self.forward_inference(input),
         }
     }
 
+    /// Applies the forward pass on the input tensor at the given training step, updating the
+    /// running statistics with the [effective momentum](Self::effective_momentum) of the step.
+    ///
+    /// The step is usually the number of optimizer updates, it is ignored during inference.
+    ///
+    /// # Shapes
+    ///
+    /// - input: `[batch_size, channels, height, width]`
+    /// - output: `[batch_size, channels, height, width]`
+    pub fn forward_with_step(&self, input: Tensor<B, 4>, step: usize) -> Tensor<B, 4> {
+        match B::ad_enabled() {
+            true => self.forward_train(input, self.effective_momentum(step)),
+            false => self.forward_inference(input),
+        }
+    }
+
+    /// The momentum used to update the running statistics at the given training step.
+    ///
+    /// During the [momentum warmup](BatchNorm2dConfig::momentum_warmup), the momentum grows
+    /// linearly from `momentum / momentum_warmup` at the first step to the configured momentum.
+    pub fn effective_momentum(&self, step: usize) -> f64 {
+        if step >= self.momentum_warmup {
+            return self.momentum;
+        }
+
+        self.momentum * (step + 1) as f64 / self.momentum_warmup as f64
+    }
+
+    /// A copy of the running mean of each feature, used to normalize the inputs during inference.
+    ///
+    /// # Shapes
+    ///
+    /// - output: `[num_features]`
+    pub fn running_mean(&self) -> Tensor<B, 1> {
+        self.running_mean.value_sync()
+    }
+
+    /// A copy of the running variance of each feature, used to normalize the inputs during
+    /// inference.
+    ///
+    /// The variance of each batch is the biased estimate, dividing by the number of elements per
+    /// feature, as used to normalize the batch during training.
+    ///
+    /// # Shapes
+    ///
+    /// - output: `[num_features]`
+    pub fn running_var(&self) -> Tensor<B, 1> {
+        self.running_var.value_sync()
+    }
+
     fn forward_inference(&self, input: Tensor<B, 4>) -> Tensor<B, 4> {
         let channels = input.dims()[1];
         let mean = self.running_mean.val().value();
@@ -78,7 +135,7 @@ impl<B: Backend> BatchNorm2d<B> {
         )
     }
 
-    fn forward_train(&self, input: Tensor<B, 4>) -> Tensor<B, 4> {
+    fn forward_train(&self, input: Tensor<B, 4>, momentum: f64) -> Tensor<B, 4> {
         let [batch_size, channels, height, width] = input.dims();
 
         let mean = input
@@ -100,16 +157,16 @@ impl<B: Backend> BatchNorm2d<B> {
         let running_mean = self.running_mean.value_sync();
         let running_var = self.running_var.value_sync();
 
-        let running_mean = running_mean.mul_scalar(1.0 - self.momentum).add(
+        let running_mean = running_mean.mul_scalar(1.0 - momentum).add(
             mean.clone()
                 .detach()
-                .mul_scalar(self.momentum)
+                .mul_scalar(momentum)
                 .reshape([channels]),
         );
-        let running_var = running_var.mul_scalar(1.0 - self.momentum).add(
+        let running_var = running_var.mul_scalar(1.0 - momentum).add(
             var.clone()
                 .detach()
-                .mul_scalar(self.momentum)
+                .mul_scalar(momentum)
                 .reshape([channels]),
         );
 
@@ -224,6 +281,30 @@ mod tests {
             .assert_approx_eq(&Data::from([0.9106, 0.9105, 0.9045]), 2);
     }
 
+    #[test]
+    fn batch_norm_2d_running_stats_should_converge_to_the_data_stats() {
+        let config = BatchNorm2dConfig::new(3).with_momentum(0.5);
+        let module = BatchNorm2d::<TestADBackend>::new(&config);
+
+        for _ in 0..30 {
+            let _output = module.forward(input_tensor());
+        }
+
+        let input = input_tensor::<TestADBackend>()
+            .swap_dims(0, 1)
+            .reshape([3, 8]);
+        let mean = input.clone().mean_dim(1);
+        let var = input.sub(mean.clone()).powf(2.0).mean_dim(1);
+        module
+            .running_mean()
+            .into_data()
+            .assert_approx_eq(&mean.reshape([3]).into_data(), 4);
+        module
+            .running_var()
+            .into_data()
+            .assert_approx_eq(&var.reshape([3]).into_data(), 4);
+    }
+
     #[test]
     fn batch_norm_2d_running_mean_inner_module() {
         let config = BatchNorm2dConfig::new(3);
@@ -242,6 +323,41 @@ mod tests {
             .assert_approx_eq(&running_mean.into_data(), 3);
     }
 
+    #[test]
+    fn batch_norm_2d_momentum_warmup() {
+        let config = BatchNorm2dConfig::new(3).with_momentum_warmup(4);
+        let module = BatchNorm2d::<TestADBackend>::new(&config);
+        let momentums: Vec<_> = (0..6).map(|step| module.effective_momentum(step)).collect();
+
+        assert!((momentums[0] - 0.025).abs() < 1e-9);
+        for window in momentums[..4].windows(2) {
+            assert!(window[0] < window[1]);
+        }
+        for momentum in &momentums[3..] {
+            assert!((momentum - 0.1).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn batch_norm_2d_forward_with_step_should_use_the_effective_momentum() {
+        let config = BatchNorm2dConfig::new(3).with_momentum_warmup(4);
+        let module = BatchNorm2d::<TestADBackend>::new(&config);
+        let module_expected =
+            BatchNorm2d::<TestADBackend>::new(&BatchNorm2dConfig::new(3).with_momentum(0.05));
+
+        let _output = module.forward_with_step(input_tensor(), 1);
+        let _output = module_expected.forward(input_tensor());
+
+        module
+            .running_mean()
+            .into_data()
+            .assert_approx_eq(&module_expected.running_mean().into_data(), 5);
+        module
+            .running_var()
+            .into_data()
+            .assert_approx_eq(&module_expected.running_var().into_data(), 5);
+    }
+
     #[test]
     fn batch_norm_2d_grads() {
         let config = BatchNorm2dConfig::new(3);
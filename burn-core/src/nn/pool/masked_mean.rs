@@ -0,0 +1,105 @@
+use crate::tensor::backend::Backend;
+use crate::tensor::{Bool, ElementConversion, Tensor};
+
+/// Averages a sequence of embeddings over its non-padded positions, which gives a single vector
+/// per sequence.
+///
+/// The padding mask follows the convention of the [attention](crate::nn::attention) modules,
+/// where `true` marks the padded positions. Sequences made only of padding are pooled to zeros.
+#[derive(Clone, Debug, Default)]
+pub struct MaskedMeanPool;
+
+impl MaskedMeanPool {
+    /// Create the module.
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Applies the forward pass on the input tensor.
+    ///
+    /// # Shapes
+    ///
+    /// - input: `[batch_size, seq_length, d_model]`
+    /// - mask_pad: `[batch_size, seq_length]`
+    /// - output: `[batch_size, d_model]`
+    pub fn forward<B: Backend>(
+        &self,
+        input: Tensor<B, 3>,
+        mask_pad: Tensor<B, 2, Bool>,
+    ) -> Tensor<B, 2> {
+        let [batch_size, seq_length, d_model] = input.dims();
+
+        let sum = input
+            .mask_fill(mask_pad.clone().reshape([batch_size, seq_length, 1]), 0.0)
+            .sum_dim(1)
+            .reshape([batch_size, d_model]);
+        let count = Tensor::<B, 2>::ones_device([batch_size, seq_length], &sum.device())
+            .mask_fill(mask_pad, 0.0)
+            .sum_dim(1);
+        // Dividing the zero sum of an all-padded sequence by one gives zeros instead of NaNs.
+        let count = count
+            .clone()
+            .mask_fill(count.equal_elem(0.0.elem::<B::FloatElem>()), 1.0);
+
+        sum / count
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TestBackend;
+    use burn_tensor::{Data, Distribution};
+
+    #[test]
+    fn forward_should_average_non_padded_positions() {
+        let input = Tensor::<TestBackend, 3>::random([3, 4, 5], Distribution::Standard);
+        let lengths = [4, 2, 1];
+        let mask_pad = Tensor::<TestBackend, 2, Bool>::from_bool(Data::from([
+            [false, false, false, false],
+            [false, false, true, true],
+            [false, true, true, true],
+        ]));
+
+        let output = MaskedMeanPool::new().forward(input.clone(), mask_pad);
+
+        let expected = lengths
+            .iter()
+            .enumerate()
+            .map(|(i, length)| {
+                input
+                    .clone()
+                    .index([i..i + 1, 0..*length, 0..5])
+                    .mean_dim(1)
+                    .reshape([1, 5])
+            })
+            .collect();
+        output
+            .into_data()
+            .assert_approx_eq(&Tensor::cat(expected, 0).into_data(), 3);
+    }
+
+    #[test]
+    fn forward_should_return_zeros_for_fully_padded_sequences() {
+        let input = Tensor::<TestBackend, 3>::random([2, 3, 4], Distribution::Standard);
+        let mask_pad = Tensor::<TestBackend, 2, Bool>::from_bool(Data::from([
+            [false, true, true],
+            [true, true, true],
+        ]));
+
+        let output = MaskedMeanPool::new().forward(input.clone(), mask_pad);
+
+        output
+            .clone()
+            .index([0..1, 0..4])
+            .into_data()
+            .assert_approx_eq(
+                &input.index([0..1, 0..1, 0..4]).reshape([1, 4]).into_data(),
+                3,
+            );
+        output
+            .index([1..2, 0..4])
+            .into_data()
+            .assert_approx_eq(&Tensor::<TestBackend, 2>::zeros([1, 4]).into_data(), 3);
+    }
+}
@@ -1,3 +1,5 @@
+mod masked_mean;
 mod max_pool2d;
 
+pub use masked_mean::*;
 pub use max_pool2d::*;
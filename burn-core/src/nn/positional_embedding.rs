@@ -0,0 +1,129 @@
+use alloc::{format, vec::Vec};
+
+use crate as burn;
+
+use super::Initializer;
+use crate::config::Config;
+use crate::module::{Module, Param};
+use crate::tensor::{backend::Backend, Tensor};
+
+/// Configuration to create a [learned positional embedding](LearnedPositionalEmbedding) layer.
+#[derive(Config)]
+pub struct LearnedPositionalEmbeddingConfig {
+    /// The maximum sequence length.
+    pub max_len: usize,
+    /// The size of each vector, matching the size of the token embeddings.
+    pub d_model: usize,
+    /// The type of function used to initialize neural network parameters
+    #[config(default = "Initializer::Normal(0.0,1.0)")]
+    pub initializer: Initializer,
+}
+
+/// Absolute positional embeddings learned during training, an alternative to fixed sinusoidal
+/// encodings.
+///
+/// # Params
+///
+/// - weight: Matrix of shape `[max_len, d_model]` holding the embedding of each position.
+#[derive(Module, Debug)]
+pub struct LearnedPositionalEmbedding<B: Backend> {
+    weight: Param<Tensor<B, 2>>,
+}
+
+impl<B: Backend> LearnedPositionalEmbedding<B> {
+    /// Create the module from the given configuration.
+    pub fn new(config: &LearnedPositionalEmbeddingConfig) -> Self {
+        let weight = config
+            .initializer
+            .init([config.max_len, config.d_model])
+            .require_grad();
+
+        Self {
+            weight: Param::from(weight),
+        }
+    }
+
+    /// Add the embedding of each position to the given embeddings.
+    ///
+    /// # Panics
+    ///
+    /// If the sequence length is greater than `max_len`.
+    ///
+    /// # Shapes
+    ///
+    /// - embeddings: `[batch_size, seq_length, d_model]`
+    /// - output: `[batch_size, seq_length, d_model]`
+    pub fn forward(&self, embeddings: Tensor<B, 3>) -> Tensor<B, 3> {
+        let [_, seq_length, _] = embeddings.dims();
+        let [max_len, d_model] = self.weight.dims();
+
+        if seq_length > max_len {
+            panic!(
+                "The sequence length {} exceeds the maximum length {} of the positional embedding",
+                seq_length, max_len
+            );
+        }
+
+        let positions = self.weight.val().index([0..seq_length, 0..d_model]);
+
+        embeddings + positions.unsqueeze()
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{TestADBackend, TestBackend};
+    use burn_tensor::Distribution;
+
+    #[test]
+    fn embedding_should_receive_gradients_of_the_used_positions() {
+        let embedding = LearnedPositionalEmbedding::<TestADBackend>::new(
+            &LearnedPositionalEmbeddingConfig::new(5, 4),
+        );
+        let input = Tensor::<TestADBackend, 3>::random([2, 3, 4], Distribution::Standard);
+
+        let grads = embedding.forward(input).sum().backward();
+
+        let grad = embedding.weight.grad(&grads).unwrap();
+        grad.clone()
+            .index([0..3, 0..4])
+            .into_data()
+            .assert_approx_eq(
+                &Tensor::<TestBackend, 2>::ones([3, 4])
+                    .mul_scalar(2.0)
+                    .into_data(),
+                5,
+            );
+        grad.index([3..5, 0..4])
+            .into_data()
+            .assert_approx_eq(&Tensor::<TestBackend, 2>::zeros([2, 4]).into_data(), 5);
+    }
+
+    #[test]
+    fn state_should_round_trip() {
+        let config = LearnedPositionalEmbeddingConfig::new(5, 4);
+        let embedding = LearnedPositionalEmbedding::<TestBackend>::new(&config);
+        let input = Tensor::<TestBackend, 3>::random([2, 3, 4], Distribution::Standard);
+
+        let loaded = LearnedPositionalEmbedding::<TestBackend>::new(&config)
+            .load(&embedding.state())
+            .unwrap();
+
+        loaded
+            .forward(input.clone())
+            .into_data()
+            .assert_approx_eq(&embedding.forward(input).into_data(), 5);
+    }
+
+    #[test]
+    #[should_panic]
+    fn sequence_longer_than_max_len_should_panic() {
+        let embedding = LearnedPositionalEmbedding::<TestBackend>::new(
+            &LearnedPositionalEmbeddingConfig::new(2, 4),
+        );
+
+        embedding.forward(Tensor::random([1, 3, 4], Distribution::Standard));
+    }
+}
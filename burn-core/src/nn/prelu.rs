@@ -0,0 +1,103 @@
+use alloc::{format, vec::Vec};
+
+use crate as burn;
+
+use crate::{
+    config::Config,
+    module::{Module, Param},
+    tensor::{backend::Backend, Tensor},
+};
+
+/// Configuration to create a [PReLU](PReLU) layer.
+#[derive(Config)]
+pub struct PReLUConfig {
+    /// The number of learnable slopes, either 1 to share the slope or the number of channels.
+    /// Default: 1
+    #[config(default = 1)]
+    pub num_parameters: usize,
+    /// The initial value of the slopes. Default: 0.25
+    #[config(default = 0.25)]
+    pub init: f64,
+}
+
+/// Applies the parametric rectified linear unit function element-wise:
+///
+/// `y = max(0, x) + α * min(0, x)`
+///
+/// # Params
+///
+/// - alpha: Learnable slopes of shape `[num_parameters]`, applied per channel (dimension 1) when
+///   there is more than one.
+#[derive(Module, Debug)]
+pub struct PReLU<B: Backend> {
+    alpha: Param<Tensor<B, 1>>,
+}
+
+impl<B: Backend> PReLU<B> {
+    /// Create the module from the given configuration.
+    pub fn new(config: &PReLUConfig) -> Self {
+        let alpha = Tensor::ones([config.num_parameters]).mul_scalar(config.init);
+
+        Self {
+            alpha: Param::from(alpha),
+        }
+    }
+
+    /// Applies the forward pass on the input tensor.
+    ///
+    /// # Shapes
+    ///
+    /// - input: `[batch_size, channels, ...]`
+    /// - output: `[batch_size, channels, ...]`
+    pub fn forward<const D: usize>(&self, input: Tensor<B, D>) -> Tensor<B, D> {
+        let [num_parameters] = self.alpha.shape().dims;
+        let mut shape = [1; D];
+
+        if num_parameters > 1 {
+            shape[1] = num_parameters;
+        }
+
+        let alpha = self.alpha.val().reshape(shape);
+        let positive = crate::tensor::activation::relu(input.clone());
+        let negative = crate::tensor::activation::relu(input.neg());
+
+        positive.sub(negative.mul(alpha))
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TestADBackend;
+    use burn_tensor::Data;
+
+    #[test]
+    fn prelu_forward_per_channel() {
+        let config = PReLUConfig::new().with_num_parameters(2);
+        let module = PReLU::<TestADBackend>::new(&config);
+        let input = Tensor::<TestADBackend, 3>::from_floats([[[-1.0, 2.0], [-4.0, 0.5]]]);
+
+        let output = module.forward(input);
+
+        output
+            .into_data()
+            .assert_approx_eq(&Data::from([[[-0.25, 2.0], [-1.0, 0.5]]]), 5);
+    }
+
+    #[test]
+    fn prelu_grads_should_flow_to_alpha() {
+        let module = PReLU::<TestADBackend>::new(&PReLUConfig::new());
+        let input = Tensor::<TestADBackend, 2>::from_floats([[-1.0, 2.0], [-3.0, 4.0]]);
+
+        let output = module.forward(input);
+        let grads = output.sum().backward();
+
+        module
+            .alpha
+            .grad(&grads)
+            .unwrap()
+            .into_data()
+            .assert_approx_eq(&Data::from([-4.0]), 5);
+    }
+}
@@ -0,0 +1,165 @@
+use alloc::{format, vec, vec::Vec};
+
+use crate as burn;
+
+use crate::module::Module;
+use crate::module::Param;
+use crate::tensor::{backend::Backend, Data, Shape, Tensor};
+
+use libm::roundf;
+
+use super::{Activation, Linear};
+
+const QUANTIZED_MIN: f32 = -128.0;
+const QUANTIZED_MAX: f32 = 127.0;
+
+/// Linear layer with weights quantized to 8 bits integers, created with [quantize_linear].
+///
+/// Each output channel has its own scale and zero point, and the weights are dequantized on the
+/// fly during the forward pass:
+///
+/// `W = (Q - zero_point) * scale`
+///
+/// # Params
+///
+/// - weight: Matrix of shape `[d_input, d_output]` containing integers in `[-128, 127]`.
+///
+/// - scales: Vector of size `d_output`.
+///
+/// - zero_points: Vector of size `d_output` containing integers in `[-128, 127]`.
+///
+/// - bias (optional): Vector of size `d_output` kept in full precision.
+///
+/// The integer values are stored with the float element type of the backend, so they are saved
+/// and loaded with the module [state](Module::state) like any other parameter.
+#[derive(Module, Debug)]
+pub struct QuantizedLinear<B: Backend> {
+    weight: Param<Tensor<B, 2>>,
+    scales: Param<Tensor<B, 1>>,
+    zero_points: Param<Tensor<B, 1>>,
+    bias: Param<Option<Tensor<B, 1>>>,
+    activation: Option<Activation>,
+}
+
+/// Quantize the weights of a trained [linear](Linear) layer to 8 bits integers using an affine
+/// mapping per output channel.
+pub fn quantize_linear<B: Backend>(linear: Linear<B>) -> QuantizedLinear<B> {
+    let weight = linear.weight.val();
+    let device = weight.device();
+    let [d_input, d_output] = weight.shape().dims;
+    let values = weight.into_data().convert::<f32>().value;
+
+    // The range of each channel always contains zero, so zero weights are exactly represented.
+    let mut min = vec![0.0f32; d_output];
+    let mut max = vec![0.0f32; d_output];
+    for (index, value) in values.iter().enumerate() {
+        let channel = index % d_output;
+        min[channel] = f32::min(min[channel], *value);
+        max[channel] = f32::max(max[channel], *value);
+    }
+
+    let scales: Vec<f32> = min
+        .iter()
+        .zip(max.iter())
+        .map(|(min, max)| match max - min {
+            range if range > 0.0 => range / (QUANTIZED_MAX - QUANTIZED_MIN),
+            _ => 1.0,
+        })
+        .collect();
+    let zero_points: Vec<f32> = min
+        .iter()
+        .zip(scales.iter())
+        .map(|(min, scale)| clamp(roundf(QUANTIZED_MIN - min / scale)))
+        .collect();
+    let quantized: Vec<f32> = values
+        .iter()
+        .enumerate()
+        .map(|(index, value)| {
+            let channel = index % d_output;
+            clamp(roundf(value / scales[channel]) + zero_points[channel])
+        })
+        .collect();
+
+    let weight = Data::new(quantized, Shape::new([d_input, d_output]));
+    let scales = Data::new(scales, Shape::new([d_output]));
+    let zero_points = Data::new(zero_points, Shape::new([d_output]));
+
+    QuantizedLinear {
+        weight: quantized_param(Tensor::from_data_device(weight.convert(), &device)),
+        scales: quantized_param(Tensor::from_data_device(scales.convert(), &device)),
+        zero_points: quantized_param(Tensor::from_data_device(zero_points.convert(), &device)),
+        bias: Param::from_optional_tensor(linear.bias.val().map(|bias| bias.detach()), false),
+        activation: linear.activation,
+    }
+}
+
+fn quantized_param<B: Backend, const D: usize>(tensor: Tensor<B, D>) -> Param<Tensor<B, D>> {
+    Param::from_tensor(tensor, false)
+}
+
+fn clamp(value: f32) -> f32 {
+    value.clamp(QUANTIZED_MIN, QUANTIZED_MAX)
+}
+
+impl<B: Backend> QuantizedLinear<B> {
+    /// The dequantized weights of shape `[d_input, d_output]`.
+    pub fn dequantize(&self) -> Tensor<B, 2> {
+        let zero_points = self.zero_points.val().unsqueeze();
+        let scales = self.scales.val().unsqueeze();
+
+        self.weight.val().sub(zero_points).mul(scales)
+    }
+
+    /// Applies the forward pass on the input tensor.
+    ///
+    /// # Shapes
+    ///
+    /// - input: `[..., any, d_input]`
+    /// - output: `[..., any, d_output]`
+    pub fn forward<const D: usize>(&self, input: Tensor<B, D>) -> Tensor<B, D> {
+        let output = input.matmul(self.dequantize().unsqueeze());
+
+        let output = match self.bias.val() {
+            Some(bias) => output + bias.unsqueeze(),
+            None => output,
+        };
+
+        match &self.activation {
+            Some(activation) => activation.forward(output),
+            None => output,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nn::LinearConfig;
+    use crate::TestBackend;
+    use burn_tensor::Distribution;
+
+    #[test]
+    fn quantized_weights_should_be_8_bits_integers() {
+        let linear = Linear::<TestBackend>::new(&LinearConfig::new(16, 8));
+
+        let quantized = quantize_linear(linear);
+
+        for value in quantized.weight.to_data().value {
+            assert_eq!(value, roundf(value));
+            assert!((QUANTIZED_MIN..=QUANTIZED_MAX).contains(&value));
+        }
+    }
+
+    #[test]
+    fn quantized_forward_should_be_close_to_float_forward() {
+        let linear = Linear::<TestBackend>::new(&LinearConfig::new(16, 8));
+        let input = Tensor::<TestBackend, 2>::random([4, 16], Distribution::Normal(0.0, 1.0));
+        let output = linear.forward(input.clone());
+
+        let output_quantized = quantize_linear(linear).forward(input);
+
+        let error = (output - output_quantized).into_data().value;
+        let error = error.iter().fold(0.0f32, |max, value| max.max(value.abs()));
+        assert!(error < 0.02, "The quantization error is too large: {error}");
+    }
+}
@@ -0,0 +1,204 @@
+//! Sampling strategies to select the next token from the logits produced by a model during
+//! generation.
+//!
+//! Every function takes logits of shape `[batch_size, vocab_size]` and returns the selected
+//! indices of shape `[batch_size]`. The random strategies take a seeded random number generator so
+//! that generation is reproducible.
+
+use alloc::{vec, vec::Vec};
+
+use crate::tensor::{backend::Backend, Data, ElementConversion, Int, Shape, Tensor};
+use libm::exp;
+use rand::{rngs::StdRng, Rng};
+
+/// Select the index with the highest logit.
+pub fn greedy<B: Backend>(logits: Tensor<B, 2>) -> Tensor<B, 1, Int> {
+    let [batch_size, _] = logits.dims();
+
+    logits.argmax(1).reshape([batch_size])
+}
+
+/// Sample an index from the softmax of the logits scaled by the given temperature.
+///
+/// A temperature of zero is equivalent to [greedy] sampling.
+pub fn temperature<B: Backend>(
+    logits: Tensor<B, 2>,
+    temperature: f64,
+    rng: &mut StdRng,
+) -> Tensor<B, 1, Int> {
+    top_k(logits, usize::MAX, temperature, rng)
+}
+
+/// Sample an index among the `k` highest logits scaled by the given temperature.
+///
+/// A temperature of zero is equivalent to [greedy] sampling.
+pub fn top_k<B: Backend>(
+    logits: Tensor<B, 2>,
+    k: usize,
+    temperature: f64,
+    rng: &mut StdRng,
+) -> Tensor<B, 1, Int> {
+    sample(logits, temperature, rng, |candidates| {
+        candidates.truncate(usize::max(k, 1));
+    })
+}
+
+/// Sample an index among the smallest set of highest logits whose cumulative probability is at
+/// least `p`, also known as nucleus sampling.
+///
+/// A temperature of zero is equivalent to [greedy] sampling.
+pub fn top_p<B: Backend>(
+    logits: Tensor<B, 2>,
+    p: f64,
+    temperature: f64,
+    rng: &mut StdRng,
+) -> Tensor<B, 1, Int> {
+    sample(logits, temperature, rng, |candidates| {
+        let mut cumulative = 0.0;
+        let mut num_kept = 0;
+
+        for (_, prob) in candidates.iter() {
+            cumulative += prob;
+            num_kept += 1;
+
+            if cumulative >= p {
+                break;
+            }
+        }
+
+        candidates.truncate(num_kept);
+    })
+}
+
+/// Sample an index for each row of the logits.
+///
+/// The `filter` receives the candidates `(index, probability)` sorted by decreasing probability
+/// and removes the ones that can't be sampled, the remaining probabilities are normalized.
+///
+/// NaN logits have a probability of zero, so they are only sampled when the whole row is NaN.
+fn sample<B: Backend, F>(
+    logits: Tensor<B, 2>,
+    temperature: f64,
+    rng: &mut StdRng,
+    filter: F,
+) -> Tensor<B, 1, Int>
+where
+    F: Fn(&mut Vec<(usize, f64)>),
+{
+    if temperature <= 0.0 {
+        return greedy(logits);
+    }
+
+    let device = logits.device();
+    let [batch_size, vocab_size] = logits.dims();
+    let data = logits.into_data();
+    let mut indices = vec![B::IntElem::from_elem(0); batch_size];
+
+    for (row, index) in indices.iter_mut().enumerate() {
+        let start = row * vocab_size;
+        let logits_row = data.value[start..start + vocab_size]
+            .iter()
+            .map(|logit| logit.elem::<f64>())
+            .map(|logit| match logit.is_nan() {
+                true => f64::NEG_INFINITY,
+                false => logit / temperature,
+            })
+            .collect::<Vec<_>>();
+
+        let max = logits_row.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+        let exp = logits_row
+            .iter()
+            .map(|logit| exp(logit - max))
+            .collect::<Vec<_>>();
+        let sum: f64 = exp.iter().sum();
+
+        let mut candidates = exp
+            .into_iter()
+            .map(|value| value / sum)
+            .enumerate()
+            .collect::<Vec<_>>();
+        candidates.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+        filter(&mut candidates);
+
+        let total: f64 = candidates.iter().map(|(_, prob)| prob).sum();
+        let mut threshold = rng.gen::<f64>() * total;
+        let mut selected = candidates[candidates.len() - 1].0;
+
+        for (candidate, prob) in candidates {
+            if threshold < prob {
+                selected = candidate;
+                break;
+            }
+            threshold -= prob;
+        }
+
+        *index = B::IntElem::from_elem(selected);
+    }
+
+    Tensor::from_data_device(Data::new(indices, Shape::new([batch_size])), &device)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TestBackend;
+    use burn_tensor::Distribution;
+    use rand::SeedableRng;
+
+    #[test]
+    fn temperature_zero_should_equal_greedy() {
+        let logits = Tensor::<TestBackend, 2>::random([4, 10], Distribution::Standard);
+        let mut rng = StdRng::seed_from_u64(0);
+
+        let expected = greedy(logits.clone()).into_data();
+        let output = temperature(logits, 0.0, &mut rng).into_data();
+
+        assert_eq!(output, expected);
+    }
+
+    #[test]
+    fn top_k_should_restrict_the_support() {
+        let logits = Tensor::<TestBackend, 2>::from_floats([[0.1, 3.0, 0.2, 2.9, 0.3]]);
+        let mut rng = StdRng::seed_from_u64(0);
+
+        for _ in 0..100 {
+            let index = top_k(logits.clone(), 2, 1.0, &mut rng).into_data().value[0];
+            assert!(
+                index == 1 || index == 3,
+                "Index {index} is not in the top 2"
+            );
+        }
+    }
+
+    #[test]
+    fn top_p_should_restrict_the_support() {
+        let logits = Tensor::<TestBackend, 2>::from_floats([[0.0, 10.0, 0.0, 0.0]]);
+        let mut rng = StdRng::seed_from_u64(0);
+
+        for _ in 0..100 {
+            let index = top_p(logits.clone(), 0.9, 1.0, &mut rng).into_data().value[0];
+            assert_eq!(index, 1);
+        }
+    }
+
+    #[test]
+    fn nan_logits_should_never_be_sampled() {
+        let logits = Tensor::<TestBackend, 2>::from_floats([[0.1, f32::NAN, 0.2, f32::NAN]]);
+        let mut rng = StdRng::seed_from_u64(0);
+
+        for _ in 0..100 {
+            let index = temperature(logits.clone(), 1.0, &mut rng).into_data().value[0];
+            assert!(index == 0 || index == 2, "Index {index} has a NaN logit");
+        }
+    }
+
+    #[test]
+    fn sampling_should_be_reproducible_given_the_seed() {
+        let logits = Tensor::<TestBackend, 2>::random([8, 10], Distribution::Standard);
+
+        let output_1 = temperature(logits.clone(), 1.0, &mut StdRng::seed_from_u64(42));
+        let output_2 = temperature(logits, 1.0, &mut StdRng::seed_from_u64(42));
+
+        assert_eq!(output_1.into_data(), output_2.into_data());
+    }
+}
@@ -0,0 +1,85 @@
+use alloc::{format, vec::Vec};
+
+use crate as burn;
+
+use super::{Embedding, EmbeddingConfig, Initializer};
+use crate::config::Config;
+use crate::module::{Module, Param};
+use crate::tensor::{backend::Backend, Int, Tensor};
+
+/// Configuration to create a [segment embedding](SegmentEmbedding) layer.
+#[derive(Config)]
+pub struct SegmentEmbeddingConfig {
+    /// The size of each vector, matching the size of the token embeddings.
+    pub d_model: usize,
+    /// The number of segments.
+    #[config(default = 2)]
+    pub n_segment: usize,
+    /// The type of function used to initialize neural network parameters
+    #[config(default = "Initializer::Normal(0.0,1.0)")]
+    pub initializer: Initializer,
+}
+
+/// Token type embeddings used by BERT-style models to distinguish the segments of an input made
+/// of multiple sentences.
+///
+/// # Params
+///
+/// - embedding: [Embedding](Embedding) table of shape `[n_segment, d_model]`.
+#[derive(Module, Debug)]
+pub struct SegmentEmbedding<B: Backend> {
+    embedding: Param<Embedding<B>>,
+}
+
+impl<B: Backend> SegmentEmbedding<B> {
+    /// Create the module from the given configuration.
+    pub fn new(config: &SegmentEmbeddingConfig) -> Self {
+        let embedding = Embedding::new(
+            &EmbeddingConfig::new(config.n_segment, config.d_model)
+                .with_initializer(config.initializer.clone()),
+        );
+
+        Self {
+            embedding: Param::from(embedding),
+        }
+    }
+
+    /// Add the embedding of each segment id to the given embeddings.
+    ///
+    /// The input embeddings are usually the sum of the token and the positional embeddings.
+    ///
+    /// # Shapes
+    ///
+    /// - embeddings: `[batch_size, seq_length, d_model]`
+    /// - segment_ids: `[batch_size, seq_length]`
+    /// - output: `[batch_size, seq_length, d_model]`
+    pub fn forward(
+        &self,
+        embeddings: Tensor<B, 3>,
+        segment_ids: Tensor<B, 2, Int>,
+    ) -> Tensor<B, 3> {
+        embeddings + self.embedding.forward(segment_ids)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TestBackend;
+    use burn_tensor::{Data, Distribution};
+
+    #[test]
+    fn different_segments_should_add_different_embeddings() {
+        let segment = SegmentEmbedding::<TestBackend>::new(&SegmentEmbeddingConfig::new(4));
+        let embeddings = Tensor::<TestBackend, 3>::random([1, 3, 4], Distribution::Standard);
+        let segment_ids = Tensor::<TestBackend, 2, Int>::from_data(Data::from([[0, 0, 1]]));
+
+        let added = segment.forward(embeddings.clone(), segment_ids) - embeddings;
+
+        let first = added.clone().index([0..1, 0..1, 0..4]);
+        let second = added.clone().index([0..1, 1..2, 0..4]);
+        let third = added.index([0..1, 2..3, 0..4]);
+        first.to_data().assert_approx_eq(&second.into_data(), 5);
+        assert_ne!(first.into_data(), third.into_data());
+    }
+}
@@ -0,0 +1,299 @@
+use alloc::{format, vec::Vec};
+
+use crate as burn;
+
+use crate::config::Config;
+use crate::module::{Module, Param, RunningState};
+use crate::tensor::{backend::Backend, Distribution, Tensor};
+
+use super::{conv::Conv2d, Linear};
+
+/// Configuration of the [spectral normalization](SpectralNorm).
+#[derive(Config)]
+pub struct SpectralNormConfig {
+    /// The number of power iterations executed on each training forward pass. Default: 1
+    #[config(default = 1)]
+    pub n_power_iterations: usize,
+    /// A value required for numerical stability. Default: 1e-12
+    #[config(default = 1e-12)]
+    pub epsilon: f64,
+}
+
+/// Estimates the largest singular value of a weight viewed as a matrix of shape
+/// `[d_output, rest]` using the power iteration method, as described in the paper
+/// [Spectral Normalization for Generative Adversarial Networks](https://arxiv.org/abs/1802.05957).
+///
+/// The left singular vector estimate `u` is refined on each forward pass when the backend tracks
+/// gradients, otherwise the cached estimate is used as is. It is saved with the module state but
+/// never receives gradients.
+#[derive(Module, Debug)]
+pub struct SpectralNorm<B: Backend> {
+    u: Param<RunningState<Tensor<B, 1>>>,
+    n_power_iterations: usize,
+    epsilon: f64,
+}
+
+impl<B: Backend> SpectralNorm<B> {
+    fn new(config: &SpectralNormConfig, d_output: usize) -> Self {
+        let u = Tensor::random([d_output], Distribution::Normal(0.0, 1.0));
+
+        Self {
+            u: Param::from(RunningState::new(normalize(u, config.epsilon))),
+            n_power_iterations: config.n_power_iterations,
+            epsilon: config.epsilon,
+        }
+    }
+
+    /// Divide the weight by its estimated largest singular value, where the first dimension of
+    /// the weight is the output dimension.
+    fn normalize<const D: usize>(&self, weight: Tensor<B, D>) -> Tensor<B, D> {
+        let shape = weight.shape();
+        let d_output = shape.dims[0];
+        let matrix = weight
+            .clone()
+            .reshape([d_output, shape.num_elements() / d_output]);
+
+        let (u, v) = match B::ad_enabled() {
+            true => {
+                let (u, v) = self.power_iteration(matrix.clone().detach());
+                self.u.update(u.clone());
+                (u, v)
+            }
+            false => {
+                let u = self.u.val().value();
+                let v = self.right_vector(matrix.clone(), u.clone());
+                (u, v)
+            }
+        };
+
+        let [rest] = v.dims();
+        let sigma = u
+            .reshape([1, d_output])
+            .matmul(matrix)
+            .matmul(v.reshape([rest, 1]));
+
+        weight.div(sigma.reshape([1; D]))
+    }
+
+    fn power_iteration(&self, matrix: Tensor<B, 2>) -> (Tensor<B, 1>, Tensor<B, 1>) {
+        let [d_output, rest] = matrix.dims();
+        let mut u = self.u.value_sync();
+        let mut v = self.right_vector(matrix.clone(), u.clone());
+
+        for _ in 0..self.n_power_iterations {
+            v = self.right_vector(matrix.clone(), u);
+            u = normalize(
+                matrix
+                    .clone()
+                    .matmul(v.clone().reshape([rest, 1]))
+                    .reshape([d_output]),
+                self.epsilon,
+            );
+        }
+
+        (u, v)
+    }
+
+    fn right_vector(&self, matrix: Tensor<B, 2>, u: Tensor<B, 1>) -> Tensor<B, 1> {
+        let [d_output, rest] = matrix.dims();
+        let v = matrix
+            .transpose()
+            .matmul(u.reshape([d_output, 1]))
+            .reshape([rest]);
+
+        normalize(v.detach(), self.epsilon)
+    }
+}
+
+fn normalize<B: Backend>(vector: Tensor<B, 1>, epsilon: f64) -> Tensor<B, 1> {
+    let norm = vector.clone().powf(2.0).sum().sqrt().add_scalar(epsilon);
+
+    vector.div(norm)
+}
+
+/// A [linear](Linear) layer where the weight is divided by its largest singular value.
+#[derive(Module, Debug)]
+pub struct SpectralNormLinear<B: Backend> {
+    linear: Param<Linear<B>>,
+    norm: Param<SpectralNorm<B>>,
+}
+
+impl<B: Backend> SpectralNormLinear<B> {
+    /// Apply the spectral normalization to an existing [linear](Linear) layer.
+    pub fn from_linear(config: &SpectralNormConfig, linear: Linear<B>) -> Self {
+        let [_, d_output] = linear.weight.shape().dims;
+
+        Self {
+            linear: Param::from(linear),
+            norm: Param::from(SpectralNorm::new(config, d_output)),
+        }
+    }
+
+    /// The normalized weight of shape `[d_input, d_output]`.
+    ///
+    /// # Notes
+    ///
+    /// The singular vector estimate is updated when the backend tracks gradients.
+    pub fn weight(&self) -> Tensor<B, 2> {
+        let weight = self.linear.weight.val().transpose();
+
+        self.norm.normalize(weight).transpose()
+    }
+
+    /// Applies the forward pass on the input tensor.
+    ///
+    /// # Shapes
+    ///
+    /// - input: `[..., any, d_input]`
+    /// - output: `[..., any, d_output]`
+    pub fn forward<const D: usize>(&self, input: Tensor<B, D>) -> Tensor<B, D> {
+        let output = input.matmul(self.weight().unsqueeze());
+
+        match self.linear.bias.val() {
+            Some(bias) => output + bias.unsqueeze(),
+            None => output,
+        }
+    }
+}
+
+/// A [2D convolution](Conv2d) layer where the weight, viewed as a matrix of shape
+/// `[channels_out, channels_in * kernel_size_1 * kernel_size_2]`, is divided by its largest
+/// singular value.
+#[derive(Module, Debug)]
+pub struct SpectralNormConv2d<B: Backend> {
+    conv: Param<Conv2d<B>>,
+    norm: Param<SpectralNorm<B>>,
+}
+
+impl<B: Backend> SpectralNormConv2d<B> {
+    /// Apply the spectral normalization to an existing [2D convolution](Conv2d) layer.
+    pub fn from_conv2d(config: &SpectralNormConfig, conv: Conv2d<B>) -> Self {
+        let [channels_out, _, _, _] = conv.weight.shape().dims;
+
+        Self {
+            conv: Param::from(conv),
+            norm: Param::from(SpectralNorm::new(config, channels_out)),
+        }
+    }
+
+    /// The normalized weight of shape `[channels_out, channels_in, kernel_size_1, kernel_size_2]`.
+    ///
+    /// # Notes
+    ///
+    /// The singular vector estimate is updated when the backend tracks gradients.
+    pub fn weight(&self) -> Tensor<B, 4> {
+        self.norm.normalize(self.conv.weight.val())
+    }
+
+    /// Applies the forward pass on the input tensor.
+    ///
+    /// # Shapes
+    ///
+    /// - input: [batch_size, channels_in, height_in, width_in],
+    /// - output: [batch_size, channels_out, height_out, width_out],
+    pub fn forward(&self, input: Tensor<B, 4>) -> Tensor<B, 4> {
+        self.conv.forward_with_weight(input, self.weight())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nn::{conv::Conv2dConfig, LinearConfig};
+    use crate::TestBackend;
+
+    /// Compute the largest singular value with many power iterations.
+    fn largest_singular_value(matrix: Tensor<TestBackend, 2>) -> f32 {
+        let norm = SpectralNorm::new(&SpectralNormConfig::new(), matrix.dims()[0]);
+        let (u, v) = norm.power_iteration_steps(matrix.clone(), 200);
+        let [d_output, rest] = matrix.dims();
+
+        u.reshape([1, d_output])
+            .matmul(matrix)
+            .matmul(v.reshape([rest, 1]))
+            .into_data()
+            .value[0]
+    }
+
+    impl<B: Backend> SpectralNorm<B> {
+        fn power_iteration_steps(
+            mut self,
+            matrix: Tensor<B, 2>,
+            steps: usize,
+        ) -> (Tensor<B, 1>, Tensor<B, 1>) {
+            self.n_power_iterations = steps;
+            self.power_iteration(matrix)
+        }
+    }
+
+    #[test]
+    fn largest_singular_value_of_diagonal_matrix() {
+        let matrix = Tensor::<TestBackend, 2>::from_floats([[3.0, 0.0], [0.0, 1.0], [0.0, 0.0]]);
+
+        assert!((largest_singular_value(matrix) - 3.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn inference_should_use_the_cached_estimate() {
+        let linear = Linear::<TestBackend>::new(&LinearConfig::new(6, 4));
+        let linear = SpectralNormLinear::from_linear(&SpectralNormConfig::new(), linear);
+        let u = linear.norm.u.val().value();
+
+        linear.forward(Tensor::<TestBackend, 2>::ones([2, 6]));
+
+        assert_eq!(linear.norm.u.val().value().into_data(), u.into_data());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn linear_weight_should_have_a_unit_spectral_norm_after_training_forwards() {
+        use crate::{module::ADModule, TestADBackend};
+
+        let linear = Linear::<TestADBackend>::new(&LinearConfig::new(6, 4));
+        let linear = SpectralNormLinear::from_linear(&SpectralNormConfig::new(), linear);
+        let input = Tensor::<TestADBackend, 2>::random([2, 6], Distribution::Standard);
+
+        for _ in 0..50 {
+            linear.forward(input.clone());
+        }
+
+        let weight = linear.inner().weight();
+        assert!((largest_singular_value(weight) - 1.0).abs() < 1e-2);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn conv2d_weight_should_have_a_unit_spectral_norm_after_training_forwards() {
+        use crate::{module::ADModule, TestADBackend};
+
+        let conv = Conv2d::<TestADBackend>::new(&Conv2dConfig::new([2, 3], [3, 3]));
+        let conv = SpectralNormConv2d::from_conv2d(&SpectralNormConfig::new(), conv);
+        let input = Tensor::<TestADBackend, 4>::random([1, 2, 5, 5], Distribution::Standard);
+
+        for _ in 0..50 {
+            conv.forward(input.clone());
+        }
+
+        let weight = conv.inner().weight().reshape([3, 18]);
+        assert!((largest_singular_value(weight) - 1.0).abs() < 1e-2);
+    }
+
+    #[test]
+    fn state_should_contain_the_singular_vector_estimate() {
+        let linear = Linear::<TestBackend>::new(&LinearConfig::new(6, 4));
+        let linear = SpectralNormLinear::from_linear(&SpectralNormConfig::new(), linear);
+        let state = linear.state();
+
+        let loaded = SpectralNormLinear::from_linear(
+            &SpectralNormConfig::new(),
+            Linear::<TestBackend>::new(&LinearConfig::new(6, 4)),
+        )
+        .load(&state)
+        .unwrap();
+
+        assert_eq!(
+            loaded.norm.u.val().value().into_data(),
+            linear.norm.u.val().value().into_data()
+        );
+    }
+}
@@ -0,0 +1,296 @@
+use alloc::{vec, vec::Vec};
+
+use burn_tensor::{Bool, Data, Shape};
+
+use super::{TransformerEncoder, TransformerEncoderAutoregressiveCache, TransformerEncoderInput};
+use crate::tensor::{backend::Backend, Tensor};
+
+/// Autoregressive generation over a batch of prompts of different lengths with a
+/// [transformer encoder](TransformerEncoder).
+///
+/// The prompts are left padded to the same length and the padding is masked, so every sequence
+/// produces its next token at the last position. A sequence marked as
+/// [finished](Self::finish) stops contributing: the tokens appended for it are masked and its
+/// outputs are zeros.
+///
+/// Created with [new_batched_generation](TransformerEncoder::new_batched_generation).
+pub struct TransformerEncoderBatchedGeneration<B: Backend> {
+    cache: TransformerEncoderAutoregressiveCache<B>,
+    tensor: Tensor<B, 3>,
+    masked: Vec<Vec<bool>>,
+    offsets: Vec<usize>,
+    finished: Vec<bool>,
+}
+
+impl<B: Backend> TransformerEncoderBatchedGeneration<B> {
+    pub(super) fn new(
+        cache: TransformerEncoderAutoregressiveCache<B>,
+        prompts: Vec<Tensor<B, 2>>,
+    ) -> Self {
+        let max_length = prompts
+            .iter()
+            .map(|prompt| prompt.dims()[0])
+            .max()
+            .expect("At least one prompt is required");
+        let offsets: Vec<usize> = prompts
+            .iter()
+            .map(|prompt| max_length - prompt.dims()[0])
+            .collect();
+
+        let tensor = Tensor::cat(
+            prompts
+                .into_iter()
+                .zip(offsets.iter())
+                .map(|(prompt, offset)| {
+                    let [_, d_model] = prompt.dims();
+                    let prompt = match offset {
+                        0 => prompt,
+                        _ => Tensor::cat(vec![Tensor::zeros([*offset, d_model]), prompt], 0),
+                    };
+
+                    prompt.unsqueeze()
+                })
+                .collect(),
+            0,
+        );
+        let masked = offsets
+            .iter()
+            .map(|offset| (0..max_length).map(|position| position < *offset).collect())
+            .collect();
+
+        Self {
+            cache,
+            tensor,
+            masked,
+            finished: vec![false; offsets.len()],
+            offsets,
+        }
+    }
+
+    /// Compute the output at the last position of every sequence, the outputs of the finished
+    /// sequences are zeros.
+    ///
+    /// # Shapes
+    ///
+    /// - output: `[batch_size, d_model]`
+    pub fn forward(&mut self, encoder: &TransformerEncoder<B>) -> Tensor<B, 2> {
+        let [batch_size, _, d_model] = self.tensor.dims();
+        let input = TransformerEncoderInput::new(self.tensor.clone()).mask_attn(self.mask_attn());
+
+        let output = encoder.forward_autoregressive_inference(input, &mut self.cache);
+        let [_, seq_length_output, _] = output.dims();
+        let output = output
+            .index([
+                0..batch_size,
+                seq_length_output - 1..seq_length_output,
+                0..d_model,
+            ])
+            .reshape([batch_size, d_model]);
+
+        output.mask_fill(self.finished_mask(), 0.0)
+    }
+
+    /// Append the next token of every sequence, the tokens of the finished sequences are
+    /// ignored.
+    ///
+    /// # Shapes
+    ///
+    /// - tokens: `[batch_size, d_model]`
+    pub fn append(&mut self, tokens: Tensor<B, 2>) {
+        let [batch_size, d_model] = tokens.dims();
+        let tokens = tokens.mask_fill(self.finished_mask(), 0.0);
+
+        self.tensor = Tensor::cat(
+            vec![
+                self.tensor.clone(),
+                tokens.reshape([batch_size, 1, d_model]),
+            ],
+            1,
+        );
+        for (masked, finished) in self.masked.iter_mut().zip(self.finished.iter()) {
+            masked.push(*finished);
+        }
+    }
+
+    /// Mark the sequence at the given index as finished.
+    pub fn finish(&mut self, index: usize) {
+        self.finished[index] = true;
+    }
+
+    /// If the sequence at the given index is finished.
+    pub fn is_finished(&self, index: usize) -> bool {
+        self.finished[index]
+    }
+
+    /// If every sequence is finished.
+    pub fn is_done(&self) -> bool {
+        self.finished.iter().all(|finished| *finished)
+    }
+
+    /// The number of padding positions before each sequence, which is the offset between the
+    /// position in the batch and the position in the sequence.
+    pub fn position_offsets(&self) -> &[usize] {
+        &self.offsets
+    }
+
+    /// The position in its own sequence of the next token appended for each sequence.
+    pub fn next_positions(&self) -> Vec<usize> {
+        let [_, seq_length, _] = self.tensor.dims();
+
+        self.offsets
+            .iter()
+            .map(|offset| seq_length - offset)
+            .collect()
+    }
+
+    /// The causal mask where the masked positions are also hidden from the other positions.
+    ///
+    /// A masked position still attends to itself, so that no row of the attention scores is
+    /// fully masked.
+    fn mask_attn(&self) -> Tensor<B, 3, Bool> {
+        let [batch_size, seq_length, _] = self.tensor.dims();
+        let values = self
+            .masked
+            .iter()
+            .flat_map(|masked| {
+                (0..seq_length)
+                    .flat_map(move |i| (0..seq_length).map(move |j| j > i || (masked[j] && j != i)))
+            })
+            .collect();
+
+        Tensor::from_bool_device(
+            Data::new(values, Shape::new([batch_size, seq_length, seq_length])),
+            &self.tensor.device(),
+        )
+    }
+
+    fn finished_mask(&self) -> Tensor<B, 2, Bool> {
+        let [_, _, d_model] = self.tensor.dims();
+
+        let values = self
+            .finished
+            .iter()
+            .flat_map(|finished| vec![*finished; d_model])
+            .collect();
+
+        Tensor::from_bool_device(
+            Data::new(values, Shape::new([self.finished.len(), d_model])),
+            &self.tensor.device(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nn::attention::generate_autoregressive_mask;
+    use crate::nn::transformer::TransformerEncoderConfig;
+    use crate::TestBackend;
+    use burn_tensor::Distribution;
+
+    #[test]
+    fn ragged_generation_should_match_each_sequence_generated_alone() {
+        let d_model = 8;
+        let encoder = TransformerEncoder::<TestBackend>::new(&TransformerEncoderConfig::new(
+            d_model, 16, 2, 2,
+        ));
+        let lengths = [2, 4];
+        let num_steps = 3;
+        let sequences = lengths.map(|length| {
+            Tensor::<TestBackend, 2>::random([length + num_steps, d_model], Distribution::Standard)
+        });
+
+        let mut generation = encoder.new_batched_generation(
+            sequences
+                .iter()
+                .zip(lengths)
+                .map(|(sequence, length)| sequence.clone().index([0..length, 0..d_model]))
+                .collect(),
+        );
+        assert_eq!(generation.position_offsets(), &[2, 0]);
+
+        for step in 0..num_steps {
+            let output = generation.forward(&encoder);
+
+            for (index, (sequence, length)) in sequences.iter().zip(lengths).enumerate() {
+                let expected = expected_last_output(&encoder, sequence, length + step);
+                let output = output.clone().index([index..index + 1, 0..d_model]);
+
+                output
+                    .into_data()
+                    .assert_approx_eq(&expected.into_data(), 3);
+            }
+
+            assert_eq!(
+                generation.next_positions(),
+                lengths.map(|length| length + step)
+            );
+            generation.append(Tensor::cat(
+                sequences
+                    .iter()
+                    .zip(lengths)
+                    .map(|(sequence, length)| {
+                        let position = length + step;
+                        sequence.clone().index([position..position + 1, 0..d_model])
+                    })
+                    .collect(),
+                0,
+            ));
+        }
+    }
+
+    #[test]
+    fn finished_sequences_should_stop_contributing() {
+        let d_model = 8;
+        let encoder = TransformerEncoder::<TestBackend>::new(&TransformerEncoderConfig::new(
+            d_model, 16, 2, 2,
+        ));
+        let sequence = Tensor::<TestBackend, 2>::random([4, d_model], Distribution::Standard);
+        let prompts = vec![
+            Tensor::random([3, d_model], Distribution::Standard),
+            sequence.clone().index([0..3, 0..d_model]),
+        ];
+
+        let mut generation = encoder.new_batched_generation(prompts);
+        generation.forward(&encoder);
+        generation.finish(0);
+        generation.append(Tensor::cat(
+            vec![
+                Tensor::random([1, d_model], Distribution::Standard),
+                sequence.clone().index([3..4, 0..d_model]),
+            ],
+            0,
+        ));
+        let output = generation.forward(&encoder);
+
+        assert!(generation.is_finished(0));
+        assert!(!generation.is_done());
+        output
+            .clone()
+            .index([0..1, 0..d_model])
+            .into_data()
+            .assert_approx_eq(
+                &Tensor::<TestBackend, 2>::zeros([1, d_model]).into_data(),
+                3,
+            );
+        output
+            .index([1..2, 0..d_model])
+            .into_data()
+            .assert_approx_eq(&expected_last_output(&encoder, &sequence, 4).into_data(), 3);
+    }
+
+    fn expected_last_output(
+        encoder: &TransformerEncoder<TestBackend>,
+        sequence: &Tensor<TestBackend, 2>,
+        length: usize,
+    ) -> Tensor<TestBackend, 2> {
+        let [_, d_model] = sequence.dims();
+        let tensor = sequence.clone().index([0..length, 0..d_model]).unsqueeze();
+        let mask_attn = generate_autoregressive_mask(1, length, &tensor.device());
+        let output = encoder.forward(TransformerEncoderInput::new(tensor).mask_attn(mask_attn));
+
+        output
+            .index([0..1, length - 1..length, 0..d_model])
+            .reshape([1, d_model])
+    }
+}
@@ -0,0 +1,151 @@
+use alloc::{format, vec, vec::Vec};
+
+use crate as burn;
+
+use super::{TransformerEncoder, TransformerEncoderConfig, TransformerEncoderInput};
+use crate::module::{Module, Param};
+use crate::nn::Initializer;
+use crate::tensor::{backend::Backend, Bool, Int, Tensor};
+
+/// A [transformer encoder](TransformerEncoder) with a learnable classification token prepended
+/// to each input sequence, as done by BERT.
+///
+/// The final representation of the classification token attends to the whole sequence, which
+/// makes it a summary of the sequence suited for classification.
+///
+/// # Params
+///
+/// - encoder: The wrapped [transformer encoder](TransformerEncoder).
+/// - cls_token: Vector of size `d_model` initialized from a normal distribution `N(0, 0.02)`.
+#[derive(Module, Debug)]
+pub struct ClsTransformerEncoder<B: Backend> {
+    encoder: Param<TransformerEncoder<B>>,
+    cls_token: Param<Tensor<B, 1>>,
+}
+
+impl<B: Backend> ClsTransformerEncoder<B> {
+    /// Create the module from the given configuration.
+    pub fn new(config: &TransformerEncoderConfig) -> Self {
+        Self {
+            encoder: Param::from(TransformerEncoder::new(config)),
+            cls_token: Param::from(Initializer::Normal(0.0, 0.02).init([config.d_model])),
+        }
+    }
+
+    /// Applies the forward pass on the input tensor, returning the output of the classification
+    /// token.
+    ///
+    /// The masks are extended so that the classification token is never padded and attends to,
+    /// and is attended by, every position.
+    ///
+    /// # Shapes
+    ///
+    /// - tensor: `[batch_size, seq_length, d_model]`
+    /// - output: `[batch_size, d_model]`
+    pub fn forward(&self, input: TransformerEncoderInput<B>) -> Tensor<B, 2> {
+        let [batch_size, _seq_length, d_model] = input.tensor.dims();
+        let device = input.tensor.device();
+
+        let cls_token = self
+            .cls_token
+            .val()
+            .reshape([1, 1, d_model])
+            .repeat(0, batch_size);
+        let tensor = Tensor::cat(vec![cls_token, input.tensor], 1);
+        let mut input_cls = TransformerEncoderInput::new(tensor);
+
+        if let Some(mask_pad) = input.mask_pad {
+            let mask_cls = unmasked([batch_size, 1], &device);
+            input_cls = input_cls.mask_pad(Tensor::cat(vec![mask_cls, mask_pad], 1));
+        }
+
+        if let Some(mask_attn) = input.mask_attn {
+            let [batch_size_mask, seq_length_1, seq_length_2] = mask_attn.dims();
+            let mask_row = unmasked([batch_size_mask, 1, seq_length_2], &device);
+            let mask_attn = Tensor::cat(vec![mask_row, mask_attn], 1);
+            let mask_column = unmasked([batch_size_mask, seq_length_1 + 1, 1], &device);
+            input_cls = input_cls.mask_attn(Tensor::cat(vec![mask_column, mask_attn], 2));
+        }
+
+        self.encoder
+            .forward(input_cls)
+            .index([0..batch_size, 0..1, 0..d_model])
+            .reshape([batch_size, d_model])
+    }
+}
+
+fn unmasked<B: Backend, const D: usize>(
+    shape: [usize; D],
+    device: &B::Device,
+) -> Tensor<B, D, Bool> {
+    Tensor::<B, D, Int>::zeros(shape)
+        .to_device(device)
+        .equal_elem(1_i64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TestBackend;
+    use burn_tensor::{Data, Distribution};
+
+    fn config() -> TransformerEncoderConfig {
+        TransformerEncoderConfig::new(12, 24, 2, 2)
+    }
+
+    #[test]
+    fn cls_output_should_depend_on_the_input() {
+        let encoder = ClsTransformerEncoder::<TestBackend>::new(&config());
+        let tensor_1 = Tensor::<TestBackend, 3>::random([2, 5, 12], Distribution::Standard);
+        let tensor_2 = Tensor::<TestBackend, 3>::random([2, 5, 12], Distribution::Standard);
+
+        let output_1 = encoder.forward(TransformerEncoderInput::new(tensor_1.clone()));
+        let output_2 = encoder.forward(TransformerEncoderInput::new(tensor_2));
+        let output_3 = encoder.forward(TransformerEncoderInput::new(tensor_1));
+
+        assert_eq!(output_1.dims(), [2, 12]);
+        assert_ne!(output_1.to_data(), output_2.to_data());
+        output_1
+            .into_data()
+            .assert_approx_eq(&output_3.into_data(), 5);
+    }
+
+    #[test]
+    fn cls_output_should_ignore_padded_positions() {
+        let encoder = ClsTransformerEncoder::<TestBackend>::new(&config());
+        let mask_pad = Tensor::<TestBackend, 2, Bool>::from_bool(Data::from([
+            [false, false, false, true, true],
+            [false, false, false, false, true],
+        ]));
+        let tensor_1 = Tensor::<TestBackend, 3>::random([2, 5, 12], Distribution::Standard);
+        let tensor_2 = tensor_1.clone().index_assign(
+            [0..2, 4..5, 0..12],
+            Tensor::random([2, 1, 12], Distribution::Standard),
+        );
+
+        let output_1 =
+            encoder.forward(TransformerEncoderInput::new(tensor_1).mask_pad(mask_pad.clone()));
+        let output_2 = encoder.forward(TransformerEncoderInput::new(tensor_2).mask_pad(mask_pad));
+
+        output_1
+            .into_data()
+            .assert_approx_eq(&output_2.into_data(), 3);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn cls_token_should_receive_gradients() {
+        use crate::TestADBackend;
+
+        let encoder = ClsTransformerEncoder::<TestADBackend>::new(&config());
+        let tensor = Tensor::<TestADBackend, 3>::random([2, 5, 12], Distribution::Standard);
+
+        let grads = encoder
+            .forward(TransformerEncoderInput::new(tensor))
+            .powf(2.0)
+            .sum()
+            .backward();
+
+        assert!(encoder.cls_token.grad(&grads).is_some());
+    }
+}
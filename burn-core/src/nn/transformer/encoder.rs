@@ -6,7 +6,9 @@ use crate::{
     nn::{attention::MHAAutoregressiveCache, cache::TensorCache},
 };
 
-use super::{PositionWiseFeedForward, PositionWiseFeedForwardConfig};
+use super::{
+    PositionWiseFeedForward, PositionWiseFeedForwardConfig, TransformerEncoderBatchedGeneration,
+};
 use crate::{
     config::Config,
     module::{Module, Param},
@@ -14,9 +16,14 @@ use crate::{
         attention::{MhaInput, MultiHeadAttention, MultiHeadAttentionConfig},
         Dropout, DropoutConfig, LayerNorm, LayerNormConfig,
     },
-    tensor::{backend::Backend, Tensor},
+    tensor::{backend::Backend, module::checkpoint, Tensor},
 };
 
+#[cfg(feature = "std")]
+use crate::{module::list_param_ids, optim::ParamGroups};
+
+use libm::sqrt;
+
 /// Configuration to create a [Transformer Encoder](TransformerEncoder) layer.
 #[derive(Config)]
 pub struct TransformerEncoderConfig {
@@ -34,6 +41,52 @@ pub struct TransformerEncoderConfig {
     /// Layer norm will be applied first instead of after the other modules.
     #[config(default = false)]
     pub norm_first: bool,
+    /// The scaling applied to the output of each sublayer before adding the residual.
+    /// Default: ResidualScale::Fixed(1.0)
+    #[config(default = "ResidualScale::Fixed(1.0)")]
+    pub residual_scale: ResidualScale,
+    /// The parts of each layer recomputed during the backward pass instead of keeping their
+    /// activations in memory. Default: CheckpointGranularity::None
+    #[config(default = "CheckpointGranularity::None")]
+    pub checkpointing: CheckpointGranularity,
+    /// Every layer norm of every layer shares the parameters of a single layer norm, as in
+    /// ALBERT-style models, reducing the number of parameters. Default: false
+    #[config(default = false)]
+    pub shared_norm: bool,
+    /// Scale the initial weights of the output projections of the attention and the
+    /// feed-forward network by `1 / sqrt(2 * n_layers)`, as done in GPT-2, so that the residual
+    /// branches start small in deep models. Default: false
+    #[config(default = false)]
+    pub scaled_init: bool,
+}
+
+/// Scaling of the residual branches of a [transformer encoder](TransformerEncoder) layer, which
+/// helps to train very deep models.
+#[derive(Config, Debug, PartialEq)]
+pub enum ResidualScale {
+    /// Multiply the output of each sublayer by a constant factor.
+    Fixed(f64),
+    /// Multiply the output of each sublayer by a learnable scalar per layer initialized to zero,
+    /// as described in the paper [ReZero is All You Need](https://arxiv.org/abs/2003.04887).
+    ///
+    /// Layer norms are not applied, so each layer starts as the identity function.
+    ReZero,
+}
+
+/// Gradient checkpointing granularity of a [transformer encoder](TransformerEncoder), trading
+/// compute for memory during training.
+///
+/// The checkpointed parts are computed twice, once during the forward pass and once during the
+/// backward pass, and their intermediate activations aren't kept in memory in between.
+#[derive(Config, Debug, PartialEq)]
+pub enum CheckpointGranularity {
+    /// Keep all the activations.
+    None,
+    /// Recompute each whole layer.
+    Layer,
+    /// Only recompute the position-wise feed-forward network of each layer, which holds the
+    /// largest activations.
+    FfnOnly,
 }
 
 /// The transformer encoder module as describe in the paper [Attention Is All You Need](https://arxiv.org/abs/1706.03762).
@@ -44,14 +97,15 @@ pub struct TransformerEncoderConfig {
 #[derive(Module, Debug)]
 pub struct TransformerEncoder<B: Backend> {
     layers: Param<Vec<TransformerEncoderLayer<B>>>,
+    checkpoint_layers: bool,
 }
 
 /// [Transformer Encoder](TransformerEncoder) forward pass input argument.
 #[derive(Debug)]
 pub struct TransformerEncoderInput<B: Backend> {
-    tensor: Tensor<B, 3>,
-    mask_pad: Option<Tensor<B, 2, Bool>>,
-    mask_attn: Option<Tensor<B, 3, Bool>>,
+    pub(super) tensor: Tensor<B, 3>,
+    pub(super) mask_pad: Option<Tensor<B, 2, Bool>>,
+    pub(super) mask_attn: Option<Tensor<B, 3, Bool>>,
 }
 
 impl<B: Backend> TransformerEncoderInput<B> {
@@ -80,12 +134,19 @@ impl<B: Backend> TransformerEncoderInput<B> {
 impl<B: Backend> TransformerEncoder<B> {
     /// Create the module from the given configuration.
     pub fn new(config: &TransformerEncoderConfig) -> Self {
+        // The layers reference the same parameter ids, so their gradients are accumulated and
+        // they are updated together by the optimizer.
+        let norm = match config.shared_norm {
+            true => Some(LayerNorm::new(&LayerNormConfig::new(config.d_model))),
+            false => None,
+        };
         let layers = (0..config.n_layers)
-            .map(|_| TransformerEncoderLayer::new(config))
+            .map(|_| TransformerEncoderLayer::new(config, norm.as_ref()))
             .collect::<Vec<_>>();
 
         Self {
             layers: Param::from(layers),
+            checkpoint_layers: config.checkpointing == CheckpointGranularity::Layer,
         }
     }
 
@@ -99,13 +160,63 @@ impl<B: Backend> TransformerEncoder<B> {
         let mut x = input.tensor;
 
         for layer in self.layers.iter() {
-            x = layer.forward(x, input.mask_pad.clone(), input.mask_attn.clone());
+            x = self.forward_layer(layer, x, &input.mask_pad, &input.mask_attn);
         }
 
         x
     }
+
+    /// Applies the forward pass on the input tensor, returning the output of every layer.
+    ///
+    /// The output has one tensor per layer, the last one being the output of
+    /// [forward](Self::forward). The embedding input isn't included since it's already known by
+    /// the caller.
+    ///
+    /// # Shapes
+    ///
+    /// - tensor: `[batch_size, seq_length, d_model]`
+    /// - output: `n_layers` tensors of shape `[batch_size, seq_length, d_model]`
+    pub fn forward_all_layers(&self, input: TransformerEncoderInput<B>) -> Vec<Tensor<B, 3>> {
+        let mut x = input.tensor;
+        let mut outputs = Vec::with_capacity(self.layers.len());
+
+        for layer in self.layers.iter() {
+            x = self.forward_layer(layer, x, &input.mask_pad, &input.mask_attn);
+            outputs.push(x.clone());
+        }
+
+        outputs
+    }
+
+    fn forward_layer(
+        &self,
+        layer: &TransformerEncoderLayer<B>,
+        x: Tensor<B, 3>,
+        mask_pad: &Option<Tensor<B, 2, Bool>>,
+        mask_attn: &Option<Tensor<B, 3, Bool>>,
+    ) -> Tensor<B, 3> {
+        let mask_pad = mask_pad.clone();
+        let mask_attn = mask_attn.clone();
+
+        match self.checkpoint_layers {
+            true => {
+                let layer = layer.clone();
+                checkpoint(x, move |x| {
+                    layer.forward(x, mask_pad.clone(), mask_attn.clone())
+                })
+            }
+            false => layer.forward(x, mask_pad, mask_attn),
+        }
+    }
+
     /// Applies the forward pass on the input tensor using autoregressive cache.
     ///
+    /// With a [windowed](Self::new_autoregressive_cache_windowed) cache, the output only contains
+    /// the last `window` positions.
+    ///
+    /// Dropout is never applied, so repeated generations with the same inputs are identical even
+    /// with an autodiff backend.
+    ///
     /// # Shapes
     ///
     /// - tensor: `[batch_size, seq_length, d_model]`
@@ -117,6 +228,14 @@ impl<B: Backend> TransformerEncoder<B> {
     ) -> Tensor<B, 3> {
         let mut x = input.tensor;
 
+        if let Some(window) = cache.window {
+            let [batch_size, seq_length, d_model] = x.dims();
+
+            if seq_length > window {
+                x = x.index([0..batch_size, seq_length - window..seq_length, 0..d_model]);
+            }
+        }
+
         for i in 0..self.layers.len() {
             let layer = self.layers.get(i).unwrap();
             let cache = cache.layers.get_mut(i).unwrap();
@@ -136,6 +255,56 @@ impl<B: Backend> TransformerEncoder<B> {
     pub fn new_autoregressive_cache(&self) -> TransformerEncoderAutoregressiveCache<B> {
         TransformerEncoderAutoregressiveCache::empty(self.layers.len())
     }
+
+    /// Create an autoregressive cache preallocated for sequences of at most `max_seq_length`
+    /// tokens, avoiding reallocations at each decoding step.
+    pub fn new_autoregressive_cache_preallocated(
+        &self,
+        max_seq_length: usize,
+    ) -> TransformerEncoderAutoregressiveCache<B> {
+        TransformerEncoderAutoregressiveCache::preallocated(self.layers.len(), max_seq_length)
+    }
+
+    /// Create an autoregressive cache keeping only the last `window` positions, each token only
+    /// attends to the `window` most recent tokens, bounding the memory used by long generations.
+    pub fn new_autoregressive_cache_windowed(
+        &self,
+        window: usize,
+    ) -> TransformerEncoderAutoregressiveCache<B> {
+        TransformerEncoderAutoregressiveCache::windowed(self.layers.len(), window)
+    }
+
+    /// Parameter groups applying a layer-wise learning rate decay when fine-tuning, the layers
+    /// closer to the input have a smaller learning rate.
+    ///
+    /// With `n` layers, the learning rate of the layer `i` is multiplied by `decay^(n - i)`, so
+    /// the first layer has a factor of `decay^n` and the parameters above the encoder, like a
+    /// task head, keep the learning rate of the [optimizer](crate::optim::ParamGroupsOptimizer).
+    #[cfg(feature = "std")]
+    pub fn layerwise_lr_decay(&self, decay: f64) -> ParamGroups {
+        let num_layers = self.layers.len();
+
+        self.layers
+            .iter()
+            .enumerate()
+            .fold(ParamGroups::new(), |groups, (i, layer)| {
+                let lr_factor = libm::pow(decay, (num_layers - i) as f64);
+                groups.with_group(&list_param_ids(layer), lr_factor)
+            })
+    }
+
+    /// Start an autoregressive generation over a batch of prompts of different lengths, see
+    /// [TransformerEncoderBatchedGeneration].
+    ///
+    /// # Shapes
+    ///
+    /// - prompts: `[seq_length, d_model]` each
+    pub fn new_batched_generation(
+        &self,
+        prompts: Vec<Tensor<B, 2>>,
+    ) -> TransformerEncoderBatchedGeneration<B> {
+        TransformerEncoderBatchedGeneration::new(self.new_autoregressive_cache(), prompts)
+    }
 }
 
 #[derive(Module, Debug)]
@@ -146,22 +315,37 @@ struct TransformerEncoderLayer<B: Backend> {
     norm_2: Param<LayerNorm<B>>,
     dropout: Dropout,
     norm_first: bool,
+    residual_factor: f64,
+    rezero: Param<Option<Tensor<B, 1>>>,
+    checkpoint_ffn: bool,
 }
 
 impl<B: Backend> TransformerEncoderLayer<B> {
-    fn new(config: &TransformerEncoderConfig) -> Self {
+    fn new(config: &TransformerEncoderConfig, norm: Option<&LayerNorm<B>>) -> Self {
         let config_norm = LayerNormConfig::new(config.d_model);
         let config_dropout = DropoutConfig::new(config.dropout);
+        let output_proj_scale = match config.scaled_init {
+            true => 1.0 / sqrt(2.0 * config.n_layers as f64),
+            false => 1.0,
+        };
         let config_mha = MultiHeadAttentionConfig::new(config.d_model, config.n_heads)
-            .with_dropout(config.dropout);
+            .with_dropout(config.dropout)
+            .with_output_proj_scale(output_proj_scale);
         let config_pwff = PositionWiseFeedForwardConfig::new(config.d_model, config.d_ff)
-            .with_dropout(config.dropout);
+            .with_dropout(config.dropout)
+            .with_output_proj_scale(output_proj_scale);
 
         let mha = MultiHeadAttention::new(&config_mha);
-        let norm_1 = LayerNorm::new(&config_norm);
-        let norm_2 = LayerNorm::new(&config_norm);
+        let (norm_1, norm_2) = match norm {
+            Some(norm) => (norm.clone(), norm.clone()),
+            None => (LayerNorm::new(&config_norm), LayerNorm::new(&config_norm)),
+        };
         let dropout = Dropout::new(&config_dropout);
         let pwff = PositionWiseFeedForward::new(&config_pwff);
+        let (residual_factor, rezero) = match config.residual_scale {
+            ResidualScale::Fixed(factor) => (factor, None),
+            ResidualScale::ReZero => (1.0, Some(Tensor::zeros([1]))),
+        };
 
         Self {
             mha: Param::from(mha),
@@ -170,16 +354,31 @@ impl<B: Backend> TransformerEncoderLayer<B> {
             pwff: Param::from(pwff),
             dropout,
             norm_first: config.norm_first,
+            residual_factor,
+            rezero: Param::from(rezero),
+            checkpoint_ffn: config.checkpointing == CheckpointGranularity::FfnOnly,
+        }
+    }
+
+    fn scale_residual(&self, tensor: Tensor<B, 3>) -> Tensor<B, 3> {
+        match self.rezero.val() {
+            Some(alpha) => tensor * alpha.unsqueeze(),
+            None if self.residual_factor == 1.0 => tensor,
+            None => tensor.mul_scalar(self.residual_factor),
         }
     }
 
+    fn use_norm(&self) -> bool {
+        self.rezero.is_none()
+    }
+
     fn forward(
         &self,
         mut input: Tensor<B, 3>,
         mask_pad: Option<Tensor<B, 2, Bool>>,
         mask_attn: Option<Tensor<B, 3, Bool>>,
     ) -> Tensor<B, 3> {
-        if self.norm_first {
+        if self.norm_first && self.use_norm() {
             input = self.norm_2.forward(input)
         }
 
@@ -194,13 +393,22 @@ impl<B: Backend> TransformerEncoderLayer<B> {
         }
 
         let x_1 = self.mha.forward(input_mhs);
-        let x_1 = self.dropout.forward(x_1.context) + input;
-        let x_1 = self.norm_1.forward(x_1);
+        let mut x_1 = self.scale_residual(self.dropout.forward(x_1.context)) + input;
+
+        if self.use_norm() {
+            x_1 = self.norm_1.forward(x_1);
+        }
 
-        let x_2 = self.pwff.forward(x_1.clone());
-        let mut x_2 = self.dropout.forward(x_2) + x_1;
+        let x_2 = match self.checkpoint_ffn {
+            true => {
+                let pwff = self.pwff.clone();
+                checkpoint(x_1.clone(), move |x| pwff.forward(x))
+            }
+            false => self.pwff.forward(x_1.clone()),
+        };
+        let mut x_2 = self.scale_residual(self.dropout.forward(x_2)) + x_1;
 
-        if !self.norm_first {
+        if !self.norm_first && self.use_norm() {
             x_2 = self.norm_2.forward(x_2)
         }
 
@@ -214,7 +422,7 @@ impl<B: Backend> TransformerEncoderLayer<B> {
         mask_attn: Option<Tensor<B, 3, Bool>>,
         cache: &mut TransformerEncoderLayerAutoregressiveCache<B>,
     ) -> Tensor<B, 3> {
-        if self.norm_first {
+        if self.norm_first && self.use_norm() {
             input = cache
                 .norm_2
                 .forward_autoregressive(input, 1, |input| self.norm_2.forward(input));
@@ -233,17 +441,20 @@ impl<B: Backend> TransformerEncoderLayer<B> {
         let x_1 = self
             .mha
             .forward_autoregressive_inference(input_mhs, &mut cache.mha);
-        let x_1 = self.dropout.forward(x_1.context) + input;
-        let x_1 = cache
-            .norm_1
-            .forward_autoregressive(x_1, 1, |x_1| self.norm_1.forward(x_1));
+        let mut x_1 = self.scale_residual(x_1.context) + input;
+
+        if self.use_norm() {
+            x_1 = cache
+                .norm_1
+                .forward_autoregressive(x_1, 1, |x_1| self.norm_1.forward(x_1));
+        }
 
         let x_2 = cache
             .pwff
-            .forward_autoregressive(x_1.clone(), 1, |x_1| self.pwff.forward(x_1));
-        let mut x_2 = self.dropout.forward(x_2) + x_1;
+            .forward_autoregressive(x_1.clone(), 1, |x_1| self.pwff.forward_inference(x_1));
+        let mut x_2 = self.scale_residual(x_2) + x_1;
 
-        if !self.norm_first {
+        if !self.norm_first && self.use_norm() {
             x_2 = cache
                 .norm_2
                 .forward_autoregressive(x_2, 1, |x_2| self.norm_2.forward(x_2));
@@ -253,7 +464,7 @@ impl<B: Backend> TransformerEncoderLayer<B> {
     }
 }
 
-#[derive(Default)]
+#[derive(Default, Clone)]
 struct TransformerEncoderLayerAutoregressiveCache<B: Backend> {
     mha: MHAAutoregressiveCache<B>,
     pwff: TensorCache<B, 3>,
@@ -265,13 +476,33 @@ impl<B: Backend> TransformerEncoderLayerAutoregressiveCache<B> {
     fn new() -> Self {
         Self::default()
     }
+
+    fn preallocated(max_seq_length: usize) -> Self {
+        Self {
+            mha: MHAAutoregressiveCache::preallocated(max_seq_length),
+            pwff: TensorCache::preallocated(max_seq_length),
+            norm_1: TensorCache::preallocated(max_seq_length),
+            norm_2: TensorCache::preallocated(max_seq_length),
+        }
+    }
+
+    fn windowed(window: usize) -> Self {
+        Self {
+            mha: MHAAutoregressiveCache::windowed(window),
+            pwff: TensorCache::windowed(window),
+            norm_1: TensorCache::windowed(window),
+            norm_2: TensorCache::windowed(window),
+        }
+    }
 }
 
 /// Autoregressive cache for the [Transformer Encoder](TransformerEncoder) layer.
 ///
 /// To be used during inference when decoding tokens.
+#[derive(Clone)]
 pub struct TransformerEncoderAutoregressiveCache<B: Backend> {
     layers: Vec<TransformerEncoderLayerAutoregressiveCache<B>>,
+    window: Option<usize>,
 }
 
 impl<B: Backend> TransformerEncoderAutoregressiveCache<B> {
@@ -280,6 +511,25 @@ impl<B: Backend> TransformerEncoderAutoregressiveCache<B> {
             layers: (0..num_layers)
                 .map(|_| TransformerEncoderLayerAutoregressiveCache::new())
                 .collect(),
+            window: None,
+        }
+    }
+
+    fn preallocated(num_layers: usize, max_seq_length: usize) -> Self {
+        Self {
+            layers: (0..num_layers)
+                .map(|_| TransformerEncoderLayerAutoregressiveCache::preallocated(max_seq_length))
+                .collect(),
+            window: None,
+        }
+    }
+
+    fn windowed(num_layers: usize, window: usize) -> Self {
+        Self {
+            layers: (0..num_layers)
+                .map(|_| TransformerEncoderLayerAutoregressiveCache::windowed(window))
+                .collect(),
+            window: Some(window),
         }
     }
 }
@@ -288,7 +538,7 @@ impl<B: Backend> TransformerEncoderAutoregressiveCache<B> {
 mod tests {
     use super::*;
     use crate::{nn::attention::generate_autoregressive_mask, TestBackend};
-    use burn_tensor::Distribution;
+    use burn_tensor::{Data, Distribution};
 
     #[test]
     fn test_autoregressive_norm_last() {
@@ -307,10 +557,333 @@ mod tests {
         )
     }
 
-    fn test_autoregressive(config: TransformerEncoderConfig) {
-        let [batch_size, seq_length, d_model] = [3, 4, config.d_model];
+    #[test]
+    fn test_autoregressive_preallocated_cache_should_match_concatenation() {
+        let [d_model, d_ff, n_heads, num_layers] = [12, 24, 2, 3];
+        let [batch_size, seq_length] = [3, 4];
+        let config = TransformerEncoderConfig::new(d_model, d_ff, n_heads, num_layers);
         let transformer = TransformerEncoder::new(&config);
 
+        let tensor = Tensor::<TestBackend, 3>::random(
+            [batch_size, seq_length, d_model],
+            Distribution::Standard,
+        );
+        let mut cache_cat = transformer.new_autoregressive_cache();
+        let mut cache_preallocated = transformer.new_autoregressive_cache_preallocated(8);
+
+        for i in 1..seq_length + 1 {
+            let tensor = tensor.clone().index([0..batch_size, 0..i, 0..d_model]);
+            let output_cat = transformer.forward_autoregressive_inference(
+                TransformerEncoderInput::new(tensor.clone()),
+                &mut cache_cat,
+            );
+            let output_preallocated = transformer.forward_autoregressive_inference(
+                TransformerEncoderInput::new(tensor),
+                &mut cache_preallocated,
+            );
+
+            output_cat
+                .into_data()
+                .assert_approx_eq(&output_preallocated.into_data(), 5);
+        }
+    }
+
+    #[test]
+    fn test_autoregressive_windowed_cache_should_stay_bounded() {
+        let [d_model, d_ff, n_heads, num_layers] = [12, 24, 2, 2];
+        let [batch_size, seq_length, window] = [2, 12, 3];
+        let config = TransformerEncoderConfig::new(d_model, d_ff, n_heads, num_layers);
+        let transformer = TransformerEncoder::<TestBackend>::new(&config);
+        let tensor = Tensor::<TestBackend, 3>::random(
+            [batch_size, seq_length, d_model],
+            Distribution::Standard,
+        );
+
+        let mut cache = transformer.new_autoregressive_cache_windowed(window);
+
+        for i in 1..seq_length + 1 {
+            let output = transformer.forward_autoregressive_inference(
+                TransformerEncoderInput::new(input_prefix(&tensor, i)),
+                &mut cache,
+            );
+            let length = usize::min(i, window);
+
+            assert_eq!(output.dims(), [batch_size, length, d_model]);
+            for layer in cache.layers.iter() {
+                let state = layer.pwff.state.as_ref().unwrap();
+                assert_eq!(state.dims()[1], length);
+            }
+
+            // Each token only attends to the tokens of its window.
+            let mask_attn = generate_sliding_window_mask(batch_size, i, window);
+            let expected = transformer
+                .forward(
+                    TransformerEncoderInput::new(input_prefix(&tensor, i)).mask_attn(mask_attn),
+                )
+                .index([0..batch_size, i - 1..i, 0..d_model]);
+            output
+                .index([0..batch_size, length - 1..length, 0..d_model])
+                .into_data()
+                .assert_approx_eq(&expected.into_data(), 3);
+        }
+    }
+
+    fn input_prefix(tensor: &Tensor<TestBackend, 3>, length: usize) -> Tensor<TestBackend, 3> {
+        let [batch_size, _, d_model] = tensor.dims();
+
+        tensor.clone().index([0..batch_size, 0..length, 0..d_model])
+    }
+
+    fn generate_sliding_window_mask(
+        batch_size: usize,
+        seq_length: usize,
+        window: usize,
+    ) -> Tensor<TestBackend, 3, Bool> {
+        let values = (0..seq_length)
+            .flat_map(|i| (0..seq_length).map(move |j| j > i || j + window <= i))
+            .collect();
+        let mask = Tensor::<TestBackend, 3, Bool>::from_bool(Data::new(
+            values,
+            [1, seq_length, seq_length].into(),
+        ));
+
+        mask.repeat(0, batch_size)
+    }
+
+    #[test]
+    fn test_autoregressive_rezero() {
+        let [d_model, d_ff, n_heads, num_layers] = [12, 24, 2, 3];
+        let config = TransformerEncoderConfig::new(d_model, d_ff, n_heads, num_layers)
+            .with_residual_scale(ResidualScale::ReZero);
+        let mut transformer = TransformerEncoder::<TestBackend>::new(&config);
+        transformer.layers = Param::from(
+            transformer
+                .layers
+                .iter()
+                .map(|layer| {
+                    let mut layer = layer.clone();
+                    layer.rezero = Param::from(Some(Tensor::ones([1]).mul_scalar(0.5)));
+                    layer
+                })
+                .collect::<Vec<_>>(),
+        );
+
+        test_autoregressive_transformer(transformer, d_model);
+    }
+
+    #[test]
+    fn test_rezero_should_be_identity_at_initialization() {
+        let [d_model, d_ff, n_heads, num_layers] = [12, 24, 2, 3];
+        let config = TransformerEncoderConfig::new(d_model, d_ff, n_heads, num_layers)
+            .with_residual_scale(ResidualScale::ReZero);
+        let transformer = TransformerEncoder::<TestBackend>::new(&config);
+        let tensor = Tensor::<TestBackend, 3>::random([2, 5, d_model], Distribution::Standard);
+
+        let output = transformer.forward(TransformerEncoderInput::new(tensor.clone()));
+
+        output.into_data().assert_approx_eq(&tensor.into_data(), 5);
+    }
+
+    #[test]
+    fn test_forward_all_layers_should_end_with_forward_output() {
+        let [d_model, d_ff, n_heads, num_layers] = [12, 24, 2, 3];
+        let config = TransformerEncoderConfig::new(d_model, d_ff, n_heads, num_layers);
+        let transformer = TransformerEncoder::<TestBackend>::new(&config);
+        let tensor = Tensor::<TestBackend, 3>::random([2, 5, d_model], Distribution::Standard);
+        let mask_attn = generate_autoregressive_mask(2, 5, &tensor.device());
+
+        let output = transformer
+            .forward(TransformerEncoderInput::new(tensor.clone()).mask_attn(mask_attn.clone()));
+        let outputs = transformer
+            .forward_all_layers(TransformerEncoderInput::new(tensor).mask_attn(mask_attn));
+
+        assert_eq!(outputs.len(), num_layers);
+        outputs
+            .last()
+            .unwrap()
+            .to_data()
+            .assert_approx_eq(&output.into_data(), 5);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_ffn_checkpointing_should_match_standard_forward() {
+        test_checkpointing(CheckpointGranularity::FfnOnly);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_layer_checkpointing_should_match_standard_forward() {
+        test_checkpointing(CheckpointGranularity::Layer);
+    }
+
+    #[cfg(feature = "std")]
+    fn test_checkpointing(checkpointing: CheckpointGranularity) {
+        use crate::TestADBackend;
+
+        let [d_model, d_ff, n_heads, num_layers] = [12, 24, 2, 2];
+        let config = TransformerEncoderConfig::new(d_model, d_ff, n_heads, num_layers)
+            .with_dropout(0.0)
+            .with_checkpointing(checkpointing);
+        let transformer = TransformerEncoder::<TestADBackend>::new(&config);
+        let mut transformer_standard = transformer.clone();
+        transformer_standard.checkpoint_layers = false;
+        transformer_standard.layers = Param::from(
+            transformer
+                .layers
+                .iter()
+                .map(|layer| {
+                    let mut layer = layer.clone();
+                    layer.checkpoint_ffn = false;
+                    layer
+                })
+                .collect::<Vec<_>>(),
+        );
+        let data =
+            Tensor::<TestBackend, 3>::random([2, 5, d_model], Distribution::Standard).into_data();
+
+        let forward = |transformer: &TransformerEncoder<TestADBackend>| {
+            let tensor = Tensor::<TestADBackend, 3>::from_data(data.clone()).require_grad();
+            let output = transformer.forward(TransformerEncoderInput::new(tensor.clone()));
+            let grads = output.clone().powf(2.0).sum().backward();
+
+            (output.into_data(), tensor.grad(&grads).unwrap().into_data())
+        };
+        let (output, grad) = forward(&transformer);
+        let (output_standard, grad_standard) = forward(&transformer_standard);
+
+        output.assert_approx_eq(&output_standard, 5);
+        grad.assert_approx_eq(&grad_standard, 4);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_shared_norm_should_share_params_and_accumulate_grads() {
+        use crate::optim::GradientsParams;
+        use crate::TestADBackend;
+
+        let [d_model, d_ff, n_heads, num_layers] = [12, 24, 2, 3];
+        let config =
+            TransformerEncoderConfig::new(d_model, d_ff, n_heads, num_layers).with_dropout(0.0);
+        let config_shared = TransformerEncoderConfig::new(d_model, d_ff, n_heads, num_layers)
+            .with_dropout(0.0)
+            .with_shared_norm(true);
+        let transformer = TransformerEncoder::<TestADBackend>::new(&config);
+        let transformer_shared = TransformerEncoder::<TestADBackend>::new(&config_shared);
+
+        // Each of the 2 * num_layers layer norms has a gamma and a beta of size d_model.
+        assert_eq!(
+            transformer.num_params() - transformer_shared.num_params(),
+            (2 * num_layers - 1) * 2 * d_model
+        );
+        let norm_ids = list_param_ids(&transformer_shared.layers[0].norm_1);
+        for layer in transformer_shared.layers.iter() {
+            assert_eq!(list_param_ids(&layer.norm_1), norm_ids);
+            assert_eq!(list_param_ids(&layer.norm_2), norm_ids);
+        }
+
+        // Loading the state gives a distinct tensor to each layer with the same parameter ids.
+        let transformer_loaded = transformer_shared
+            .clone()
+            .load(&transformer_shared.state())
+            .unwrap();
+        let tensor = Tensor::<TestADBackend, 3>::random([2, 5, d_model], Distribution::Standard);
+        let grads = |transformer: &TransformerEncoder<TestADBackend>| {
+            let output = transformer.forward(TransformerEncoderInput::new(tensor.clone()));
+            GradientsParams::from_grads(output.powf(2.0).sum().backward(), transformer)
+        };
+        let (grads_shared, grads_loaded) = (grads(&transformer_shared), grads(&transformer_loaded));
+
+        for id in norm_ids {
+            grads_loaded
+                .get::<TestBackend, 1>(&id)
+                .unwrap()
+                .into_data()
+                .assert_approx_eq(
+                    &grads_shared.get::<TestBackend, 1>(&id).unwrap().into_data(),
+                    3,
+                );
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_shared_norm_should_be_scaled_and_averaged_once() {
+        use crate::optim::{GradientsParams, Swa};
+        use crate::TestADBackend;
+
+        let [d_model, d_ff, n_heads, num_layers] = [12, 24, 2, 3];
+        let config = TransformerEncoderConfig::new(d_model, d_ff, n_heads, num_layers)
+            .with_dropout(0.0)
+            .with_shared_norm(true);
+        let transformer = TransformerEncoder::<TestADBackend>::new(&config);
+        let norm_ids = list_param_ids(&transformer.layers[0].norm_1);
+
+        let tensor = Tensor::<TestADBackend, 3>::random([2, 5, d_model], Distribution::Standard);
+        let output = transformer.forward(TransformerEncoderInput::new(tensor));
+        let grads = GradientsParams::from_grads(output.powf(2.0).sum().backward(), &transformer);
+        let grads_norm: Vec<Tensor<TestBackend, 1>> =
+            norm_ids.iter().map(|id| grads.get(id).unwrap()).collect();
+
+        assert_eq!(grads.norms(&transformer).len(), grads.len());
+        let grads = grads.scale(2.0, &transformer);
+        for (id, grad) in norm_ids.iter().zip(grads_norm) {
+            grads
+                .get::<TestBackend, 1>(id)
+                .unwrap()
+                .into_data()
+                .assert_approx_eq(&grad.mul_scalar(2.0).into_data(), 3);
+        }
+
+        let mut swa = Swa::new(0);
+        swa.update(
+            &transformer
+                .clone()
+                .map_params(|_, tensor| tensor.zeros_like()),
+            0,
+        );
+        swa.update(
+            &transformer
+                .clone()
+                .map_params(|_, tensor| tensor.ones_like()),
+            1,
+        );
+        swa.averaged(transformer).map_params(|_, tensor| {
+            assert_eq!(
+                tensor.clone().into_data(),
+                tensor.ones_like().mul_scalar(0.5).into_data()
+            );
+            tensor
+        });
+    }
+
+    #[test]
+    fn test_fixed_residual_scale_should_scale_sublayers() {
+        let [d_model, d_ff, n_heads] = [12, 24, 2];
+        let config = TransformerEncoderConfig::new(d_model, d_ff, n_heads, 1)
+            .with_residual_scale(ResidualScale::Fixed(0.0));
+        let transformer = TransformerEncoder::<TestBackend>::new(&config);
+        let tensor = Tensor::<TestBackend, 3>::random([2, 5, d_model], Distribution::Standard);
+
+        let output = transformer.forward(TransformerEncoderInput::new(tensor.clone()));
+
+        let layer = &transformer.layers[0];
+        let expected = layer.norm_2.forward(layer.norm_1.forward(tensor));
+        output
+            .into_data()
+            .assert_approx_eq(&expected.into_data(), 5);
+    }
+
+    fn test_autoregressive(config: TransformerEncoderConfig) {
+        test_autoregressive_transformer(TransformerEncoder::new(&config), config.d_model);
+    }
+
+    fn test_autoregressive_transformer(
+        transformer: TransformerEncoder<TestBackend>,
+        d_model: usize,
+    ) {
+        let [batch_size, seq_length] = [3, 4];
+
         let tensor = Tensor::<TestBackend, 3>::random(
             [batch_size, seq_length, d_model],
             Distribution::Standard,
@@ -337,4 +910,60 @@ mod tests {
             .into_data()
             .assert_approx_eq(&output_2.into_data(), 3);
     }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_layerwise_lr_decay_should_decay_geometrically_from_the_top() {
+        let [d_model, d_ff, n_heads, num_layers] = [12, 24, 2, 3];
+        let config = TransformerEncoderConfig::new(d_model, d_ff, n_heads, num_layers);
+        let transformer = TransformerEncoder::<TestBackend>::new(&config);
+        let head = crate::nn::Linear::<TestBackend>::new(&crate::nn::LinearConfig::new(d_model, 2));
+
+        let groups = transformer.layerwise_lr_decay(0.5);
+
+        for (layer, expected) in transformer.layers.iter().zip([0.125, 0.25, 0.5]) {
+            for id in list_param_ids(layer) {
+                assert_eq!(groups.lr_factor(&id), expected);
+            }
+        }
+        for id in list_param_ids(&head) {
+            assert_eq!(groups.lr_factor(&id), 1.0);
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_autoregressive_inference_should_not_apply_dropout() {
+        use crate::TestADBackend;
+
+        let [d_model, d_ff, n_heads, num_layers] = [12, 24, 2, 2];
+        let [batch_size, seq_length] = [2, 4];
+        let config =
+            TransformerEncoderConfig::new(d_model, d_ff, n_heads, num_layers).with_dropout(0.5);
+        let transformer = TransformerEncoder::<TestADBackend>::new(&config);
+        let tensor = Tensor::<TestADBackend, 3>::random(
+            [batch_size, seq_length, d_model],
+            Distribution::Standard,
+        );
+
+        let generate = || {
+            let mut cache = transformer.new_autoregressive_cache();
+            let mut outputs = Vec::new();
+
+            for i in 1..seq_length + 1 {
+                let input = TransformerEncoderInput::new(tensor.clone().index([
+                    0..batch_size,
+                    0..i,
+                    0..d_model,
+                ]));
+                let output = transformer.forward_autoregressive_inference(input, &mut cache);
+                let next_tok = output.index([0..batch_size, i - 1..i, 0..d_model]);
+                outputs.push(next_tok.into_data());
+            }
+
+            outputs
+        };
+
+        assert_eq!(generate(), generate());
+    }
 }
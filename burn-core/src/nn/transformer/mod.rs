@@ -1,5 +1,9 @@
+mod batched;
+mod cls;
 mod encoder;
 mod pwff;
 
+pub use batched::*;
+pub use cls::*;
 pub use encoder::*;
 pub use pwff::*;
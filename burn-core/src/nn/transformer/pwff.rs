@@ -5,7 +5,7 @@ use crate as burn;
 use crate::{
     config::Config,
     module::{Module, Param},
-    nn::{Dropout, DropoutConfig, Linear, LinearConfig, GELU},
+    nn::{Dropout, DropoutConfig, Initializer, Linear, LinearConfig, GELU},
     tensor::{backend::Backend, Tensor},
 };
 
@@ -19,6 +19,12 @@ pub struct PositionWiseFeedForwardConfig {
     /// The dropout rate. Default: 0.1
     #[config(default = 0.1)]
     pub dropout: f64,
+    /// Use the tanh approximation of [GELU](GELU). Default: false
+    #[config(default = false)]
+    pub gelu_approximate: bool,
+    /// The factor scaling the initial weights of the outer linear layer. Default: 1.0
+    #[config(default = 1.0)]
+    pub output_proj_scale: f64,
 }
 
 /// Applies the position-wise feed-forward network to the input tensor.
@@ -40,9 +46,13 @@ impl<B: Backend> PositionWiseFeedForward<B> {
     pub fn new(config: &PositionWiseFeedForwardConfig) -> Self {
         Self {
             linear_inner: Param::from(Linear::new(&LinearConfig::new(config.d_model, config.d_ff))),
-            linear_outer: Param::from(Linear::new(&LinearConfig::new(config.d_ff, config.d_model))),
+            linear_outer: Param::from(Linear::new(
+                &LinearConfig::new(config.d_ff, config.d_model).with_initializer(
+                    Initializer::scaled_uniform_default(config.d_ff, config.output_proj_scale),
+                ),
+            )),
             dropout: Dropout::new(&DropoutConfig::new(config.dropout)),
-            gelu: GELU::new(),
+            gelu: GELU::new().with_approximate(config.gelu_approximate),
         }
     }
 
@@ -59,4 +69,17 @@ impl<B: Backend> PositionWiseFeedForward<B> {
 
         self.linear_outer.forward(x)
     }
+
+    /// Applies the forward pass on the input tensor without dropout, as done during inference.
+    ///
+    /// # Shapes
+    ///
+    /// - tensor: `[batch_size, seq_length, d_model]`
+    /// - output: `[batch_size, seq_length, d_model]`
+    pub fn forward_inference<const D: usize>(&self, input: Tensor<B, D>) -> Tensor<B, D> {
+        let x = self.linear_inner.forward(input);
+        let x = self.gelu.forward(x);
+
+        self.linear_outer.forward(x)
+    }
 }
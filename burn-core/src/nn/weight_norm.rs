@@ -0,0 +1,139 @@
+use alloc::{format, vec::Vec};
+
+use crate as burn;
+
+use crate::module::Module;
+use crate::module::Param;
+use crate::tensor::{backend::Backend, Tensor};
+
+use super::{Linear, LinearConfig};
+
+/// Applies a linear transformation with a weight normalization to the input tensor, as described
+/// in the paper [Weight Normalization](https://arxiv.org/abs/1602.07868):
+///
+/// `O = IW + b` where `W = g * v / ||v||`
+///
+/// The norm of `v` is computed for each output feature, so the column `j` of the effective weight
+/// has the norm `g[j]`, decoupling the magnitude of the weights from their direction.
+///
+/// # Params
+///
+/// - weight_g: Vector of size `d_output` initialized to the norms of the initial weight.
+///
+/// - weight_v: Matrix of shape `[d_input, d_output]` initialized like the [linear](Linear) weight.
+///
+/// - bias (optional): Vector of size `d_output` initialized like the [linear](Linear) bias.
+#[derive(Module, Debug)]
+pub struct WeightNormLinear<B: Backend> {
+    weight_g: Param<Tensor<B, 1>>,
+    weight_v: Param<Tensor<B, 2>>,
+    bias: Param<Option<Tensor<B, 1>>>,
+}
+
+impl<B: Backend> WeightNormLinear<B> {
+    /// Create the module from the given [linear](Linear) configuration.
+    pub fn new(config: &LinearConfig) -> Self {
+        Self::from_linear(Linear::new(config))
+    }
+
+    /// Create the module from an existing [linear](Linear) layer, computing the same
+    /// transformation.
+    pub fn from_linear(linear: Linear<B>) -> Self {
+        let weight = linear.weight.val().detach();
+        let [_, d_output] = weight.dims();
+        let weight_g = column_norms(weight.clone()).reshape([d_output]);
+
+        Self {
+            weight_g: Param::from(weight_g),
+            weight_v: Param::from(weight),
+            bias: Param::from(linear.bias.val().map(|bias| bias.detach())),
+        }
+    }
+
+    /// The effective weight `g * v / ||v||` of shape `[d_input, d_output]`.
+    pub fn weight(&self) -> Tensor<B, 2> {
+        let weight_v = self.weight_v.val();
+        let norms = column_norms(weight_v.clone());
+
+        weight_v.div(norms).mul(self.weight_g.val().unsqueeze())
+    }
+
+    /// Applies the forward pass on the input tensor.
+    ///
+    /// # Shapes
+    ///
+    /// - input: `[..., any, d_input]`
+    /// - output: `[..., any, d_output]`
+    pub fn forward<const D: usize>(&self, input: Tensor<B, D>) -> Tensor<B, D> {
+        let output = input.matmul(self.weight().unsqueeze());
+
+        match self.bias.val() {
+            Some(bias) => output + bias.unsqueeze(),
+            None => output,
+        }
+    }
+}
+
+fn column_norms<B: Backend>(weight: Tensor<B, 2>) -> Tensor<B, 2> {
+    weight.powf(2.0).sum_dim(0).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TestBackend;
+    use burn_tensor::{Data, Distribution};
+
+    #[test]
+    fn effective_weight_should_have_the_norms_of_g() {
+        let mut linear = WeightNormLinear::<TestBackend>::new(&LinearConfig::new(5, 3));
+        linear.weight_g = Param::from(Tensor::from_data(Data::from([0.5, 2.0, 3.0])));
+
+        let norms = column_norms(linear.weight()).reshape([3]);
+
+        norms
+            .into_data()
+            .assert_approx_eq(&Data::from([0.5, 2.0, 3.0]), 4);
+    }
+
+    #[test]
+    fn from_linear_should_compute_the_same_transformation() {
+        let linear = Linear::<TestBackend>::new(&LinearConfig::new(5, 3));
+        let input = Tensor::<TestBackend, 3>::random([2, 4, 5], Distribution::Standard);
+
+        let output = linear.forward(input.clone());
+        let output_normalized = WeightNormLinear::from_linear(linear).forward(input);
+
+        output
+            .into_data()
+            .assert_approx_eq(&output_normalized.into_data(), 4);
+    }
+
+    #[test]
+    fn state_should_contain_g_and_v() {
+        let linear = WeightNormLinear::<TestBackend>::new(&LinearConfig::new(5, 3));
+        let state = linear.state();
+
+        let loaded = WeightNormLinear::<TestBackend>::new(&LinearConfig::new(5, 3))
+            .load(&state)
+            .unwrap();
+
+        assert!(state.get("weight_g").is_some());
+        assert!(state.get("weight_v").is_some());
+        assert_eq!(loaded.weight().into_data(), linear.weight().into_data());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn gradients_should_flow_to_g_and_v() {
+        use crate::TestADBackend;
+
+        let linear = WeightNormLinear::<TestADBackend>::new(&LinearConfig::new(5, 3));
+        let input = Tensor::<TestADBackend, 2>::random([2, 5], Distribution::Standard);
+
+        let grads = linear.forward(input).powf(2.0).sum().backward();
+
+        assert!(linear.weight_g.grad(&grads).is_some());
+        assert!(linear.weight_v.grad(&grads).is_some());
+    }
+}
@@ -0,0 +1,204 @@
+use crate as burn;
+
+use super::{
+    decay::{WeightDecay, WeightDecayConfig},
+    load_state_gradients, register_state_gradients, GradientsParams,
+};
+use crate::config::Config;
+use crate::lr_scheduler::LearningRate;
+use crate::module::{ParamId, StateNamed};
+use crate::optim::{centralize_gradient, Optimizer};
+use crate::tensor::{backend::ADBackend, Tensor};
+use burn_tensor::ElementConversion;
+
+/// Configuration to create the [Adagrad](Adagrad) optimizer.
+#[derive(Config)]
+pub struct AdagradConfig {
+    /// Learning rate for the optimizer.
+    pub learning_rate: f64,
+    /// Decay applied to the learning rate at each step: `lr / (1 + (t - 1) * lr_decay)`.
+    #[config(default = 0.0)]
+    pub lr_decay: f64,
+    /// A value required for numerical stability.
+    #[config(default = 1e-10)]
+    pub epsilon: f32,
+    /// [Weight decay](WeightDecayConfig) config.
+    pub weight_decay: Option<WeightDecayConfig>,
+    /// If the gradients of parameters of rank 2 or more should be
+    /// [centralized](centralize_gradient) before being applied.
+    #[config(default = false)]
+    pub gradient_centralization: bool,
+}
+
+/// Adagrad optimizer as described in the paper
+/// [Adaptive Subgradient Methods for Online Learning and Stochastic Optimization](https://jmlr.org/papers/v12/duchi11a.html).
+///
+/// Each parameter has its own learning rate, scaled by the inverse square root of the sum of all
+/// its past squared gradients, which makes it well suited for sparse features.
+pub struct Adagrad<B: ADBackend> {
+    learning_rate: LearningRate,
+    lr_decay: f64,
+    epsilon: f32,
+    sum: GradientsParams,
+    time: GradientsParams,
+    weight_decay: Option<WeightDecay<B>>,
+    gradient_centralization: bool,
+}
+
+impl<B: ADBackend> Adagrad<B> {
+    pub fn new(config: &AdagradConfig) -> Self {
+        Self {
+            learning_rate: config.learning_rate,
+            lr_decay: config.lr_decay,
+            epsilon: config.epsilon,
+            sum: GradientsParams::new(),
+            time: GradientsParams::new(),
+            weight_decay: config
+                .weight_decay
+                .as_ref()
+                .map(|config| WeightDecay::new(config)),
+            gradient_centralization: config.gradient_centralization,
+        }
+    }
+
+    fn state_key_sum(id: &ParamId) -> String {
+        format!("sum-{id}")
+    }
+
+    fn state_key_time(id: &ParamId) -> String {
+        format!("time-{id}")
+    }
+}
+
+impl<B: ADBackend> Optimizer for Adagrad<B> {
+    type Backend = B;
+
+    fn update_tensor<const D: usize>(
+        &mut self,
+        id: &ParamId,
+        tensor: Tensor<B, D>,
+        grad: Tensor<B::InnerBackend, D>,
+    ) -> Tensor<B, D> {
+        let grad = match self.gradient_centralization {
+            true => centralize_gradient(grad),
+            false => grad,
+        };
+        let grad = match &mut self.weight_decay {
+            Some(weight_decay) => weight_decay.transform(id, grad),
+            None => grad,
+        };
+
+        let sum = match self.sum.remove::<B::InnerBackend, D>(id) {
+            Some(sum) => sum.add(grad.clone().powf(2.0)),
+            None => grad.clone().powf(2.0),
+        };
+        let time = match self.time.remove::<B::InnerBackend, 1>(id) {
+            Some(time) => time.add_scalar(1),
+            None => Tensor::ones([1]),
+        };
+
+        self.sum.register(id.clone(), sum.clone());
+        self.time.register(id.clone(), time.clone());
+
+        let time: f64 = time.single_value().elem();
+        let learning_rate = self.learning_rate / (1.0 + (time - 1.0) * self.lr_decay);
+        let delta = grad
+            .div(sum.sqrt().add_scalar(self.epsilon))
+            .mul_scalar(learning_rate);
+
+        Tensor::from_inner(tensor.inner() - delta)
+    }
+
+    fn set_learning_rate(&mut self, learning_rate: LearningRate) {
+        self.learning_rate = learning_rate;
+    }
+
+    fn learning_rate(&self) -> LearningRate {
+        self.learning_rate
+    }
+
+    fn register_param_state<const D: usize>(
+        &self,
+        id: &ParamId,
+        state: &mut StateNamed<B::FloatElem>,
+    ) {
+        register_state_gradients::<D, B, _>(id, state, &self.sum, Self::state_key_sum);
+        register_state_gradients::<1, B, _>(id, state, &self.time, Self::state_key_time);
+
+        if let Some(weight_decay) = &self.weight_decay {
+            weight_decay.register_state::<D>(id, state);
+        }
+    }
+
+    fn load_param_state<const D: usize>(
+        &mut self,
+        id: &ParamId,
+        state: &StateNamed<B::FloatElem>,
+        device: &B::Device,
+    ) {
+        load_state_gradients::<D, B, _>(id, state, &mut self.sum, Self::state_key_sum, device);
+        load_state_gradients::<1, B, _>(id, state, &mut self.time, Self::state_key_time, device);
+
+        if let Some(weight_decay) = &mut self.weight_decay {
+            weight_decay.load_state::<D>(id, state, device);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::module::Param;
+    use crate::tensor::{Data, Distribution};
+    use crate::{nn, TestADBackend};
+
+    #[test]
+    fn test_adagrad_optimizer_save_load_state() {
+        let linear = nn::Linear::new(&nn::LinearConfig::new(6, 6));
+        let x = Tensor::<TestADBackend, 2>::random([2, 6], Distribution::Standard);
+        let mut optimizer = Adagrad::new(&AdagradConfig::new(0.01));
+        let grads = linear.forward(x).backward();
+        let grads = GradientsParams::from_grads(grads, &linear);
+        let linear = optimizer.update_module(linear, grads);
+
+        let state_optim_before = optimizer.state(&linear);
+        let mut optimizer = Adagrad::new(&AdagradConfig::new(0.01));
+        optimizer.load(&linear, &state_optim_before).unwrap();
+        let state_optim_after = optimizer.state(&linear);
+
+        assert!(!state_optim_before.is_empty());
+        assert_eq!(state_optim_before, state_optim_after);
+    }
+
+    #[test]
+    fn test_adagrad_optimizer_should_match_closed_form_on_scalar() {
+        let (learning_rate, lr_decay, epsilon) = (0.5, 0.1, 1e-10);
+        let mut optimizer = Adagrad::new(
+            &AdagradConfig::new(learning_rate)
+                .with_lr_decay(lr_decay)
+                .with_epsilon(epsilon as f32),
+        );
+        let mut param = Param::from_tensor(
+            Tensor::<TestADBackend, 1>::from_data(Data::from([2.0])),
+            true,
+        );
+
+        // Minimize `x^2`, the gradient is `2x`.
+        let mut expected = 2.0f64;
+        let mut sum = 0.0;
+        for step in 1..=5 {
+            let grads = param.val().powf(2.0).sum().backward();
+            let grads = GradientsParams::from_grads(grads, &param);
+            param = optimizer.update_module(param, grads);
+
+            let grad = 2.0 * expected;
+            sum += grad * grad;
+            let lr = learning_rate / (1.0 + (step - 1) as f64 * lr_decay);
+            expected -= lr * grad / (sum.sqrt() + epsilon);
+
+            param
+                .to_data()
+                .assert_approx_eq(&Data::from([expected as f32]), 4);
+        }
+    }
+}
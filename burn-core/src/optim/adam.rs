@@ -5,8 +5,9 @@ use super::{
     load_state_gradients, register_state_gradients, GradientsParams,
 };
 use crate::config::Config;
+use crate::lr_scheduler::LearningRate;
 use crate::module::{ParamId, StateNamed};
-use crate::optim::Optimizer;
+use crate::optim::{centralize_gradient, Optimizer};
 use crate::tensor::{backend::ADBackend, Tensor};
 use burn_tensor::ElementConversion;
 
@@ -25,19 +26,24 @@ pub struct AdamConfig {
     epsilon: f32,
     /// [Weight decay](WeightDecayConfig) config.
     pub weight_decay: Option<WeightDecayConfig>,
+    /// If the gradients of parameters of rank 2 or more should be
+    /// [centralized](centralize_gradient) before being applied.
+    #[config(default = false)]
+    pub gradient_centralization: bool,
 }
 
 /// Adam optimizer as described in the paper [Adam: A Method for Stochastic Optimization](https://arxiv.org/pdf/1412.6980.pdf).
 pub struct Adam<B: ADBackend> {
-    learning_rate: B::FloatElem,
+    learning_rate: LearningRate,
     momentum: AdaptiveMomentum,
     weight_decay: Option<WeightDecay<B>>,
+    gradient_centralization: bool,
 }
 
 impl<B: ADBackend> Adam<B> {
     pub fn new(config: &AdamConfig) -> Self {
         Self {
-            learning_rate: config.learning_rate.elem(),
+            learning_rate: config.learning_rate,
             momentum: AdaptiveMomentum {
                 beta_1: config.beta_1,
                 beta_2: config.beta_2,
@@ -50,6 +56,7 @@ impl<B: ADBackend> Adam<B> {
                 .weight_decay
                 .as_ref()
                 .map(|config| WeightDecay::new(config)),
+            gradient_centralization: config.gradient_centralization,
         }
     }
 }
@@ -63,6 +70,10 @@ impl<B: ADBackend> Optimizer for Adam<B> {
         tensor: Tensor<B, D>,
         grad: Tensor<B::InnerBackend, D>,
     ) -> Tensor<B, D> {
+        let grad = match self.gradient_centralization {
+            true => centralize_gradient(grad),
+            false => grad,
+        };
         let grad = match &mut self.weight_decay {
             Some(weight_decay) => weight_decay.transform(id, grad),
             None => grad,
@@ -73,6 +84,14 @@ impl<B: ADBackend> Optimizer for Adam<B> {
         Tensor::from_inner(tensor.inner() - delta)
     }
 
+    fn set_learning_rate(&mut self, learning_rate: LearningRate) {
+        self.learning_rate = learning_rate;
+    }
+
+    fn learning_rate(&self) -> LearningRate {
+        self.learning_rate
+    }
+
     fn register_param_state<const D: usize>(
         &self,
         id: &ParamId,
@@ -0,0 +1,128 @@
+use crate::module::{ADModule, ModuleVisitor, ParamId};
+
+use burn_tensor::{backend::ADBackend, Tensor};
+
+use super::GradientsParams;
+
+/// Reduce the gradients computed on multiple devices into a single [GradientsParams].
+pub trait AllReduce {
+    /// Sum the given gradients for each parameter in the given module.
+    ///
+    /// The resulting gradients are located on the device of the first gradients.
+    fn reduce<M: ADModule>(&self, module: &M, grads: Vec<GradientsParams>) -> GradientsParams;
+}
+
+/// Reduce gradients using a binary tree.
+///
+/// Gradients are summed pairwise at each level, so the number of sequential transfers grows
+/// logarithmically with the number of devices instead of linearly when everything is sent to the
+/// main device.
+#[derive(Default, Debug, Clone)]
+pub struct TreeAllReduce;
+
+impl AllReduce for TreeAllReduce {
+    fn reduce<M: ADModule>(&self, module: &M, grads: Vec<GradientsParams>) -> GradientsParams {
+        let mut grads = grads;
+
+        while grads.len() > 1 {
+            let mut grads_next = Vec::new();
+            let mut iter = grads.into_iter();
+
+            while let Some(mut grads_lhs) = iter.next() {
+                if let Some(grads_rhs) = iter.next() {
+                    let mut visitor = GradientsParamsReducer::new(&mut grads_lhs, grads_rhs);
+                    module.visit(&mut visitor);
+                }
+                grads_next.push(grads_lhs);
+            }
+
+            grads = grads_next;
+        }
+
+        grads.pop().unwrap_or_default()
+    }
+}
+
+#[derive(new)]
+struct GradientsParamsReducer<'a> {
+    grads: &'a mut GradientsParams,
+    grads_other: GradientsParams,
+}
+
+impl<'a, B: ADBackend> ModuleVisitor<B> for GradientsParamsReducer<'a> {
+    fn visit<const D: usize>(&mut self, id: &ParamId, _tensor: &Tensor<B, D>) {
+        let grad_other = match self.grads_other.remove::<B::InnerBackend, D>(id) {
+            Some(grad) => grad,
+            None => return,
+        };
+
+        let grad_reduced = match self.grads.remove::<B::InnerBackend, D>(id) {
+            Some(grad) => {
+                let device = grad.device();
+                grad.add(grad_other.to_device(&device))
+            }
+            None => grad_other,
+        };
+
+        self.grads
+            .register::<B::InnerBackend, D>(id.clone(), grad_reduced);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        module::list_param_ids,
+        nn::{Linear, LinearConfig},
+        optim::GradientsAccumulator,
+        TestADBackend, TestBackend,
+    };
+    use burn_tensor::Distribution;
+
+    #[test]
+    fn test_tree_reduce_equals_sum() {
+        let layer = layer();
+        let mut accumulator = GradientsAccumulator::new();
+        let mut grads_devices = Vec::new();
+
+        for _ in 0..5 {
+            let loss = layer.forward(random_tensor());
+            let grads = GradientsParams::from_grads(loss.backward(), &layer);
+            grads_devices.push(clone_grads(&layer, &grads));
+            accumulator.accumulate(&layer, grads);
+        }
+
+        let grads_sum = accumulator.grads();
+        let grads_reduced = TreeAllReduce.reduce(&layer, grads_devices);
+
+        assert_eq!(grads_reduced.len(), grads_sum.len());
+        for id in list_param_ids(&layer) {
+            let expected = grads_sum.get::<TestBackend, 2>(&id).unwrap();
+            let actual = grads_reduced.get::<TestBackend, 2>(&id).unwrap();
+
+            expected
+                .into_data()
+                .assert_approx_eq(&actual.into_data(), 3);
+        }
+    }
+
+    fn clone_grads(layer: &Linear<TestADBackend>, grads: &GradientsParams) -> GradientsParams {
+        let mut grads_cloned = GradientsParams::new();
+
+        for id in list_param_ids(layer) {
+            let grad = grads.get::<TestBackend, 2>(&id).unwrap();
+            grads_cloned.register(id, grad);
+        }
+
+        grads_cloned
+    }
+
+    fn layer() -> Linear<TestADBackend> {
+        Linear::<TestADBackend>::new(&LinearConfig::new(20, 20).with_bias(false))
+    }
+
+    fn random_tensor() -> Tensor<TestADBackend, 2> {
+        Tensor::<TestADBackend, 2>::random([2, 20], Distribution::Standard)
+    }
+}
@@ -2,6 +2,7 @@ use super::mapper::ModuleTensorUpdater;
 use super::visitor::{GradientsLoader, GradientsRegister};
 use super::GradientsParams;
 
+use crate::lr_scheduler::LearningRate;
 use crate::module::{ADModule, LoadingError, Module, ParamId, State, StateNamed};
 use crate::tensor::backend::{ADBackend, Backend};
 use crate::tensor::{Data, Tensor};
@@ -17,6 +18,13 @@ pub trait Optimizer: Send + Sync {
         grad: Tensor<<Self::Backend as ADBackend>::InnerBackend, D>,
     ) -> Tensor<Self::Backend, D>;
 
+    /// Set the learning rate used by the next updates, this is how a
+    /// [learning rate scheduler](crate::lr_scheduler::LrScheduler) controls the optimizer.
+    fn set_learning_rate(&mut self, learning_rate: LearningRate);
+
+    /// The learning rate used by the next updates.
+    fn learning_rate(&self) -> LearningRate;
+
     /// Update the parameters of the given module using the given the gradients.
     fn update_module<M>(&mut self, module: M, grads: GradientsParams) -> M
     where
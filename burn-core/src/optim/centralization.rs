@@ -0,0 +1,92 @@
+use crate::tensor::backend::Backend;
+use crate::tensor::Tensor;
+
+/// Centralize a gradient by subtracting, for each output unit, its mean over the fan-in of the
+/// unit, as described in the paper
+/// [Gradient Centralization: A New Optimization Technique for Deep Neural Networks](https://arxiv.org/abs/2004.01461).
+///
+/// Gradients of rank 2 follow the `[d_input, d_output]` layout of the [linear](crate::nn::Linear)
+/// weights and are centralized over the first dimension. Gradients of higher rank follow the
+/// `[channels_out, channels_in, kernel_size...]` layout of the convolution weights and are
+/// centralized over every dimension except the first one. Gradients of rank 1, such as the ones
+/// of biases, are returned unchanged.
+pub fn centralize_gradient<B: Backend, const D: usize>(grad: Tensor<B, D>) -> Tensor<B, D> {
+    if D < 2 {
+        return grad;
+    }
+
+    if D == 2 {
+        let mean = grad.clone().mean_dim(0);
+        return grad - mean;
+    }
+
+    let shape = grad.shape();
+    let num_rows = shape.dims[0];
+    let num_cols = shape.num_elements() / num_rows;
+
+    let grad = grad.reshape([num_rows, num_cols]);
+    let mean = grad.clone().mean_dim(1);
+
+    (grad - mean).reshape(shape)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nn::{Linear, LinearConfig};
+    use crate::optim::{GradientsParams, Optimizer, Sgd, SgdConfig};
+    use crate::tensor::{Data, Distribution};
+    use crate::{TestADBackend, TestBackend};
+
+    #[test]
+    fn rank_2_gradient_should_have_zero_mean_per_column() {
+        let grad = Tensor::<TestBackend, 2>::random([6, 4], Distribution::Standard);
+
+        let centralized = centralize_gradient(grad);
+
+        centralized
+            .mean_dim(0)
+            .into_data()
+            .assert_approx_eq(&Data::zeros([1, 4]), 5);
+    }
+
+    #[test]
+    fn rank_3_gradient_should_have_zero_mean_per_row() {
+        let grad = Tensor::<TestBackend, 3>::random([4, 3, 5], Distribution::Standard);
+
+        let centralized = centralize_gradient(grad.clone());
+
+        assert_eq!(centralized.shape(), grad.shape());
+        centralized
+            .reshape([4, 15])
+            .mean_dim(1)
+            .into_data()
+            .assert_approx_eq(&Data::zeros([4, 1]), 5);
+    }
+
+    #[test]
+    fn optimizer_should_centralize_weight_and_not_bias_gradients() {
+        let linear = Linear::<TestADBackend>::new(&LinearConfig::new(6, 4));
+        let weight = linear.weight.val();
+        let bias = linear.bias.val().unwrap();
+        let mut optim = Sgd::new(&SgdConfig::new(1.0).with_gradient_centralization(true));
+        let input = Tensor::<TestADBackend, 2>::random([3, 6], Distribution::Standard);
+
+        let grads = linear.forward(input).backward();
+        let grad_bias = bias.grad(&grads).unwrap();
+        let grads = GradientsParams::from_grads(grads, &linear);
+        let linear = optim.update_module(linear, grads);
+
+        let delta_weight = weight.inner() - linear.weight.val().inner();
+        let delta_bias = bias.inner() - linear.bias.val().unwrap().inner();
+
+        // The weight has the shape [d_input, d_output], each output unit is centralized.
+        delta_weight
+            .mean_dim(0)
+            .into_data()
+            .assert_approx_eq(&Data::zeros([1, 4]), 5);
+        delta_bias
+            .into_data()
+            .assert_approx_eq(&grad_bias.into_data(), 5);
+    }
+}
@@ -1,4 +1,4 @@
-use crate::module::{Module, ModuleVisitor, ParamId};
+use crate::module::{ADModule, Module, ModuleVisitor, ParamId};
 
 use burn_tensor::{backend::ADBackend, Tensor};
 
@@ -7,6 +7,7 @@ use super::GradientsParams;
 /// Accumulate gradients into a single [Gradients](ADBackend::Gradients) object.
 pub struct GradientsAccumulator {
     grads: GradientsParams,
+    offload: bool,
 }
 
 impl Default for GradientsAccumulator {
@@ -20,6 +21,20 @@ impl GradientsAccumulator {
     pub fn new() -> Self {
         Self {
             grads: GradientsParams::new(),
+            offload: false,
+        }
+    }
+
+    /// Create a new gradients accumulator keeping the accumulated gradients on the default device
+    /// of the backend, the CPU for most backends, between the accumulation steps.
+    ///
+    /// Only the gradients of the current step are on the device of the parameters, which trades
+    /// the speed of the transfers for device memory. The accumulated gradients must be retrieved
+    /// with [grads_to_device](Self::grads_to_device).
+    pub fn offloaded() -> Self {
+        Self {
+            grads: GradientsParams::new(),
+            offload: true,
         }
     }
 }
@@ -30,7 +45,7 @@ impl GradientsAccumulator {
     where
         M: Module<Backend = B>,
     {
-        let mut visitor = ModuleGradsAccumulator::new(&mut self.grads, grads);
+        let mut visitor = ModuleGradsAccumulator::new(&mut self.grads, grads, self.offload);
         module.visit(&mut visitor);
     }
 
@@ -41,19 +56,38 @@ impl GradientsAccumulator {
 
         grads
     }
+
+    /// Return the accumulated gradients on the device of their parameter in the given module and
+    /// reset the accumulator state.
+    pub fn grads_to_device<M: ADModule>(&mut self, module: &M) -> GradientsParams {
+        let mut grads = self.grads();
+
+        if self.offload {
+            module.visit(&mut GradientsDeviceRestorer { grads: &mut grads });
+        }
+
+        grads
+    }
 }
 
 #[derive(new)]
 struct ModuleGradsAccumulator<'a> {
     grads: &'a mut GradientsParams,
     grads_new: GradientsParams,
+    offload: bool,
+}
+
+struct GradientsDeviceRestorer<'a> {
+    grads: &'a mut GradientsParams,
 }
 
 impl<'a, B: ADBackend> ModuleVisitor<B> for ModuleGradsAccumulator<'a> {
     fn visit<const D: usize>(&mut self, id: &ParamId, _tensor: &Tensor<B, D>) {
         let grad_updated = match self.grads_new.remove::<B::InnerBackend, D>(id) {
+            // The accumulated gradients may be offloaded, the sum is computed on the device of
+            // the new gradients.
             Some(new) => match self.grads.remove::<B::InnerBackend, D>(id) {
-                Some(grad) => grad.add(new),
+                Some(grad) => grad.to_device(&new.device()).add(new),
                 None => new,
             },
             None => match self.grads.remove::<B::InnerBackend, D>(id) {
@@ -61,12 +95,25 @@ impl<'a, B: ADBackend> ModuleVisitor<B> for ModuleGradsAccumulator<'a> {
                 None => return,
             },
         };
+        let grad_updated = match self.offload {
+            true => grad_updated.to_device(&Default::default()),
+            false => grad_updated,
+        };
 
         self.grads
             .register::<B::InnerBackend, D>(id.clone(), grad_updated);
     }
 }
 
+impl<'a, B: ADBackend> ModuleVisitor<B> for GradientsDeviceRestorer<'a> {
+    fn visit<const D: usize>(&mut self, id: &ParamId, tensor: &Tensor<B, D>) {
+        if let Some(grad) = self.grads.remove::<B::InnerBackend, D>(id) {
+            self.grads
+                .register::<B::InnerBackend, D>(id.clone(), grad.to_device(&tensor.device()));
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -105,6 +152,29 @@ mod tests {
         assert_eq!(grads.len(), 2)
     }
 
+    #[test]
+    fn test_offloaded_accumulation_should_match_device_accumulation() {
+        let mut accumulator = GradientsAccumulator::new();
+        let mut accumulator_offloaded = GradientsAccumulator::offloaded();
+        let layer = layer();
+
+        for _ in 0..3 {
+            // Each accumulator gets the gradients of its own graph, built from the same input.
+            let input = random_tensor().into_data();
+
+            let loss = layer.forward(Tensor::from_data(input.clone()));
+            accumulator.accumulate(&layer, GradientsParams::from_grads(loss.backward(), &layer));
+
+            let loss = layer.forward(Tensor::from_data(input));
+            accumulator_offloaded
+                .accumulate(&layer, GradientsParams::from_grads(loss.backward(), &layer));
+        }
+
+        let grads = accumulator.grads().state(&layer);
+        let grads_offloaded = accumulator_offloaded.grads_to_device(&layer).state(&layer);
+        assert_eq!(grads, grads_offloaded);
+    }
+
     fn layer() -> Linear<TestADBackend> {
         Linear::<TestADBackend>::new(&LinearConfig::new(20, 20).with_bias(true))
     }
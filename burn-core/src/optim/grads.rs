@@ -4,11 +4,18 @@ use burn_tensor::{
     Tensor,
 };
 
-use crate::module::{ADModule, ParamId};
+use crate::module::{ADModule, ParamId, State, StateNamed, UniqueParamVisitor};
 
-use super::visitor::{GradientsParamsChangeDevice, GradientsParamsConverter};
+use super::visitor::{
+    GradientsParamsChangeDevice, GradientsParamsClipper, GradientsParamsConverter,
+    GradientsParamsFilter, GradientsParamsFiniteChecker, GradientsParamsNormCollector,
+    GradientsParamsScaler, GradientsParamsSerializer,
+};
 
 /// Data type that contains gradients for parameters.
+///
+/// The gradients are keyed by [parameter id](ParamId), a parameter shared by multiple
+/// sub-modules has a single gradients tensor, which is transformed once by each method.
 #[derive(Default)]
 pub struct GradientsParams {
     container: TensorContainer<ParamId>,
@@ -69,10 +76,75 @@ impl GradientsParams {
         module: &M,
     ) -> Self {
         let mut visitor = GradientsParamsChangeDevice::new(device, &mut self);
-        module.visit(&mut visitor);
+        module.visit(&mut UniqueParamVisitor::new(&mut visitor));
+        self
+    }
+
+    /// Multiply each tensor gradients registered for the given [module](ADModule) by a factor.
+    pub fn scale<M: ADModule>(mut self, factor: f64, module: &M) -> Self {
+        let mut visitor = GradientsParamsScaler::new(factor, &mut self);
+        module.visit(&mut UniqueParamVisitor::new(&mut visitor));
+        self
+    }
+
+    /// Clip each tensor gradients registered for the given [module](ADModule) to the range
+    /// `[min, max]`, which is value-based gradient clipping.
+    pub fn clip_by_value<M: ADModule>(mut self, min: f64, max: f64, module: &M) -> Self {
+        let mut visitor = GradientsParamsClipper::new(min, max, &mut self);
+        module.visit(&mut UniqueParamVisitor::new(&mut visitor));
         self
     }
 
+    /// Keep only the gradients of the parameters of the given [module](ADModule) for which the
+    /// predicate returns true.
+    ///
+    /// The predicate receives the [parameter id](ParamId) and the rank of the parameter, which
+    /// allows selecting the weights and skipping the biases, or keeping only the parameters of a
+    /// submodule using the ids collected with [list_param_ids](crate::module::list_param_ids).
+    pub fn filter_by<M, P>(self, module: &M, predicate: P) -> Self
+    where
+        M: ADModule,
+        P: FnMut(&ParamId, usize) -> bool,
+    {
+        let mut grads_filtered = GradientsParams::new();
+        let mut visitor = GradientsParamsFilter::new(&self, &mut grads_filtered, predicate);
+
+        module.visit(&mut UniqueParamVisitor::new(&mut visitor));
+        grads_filtered
+    }
+
+    /// If the gradients of the given [module](ADModule) only contain finite values.
+    ///
+    /// Each tensor is reduced to a single value before being read, so the check stays cheap for
+    /// large models.
+    pub fn is_finite<M: ADModule>(&self, module: &M) -> bool {
+        let mut is_finite = true;
+        let mut visitor = GradientsParamsFiniteChecker::new(self, &mut is_finite);
+
+        module.visit(&mut UniqueParamVisitor::new(&mut visitor));
+        is_finite
+    }
+
+    /// The L2 norm of the gradients of each parameter of the given [module](ADModule), in the
+    /// order the parameters are visited. Parameters without gradients are skipped.
+    pub fn norms<M: ADModule>(&self, module: &M) -> Vec<(ParamId, f64)> {
+        let mut norms = Vec::new();
+        let mut visitor = GradientsParamsNormCollector::new(self, &mut norms);
+
+        module.visit(&mut UniqueParamVisitor::new(&mut visitor));
+        norms
+    }
+
+    /// The [state](State) of the gradients of the given [module](ADModule), where each gradient is
+    /// registered with the id of its parameter.
+    pub fn state<M: ADModule>(&self, module: &M) -> State<<M::Backend as Backend>::FloatElem> {
+        let mut state = StateNamed::new();
+        let mut visitor = GradientsParamsSerializer::new(self, &mut state);
+
+        module.visit(&mut UniqueParamVisitor::new(&mut visitor));
+        State::StateNamed(state)
+    }
+
     /// Extract each tensor gradients for the given [module](ADModule).
     pub fn from_grads<M: ADModule>(
         grads: <M::ADBackend as ADBackend>::Gradients,
@@ -89,8 +161,9 @@ impl GradientsParams {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate as burn;
     use crate::{
-        module::{list_param_ids, Module},
+        module::{list_param_ids, Module, Param},
         nn::{Linear, LinearConfig},
         TestADBackend,
     };
@@ -116,6 +189,80 @@ mod tests {
         assert_eq!(grads_2.len(), param_ids_2.len());
     }
 
+    #[test]
+    fn test_filter_by_rank() {
+        let layer = layer();
+        let loss = layer.forward(random_tensor());
+        let grads = GradientsParams::from_grads(loss.backward(), &layer);
+
+        let grads = grads.filter_by(&layer, |_, rank| rank == 2);
+
+        let weight_id = &list_param_ids(&layer.weight)[0];
+        let bias_id = &list_param_ids(&layer.bias)[0];
+        assert_eq!(grads.len(), 1);
+        assert!(grads.get::<InnerBackend, 2>(weight_id).is_some());
+        assert!(grads.get::<InnerBackend, 1>(bias_id).is_none());
+    }
+
+    #[test]
+    fn test_filter_by_submodule_param_ids() {
+        let model = TwoLayers {
+            first: Param::from(layer()),
+            second: Param::from(layer()),
+        };
+        let loss = model.second.forward(model.first.forward(random_tensor()));
+        let grads = GradientsParams::from_grads(loss.backward(), &model);
+        let param_ids = list_param_ids(&model.second);
+
+        let grads = grads.filter_by(&model, |id, _| param_ids.contains(id));
+
+        assert_eq!(grads.len(), 2);
+    }
+
+    #[test]
+    fn test_is_finite_should_detect_nan_gradients() {
+        let layer = layer();
+        let loss = layer.forward(random_tensor());
+        let mut grads = GradientsParams::from_grads(loss.backward(), &layer);
+        assert!(grads.is_finite(&layer));
+
+        let weight_id = list_param_ids(&layer.weight)[0].clone();
+        let grad = grads.get::<InnerBackend, 2>(&weight_id).unwrap();
+        grads.register(
+            weight_id,
+            grad.index_assign([0..1, 0..1], Tensor::ones([1, 1]).mul_scalar(f32::NAN)),
+        );
+
+        assert!(!grads.is_finite(&layer));
+    }
+
+    #[test]
+    fn test_clip_by_value_should_bound_every_gradient() {
+        let layer = layer();
+        let loss = layer.forward(random_tensor().mul_scalar(100.0));
+        let grads = GradientsParams::from_grads(loss.backward(), &layer);
+
+        let grads = grads.clip_by_value(-0.1, 0.1, &layer);
+
+        assert_eq!(grads.len(), 2);
+        for id in list_param_ids(&layer.weight) {
+            let grad = grads.get::<InnerBackend, 2>(&id).unwrap().into_data();
+            assert!(grad.value.iter().all(|value| (-0.1..=0.1).contains(value)));
+        }
+        for id in list_param_ids(&layer.bias) {
+            let grad = grads.get::<InnerBackend, 1>(&id).unwrap().into_data();
+            assert!(grad.value.iter().all(|value| (-0.1..=0.1).contains(value)));
+        }
+    }
+
+    type InnerBackend = <TestADBackend as ADBackend>::InnerBackend;
+
+    #[derive(Module, Debug)]
+    struct TwoLayers<B: Backend> {
+        first: Param<Linear<B>>,
+        second: Param<Linear<B>>,
+    }
+
     fn layer() -> Linear<TestADBackend> {
         Linear::<TestADBackend>::new(&LinearConfig::new(20, 20).with_bias(true))
     }
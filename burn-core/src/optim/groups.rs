@@ -0,0 +1,147 @@
+use hashbrown::HashMap;
+
+use super::Optimizer;
+use crate::lr_scheduler::LearningRate;
+use crate::module::{ParamId, StateNamed};
+use crate::tensor::{backend::ADBackend, Tensor};
+
+/// Groups of parameters with their own learning rate, given as a factor of the learning rate of
+/// the optimizer, see [ParamGroupsOptimizer].
+///
+/// The parameters without a group keep the learning rate of the optimizer.
+#[derive(Clone, Debug, Default)]
+pub struct ParamGroups {
+    lr_factors: HashMap<ParamId, f64>,
+}
+
+impl ParamGroups {
+    /// Create empty parameter groups.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a group of parameters whose learning rate is multiplied by the given factor, replacing
+    /// the factor of the parameters already in a group.
+    pub fn with_group(mut self, param_ids: &[ParamId], lr_factor: f64) -> Self {
+        for id in param_ids {
+            self.lr_factors.insert(id.clone(), lr_factor);
+        }
+        self
+    }
+
+    /// The learning rate factor of the given parameter, `1.0` when it isn't in a group.
+    pub fn lr_factor(&self, id: &ParamId) -> f64 {
+        self.lr_factors.get(id).cloned().unwrap_or(1.0)
+    }
+}
+
+/// Optimizer wrapper applying the learning rate of the [parameter groups](ParamGroups).
+///
+/// The learning rate of the wrapped optimizer is scaled before updating the parameters of a
+/// group and restored after, so the state of the optimizer, such as the momentum, is shared by
+/// every group. The factors apply to the current learning rate of the optimizer, including the
+/// one set by a [learning rate scheduler](crate::lr_scheduler::LrScheduler).
+pub struct ParamGroupsOptimizer<O> {
+    optim: O,
+    groups: ParamGroups,
+}
+
+impl<O> ParamGroupsOptimizer<O> {
+    pub fn new(optim: O, groups: ParamGroups) -> Self {
+        Self { optim, groups }
+    }
+
+    /// Returns the wrapped optimizer.
+    pub fn into_inner(self) -> O {
+        self.optim
+    }
+}
+
+impl<B: ADBackend, O: Optimizer<Backend = B>> Optimizer for ParamGroupsOptimizer<O> {
+    type Backend = B;
+
+    fn update_tensor<const D: usize>(
+        &mut self,
+        id: &ParamId,
+        tensor: Tensor<B, D>,
+        grad: Tensor<B::InnerBackend, D>,
+    ) -> Tensor<B, D> {
+        let lr_factor = self.groups.lr_factor(id);
+        if lr_factor == 1.0 {
+            return self.optim.update_tensor(id, tensor, grad);
+        }
+
+        let learning_rate = self.optim.learning_rate();
+        self.optim.set_learning_rate(learning_rate * lr_factor);
+        let tensor = self.optim.update_tensor(id, tensor, grad);
+        self.optim.set_learning_rate(learning_rate);
+
+        tensor
+    }
+
+    fn set_learning_rate(&mut self, learning_rate: LearningRate) {
+        self.optim.set_learning_rate(learning_rate);
+    }
+
+    fn learning_rate(&self) -> LearningRate {
+        self.optim.learning_rate()
+    }
+
+    fn register_param_state<const D: usize>(
+        &self,
+        id: &ParamId,
+        state: &mut StateNamed<B::FloatElem>,
+    ) {
+        self.optim.register_param_state::<D>(id, state);
+    }
+
+    fn load_param_state<const D: usize>(
+        &mut self,
+        id: &ParamId,
+        state: &StateNamed<B::FloatElem>,
+        device: &B::Device,
+    ) {
+        self.optim.load_param_state::<D>(id, state, device);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::module::list_param_ids;
+    use crate::nn::{Linear, LinearConfig};
+    use crate::optim::{GradientsParams, Sgd, SgdConfig};
+    use crate::tensor::Distribution;
+    use crate::TestADBackend;
+
+    #[test]
+    fn param_groups_should_scale_the_learning_rate_of_their_params() {
+        let linear = Linear::<TestADBackend>::new(&LinearConfig::new(4, 2));
+        let groups = ParamGroups::new().with_group(&list_param_ids(&linear.weight), 0.5);
+        // The factors apply to the learning rate set after the creation, like by a scheduler.
+        let mut optim = ParamGroupsOptimizer::new(Sgd::new(&SgdConfig::new(1.0)), groups);
+        optim.set_learning_rate(0.1);
+        let mut optim_half = Sgd::new(&SgdConfig::new(0.05));
+        let mut optim_full = Sgd::new(&SgdConfig::new(0.1));
+        let input = Tensor::<TestADBackend, 2>::random([2, 4], Distribution::Standard);
+        let grads = || {
+            let loss = linear.forward(input.clone()).sum();
+            GradientsParams::from_grads(loss.backward(), &linear)
+        };
+
+        let updated = optim.update_module(linear.clone(), grads());
+        let updated_half = optim_half.update_module(linear.clone(), grads());
+        let updated_full = optim_full.update_module(linear.clone(), grads());
+
+        updated
+            .weight
+            .to_data()
+            .assert_approx_eq(&updated_half.weight.to_data(), 5);
+        updated
+            .bias
+            .as_ref()
+            .unwrap()
+            .to_data()
+            .assert_approx_eq(&updated_full.bias.as_ref().unwrap().to_data(), 5);
+    }
+}
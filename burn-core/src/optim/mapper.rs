@@ -1,4 +1,4 @@
-use burn_tensor::{backend::ADBackend, Tensor};
+use burn_tensor::{backend::ADBackend, container::TensorContainer, Tensor};
 
 use crate::module::{ModuleMapper, ParamId};
 
@@ -8,12 +8,22 @@ use super::{GradientsParams, Optimizer};
 pub struct ModuleTensorUpdater<'a, O> {
     optimizer: &'a mut O,
     grads: GradientsParams,
+    #[new(default)]
+    updated: TensorContainer<ParamId>,
 }
 
 impl<'a, B: ADBackend, O: Optimizer<Backend = B>> ModuleMapper<B> for ModuleTensorUpdater<'a, O> {
     fn map<const D: usize>(&mut self, id: &ParamId, tensor: Tensor<B, D>) -> Tensor<B, D> {
+        // A parameter shared by multiple sub-modules is updated once, every sub-module then
+        // receives the same updated tensor.
+        if let Some(tensor) = self.updated.get::<B, D>(id) {
+            return tensor;
+        }
+
         if let Some(grad) = self.grads.remove::<B::InnerBackend, D>(id) {
-            self.optimizer.update_tensor(id, tensor, grad)
+            let tensor = self.optimizer.update_tensor(id, tensor, grad);
+            self.updated.register(id.clone(), tensor.clone());
+            tensor
         } else {
             tensor
         }
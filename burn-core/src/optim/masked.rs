@@ -0,0 +1,122 @@
+use super::Optimizer;
+use crate::lr_scheduler::LearningRate;
+use crate::module::{ParamId, PruningMasks, StateNamed};
+use crate::tensor::{backend::ADBackend, Tensor};
+
+/// Optimizer wrapper keeping the weights [pruned](crate::module::prune_unstructured) at zero.
+///
+/// The gradients of the pruned weights are masked before being given to the wrapped optimizer,
+/// so no state such as momentum is accumulated for them, and the updated weights are masked
+/// again so they can't be revived by a state accumulated before pruning.
+pub struct MaskedOptimizer<O> {
+    optim: O,
+    masks: PruningMasks,
+}
+
+impl<O> MaskedOptimizer<O> {
+    pub fn new(optim: O, masks: PruningMasks) -> Self {
+        Self { optim, masks }
+    }
+
+    /// Returns the wrapped optimizer.
+    pub fn into_inner(self) -> O {
+        self.optim
+    }
+}
+
+impl<B: ADBackend, O: Optimizer<Backend = B>> Optimizer for MaskedOptimizer<O> {
+    type Backend = B;
+
+    fn update_tensor<const D: usize>(
+        &mut self,
+        id: &ParamId,
+        tensor: Tensor<B, D>,
+        grad: Tensor<B::InnerBackend, D>,
+    ) -> Tensor<B, D> {
+        let mask = match self.masks.get::<B, D>(id) {
+            Some(mask) => mask,
+            None => return self.optim.update_tensor(id, tensor, grad),
+        };
+
+        let grad = grad.mul(mask.clone().inner());
+        let tensor = self.optim.update_tensor(id, tensor, grad);
+
+        Tensor::from_inner(tensor.inner().mul(mask.inner()))
+    }
+
+    fn set_learning_rate(&mut self, learning_rate: LearningRate) {
+        self.optim.set_learning_rate(learning_rate);
+    }
+
+    fn learning_rate(&self) -> LearningRate {
+        self.optim.learning_rate()
+    }
+
+    fn register_param_state<const D: usize>(
+        &self,
+        id: &ParamId,
+        state: &mut StateNamed<B::FloatElem>,
+    ) {
+        self.optim.register_param_state::<D>(id, state);
+    }
+
+    fn load_param_state<const D: usize>(
+        &mut self,
+        id: &ParamId,
+        state: &StateNamed<B::FloatElem>,
+        device: &B::Device,
+    ) {
+        self.optim.load_param_state::<D>(id, state, device);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::module::prune_unstructured;
+    use crate::nn::{Linear, LinearConfig};
+    use crate::optim::momentum::MomentumConfig;
+    use crate::optim::{GradientsParams, Sgd, SgdConfig};
+    use crate::tensor::Distribution;
+    use crate::TestADBackend;
+
+    #[test]
+    fn pruned_weights_should_not_be_revived_by_momentum() {
+        let mut linear = Linear::<TestADBackend>::new(&LinearConfig::new(8, 6));
+        let mut optim = Sgd::new(&SgdConfig::new(0.1).with_momentum(Some(MomentumConfig {
+            momentum: 0.9,
+            dampening: 0.0,
+            nesterov: false,
+        })));
+
+        // Accumulate some momentum before pruning.
+        for _ in 0..2 {
+            linear = step(linear, &mut optim);
+        }
+        let (mut linear, masks) = prune_unstructured(linear, 0.5);
+        let mut optim = MaskedOptimizer::new(optim, masks);
+
+        for _ in 0..3 {
+            linear = step(linear, &mut optim);
+        }
+
+        let num_zeros = linear
+            .weight
+            .to_data()
+            .value
+            .iter()
+            .filter(|value| **value == 0.0)
+            .count();
+        assert_eq!(num_zeros, 24);
+    }
+
+    fn step<O: Optimizer<Backend = TestADBackend>>(
+        linear: Linear<TestADBackend>,
+        optim: &mut O,
+    ) -> Linear<TestADBackend> {
+        let input = Tensor::<TestADBackend, 2>::random([2, 8], Distribution::Standard);
+        let grads = GradientsParams::from_grads(linear.forward(input).sum().backward(), &linear);
+
+        optim.update_module(linear, grads)
+    }
+}
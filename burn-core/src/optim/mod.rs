@@ -1,16 +1,28 @@
 pub mod decay;
 pub mod momentum;
 
+mod adagrad;
 mod adam;
+mod all_reduce;
 mod base;
+mod centralization;
 mod grad_accum;
 mod grads;
+mod groups;
 mod mapper;
+mod masked;
 mod sgd;
+mod swa;
 mod visitor;
 
+pub use adagrad::*;
 pub use adam::*;
+pub use all_reduce::*;
 pub use base::*;
+pub use centralization::*;
 pub use grad_accum::*;
 pub use grads::*;
+pub use groups::*;
+pub use masked::*;
 pub use sgd::*;
+pub use swa::*;
@@ -3,10 +3,11 @@ use crate as burn;
 use super::decay::{WeightDecay, WeightDecayConfig};
 use super::momentum::{Momentum, MomentumConfig};
 use crate::config::Config;
+use crate::lr_scheduler::LearningRate;
 use crate::module::{ParamId, StateNamed};
-use crate::optim::Optimizer;
+use crate::optim::{centralize_gradient, Optimizer};
 use crate::tensor::backend::ADBackend;
-use crate::tensor::{ElementConversion, Tensor};
+use crate::tensor::Tensor;
 
 /// Configuration to create the [Sgd](Sgd) optimizer.
 #[derive(Config)]
@@ -15,6 +16,10 @@ pub struct SgdConfig {
     pub learning_rate: f64,
     /// [Weight decay](WeightDecayConfig) config.
     pub weight_decay: Option<WeightDecayConfig>,
+    /// If the gradients of parameters of rank 2 or more should be
+    /// [centralized](centralize_gradient) before being applied.
+    #[config(default = false)]
+    pub gradient_centralization: bool,
     /// [Momentum](MomentumConfig) config.
     pub momentum: Option<MomentumConfig>,
 }
@@ -23,14 +28,15 @@ pub struct SgdConfig {
 ///
 /// Momentum is optinal and can be [configured](SgdConfig::momentum).
 pub struct Sgd<B: ADBackend> {
-    learning_rate: B::FloatElem,
+    learning_rate: LearningRate,
     momentum: Option<Momentum<B>>,
     weight_decay: Option<WeightDecay<B>>,
+    gradient_centralization: bool,
 }
 
 impl<B: ADBackend> Sgd<B> {
     pub fn new(config: &SgdConfig) -> Self {
-        let learning_rate = config.learning_rate.elem();
+        let learning_rate = config.learning_rate;
         let momentum = config.momentum.as_ref().map(|config| Momentum::new(config));
         let weight_decay = config
             .weight_decay
@@ -41,6 +47,7 @@ impl<B: ADBackend> Sgd<B> {
             learning_rate,
             momentum,
             weight_decay,
+            gradient_centralization: config.gradient_centralization,
         }
     }
 }
@@ -54,6 +61,10 @@ impl<B: ADBackend> Optimizer for Sgd<B> {
         tensor: Tensor<B, D>,
         grad: Tensor<B::InnerBackend, D>,
     ) -> Tensor<B, D> {
+        let grad = match self.gradient_centralization {
+            true => centralize_gradient(grad),
+            false => grad,
+        };
         let grad = match &mut self.weight_decay {
             Some(weight_decay) => weight_decay.transform(id, grad),
             None => grad,
@@ -67,6 +78,14 @@ impl<B: ADBackend> Optimizer for Sgd<B> {
         Tensor::from_inner(tensor.inner() - delta)
     }
 
+    fn set_learning_rate(&mut self, learning_rate: LearningRate) {
+        self.learning_rate = learning_rate;
+    }
+
+    fn learning_rate(&self) -> LearningRate {
+        self.learning_rate
+    }
+
     fn register_param_state<const D: usize>(
         &self,
         id: &ParamId,
@@ -101,6 +120,7 @@ impl<B: ADBackend> Optimizer for Sgd<B> {
 mod tests {
     use super::*;
     use crate::{
+        module::Param,
         nn::{Linear, LinearConfig},
         optim::GradientsParams,
         tensor::{Distribution, Shape},
@@ -146,6 +166,21 @@ mod tests {
         assert!(state.is_empty());
     }
 
+    #[test]
+    fn with_gradient_centralization_should_not_have_state() {
+        let layer = layer();
+        let mut optim = sgd_with_centralization();
+        let loss = layer.forward(random_tensor());
+        let grads = loss.backward();
+        let grads = GradientsParams::from_grads(grads, &layer);
+
+        let layer = optim.update_module(layer, grads);
+
+        let state = optim.state(&layer);
+
+        assert!(state.is_empty());
+    }
+
     #[test]
     fn should_load_state() {
         let layer = layer();
@@ -165,6 +200,26 @@ mod tests {
         assert_eq!(state, state_restored);
     }
 
+    #[test]
+    fn non_trainable_param_should_not_be_updated() {
+        let mut optim = sgd_with_all();
+        let weight = Tensor::random(Shape::new([20, 20]), Distribution::Standard);
+        let param_frozen = Param::from_tensor(weight.clone(), false);
+        let param_trainable = Param::from_tensor(weight.clone(), true);
+
+        let loss = random_tensor().require_grad().matmul(param_frozen.val());
+        let grads = GradientsParams::from_grads(loss.backward(), &param_frozen);
+        let param_frozen = optim.update_module(param_frozen, grads);
+
+        let loss = random_tensor().matmul(param_trainable.val());
+        let grads = GradientsParams::from_grads(loss.backward(), &param_trainable);
+        let param_trainable = optim.update_module(param_trainable, grads);
+
+        assert!(!param_frozen.is_trainable());
+        assert_eq!(param_frozen.to_data(), weight.to_data());
+        assert_ne!(param_trainable.to_data(), weight.to_data());
+    }
+
     fn random_tensor() -> Tensor<TestADBackend, 2> {
         Tensor::<TestADBackend, 2>::random(Shape::new([2, 20]), Distribution::Standard)
     }
@@ -182,6 +237,16 @@ mod tests {
                 dampening: 0.1,
                 nesterov: true,
             }),
+            gradient_centralization: false,
+        })
+    }
+
+    fn sgd_with_centralization() -> Sgd<TestADBackend> {
+        Sgd::new(&SgdConfig {
+            learning_rate: 0.02,
+            weight_decay: None,
+            momentum: None,
+            gradient_centralization: true,
         })
     }
 
@@ -190,6 +255,7 @@ mod tests {
             learning_rate: 0.02,
             weight_decay: None,
             momentum: None,
+            gradient_centralization: false,
         })
     }
 }
@@ -0,0 +1,154 @@
+use crate::module::{Module, ModuleMapper, ModuleVisitor, ParamId, UniqueParamVisitor};
+use crate::tensor::{backend::Backend, container::TensorContainer, Tensor};
+
+/// Stochastic Weight Averaging, as described in the paper
+/// [Averaging Weights Leads to Wider Optima and Better Generalization](https://arxiv.org/abs/1803.05407).
+///
+/// The weights of the model are [collected](Self::update) at the end of each epoch from the start
+/// epoch, and the running arithmetic mean of the collected snapshots is kept separately from the
+/// trained model.
+///
+/// # Notes
+///
+/// The running statistics of batch normalization layers are averaged like the other parameters,
+/// which doesn't produce valid statistics for the averaged weights. They must be recomputed with
+/// [finalize](Self::finalize).
+pub struct Swa {
+    start_epoch: usize,
+    weights: TensorContainer<ParamId>,
+    num_averaged: usize,
+}
+
+impl Swa {
+    /// Create the averaging, collecting the weights of the epochs from `start_epoch`.
+    pub fn new(start_epoch: usize) -> Self {
+        Self {
+            start_epoch,
+            weights: TensorContainer::new(),
+            num_averaged: 0,
+        }
+    }
+
+    /// Add the weights of the model at the end of the given epoch to the average, epochs before
+    /// the start epoch are ignored.
+    pub fn update<M: Module>(&mut self, model: &M, epoch: usize) {
+        if epoch < self.start_epoch {
+            return;
+        }
+
+        // A parameter shared by multiple sub-modules is added once to its average.
+        let mut accumulator = SwaAccumulator {
+            weights: &mut self.weights,
+            num_averaged: self.num_averaged,
+        };
+        model.visit(&mut UniqueParamVisitor::new(&mut accumulator));
+        self.num_averaged += 1;
+    }
+
+    /// The number of snapshots averaged.
+    pub fn num_averaged(&self) -> usize {
+        self.num_averaged
+    }
+
+    /// Replace the parameters of the given model by the averaged ones.
+    ///
+    /// The parameters that were never collected are kept as-is.
+    pub fn averaged<M: Module>(&self, model: M) -> M {
+        model.map(&mut SwaApplier {
+            weights: &self.weights,
+        })
+    }
+
+    /// Replace the parameters of the given model by the averaged ones, then recompute the batch
+    /// normalization statistics by calling `forward` on each training batch.
+    ///
+    /// The statistics are only updated by forward passes in training mode, so the model should be
+    /// on an [autodiff backend](crate::tensor::backend::ADBackend).
+    pub fn finalize<M, I, F>(
+        &self,
+        model: M,
+        batches: impl IntoIterator<Item = I>,
+        mut forward: F,
+    ) -> M
+    where
+        M: Module,
+        F: FnMut(&M, I),
+    {
+        let model = self.averaged(model);
+
+        for batch in batches {
+            forward(&model, batch);
+        }
+
+        model
+    }
+}
+
+struct SwaAccumulator<'a> {
+    weights: &'a mut TensorContainer<ParamId>,
+    num_averaged: usize,
+}
+
+struct SwaApplier<'a> {
+    weights: &'a TensorContainer<ParamId>,
+}
+
+impl<'a, B: Backend> ModuleVisitor<B> for SwaAccumulator<'a> {
+    fn visit<const D: usize>(&mut self, id: &ParamId, tensor: &Tensor<B, D>) {
+        let tensor = tensor.clone().detach();
+        let average = match self.weights.get::<B, D>(id) {
+            Some(average) => {
+                let delta = tensor.sub(average.clone());
+
+                average.add(delta.div_scalar(self.num_averaged as f64 + 1.0))
+            }
+            None => tensor,
+        };
+
+        self.weights.register(id.clone(), average);
+    }
+}
+
+impl<'a, B: Backend> ModuleMapper<B> for SwaApplier<'a> {
+    fn map<const D: usize>(&mut self, id: &ParamId, tensor: Tensor<B, D>) -> Tensor<B, D> {
+        match self.weights.get::<B, D>(id) {
+            Some(average) => average.detach(),
+            None => tensor,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nn::{Linear, LinearConfig};
+    use crate::tensor::Distribution;
+    use crate::TestBackend;
+
+    #[test]
+    fn averaged_weights_should_be_the_mean_of_the_snapshots() {
+        let linear = Linear::<TestBackend>::new(&LinearConfig::new(4, 3));
+        let snapshots: Vec<_> = (0..4)
+            .map(|_| Tensor::<TestBackend, 2>::random([4, 3], Distribution::Standard))
+            .collect();
+        let mut swa = Swa::new(2);
+
+        for (epoch, weight) in snapshots.iter().enumerate() {
+            let mut linear = linear.clone();
+            linear.weight.value = weight.clone();
+            swa.update(&linear, epoch + 1);
+        }
+
+        let averaged = swa.averaged(linear);
+
+        // The first epoch is before the start epoch.
+        let expected =
+            (snapshots[1].clone() + snapshots[2].clone() + snapshots[3].clone()).div_scalar(3.0);
+        assert_eq!(swa.num_averaged(), 3);
+        averaged
+            .weight
+            .val()
+            .into_data()
+            .assert_approx_eq(&expected.into_data(), 5);
+    }
+}
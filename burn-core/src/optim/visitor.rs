@@ -1,6 +1,7 @@
 use super::{GradientsParams, Optimizer};
+use crate::module::State;
 use crate::module::{ModuleVisitor, ParamId, StateNamed};
-use burn_tensor::{backend::ADBackend, Tensor};
+use burn_tensor::{backend::ADBackend, ElementConversion, Tensor};
 
 #[derive(new)]
 pub struct GradientsRegister<'a, B: ADBackend, O> {
@@ -26,6 +27,44 @@ pub struct GradientsParamsChangeDevice<'a, B: ADBackend> {
     grads: &'a mut GradientsParams,
 }
 
+#[derive(new)]
+pub struct GradientsParamsScaler<'a> {
+    factor: f64,
+    grads: &'a mut GradientsParams,
+}
+
+#[derive(new)]
+pub struct GradientsParamsClipper<'a> {
+    min: f64,
+    max: f64,
+    grads: &'a mut GradientsParams,
+}
+
+#[derive(new)]
+pub struct GradientsParamsFilter<'a, P> {
+    grads: &'a GradientsParams,
+    grads_filtered: &'a mut GradientsParams,
+    predicate: P,
+}
+
+#[derive(new)]
+pub struct GradientsParamsFiniteChecker<'a> {
+    grads: &'a GradientsParams,
+    is_finite: &'a mut bool,
+}
+
+#[derive(new)]
+pub struct GradientsParamsNormCollector<'a> {
+    grads: &'a GradientsParams,
+    norms: &'a mut Vec<(ParamId, f64)>,
+}
+
+#[derive(new)]
+pub struct GradientsParamsSerializer<'a, B: ADBackend> {
+    grads: &'a GradientsParams,
+    state: &'a mut StateNamed<B::FloatElem>,
+}
+
 impl<'a, B: ADBackend, O: Optimizer<Backend = B>> ModuleVisitor<B> for GradientsRegister<'a, B, O> {
     fn visit<const D: usize>(&mut self, id: &ParamId, _tensor: &Tensor<B, D>) {
         self.optimizer.register_param_state::<D>(id, self.state)
@@ -42,6 +81,12 @@ impl<'a, B: ADBackend, O: Optimizer<Backend = B>> ModuleVisitor<B> for Gradients
 impl<'a, B: ADBackend> ModuleVisitor<B> for GradientsParamsConverter<'a, B> {
     fn visit<const D: usize>(&mut self, id: &ParamId, tensor: &Tensor<B, D>) {
         if let Some(grad) = tensor.grad_remove(&mut self.grads) {
+            // A parameter shared by multiple sub-modules can be tracked by multiple tensors, their
+            // gradients are accumulated.
+            let grad = match self.grads_params.get::<B::InnerBackend, D>(id) {
+                Some(grad_previous) => grad_previous.add(grad),
+                None => grad,
+            };
             self.grads_params
                 .register::<B::InnerBackend, D>(id.clone(), grad);
         }
@@ -56,3 +101,68 @@ impl<'a, B: ADBackend> ModuleVisitor<B> for GradientsParamsChangeDevice<'a, B> {
         }
     }
 }
+
+impl<'a, B: ADBackend> ModuleVisitor<B> for GradientsParamsScaler<'a> {
+    fn visit<const D: usize>(&mut self, id: &ParamId, _tensor: &Tensor<B, D>) {
+        if let Some(grad) = self.grads.remove::<B::InnerBackend, D>(id) {
+            self.grads
+                .register::<B::InnerBackend, D>(id.clone(), grad.mul_scalar(self.factor));
+        }
+    }
+}
+
+impl<'a, B: ADBackend> ModuleVisitor<B> for GradientsParamsClipper<'a> {
+    fn visit<const D: usize>(&mut self, id: &ParamId, _tensor: &Tensor<B, D>) {
+        if let Some(grad) = self.grads.remove::<B::InnerBackend, D>(id) {
+            self.grads
+                .register::<B::InnerBackend, D>(id.clone(), grad.clamp(self.min, self.max));
+        }
+    }
+}
+
+impl<'a, B, P> ModuleVisitor<B> for GradientsParamsFilter<'a, P>
+where
+    B: ADBackend,
+    P: FnMut(&ParamId, usize) -> bool,
+{
+    fn visit<const D: usize>(&mut self, id: &ParamId, _tensor: &Tensor<B, D>) {
+        if !(self.predicate)(id, D) {
+            return;
+        }
+
+        if let Some(grad) = self.grads.get::<B::InnerBackend, D>(id) {
+            self.grads_filtered
+                .register::<B::InnerBackend, D>(id.clone(), grad);
+        }
+    }
+}
+
+impl<'a, B: ADBackend> ModuleVisitor<B> for GradientsParamsNormCollector<'a> {
+    fn visit<const D: usize>(&mut self, id: &ParamId, _tensor: &Tensor<B, D>) {
+        if let Some(grad) = self.grads.get::<B::InnerBackend, D>(id) {
+            let norm: f64 = grad.powf(2.0).sum().sqrt().single_value().elem();
+            self.norms.push((id.clone(), norm));
+        }
+    }
+}
+
+impl<'a, B: ADBackend> ModuleVisitor<B> for GradientsParamsFiniteChecker<'a> {
+    fn visit<const D: usize>(&mut self, id: &ParamId, _tensor: &Tensor<B, D>) {
+        if !*self.is_finite {
+            return;
+        }
+
+        if let Some(grad) = self.grads.get::<B::InnerBackend, D>(id) {
+            *self.is_finite = grad.is_finite();
+        }
+    }
+}
+
+impl<'a, B: ADBackend> ModuleVisitor<B> for GradientsParamsSerializer<'a, B> {
+    fn visit<const D: usize>(&mut self, id: &ParamId, _tensor: &Tensor<B, D>) {
+        if let Some(grad) = self.grads.get::<B::InnerBackend, D>(id) {
+            let data = State::Data(grad.into_data().serialize());
+            self.state.register_state(id.to_string().as_str(), data);
+        }
+    }
+}
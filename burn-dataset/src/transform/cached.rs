@@ -0,0 +1,263 @@
+use crate::Dataset;
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+
+/// Memoize the items of a dataset after their first access.
+///
+/// By default every item is kept in memory, a bounded [LRU](CachedDataset::with_capacity) cache
+/// can be used instead when the dataset doesn't fit in memory.
+pub struct CachedDataset<I> {
+    dataset: Arc<dyn Dataset<I>>,
+    cache: Mutex<ItemCache<I>>,
+}
+
+/// The cached items, each with the generation of its last access.
+///
+/// The accesses are queued in order, an access is stale when the item was accessed again since,
+/// so finding the least recently used item and touching an item are amortized O(1).
+struct ItemCache<I> {
+    items: HashMap<usize, (I, u64)>,
+    recently_used: VecDeque<(usize, u64)>,
+    generation: u64,
+    capacity: Option<usize>,
+}
+
+impl<I> CachedDataset<I> {
+    /// Create a cached dataset keeping every accessed item in memory.
+    pub fn new(dataset: Arc<dyn Dataset<I>>) -> Self {
+        Self {
+            dataset,
+            cache: Mutex::new(ItemCache::new(None)),
+        }
+    }
+
+    /// Create a cached dataset keeping at most `capacity` items in memory, evicting the least
+    /// recently used ones first.
+    pub fn with_capacity(dataset: Arc<dyn Dataset<I>>, capacity: usize) -> Self {
+        Self {
+            dataset,
+            cache: Mutex::new(ItemCache::new(Some(capacity))),
+        }
+    }
+}
+
+impl<I> ItemCache<I> {
+    fn new(capacity: Option<usize>) -> Self {
+        Self {
+            items: HashMap::new(),
+            recently_used: VecDeque::new(),
+            generation: 0,
+            capacity,
+        }
+    }
+
+    fn get(&mut self, index: usize) -> Option<&I> {
+        if !self.items.contains_key(&index) {
+            return None;
+        }
+
+        self.touch(index);
+        self.items.get(&index).map(|(item, _)| item)
+    }
+
+    fn touch(&mut self, index: usize) {
+        if self.capacity.is_none() {
+            return;
+        }
+
+        let generation = self.generation;
+        self.generation += 1;
+
+        if let Some((_, generation_item)) = self.items.get_mut(&index) {
+            *generation_item = generation;
+        }
+        self.recently_used.push_back((index, generation));
+
+        // Drop the stale accesses once they outnumber the items, which amortizes the cost.
+        if self.recently_used.len() > 2 * self.items.len() {
+            let items = &self.items;
+            self.recently_used.retain(|(index, generation)| {
+                matches!(items.get(index), Some((_, current)) if current == generation)
+            });
+        }
+    }
+
+    fn evict(&mut self) {
+        while let Some((index, generation)) = self.recently_used.pop_front() {
+            if matches!(self.items.get(&index), Some((_, current)) if *current == generation) {
+                self.items.remove(&index);
+                return;
+            }
+        }
+    }
+
+    fn insert(&mut self, index: usize, item: I) {
+        if let Some(capacity) = self.capacity {
+            if capacity == 0 {
+                return;
+            }
+
+            // The item may have been loaded by another thread in the meantime.
+            if !self.items.contains_key(&index) {
+                while self.items.len() >= capacity && !self.recently_used.is_empty() {
+                    self.evict();
+                }
+            }
+        }
+
+        self.items.insert(index, (item, 0));
+        self.touch(index);
+    }
+}
+
+impl<I> Dataset<I> for CachedDataset<I>
+where
+    I: Clone + Send + Sync,
+{
+    fn get(&self, index: usize) -> Option<I> {
+        if let Some(item) = self.cache.lock().unwrap().get(index) {
+            return Some(item.clone());
+        }
+
+        // The lock isn't held while loading, so the other items can be accessed concurrently.
+        let item = self.dataset.get(index)?;
+        self.cache.lock().unwrap().insert(index, item.clone());
+
+        Some(item)
+    }
+
+    fn len(&self) -> usize {
+        self.dataset.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_data, InMemDataset};
+    use std::sync::mpsc;
+    use std::time::Duration;
+
+    struct CountingDataset {
+        dataset: InMemDataset<String>,
+        counts: Mutex<Vec<usize>>,
+    }
+
+    impl CountingDataset {
+        fn new(items: Vec<String>) -> Self {
+            let counts = Mutex::new(vec![0; items.len()]);
+            let dataset = InMemDataset::new(items);
+
+            Self { dataset, counts }
+        }
+
+        fn counts(&self) -> Vec<usize> {
+            self.counts.lock().unwrap().clone()
+        }
+    }
+
+    impl Dataset<String> for CountingDataset {
+        fn get(&self, index: usize) -> Option<String> {
+            if let Some(count) = self.counts.lock().unwrap().get_mut(index) {
+                *count += 1;
+            }
+            self.dataset.get(index)
+        }
+
+        fn len(&self) -> usize {
+            self.dataset.len()
+        }
+    }
+
+    #[test]
+    fn given_cached_dataset_when_iterate_twice_should_get_each_item_once() {
+        let items_original = test_data::string_items();
+        let dataset = Arc::new(CountingDataset::new(items_original.clone()));
+        let dataset_cached = CachedDataset::new(dataset.clone());
+
+        let items_epoch_1: Vec<String> = dataset_cached.iter().collect();
+        let items_epoch_2: Vec<String> = dataset_cached.iter().collect();
+
+        assert_eq!(items_original, items_epoch_1);
+        assert_eq!(items_original, items_epoch_2);
+        assert_eq!(dataset.counts(), vec![1; items_original.len()]);
+    }
+
+    #[test]
+    fn given_bounded_cache_when_full_should_evict_least_recently_used() {
+        let dataset = Arc::new(CountingDataset::new(test_data::string_items()));
+        let dataset_cached = CachedDataset::with_capacity(dataset.clone(), 2);
+
+        dataset_cached.get(0);
+        dataset_cached.get(1);
+        dataset_cached.get(0);
+        dataset_cached.get(2); // Evicts item 1.
+        dataset_cached.get(0);
+        dataset_cached.get(1);
+
+        assert_eq!(dataset.counts(), vec![1, 2, 1, 0]);
+    }
+
+    #[test]
+    fn given_bounded_cache_when_items_are_reused_should_keep_the_recent_ones() {
+        let dataset = Arc::new(CountingDataset::new(test_data::string_items()));
+        let dataset_cached = CachedDataset::with_capacity(dataset.clone(), 2);
+
+        for _ in 0..10 {
+            dataset_cached.get(0);
+            dataset_cached.get(1);
+        }
+        dataset_cached.get(2); // Evicts item 0.
+        dataset_cached.get(1);
+
+        assert_eq!(dataset.counts(), vec![1, 1, 1, 0]);
+    }
+
+    /// Loading the item 0 waits until the item 1 is loaded by another thread.
+    struct HandshakeDataset {
+        entered: Mutex<mpsc::Sender<()>>,
+        release: Mutex<mpsc::Sender<()>>,
+        released: Mutex<mpsc::Receiver<()>>,
+    }
+
+    impl Dataset<bool> for HandshakeDataset {
+        fn get(&self, index: usize) -> Option<bool> {
+            match index {
+                0 => {
+                    self.entered.lock().unwrap().send(()).unwrap();
+                    let released = self.released.lock().unwrap();
+                    Some(released.recv_timeout(Duration::from_secs(5)).is_ok())
+                }
+                _ => {
+                    self.release.lock().unwrap().send(()).unwrap();
+                    Some(true)
+                }
+            }
+        }
+
+        fn len(&self) -> usize {
+            2
+        }
+    }
+
+    #[test]
+    fn given_item_loading_should_not_block_the_other_items() {
+        let (entered, entered_receiver) = mpsc::channel();
+        let (release, released) = mpsc::channel();
+        let dataset = HandshakeDataset {
+            entered: Mutex::new(entered),
+            release: Mutex::new(release),
+            released: Mutex::new(released),
+        };
+        let dataset_cached = Arc::new(CachedDataset::with_capacity(Arc::new(dataset), 2));
+
+        let handle = {
+            let dataset_cached = dataset_cached.clone();
+            std::thread::spawn(move || dataset_cached.get(0))
+        };
+        entered_receiver.recv().unwrap();
+
+        assert_eq!(dataset_cached.get(1), Some(true));
+        assert_eq!(handle.join().unwrap(), Some(true));
+    }
+}
@@ -1,9 +1,13 @@
 use crate::Dataset;
 
+/// Dataset indexing sequentially across multiple datasets.
 pub struct ComposedDataset<I> {
     datasets: Vec<Box<dyn Dataset<I>>>,
 }
 
+/// Same as [ComposedDataset], named after the equivalent PyTorch utility.
+pub type ConcatDataset<I> = ComposedDataset<I>;
+
 impl<I> ComposedDataset<I> {
     pub fn new(datasets: Vec<Box<dyn Dataset<I>>>) -> Self {
         Self { datasets }
@@ -32,3 +36,25 @@ where
         total
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_data, InMemDataset};
+
+    #[test]
+    fn given_concat_dataset_should_index_across_boundaries() {
+        let items_1 = test_data::string_items();
+        let items_2 = vec!["5 Items".to_string(), "6 Items".to_string()];
+        let dataset = ConcatDataset::new(vec![
+            Box::new(InMemDataset::new(items_1.clone())),
+            Box::new(InMemDataset::new(items_2.clone())),
+        ]);
+
+        assert_eq!(dataset.len(), 6);
+        assert_eq!(dataset.get(3), Some(items_1[3].clone()));
+        assert_eq!(dataset.get(4), Some(items_2[0].clone()));
+        assert_eq!(dataset.get(5), Some(items_2[1].clone()));
+        assert_eq!(dataset.get(6), None);
+    }
+}
@@ -0,0 +1,54 @@
+use crate::Dataset;
+use std::marker::PhantomData;
+
+/// Apply a function to each item of a dataset when it is accessed.
+///
+/// This is a lightweight alternative to [MapperDataset](crate::transform::MapperDataset) where a
+/// closure can be used instead of implementing the [Mapper](crate::transform::Mapper) trait.
+pub struct MapDataset<D, F, I> {
+    dataset: D,
+    func: F,
+    input: PhantomData<I>,
+}
+
+impl<D, F, I> MapDataset<D, F, I> {
+    pub fn new(dataset: D, func: F) -> Self {
+        Self {
+            dataset,
+            func,
+            input: PhantomData,
+        }
+    }
+}
+
+impl<D, F, I, O> Dataset<O> for MapDataset<D, F, I>
+where
+    D: Dataset<I>,
+    F: Fn(I) -> O + Send + Sync,
+    I: Send + Sync,
+{
+    fn get(&self, index: usize) -> Option<O> {
+        self.dataset.get(index).map(&self.func)
+    }
+
+    fn len(&self) -> usize {
+        self.dataset.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_data, InMemDataset};
+
+    #[test]
+    pub fn given_map_dataset_when_iterate_should_iterate_though_all_map_items() {
+        let items_original = test_data::string_items();
+        let dataset = InMemDataset::new(items_original);
+        let dataset = MapDataset::new(dataset, |item: String| item.len());
+
+        let items: Vec<usize> = dataset.iter().collect();
+
+        assert_eq!(vec![6, 7, 7, 7], items);
+    }
+}
@@ -1,11 +1,21 @@
+mod cached;
 mod composed;
+mod map;
 mod mapper;
 mod partial;
 mod random;
+mod repeat;
 mod sampler;
+mod subset;
+mod zip;
 
+pub use cached::*;
 pub use composed::*;
+pub use map::*;
 pub use mapper::*;
 pub use partial::*;
 pub use random::*;
+pub use repeat::*;
 pub use sampler::*;
+pub use subset::*;
+pub use zip::*;
@@ -0,0 +1,137 @@
+use crate::Dataset;
+use rand::{prelude::SliceRandom, rngs::StdRng, SeedableRng};
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+
+/// Maximum number of cycle orders kept in memory when the cycles are shuffled.
+const NUM_CYCLES_CACHED: usize = 8;
+
+/// Cycle over a dataset until reaching a target length, which can be used to train for a fixed
+/// number of steps as a single long epoch.
+///
+/// Each cycle can be [shuffled](RepeatDataset::shuffle) with its own order, so the items are
+/// reshuffled every time the base dataset is exhausted.
+pub struct RepeatDataset<I> {
+    dataset: Arc<dyn Dataset<I>>,
+    len: usize,
+    seed: Option<u64>,
+    cycles: Mutex<VecDeque<(usize, Arc<Vec<usize>>)>>,
+}
+
+impl<I> RepeatDataset<I> {
+    /// Create a dataset of the given length cycling over the items of the base dataset.
+    pub fn new(dataset: Arc<dyn Dataset<I>>, len: usize) -> Self {
+        Self {
+            dataset,
+            len,
+            seed: None,
+            cycles: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    /// Create a dataset cycling forever over the items of the base dataset, its length is
+    /// `usize::MAX`.
+    pub fn infinite(dataset: Arc<dyn Dataset<I>>) -> Self {
+        Self::new(dataset, usize::MAX)
+    }
+
+    /// Shuffle the items of each cycle with a different order, derived from the given seed and
+    /// the index of the cycle.
+    pub fn shuffle(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    fn cycle_order(&self, seed: u64, cycle: usize) -> Arc<Vec<usize>> {
+        let mut cycles = self.cycles.lock().unwrap();
+
+        if let Some((_, order)) = cycles.iter().find(|(index, _)| *index == cycle) {
+            return order.clone();
+        }
+
+        let mut order: Vec<usize> = (0..self.dataset.len()).collect();
+        let mut rng = StdRng::seed_from_u64(seed.wrapping_add(cycle as u64));
+        order.shuffle(&mut rng);
+
+        let order = Arc::new(order);
+        if cycles.len() == NUM_CYCLES_CACHED {
+            cycles.pop_front();
+        }
+        cycles.push_back((cycle, order.clone()));
+
+        order
+    }
+}
+
+impl<I> Dataset<I> for RepeatDataset<I> {
+    fn get(&self, index: usize) -> Option<I> {
+        let len_base = self.dataset.len();
+        if index >= self.len || len_base == 0 {
+            return None;
+        }
+
+        let (cycle, index) = (index / len_base, index % len_base);
+
+        match self.seed {
+            Some(seed) => self.dataset.get(self.cycle_order(seed, cycle)[index]),
+            None => self.dataset.get(index),
+        }
+    }
+
+    fn len(&self) -> usize {
+        match self.dataset.is_empty() {
+            true => 0,
+            false => self.len,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::InMemDataset;
+
+    fn base_dataset() -> Arc<InMemDataset<usize>> {
+        Arc::new(InMemDataset::new(vec![0, 1, 2]))
+    }
+
+    #[test]
+    fn test_repeat_should_cycle_to_the_target_length() {
+        let dataset = RepeatDataset::new(base_dataset(), 10);
+
+        assert_eq!(dataset.len(), 10);
+        assert_eq!(
+            dataset.iter().collect::<Vec<_>>(),
+            vec![0, 1, 2, 0, 1, 2, 0, 1, 2, 0]
+        );
+    }
+
+    #[test]
+    fn test_infinite_repeat_should_keep_cycling() {
+        let dataset = RepeatDataset::infinite(base_dataset());
+
+        assert_eq!(dataset.len(), usize::MAX);
+        assert_eq!(dataset.get(3_000_001), Some(1));
+        assert_eq!(
+            dataset.iter().take(7).collect::<Vec<_>>(),
+            vec![0, 1, 2, 0, 1, 2, 0]
+        );
+    }
+
+    #[test]
+    fn test_shuffled_repeat_should_reshuffle_each_cycle() {
+        let base = Arc::new(InMemDataset::new((0..20).collect::<Vec<usize>>()));
+        let dataset = RepeatDataset::new(base.clone(), 60).shuffle(42);
+        let items: Vec<usize> = dataset.iter().collect();
+
+        for cycle in items.chunks(20) {
+            let mut sorted = cycle.to_vec();
+            sorted.sort();
+            assert_eq!(sorted, (0..20).collect::<Vec<_>>());
+        }
+        assert_ne!(items[0..20], items[20..40]);
+
+        let dataset = RepeatDataset::new(base, 60).shuffle(42);
+        assert_eq!(dataset.iter().collect::<Vec<_>>(), items);
+    }
+}
@@ -0,0 +1,136 @@
+use crate::Dataset;
+use rand::{prelude::SliceRandom, rngs::StdRng, SeedableRng};
+use std::sync::Arc;
+
+/// Dataset containing only the items at the given indexes of another dataset.
+pub struct SubsetDataset<I> {
+    dataset: Arc<dyn Dataset<I>>,
+    indexes: Vec<usize>,
+}
+
+impl<I> SubsetDataset<I> {
+    pub fn new(dataset: Arc<dyn Dataset<I>>, indexes: Vec<usize>) -> Self {
+        Self { dataset, indexes }
+    }
+
+    /// Randomly split the dataset into disjoint subsets following the given ratios.
+    ///
+    /// Every item of the dataset is part of exactly one subset and the split is deterministic
+    /// for a given seed. The ratios are normalized, so `[0.8, 0.2]` and `[4.0, 1.0]` are
+    /// equivalent.
+    ///
+    /// # Panics
+    ///
+    /// If no ratio is given, if a ratio is negative or not finite, or if all the ratios are zero.
+    pub fn split(dataset: Arc<dyn Dataset<I>>, ratios: &[f64], seed: u64) -> Vec<Self> {
+        if ratios.is_empty() {
+            panic!("At least one ratio is required to split a dataset");
+        }
+        if ratios
+            .iter()
+            .any(|ratio| !ratio.is_finite() || *ratio < 0.0)
+        {
+            panic!("Can't split a dataset with negative or non-finite ratios, got {ratios:?}");
+        }
+
+        let total: f64 = ratios.iter().sum();
+        if total == 0.0 {
+            panic!("Can't split a dataset with ratios summing to zero, got {ratios:?}");
+        }
+        if !total.is_finite() {
+            panic!("Can't split a dataset with ratios summing to infinity, got {ratios:?}");
+        }
+
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut indexes: Vec<usize> = (0..dataset.len()).collect();
+        indexes.shuffle(&mut rng);
+        let num_items = indexes.len();
+        let mut ratio_cumulative = 0.0;
+        let mut start = 0;
+        let mut datasets = Vec::with_capacity(ratios.len());
+
+        for (i, ratio) in ratios.iter().enumerate() {
+            ratio_cumulative += ratio;
+
+            let end = match i == ratios.len() - 1 {
+                true => num_items,
+                false => usize::min(
+                    (ratio_cumulative / total * num_items as f64).round() as usize,
+                    num_items,
+                ),
+            };
+
+            datasets.push(Self::new(dataset.clone(), indexes[start..end].to_vec()));
+            start = end;
+        }
+
+        datasets
+    }
+}
+
+impl<I> Dataset<I> for SubsetDataset<I> {
+    fn get(&self, index: usize) -> Option<I> {
+        let index = self.indexes.get(index)?;
+        self.dataset.get(*index)
+    }
+
+    fn len(&self) -> usize {
+        self.indexes.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::InMemDataset;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_split_should_produce_disjoint_subsets_with_all_items() {
+        let dataset = Arc::new(InMemDataset::new((0..100).collect::<Vec<usize>>()));
+        let datasets = SubsetDataset::split(dataset, &[0.8, 0.2], 42);
+
+        let items_train: HashSet<usize> = datasets[0].iter().collect();
+        let items_valid: HashSet<usize> = datasets[1].iter().collect();
+
+        assert_eq!(datasets.len(), 2);
+        assert_eq!(items_train.len(), 80);
+        assert_eq!(items_valid.len(), 20);
+        assert!(items_train.is_disjoint(&items_valid));
+        assert_eq!(items_train.union(&items_valid).count(), 100);
+    }
+
+    #[test]
+    fn test_split_is_deterministic_given_the_seed() {
+        let dataset = Arc::new(InMemDataset::new((0..100).collect::<Vec<usize>>()));
+        let datasets_1 = SubsetDataset::split(dataset.clone(), &[0.5, 0.5], 7);
+        let datasets_2 = SubsetDataset::split(dataset, &[0.5, 0.5], 7);
+
+        for (dataset_1, dataset_2) in datasets_1.iter().zip(datasets_2.iter()) {
+            let items_1: Vec<usize> = dataset_1.iter().collect();
+            let items_2: Vec<usize> = dataset_2.iter().collect();
+            assert_eq!(items_1, items_2);
+        }
+    }
+
+    #[test]
+    #[should_panic = "ratios summing to zero"]
+    fn test_split_should_panic_when_the_ratios_sum_to_zero() {
+        let dataset = Arc::new(InMemDataset::new((0..100).collect::<Vec<usize>>()));
+        SubsetDataset::split(dataset, &[0.0, 0.0], 42);
+    }
+
+    #[test]
+    #[should_panic = "non-finite ratios"]
+    fn test_split_should_panic_when_a_ratio_is_nan() {
+        let dataset = Arc::new(InMemDataset::new((0..100).collect::<Vec<usize>>()));
+        SubsetDataset::split(dataset, &[0.8, f64::NAN], 42);
+    }
+
+    #[test]
+    #[should_panic = "non-finite ratios"]
+    fn test_split_should_panic_when_a_ratio_is_infinite() {
+        let dataset = Arc::new(InMemDataset::new((0..100).collect::<Vec<usize>>()));
+        SubsetDataset::split(dataset, &[f64::INFINITY, 0.2], 42);
+    }
+}
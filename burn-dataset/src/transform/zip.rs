@@ -0,0 +1,58 @@
+use crate::Dataset;
+use std::sync::Arc;
+
+/// Pair the items of two datasets sharing the same index.
+///
+/// The length of the zipped dataset is the length of the shortest dataset.
+pub struct ZipDataset<A, B> {
+    dataset_a: Arc<dyn Dataset<A>>,
+    dataset_b: Arc<dyn Dataset<B>>,
+}
+
+impl<A, B> ZipDataset<A, B> {
+    pub fn new(dataset_a: Arc<dyn Dataset<A>>, dataset_b: Arc<dyn Dataset<B>>) -> Self {
+        Self {
+            dataset_a,
+            dataset_b,
+        }
+    }
+}
+
+impl<A, B> Dataset<(A, B)> for ZipDataset<A, B> {
+    fn get(&self, index: usize) -> Option<(A, B)> {
+        if index >= self.len() {
+            return None;
+        }
+
+        let item_a = self.dataset_a.get(index)?;
+        let item_b = self.dataset_b.get(index)?;
+
+        Some((item_a, item_b))
+    }
+
+    fn len(&self) -> usize {
+        usize::min(self.dataset_a.len(), self.dataset_b.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_data, InMemDataset};
+
+    #[test]
+    fn given_datasets_of_different_lengths_when_zip_should_have_min_length() {
+        let dataset_a = Arc::new(InMemDataset::new(test_data::string_items()));
+        let dataset_b = Arc::new(InMemDataset::new(vec![1, 2]));
+        let dataset = ZipDataset::new(dataset_a, dataset_b);
+
+        let items: Vec<(String, i32)> = dataset.iter().collect();
+
+        assert_eq!(dataset.len(), 2);
+        assert_eq!(
+            items,
+            vec![("1 Item".to_string(), 1), ("2 Items".to_string(), 2)]
+        );
+        assert!(dataset.get(2).is_none());
+    }
+}
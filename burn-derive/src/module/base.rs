@@ -10,7 +10,6 @@ pub(crate) fn module_derive_impl(ast: &syn::DeriveInput) -> TokenStream {
     let display_fn = display::display_fn(name);
 
     let param = Param::from_ast(ast);
-    let num_params_fn = param.gen_num_params_fn();
     let visit = param.gen_visit_fn();
     let map_mut = param.gen_map_fn();
     let devices_fn = param.gen_devices_fn();
@@ -33,7 +32,6 @@ pub(crate) fn module_derive_impl(ast: &syn::DeriveInput) -> TokenStream {
             #state_fn
             #load_fn
 
-            #num_params_fn
             #detach_fn
 
             #visit
@@ -26,33 +26,14 @@ impl Param {
         }
     }
 
-    pub fn gen_num_params_fn(&self) -> TokenStream {
-        let mut body = quote! {
-            let mut num_params = 0;
-        };
-        for field in self.fields_param.iter() {
-            let name = field.ident();
-            body.extend(quote! {
-                num_params += self.#name.num_params();
-            });
-        }
-        body.extend(quote! {
-            num_params
-        });
-
-        quote! {
-            fn num_params(&self) -> usize {
-                #body
-            }
-        }
-    }
-
     pub fn gen_visit_fn(&self) -> TokenStream {
         let mut body = quote! {};
         for field in self.fields_param.iter() {
             let name = field.ident();
             body.extend(quote! {
+                visitor.enter_module(stringify!(#name));
                 self.#name.visit(visitor);
+                visitor.exit_module(stringify!(#name));
             });
         }
 
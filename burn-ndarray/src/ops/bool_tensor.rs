@@ -105,7 +105,7 @@ impl<E: FloatNdArrayElement> BoolTensorOps<NdArrayBackend<E>> for NdArrayBackend
         rhs: <NdArrayBackend<E> as Backend>::BoolTensorPrimitive<D>,
     ) -> <NdArrayBackend<E> as Backend>::BoolTensorPrimitive<D> {
         let mut array = lhs.array;
-        array.zip_mut_with(&rhs.array, |a, b| *a = *a && *b);
+        array.zip_mut_with(&rhs.array, |a, b| *a = *a == *b);
 
         NdArrayTensor { array }
     }
@@ -117,4 +117,39 @@ impl<E: FloatNdArrayElement> BoolTensorOps<NdArrayBackend<E>> for NdArrayBackend
         let array = lhs.array.mapv(|a| a == rhs).into_shared();
         NdArrayTensor { array }
     }
+
+    fn bool_not<const D: usize>(tensor: NdArrayTensor<bool, D>) -> NdArrayTensor<bool, D> {
+        let array = tensor.array.mapv(|a| !a).into_shared();
+        NdArrayTensor { array }
+    }
+
+    fn bool_and<const D: usize>(
+        lhs: NdArrayTensor<bool, D>,
+        rhs: NdArrayTensor<bool, D>,
+    ) -> NdArrayTensor<bool, D> {
+        let mut array = lhs.array;
+        array.zip_mut_with(&rhs.array, |a, b| *a = *a && *b);
+
+        NdArrayTensor { array }
+    }
+
+    fn bool_or<const D: usize>(
+        lhs: NdArrayTensor<bool, D>,
+        rhs: NdArrayTensor<bool, D>,
+    ) -> NdArrayTensor<bool, D> {
+        let mut array = lhs.array;
+        array.zip_mut_with(&rhs.array, |a, b| *a = *a || *b);
+
+        NdArrayTensor { array }
+    }
+
+    fn bool_any<const D: usize>(tensor: NdArrayTensor<bool, D>) -> NdArrayTensor<bool, 1> {
+        let any = tensor.array.iter().any(|a| *a);
+        NdArrayTensor::from_data(Data::new(vec![any], Shape::new([1])))
+    }
+
+    fn bool_all<const D: usize>(tensor: NdArrayTensor<bool, D>) -> NdArrayTensor<bool, 1> {
+        let all = tensor.array.iter().all(|a| *a);
+        NdArrayTensor::from_data(Data::new(vec![all], Shape::new([1])))
+    }
 }
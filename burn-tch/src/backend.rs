@@ -1,8 +1,9 @@
 use super::element::TchElement;
+use super::memory::memory_used;
 use super::TchTensor;
 use burn_tensor::backend::Backend;
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 /// The device struct when using the `tch` backend.
 ///
 /// Note that you need to provide the device index when using Cuda.
@@ -78,4 +79,10 @@ impl<E: TchElement> Backend for TchBackend<E> {
     fn name() -> String {
         "tch".to_string()
     }
+
+    /// Only available for CUDA devices already used by libtorch, the allocator of libtorch
+    /// isn't queried so the memory it allocated isn't available.
+    fn memory_used(device: &Self::Device) -> Option<usize> {
+        memory_used(device)
+    }
 }
@@ -1,5 +1,6 @@
 mod backend;
 mod element;
+mod memory;
 mod ops;
 mod tensor;
 
@@ -0,0 +1,135 @@
+use crate::TchDevice;
+
+/// Number of bytes used on the whole device, only available for CUDA devices.
+///
+/// The value is queried from the CUDA driver, which is loaded at runtime so that the backend
+/// doesn't link to it. It includes the memory cached by the allocator of libtorch and the memory
+/// used by other processes on the device.
+///
+/// The query requires the primary context of the device, the one used by libtorch. `None` is
+/// returned when that context isn't active, so a device that libtorch didn't use yet isn't
+/// initialized by the query.
+pub(crate) fn memory_used(device: &TchDevice) -> Option<usize> {
+    match device {
+        TchDevice::Cuda(index) => cuda::memory_used(*index),
+        TchDevice::Cpu | TchDevice::Mps => None,
+    }
+}
+
+#[cfg(unix)]
+mod cuda {
+    use libc::{c_char, c_int, c_uint, c_void, size_t};
+    use std::sync::OnceLock;
+
+    type CuResult = c_int;
+    type CuDevice = c_int;
+    type CuContext = *mut c_void;
+
+    const CUDA_SUCCESS: CuResult = 0;
+
+    struct Driver {
+        device_get: unsafe extern "C" fn(*mut CuDevice, c_int) -> CuResult,
+        primary_ctx_get_state: unsafe extern "C" fn(CuDevice, *mut c_uint, *mut c_int) -> CuResult,
+        primary_ctx_retain: unsafe extern "C" fn(*mut CuContext, CuDevice) -> CuResult,
+        primary_ctx_release: unsafe extern "C" fn(CuDevice) -> CuResult,
+        ctx_push_current: unsafe extern "C" fn(CuContext) -> CuResult,
+        ctx_pop_current: unsafe extern "C" fn(*mut CuContext) -> CuResult,
+        mem_get_info: unsafe extern "C" fn(*mut size_t, *mut size_t) -> CuResult,
+    }
+
+    /// Load the symbols of the driver once, `None` when there is no CUDA driver.
+    fn driver() -> Option<&'static Driver> {
+        static DRIVER: OnceLock<Option<Driver>> = OnceLock::new();
+
+        DRIVER.get_or_init(|| unsafe { load() }).as_ref()
+    }
+
+    /// Get the function with the given name from the library, the type `F` must be a function
+    /// pointer matching the signature of the symbol.
+    unsafe fn function<F>(library: *mut c_void, name: &[u8]) -> Option<F> {
+        let symbol = libc::dlsym(library, name.as_ptr() as *const c_char);
+
+        match symbol.is_null() {
+            true => None,
+            false => Some(core::mem::transmute_copy::<*mut c_void, F>(&symbol)),
+        }
+    }
+
+    unsafe fn load() -> Option<Driver> {
+        let library = libc::dlopen(
+            b"libcuda.so.1\0".as_ptr() as *const c_char,
+            libc::RTLD_NOW | libc::RTLD_LOCAL,
+        );
+        if library.is_null() {
+            return None;
+        }
+
+        let init: unsafe extern "C" fn(c_uint) -> CuResult = function(library, b"cuInit\0")?;
+        if init(0) != CUDA_SUCCESS {
+            return None;
+        }
+
+        Some(Driver {
+            device_get: function(library, b"cuDeviceGet\0")?,
+            primary_ctx_get_state: function(library, b"cuDevicePrimaryCtxGetState\0")?,
+            primary_ctx_retain: function(library, b"cuDevicePrimaryCtxRetain\0")?,
+            primary_ctx_release: function(library, b"cuDevicePrimaryCtxRelease_v2\0")?,
+            ctx_push_current: function(library, b"cuCtxPushCurrent_v2\0")?,
+            ctx_pop_current: function(library, b"cuCtxPopCurrent_v2\0")?,
+            mem_get_info: function(library, b"cuMemGetInfo_v2\0")?,
+        })
+    }
+
+    pub(super) fn memory_used(index: usize) -> Option<usize> {
+        let driver = driver()?;
+        let mut device: CuDevice = 0;
+        let mut context: CuContext = core::ptr::null_mut();
+        let (mut flags, mut active): (c_uint, c_int) = (0, 0);
+        let (mut free, mut total): (size_t, size_t) = (0, 0);
+
+        // The primary context is the one used by libtorch. Retaining it would create it when it
+        // isn't active, so it is only retained and made current for the duration of the query
+        // when libtorch already uses it.
+        unsafe {
+            if (driver.device_get)(&mut device, index as c_int) != CUDA_SUCCESS
+                || (driver.primary_ctx_get_state)(device, &mut flags, &mut active) != CUDA_SUCCESS
+                || active == 0
+                || (driver.primary_ctx_retain)(&mut context, device) != CUDA_SUCCESS
+            {
+                return None;
+            }
+
+            let result = match (driver.ctx_push_current)(context) {
+                CUDA_SUCCESS => {
+                    let result = (driver.mem_get_info)(&mut free, &mut total);
+                    (driver.ctx_pop_current)(&mut context);
+                    result
+                }
+                error => error,
+            };
+            (driver.primary_ctx_release)(device);
+
+            match result {
+                CUDA_SUCCESS => Some(total - free),
+                _ => None,
+            }
+        }
+    }
+}
+
+#[cfg(not(unix))]
+mod cuda {
+    pub(super) fn memory_used(_index: usize) -> Option<usize> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_not_report_the_memory_of_the_cpu() {
+        assert_eq!(memory_used(&TchDevice::Cpu), None);
+    }
+}
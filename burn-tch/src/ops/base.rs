@@ -9,6 +9,18 @@ pub struct TchOps<E: tch::kind::Element + Copy + Default> {
 }
 
 impl<E: tch::kind::Element + Copy + Default> TchOps<E> {
+    /// Move the tensor to the given device, the copy is asynchronous when the tensor is in
+    /// [pinned](TchTensor::pin_memory) memory.
+    pub fn to_device<const D: usize>(
+        tensor: TchTensor<E, D>,
+        device: tch::Device,
+    ) -> TchTensor<E, D> {
+        let non_blocking = tensor.is_pinned();
+        let kind = tensor.tensor.kind();
+
+        TchTensor::new(tensor.tensor.to_device_(device, kind, non_blocking, false))
+    }
+
     pub fn reshape<const D1: usize, const D2: usize>(
         tensor: TchTensor<E, D1>,
         shape: Shape<D2>,
@@ -34,6 +46,24 @@ impl<E: tch::kind::Element + Copy + Default> TchOps<E> {
         TchTensor::from_existing(tensor, storage)
     }
 
+    pub fn split<const D: usize>(
+        tensor: TchTensor<E, D>,
+        sizes: Vec<usize>,
+        dim: usize,
+    ) -> Vec<TchTensor<E, D>> {
+        let sizes = sizes
+            .into_iter()
+            .map(|size| size as i64)
+            .collect::<Vec<_>>();
+
+        tensor
+            .tensor
+            .split_with_sizes(&sizes, dim as i64)
+            .into_iter()
+            .map(|chunk| TchTensor::from_existing(chunk, tensor.storage.clone()))
+            .collect()
+    }
+
     pub fn index_assign<const D1: usize, const D2: usize>(
         tensor: TchTensor<E, D1>,
         indexes: [Range<usize>; D2],
@@ -276,30 +306,51 @@ impl<E: tch::kind::Element + Copy + Default> TchOps<E> {
     }
 
     pub fn mean<const D: usize>(tensor: TchTensor<E, D>) -> TchTensor<E, 1> {
-        let tensor = tensor.tensor.mean(E::KIND);
-        TchTensor::new(tensor)
+        let tensor = tensor.tensor.mean(Self::accumulation_kind());
+        TchTensor::new(tensor.to_kind(E::KIND))
     }
 
     pub fn sum<const D: usize>(tensor: TchTensor<E, D>) -> TchTensor<E, 1> {
-        let tensor = tensor.tensor.sum(E::KIND);
-        TchTensor::new(tensor)
+        let tensor = tensor.tensor.sum(Self::accumulation_kind());
+        TchTensor::new(tensor.to_kind(E::KIND))
+    }
+
+    pub fn cumsum<const D: usize>(tensor: TchTensor<E, D>, dim: usize) -> TchTensor<E, D> {
+        let tensor = tensor.tensor.cumsum(dim as i64, Self::accumulation_kind());
+        TchTensor::new(tensor.to_kind(E::KIND))
+    }
+
+    pub fn cumprod<const D: usize>(tensor: TchTensor<E, D>, dim: usize) -> TchTensor<E, D> {
+        let tensor = tensor.tensor.cumprod(dim as i64, Self::accumulation_kind());
+        TchTensor::new(tensor.to_kind(E::KIND))
     }
 
     pub fn mean_dim<const D: usize>(tensor: TchTensor<E, D>, dim: usize) -> TchTensor<E, D> {
-        TchTensor::from_existing(
-            tensor
-                .tensor
-                .mean_dim(Some([dim as i64].as_slice()), true, E::KIND),
-            tensor.storage,
-        )
+        let output = tensor.tensor.mean_dim(
+            Some([dim as i64].as_slice()),
+            true,
+            Self::accumulation_kind(),
+        );
+
+        TchTensor::from_existing(output.to_kind(E::KIND), tensor.storage)
     }
 
     pub fn sum_dim<const D: usize>(tensor: TchTensor<E, D>, dim: usize) -> TchTensor<E, D> {
-        TchTensor::from_existing(
-            tensor
-                .tensor
-                .sum_dim_intlist(Some([dim as i64].as_slice()), true, E::KIND),
-            tensor.storage,
-        )
+        let output = tensor.tensor.sum_dim_intlist(
+            Some([dim as i64].as_slice()),
+            true,
+            Self::accumulation_kind(),
+        );
+
+        TchTensor::from_existing(output.to_kind(E::KIND), tensor.storage)
+    }
+
+    /// The kind used to accumulate the values of the reductions, half precision elements are
+    /// accumulated in single precision to avoid losing precision on large tensors.
+    fn accumulation_kind() -> tch::Kind {
+        match E::KIND {
+            tch::Kind::Half | tch::Kind::BFloat16 => tch::Kind::Float,
+            kind => kind,
+        }
     }
 }
@@ -32,7 +32,7 @@ impl<E: TchElement> BoolTensorOps<TchBackend<E>> for TchBackend<E> {
         tensor: TchTensor<bool, D>,
         device: &TchDevice,
     ) -> TchTensor<bool, D> {
-        TchTensor::new(tensor.tensor.to((*device).into()))
+        TchOps::to_device(tensor, (*device).into())
     }
 
     fn bool_reshape<const D1: usize, const D2: usize>(
@@ -99,7 +99,33 @@ impl<E: TchElement> BoolTensorOps<TchBackend<E>> for TchBackend<E> {
     }
 
     fn bool_into_int<const D: usize>(tensor: TchTensor<bool, D>) -> TchTensor<i64, D> {
-        let tensor = tensor.tensor.to_kind(E::KIND);
+        let tensor = tensor.tensor.to_kind(tch::Kind::Int64);
         TchTensor::new(tensor)
     }
+
+    fn bool_not<const D: usize>(tensor: TchTensor<bool, D>) -> TchTensor<bool, D> {
+        TchTensor::new(tensor.tensor.logical_not())
+    }
+
+    fn bool_and<const D: usize>(
+        lhs: TchTensor<bool, D>,
+        rhs: TchTensor<bool, D>,
+    ) -> TchTensor<bool, D> {
+        TchTensor::new(lhs.tensor.logical_and(&rhs.tensor))
+    }
+
+    fn bool_or<const D: usize>(
+        lhs: TchTensor<bool, D>,
+        rhs: TchTensor<bool, D>,
+    ) -> TchTensor<bool, D> {
+        TchTensor::new(lhs.tensor.logical_or(&rhs.tensor))
+    }
+
+    fn bool_any<const D: usize>(tensor: TchTensor<bool, D>) -> TchTensor<bool, 1> {
+        TchTensor::new(tensor.tensor.any().reshape(&[1]))
+    }
+
+    fn bool_all<const D: usize>(tensor: TchTensor<bool, D>) -> TchTensor<bool, 1> {
+        TchTensor::new(tensor.tensor.all().reshape(&[1]))
+    }
 }
@@ -29,7 +29,7 @@ impl<E: TchElement> IntTensorOps<TchBackend<E>> for TchBackend<E> {
         tensor: TchTensor<i64, D>,
         device: &TchDevice,
     ) -> TchTensor<i64, D> {
-        TchTensor::new(tensor.tensor.to((*device).into()))
+        TchOps::to_device(tensor, (*device).into())
     }
 
     fn int_reshape<const D1: usize, const D2: usize>(
@@ -1,5 +1,5 @@
 use crate::{element::TchElement, TchBackend, TchTensor};
-use burn_tensor::ops::{MaxPool2dBackward, MaxPool2dWithIndexes, ModuleOps};
+use burn_tensor::ops::{MaxPool2dBackward, MaxPool2dWithIndexes, MemoryFormat, ModuleOps};
 
 impl<E: TchElement> ModuleOps<TchBackend<E>> for TchBackend<E> {
     fn embedding(weights: TchTensor<E, 2>, indexes: TchTensor<i64, 2>) -> TchTensor<E, 3> {
@@ -66,6 +66,20 @@ impl<E: TchElement> ModuleOps<TchBackend<E>> for TchBackend<E> {
         TchTensor::new(tensor)
     }
 
+    fn memory_format(x: TchTensor<E, 4>, format: MemoryFormat) -> TchTensor<E, 4> {
+        let tensor = match format {
+            MemoryFormat::Contiguous => x.tensor.contiguous(),
+            // Same strides as `memory_format=torch.channels_last`.
+            MemoryFormat::ChannelsLast => x
+                .tensor
+                .permute(&[0, 2, 3, 1])
+                .contiguous()
+                .permute(&[0, 3, 1, 2]),
+        };
+
+        TchTensor::new(tensor)
+    }
+
     fn max_pool2d(
         x: TchTensor<E, 4>,
         kernel_size: [usize; 2],
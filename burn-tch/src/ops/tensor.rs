@@ -48,6 +48,16 @@ impl<E: TchElement> TensorOps<TchBackend<E>> for TchBackend<E> {
         TchTensor::new(tensor)
     }
 
+    fn one_hot(indices: TchTensor<i64, 1>, num_classes: usize) -> TchTensor<E, 2> {
+        let [batch_size] = indices.shape().dims;
+        let device = indices.tensor.device();
+        let indices = indices.tensor.reshape(&[batch_size as i64, 1]);
+        let tensor =
+            tch::Tensor::zeros(&[batch_size as i64, num_classes as i64], (E::KIND, device));
+
+        TchTensor::new(tensor.scatter_value(1, &indices, 1.0))
+    }
+
     fn zeros<const D: usize>(shape: Shape<D>, device: &TchDevice) -> TchTensor<E, D> {
         let shape = TchShape::from(shape);
         let device: tch::Device = (*device).into();
@@ -85,7 +95,7 @@ impl<E: TchElement> TensorOps<TchBackend<E>> for TchBackend<E> {
     }
 
     fn to_device<const D: usize>(tensor: TchTensor<E, D>, device: &TchDevice) -> TchTensor<E, D> {
-        TchTensor::new(tensor.tensor.to((*device).into()))
+        TchOps::to_device(tensor, (*device).into())
     }
 
     fn empty<const D: usize>(
@@ -367,6 +377,31 @@ impl<E: TchElement> TensorOps<TchBackend<E>> for TchBackend<E> {
         TchOps::cat(tensors, dim)
     }
 
+    fn split<const D: usize>(
+        tensor: TchTensor<E, D>,
+        sizes: Vec<usize>,
+        dim: usize,
+    ) -> Vec<TchTensor<E, D>> {
+        TchOps::split(tensor, sizes, dim)
+    }
+
+    fn cumsum<const D: usize>(tensor: TchTensor<E, D>, dim: usize) -> TchTensor<E, D> {
+        TchOps::cumsum(tensor, dim)
+    }
+
+    fn cumprod<const D: usize>(tensor: TchTensor<E, D>, dim: usize) -> TchTensor<E, D> {
+        TchOps::cumprod(tensor, dim)
+    }
+
+    fn clamp<const D: usize>(tensor: TchTensor<E, D>, min: E, max: E) -> TchTensor<E, D> {
+        let (min, max): (f64, f64) = (min.elem(), max.elem());
+
+        tensor.unary_ops(
+            |mut tensor| tensor.clamp_(min, max),
+            |tensor| tensor.clamp(min, max),
+        )
+    }
+
     fn relu<const D: usize>(tensor: TchTensor<E, D>) -> TchTensor<E, D> {
         tensor.unary_ops(|mut tensor| tensor.relu_(), |tensor| tensor.relu())
     }
@@ -60,6 +60,23 @@ impl<E: tch::kind::Element, const D: usize> TchTensor<E, D> {
     pub(crate) fn shape(&self) -> Shape<D> {
         Shape::from(self.tensor.size())
     }
+
+    /// Copy the tensor into page-locked (pinned) CPU memory.
+    ///
+    /// Moving a pinned tensor to a CUDA device is done asynchronously, which speeds up host to
+    /// device transfers. Pinning memory requires CUDA to be available.
+    pub fn pin_memory(self) -> Self {
+        Self::new(self.tensor.pin_memory(tch::Device::Cuda(0)))
+    }
+
+    /// If the tensor is stored in [pinned](TchTensor::pin_memory) CPU memory.
+    pub fn is_pinned(&self) -> bool {
+        if !tch::Cuda::is_available() || self.tensor.device() != tch::Device::Cpu {
+            return false;
+        }
+
+        self.tensor.is_pinned(tch::Device::Cuda(0))
+    }
 }
 
 // This is safe since we don't use autodiff from LibTorch.
@@ -172,6 +189,11 @@ impl<E: tch::kind::Element + Default, const D: usize> TchTensor<E, D> {
 
         Self::new(tensor)
     }
+
+    /// Create a CPU tensor in [pinned](TchTensor::pin_memory) memory from data.
+    pub fn from_data_pinned(data: Data<E, D>) -> Self {
+        Self::from_data(data, tch::Device::Cpu).pin_memory()
+    }
 }
 
 #[cfg(test)]
@@ -201,6 +223,7 @@ impl<E: tch::kind::Element + Default + Copy + std::fmt::Debug, const D: usize> T
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::ops::TchOps;
     use burn_tensor::{Distribution, Tensor};
     use rand::prelude::StdRng;
     use rand::SeedableRng;
@@ -233,6 +256,73 @@ mod tests {
         assert_eq!(data_expected, data_actual);
     }
 
+    #[test]
+    fn should_transfer_pinned_tensor_to_device() {
+        if !tch::Cuda::is_available() {
+            return;
+        }
+
+        let data_expected = Data::<f32, 2>::random(
+            Shape::new([2, 3]),
+            Distribution::Standard,
+            &mut StdRng::from_entropy(),
+        );
+        let tensor = TchTensor::from_data_pinned(data_expected.clone());
+        assert!(tensor.is_pinned());
+
+        let tensor = TchOps::to_device(tensor, tch::Device::Cuda(0));
+        assert_eq!(tensor.tensor.device(), tch::Device::Cuda(0));
+        assert_eq!(data_expected, tensor.into_data());
+    }
+
+    #[test]
+    fn should_not_report_unpinned_tensor_as_pinned() {
+        let tensor = TchTensor::<f32, 1>::from_data(Data::from([1.0, 2.0]), tch::Device::Cpu);
+
+        assert!(!tensor.is_pinned());
+    }
+
+    #[test]
+    fn should_cast_to_half_precision_and_back_keeping_gradients() {
+        type ADBackend = burn_autodiff::ADBackendDecorator<TchBackend<f32>>;
+        type ADBackendHalf = burn_autodiff::ADBackendDecorator<TchBackend<half::f16>>;
+
+        let tensor =
+            Tensor::<ADBackend, 2>::from_floats([[1.0, 2.5], [-0.3333, 0.1]]).require_grad();
+
+        let tensor_half: Tensor<ADBackendHalf, 2> = tensor.clone().cast();
+        assert_eq!(
+            tensor_half.clone().inner().into_primitive().tensor.kind(),
+            tch::Kind::Half
+        );
+        let tensor_full: Tensor<ADBackend, 2> = tensor_half.mul_scalar(2.0).cast();
+        let grads = tensor_full.clone().sum().backward();
+
+        tensor_full
+            .div_scalar(2.0)
+            .into_data()
+            .assert_approx_eq(&tensor.to_data(), 3);
+        assert_eq!(
+            tensor.grad(&grads).unwrap().into_data(),
+            Data::from([[2.0, 2.0], [2.0, 2.0]])
+        );
+    }
+
+    #[test]
+    fn should_accumulate_half_precision_sum_in_single_precision() {
+        let num_elements = 4096;
+        let tensor = Tensor::<TchBackend<half::f16>, 1>::ones([num_elements]);
+
+        // Past 2048, adding one to a half precision value is rounded away.
+        let naive = (0..num_elements).fold(half::f16::ZERO, |acc, _| acc + half::f16::ONE);
+        assert_eq!(naive.to_f32(), 2048.0);
+
+        let sum = tensor.clone().sum().into_data().value[0].to_f32();
+        let mean = tensor.mean().into_data().value[0].to_f32();
+        assert_eq!(sum, num_elements as f32);
+        assert_eq!(mean, 1.0);
+    }
+
     #[test]
     fn should_not_update_inplace_after_reshape() {
         let tensor_1 = Tensor::<TchBackend<f32>, 1>::from_floats([4.0, 4.0]);
@@ -1,8 +1,11 @@
 use crate::backend::Backend;
-use crate::Tensor;
+use crate::{Bool, ElementConversion, Int, Tensor};
 use crate::{ElementPrecision, Precision};
 use core::f64::consts::SQRT_2;
 
+/// The value of `sqrt(2 / π)`.
+const SQRT_2_DIV_PI: f64 = 0.797_884_560_802_865_4;
+
 /// Applies the rectified linear unit function.
 pub fn relu<const D: usize, B: Backend>(tensor: Tensor<B, D>) -> Tensor<B, D> {
     tensor.relu()
@@ -15,11 +18,69 @@ pub fn gelu<const D: usize, B: Backend>(tensor: Tensor<B, D>) -> Tensor<B, D> {
     tensor.mul(x) / 2
 }
 
+/// Applies the tanh approximation of the Gaussian Error Linear Units function:
+///
+/// `y = 0.5 * x * (1 + tanh(sqrt(2 / π) * (x + 0.044715 * x^3)))`
+pub fn gelu_tanh<const D: usize, B: Backend>(tensor: Tensor<B, D>) -> Tensor<B, D> {
+    let x = tensor
+        .clone()
+        .powf(3.0)
+        .mul_scalar(0.044715_f32)
+        .add(tensor.clone())
+        .mul_scalar(SQRT_2_DIV_PI)
+        .tanh()
+        .add_scalar(1.0_f32);
+
+    tensor.mul(x) / 2
+}
+
 /// Applies the softmax function.
 pub fn softmax<const D: usize, B: Backend>(tensor: Tensor<B, D>, dim: usize) -> Tensor<B, D> {
     log_softmax(tensor, dim).exp()
 }
 
+/// Applies the softmax function ignoring the elements where the mask is `true`, which get a
+/// weight of zero.
+///
+/// Rows where every element is masked have a weight of zero everywhere instead of NaN values, and
+/// don't produce NaN gradients either. The mask is broadcast to the shape of the tensor.
+pub fn masked_softmax<const D: usize, B: Backend>(
+    tensor: Tensor<B, D>,
+    mask: Tensor<B, D, Bool>,
+    dim: usize,
+) -> Tensor<B, D> {
+    // The masked elements are replaced by the minimum of their row, so the maximum of a row is
+    // the maximum of its unmasked elements, or the minimum when every element is masked.
+    let min = select_dim(tensor.clone(), dim, Tensor::argmin).detach();
+    let mask_float = tensor.zeros_like().mask_fill(mask.clone(), 1.0);
+    let tensor = tensor.mask_fill(mask.clone(), 0.0) + mask_float * min;
+
+    // The maximum is subtracted for numerical stability, so no exponential is infinite.
+    let max = select_dim(tensor.clone(), dim, Tensor::argmax).detach();
+    let exp = tensor.sub(max).exp().mask_fill(mask, 0.0);
+
+    // Fully masked rows have a sum of zero, they are set to zero by dividing by one.
+    let sum = exp.clone().sum_dim(dim);
+    let sum = sum
+        .clone()
+        .mask_fill(sum.equal_elem(B::FloatElem::from_elem(0.0)), 1.0);
+
+    exp.div(sum)
+}
+
+/// Select the element of each row of the tensor along the given dimension with the given arg
+/// function, such as [argmax](Tensor::argmax), keeping the dimension with a size of one.
+fn select_dim<const D: usize, B: Backend>(
+    tensor: Tensor<B, D>,
+    dim: usize,
+    arg: fn(Tensor<B, D>, usize) -> Tensor<B, D, Int>,
+) -> Tensor<B, D> {
+    let tensor = tensor.swap_dims(dim, D - 1);
+    let indexes = arg(tensor.clone(), D - 1);
+
+    tensor.index_select(indexes).swap_dims(dim, D - 1)
+}
+
 /// Applies the log softmax function.
 pub fn log_softmax<const D: usize, B: Backend>(tensor: Tensor<B, D>, dim: usize) -> Tensor<B, D> {
     let tensor_tmp = match B::FloatElem::precision() {
@@ -18,4 +18,37 @@ where
     pub fn into_int(self) -> Tensor<B, D, Int> {
         Tensor::new(B::bool_into_int(self.primitive))
     }
+
+    /// Applies the element wise logical not operation.
+    pub fn bool_not(self) -> Self {
+        Self::new(B::bool_not(self.primitive))
+    }
+
+    /// Applies the element wise logical and operation with another bool tensor.
+    pub fn bool_and(self, other: Self) -> Self {
+        Self::new(B::bool_and(self.primitive, other.primitive))
+    }
+
+    /// Applies the element wise logical or operation with another bool tensor.
+    pub fn bool_or(self, other: Self) -> Self {
+        Self::new(B::bool_or(self.primitive, other.primitive))
+    }
+
+    /// Test if any element of the tensor is true.
+    ///
+    /// # Returns
+    ///
+    /// A bool tensor with a single element.
+    pub fn any(self) -> Tensor<B, 1, Bool> {
+        Tensor::new(B::bool_any(self.primitive))
+    }
+
+    /// Test if every element of the tensor is true.
+    ///
+    /// # Returns
+    ///
+    /// A bool tensor with a single element.
+    pub fn all(self) -> Tensor<B, 1, Bool> {
+        Tensor::new(B::bool_all(self.primitive))
+    }
 }
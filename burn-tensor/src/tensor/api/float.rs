@@ -100,6 +100,35 @@ where
         Self::new(B::powf(self.primitive, value))
     }
 
+    /// Cumulative sum of the elements along the given dimension.
+    ///
+    /// `y_i = x_1 + x_2 + ... + x_i`
+    pub fn cumsum(self, dim: usize) -> Self {
+        Self::new(B::cumsum(self.primitive, dim))
+    }
+
+    /// Cumulative product of the elements along the given dimension.
+    ///
+    /// `y_i = x_1 * x_2 * ... * x_i`
+    pub fn cumprod(self, dim: usize) -> Self {
+        Self::new(B::cumprod(self.primitive, dim))
+    }
+
+    /// Clamp each element of the tensor in the range `[min, max]`.
+    ///
+    /// # Panics
+    ///
+    /// If `min` is greater than `max`.
+    pub fn clamp<E: ElementConversion>(self, min: E, max: E) -> Self {
+        let (min, max): (B::FloatElem, B::FloatElem) = (min.elem(), max.elem());
+
+        if min.elem::<f64>() > max.elem::<f64>() {
+            panic!("The clamp minimum {min:?} should not be greater than the maximum {max:?}");
+        }
+
+        Self::new(B::clamp(self.primitive, min, max))
+    }
+
     /// Applies element wise root square operation.
     pub fn sqrt(self) -> Self {
         Self::new(B::sqrt(self.primitive))
@@ -199,6 +228,52 @@ where
         Self::new(B::swap_dims(self.primitive, dim1, dim2))
     }
 
+    /// Split the tensor along the given dimension into chunks of the given sizes.
+    ///
+    /// # Panics
+    ///
+    /// If the sizes don't add up to the size of the dimension.
+    pub fn split(self, sizes: &[usize], dim: usize) -> Vec<Self> {
+        let size_dim = self.dims()[dim];
+        let size_total: usize = sizes.iter().sum();
+
+        if size_total != size_dim {
+            panic!(
+                "The split sizes should add up to the size {} of dimension {}, got {}",
+                size_dim, dim, size_total
+            );
+        }
+
+        B::split(self.primitive, sizes.to_vec(), dim)
+            .into_iter()
+            .map(Self::new)
+            .collect()
+    }
+
+    /// Split the tensor along the given dimension into the given number of chunks of the same
+    /// size.
+    ///
+    /// When the size of the dimension isn't divisible by the number of chunks, the last chunk is
+    /// smaller, and fewer chunks may be returned, like `torch.chunk`.
+    ///
+    /// # Panics
+    ///
+    /// If the number of chunks is zero.
+    pub fn chunk(self, chunks: usize, dim: usize) -> Vec<Self> {
+        let size_dim = self.dims()[dim];
+        let size_chunk = size_dim.div_ceil(chunks);
+        let mut sizes = Vec::new();
+        let mut remaining = size_dim;
+
+        while remaining > 0 {
+            let size = usize::min(size_chunk, remaining);
+            sizes.push(size);
+            remaining -= size;
+        }
+
+        self.split(&sizes, dim)
+    }
+
     /// Applies the matrix multiplication operation.
     ///
     /// `C = AB`
@@ -241,7 +316,36 @@ where
         Self::new(tensor)
     }
 
+    /// If the tensor doesn't contain NaN or infinite values.
+    ///
+    /// The tensor is reduced to a single value on the backend before being read.
+    pub fn is_finite(&self) -> bool {
+        // Multiplying by zero keeps the non-finite values while avoiding overflows in the sum.
+        let value: f64 = self.clone().mul_scalar(0.0).sum().single_value().elem();
+
+        value.is_finite()
+    }
+
+    /// Panics if the tensor contains NaN or infinite values, the name is used to identify the
+    /// tensor in the panic message.
+    ///
+    /// The check is only done in debug builds, it does nothing when `debug_assertions` are
+    /// disabled. See [is_finite](Self::is_finite) to check the values in every build.
+    pub fn assert_finite(&self, name: &str) {
+        #[cfg(debug_assertions)]
+        {
+            if !self.is_finite() {
+                panic!("Tensor `{name}` contains NaN or infinite values");
+            }
+        }
+
+        #[cfg(not(debug_assertions))]
+        let _ = name;
+    }
+
     /// Fill each element with the given value based on the given mask.
+    ///
+    /// The mask is broadcast to the shape of the tensor, so its dimensions can be of size 1.
     pub fn mask_fill<E: ElementConversion>(self, mask: Tensor<B, D, Bool>, value: E) -> Self {
         Self::new(B::mask_fill(self.primitive, mask.primitive, value.elem()))
     }
@@ -256,6 +360,19 @@ where
         Self::new(B::from_full_precision(tensor.primitive))
     }
 
+    /// Cast the tensor to another backend sharing the same full precision backend, which is how
+    /// the float precision of a tensor is changed, e.g. from `f32` to `f16`.
+    ///
+    /// The cast goes through the full precision backend and is tracked by autodiff, so a module
+    /// can compute some operations in half precision while keeping its parameters in full
+    /// precision.
+    pub fn cast<BOut>(self) -> Tensor<BOut, D>
+    where
+        BOut: Backend<FullPrecisionBackend = B::FullPrecisionBackend>,
+    {
+        Tensor::from_full_precision(self.to_full_precision())
+    }
+
     /// Applies the argmax function along the given dimension and returns an integer tensor.
     ///
     /// # Example
@@ -0,0 +1,49 @@
+use crate::{
+    backend::{ADBackend, Backend},
+    Int, Tensor,
+};
+
+impl<B: Backend> Tensor<B, 1, Int> {
+    /// Create a one hot matrix from the given class indices, where each row has a one at the
+    /// column of its index and zeros elsewhere.
+    ///
+    /// # Panics
+    ///
+    /// In debug mode, if an index is outside of the range `0..num_classes`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use burn_tensor::backend::Backend;
+    /// use burn_tensor::{Data, Int, Tensor};
+    ///
+    /// fn example<B: Backend>() {
+    ///     let indices = Tensor::<B, 1, Int>::from_data(Data::from([0, 2]).convert());
+    ///     let one_hot = indices.one_hot(3);
+    ///     println!("{}", one_hot.to_data());
+    ///     // [[1.0, 0.0, 0.0], [0.0, 0.0, 1.0]]
+    /// }
+    /// ```
+    pub fn one_hot(self, num_classes: usize) -> Tensor<B, 2> {
+        #[cfg(debug_assertions)]
+        for index in self.to_data().value {
+            let index: i64 = crate::ElementConversion::elem(index);
+            if index < 0 || index as usize >= num_classes {
+                panic!(
+                    "One hot index {} is out of range for {} classes",
+                    index, num_classes
+                );
+            }
+        }
+
+        Tensor::new(B::one_hot(self.primitive, num_classes))
+    }
+}
+
+impl<const D: usize, B: ADBackend> Tensor<B, D, Int> {
+    /// Get the integer tensor on the inner backend, integer tensors are never tracked by the
+    /// autodiff graph so this doesn't copy any data.
+    pub fn inner(self) -> Tensor<B::InnerBackend, D, Int> {
+        Tensor::new(B::int_inner(self.primitive))
+    }
+}
@@ -1,6 +1,7 @@
 mod base;
 mod bool;
 mod float;
+mod int;
 mod kind;
 mod numeric;
 
@@ -93,6 +93,21 @@ pub trait Backend:
 
     /// Seed the backend.
     fn seed(seed: u64);
+
+    /// Number of bytes currently allocated by the backend on the given device.
+    ///
+    /// Returns `None` when the backend doesn't track its memory usage.
+    fn memory_allocated(_device: &Self::Device) -> Option<usize> {
+        None
+    }
+
+    /// Number of bytes used on the whole device, including the memory cached by the backend and
+    /// the memory of other processes.
+    ///
+    /// Returns `None` when the backend can't query the device.
+    fn memory_used(_device: &Self::Device) -> Option<usize> {
+        None
+    }
 }
 
 pub(crate) type ADBackendTensorPrimitive<const D: usize, B> =
@@ -118,4 +133,16 @@ pub trait ADBackend: Backend {
     fn from_inner<const D: usize>(
         tensor: <Self::InnerBackend as Backend>::TensorPrimitive<D>,
     ) -> Self::TensorPrimitive<D>;
+    /// Get the integer tensor on the inner backend.
+    ///
+    /// The data is copied by default, backends sharing the integer tensors with their inner
+    /// backend should return them directly.
+    fn int_inner<const D: usize>(
+        tensor: Self::IntTensorPrimitive<D>,
+    ) -> <Self::InnerBackend as Backend>::IntTensorPrimitive<D> {
+        let device = Self::int_device(&tensor);
+        let data = Self::int_into_data(tensor).convert();
+
+        Self::InnerBackend::int_from_data(data, &device)
+    }
 }
@@ -1,4 +1,5 @@
-use crate::{backend::Backend, Int, Tensor};
+use crate::{backend::Backend, ops::MemoryFormat, Int, Tensor};
+use alloc::sync::Arc;
 
 /// Applies the [embedding module](crate::ops::ModuleOps::embedding).
 pub fn embedding<B>(weights: Tensor<B, 2>, indexes: Tensor<B, 2, Int>) -> Tensor<B, 3>
@@ -8,6 +9,25 @@ where
     Tensor::new(B::embedding(weights.primitive, indexes.primitive))
 }
 
+/// Applies the function with [gradient checkpointing](crate::ops::ModuleOps::checkpoint).
+///
+/// The intermediate activations of the function aren't kept for the backward pass, they are
+/// recomputed instead, trading compute for memory. Backends recomputing the function reseed their
+/// random number generator so that the same random values, such as dropout masks, are sampled.
+pub fn checkpoint<B, F, const D_IN: usize, const D_OUT: usize>(
+    x: Tensor<B, D_IN>,
+    func: F,
+) -> Tensor<B, D_OUT>
+where
+    B: Backend,
+    F: Fn(Tensor<B, D_IN>) -> Tensor<B, D_OUT> + Send + Sync + 'static,
+{
+    Tensor::new(B::checkpoint(
+        x.primitive,
+        Arc::new(move |x| func(Tensor::new(x)).primitive),
+    ))
+}
+
 /// Applies a [1D convolution](crate::ops::ModuleOps::conv2d).
 pub fn conv1d<B>(
     x: Tensor<B, 3>,
@@ -48,6 +68,15 @@ where
     ))
 }
 
+/// Changes the [memory format](crate::ops::ModuleOps::memory_format) of the tensor, the values are
+/// unchanged.
+pub fn memory_format<B>(x: Tensor<B, 4>, format: MemoryFormat) -> Tensor<B, 4>
+where
+    B: Backend,
+{
+    Tensor::new(B::memory_format(x.primitive, format))
+}
+
 /// Applies a [2D max pooling](crate::ops::ModuleOps::max_pool2d).
 pub fn max_pool2d<B>(
     x: Tensor<B, 4>,
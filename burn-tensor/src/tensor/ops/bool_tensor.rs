@@ -77,4 +77,15 @@ pub trait BoolTensorOps<B: Backend> {
         lhs: B::BoolTensorPrimitive<D>,
         rhs: bool,
     ) -> B::BoolTensorPrimitive<D>;
+    fn bool_not<const D: usize>(tensor: B::BoolTensorPrimitive<D>) -> B::BoolTensorPrimitive<D>;
+    fn bool_and<const D: usize>(
+        lhs: B::BoolTensorPrimitive<D>,
+        rhs: B::BoolTensorPrimitive<D>,
+    ) -> B::BoolTensorPrimitive<D>;
+    fn bool_or<const D: usize>(
+        lhs: B::BoolTensorPrimitive<D>,
+        rhs: B::BoolTensorPrimitive<D>,
+    ) -> B::BoolTensorPrimitive<D>;
+    fn bool_any<const D: usize>(tensor: B::BoolTensorPrimitive<D>) -> B::BoolTensorPrimitive<1>;
+    fn bool_all<const D: usize>(tensor: B::BoolTensorPrimitive<D>) -> B::BoolTensorPrimitive<1>;
 }
@@ -1,5 +1,6 @@
 use super::conv;
 use crate::backend::Backend;
+use alloc::sync::Arc;
 
 /// Gradient computed during the backward pass for each tensor used by [conv2d](ModuleOps::conv2d).
 #[derive(new)]
@@ -30,6 +31,23 @@ pub struct Conv1dBackward<B: Backend> {
     pub bias_grad: Option<B::TensorPrimitive<1>>,
 }
 
+/// The memory layout of a 4D tensor of shape `[batch_size, channels, height, width]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MemoryFormat {
+    /// The default row-major layout, where the width is the innermost dimension.
+    Contiguous,
+    /// The channels are the innermost dimension, which speeds up convolutions on some backends.
+    ChannelsLast,
+}
+
+/// Function applied by [checkpoint](ModuleOps::checkpoint), which may be called again during the
+/// backward pass.
+pub type CheckpointFn<B, const D_IN: usize, const D_OUT: usize> = Arc<
+    dyn Fn(<B as Backend>::TensorPrimitive<D_IN>) -> <B as Backend>::TensorPrimitive<D_OUT>
+        + Send
+        + Sync,
+>;
+
 pub trait ModuleOps<B: Backend> {
     fn embedding(
         weights: B::TensorPrimitive<2>,
@@ -121,4 +139,20 @@ pub trait ModuleOps<B: Backend> {
         output_grad: B::TensorPrimitive<4>,
         indexes: B::IntTensorPrimitive<4>,
     ) -> MaxPool2dBackward<B>;
+    /// Changes the memory layout of the tensor without changing its shape nor its values.
+    ///
+    /// Backends without memory formats return the tensor as is.
+    fn memory_format(x: B::TensorPrimitive<4>, _format: MemoryFormat) -> B::TensorPrimitive<4> {
+        x
+    }
+    /// Applies the function to the input while allowing the backend to free the intermediate
+    /// activations, which are recomputed during the backward pass.
+    ///
+    /// Backends that don't track gradients simply apply the function.
+    fn checkpoint<const D_IN: usize, const D_OUT: usize>(
+        x: B::TensorPrimitive<D_IN>,
+        func: CheckpointFn<B, D_IN, D_OUT>,
+    ) -> B::TensorPrimitive<D_OUT> {
+        func(x)
+    }
 }
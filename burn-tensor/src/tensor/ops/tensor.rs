@@ -1,3 +1,4 @@
+use alloc::vec;
 use alloc::vec::Vec;
 use core::ops::Range;
 
@@ -37,6 +38,22 @@ pub trait TensorOps<B: Backend> {
         let data = Data::new(value, shape);
         B::int_from_data(data, device)
     }
+    fn one_hot(indices: B::IntTensorPrimitive<1>, num_classes: usize) -> B::TensorPrimitive<2> {
+        let device = B::int_device(&indices);
+        let indices = B::int_into_data(indices);
+        let batch_size = indices.value.len();
+        let mut value = vec![B::FloatElem::from_elem(0); batch_size * num_classes];
+
+        for (row, index) in indices.value.into_iter().enumerate() {
+            let index: i64 = index.elem();
+            value[row * num_classes + index as usize] = B::FloatElem::from_elem(1);
+        }
+
+        Self::from_data(
+            Data::new(value, Shape::new([batch_size, num_classes])),
+            &device,
+        )
+    }
     fn empty<const D: usize>(shape: Shape<D>, device: &B::Device) -> B::TensorPrimitive<D>;
     fn repeat<const D: usize>(
         tensor: B::TensorPrimitive<D>,
@@ -229,5 +246,65 @@ pub trait TensorOps<B: Backend> {
         tensors: Vec<B::TensorPrimitive<D>>,
         dim: usize,
     ) -> B::TensorPrimitive<D>;
+    fn split<const D: usize>(
+        tensor: B::TensorPrimitive<D>,
+        sizes: Vec<usize>,
+        dim: usize,
+    ) -> Vec<B::TensorPrimitive<D>> {
+        let shape = B::shape(&tensor);
+        let mut start = 0;
+
+        sizes
+            .into_iter()
+            .map(|size| {
+                let mut indexes = shape.dims.map(|dim| 0..dim);
+                indexes[dim] = start..start + size;
+                start += size;
+
+                B::index(tensor.clone(), indexes)
+            })
+            .collect()
+    }
+    fn cumsum<const D: usize>(tensor: B::TensorPrimitive<D>, dim: usize) -> B::TensorPrimitive<D> {
+        cumulative::<B, D, _>(tensor, dim, B::add)
+    }
+    fn cumprod<const D: usize>(tensor: B::TensorPrimitive<D>, dim: usize) -> B::TensorPrimitive<D> {
+        cumulative::<B, D, _>(tensor, dim, B::mul)
+    }
+    fn clamp<const D: usize>(
+        tensor: B::TensorPrimitive<D>,
+        min: B::FloatElem,
+        max: B::FloatElem,
+    ) -> B::TensorPrimitive<D> {
+        let mask = B::lower_elem(tensor.clone(), min);
+        let tensor = B::mask_fill(tensor, mask, min);
+        let mask = B::greater_elem(tensor.clone(), max);
+
+        B::mask_fill(tensor, mask, max)
+    }
     fn relu<const D: usize>(tensor: B::TensorPrimitive<D>) -> B::TensorPrimitive<D>;
 }
+
+/// Cumulate the slices of the tensor along the given dimension with the given operation, where
+/// each slice of the output is the operation applied to the previous output slice and the
+/// corresponding input slice.
+fn cumulative<B, const D: usize, F>(
+    tensor: B::TensorPrimitive<D>,
+    dim: usize,
+    func: F,
+) -> B::TensorPrimitive<D>
+where
+    B: Backend,
+    F: Fn(B::TensorPrimitive<D>, B::TensorPrimitive<D>) -> B::TensorPrimitive<D>,
+{
+    let shape = B::shape(&tensor);
+    let mut slices = B::split(tensor, vec![1; shape.dims[dim]], dim);
+
+    for index in 1..slices.len() {
+        let previous = slices[index - 1].clone();
+        let current = slices[index].clone();
+        slices[index] = func(previous, current);
+    }
+
+    B::cat(slices, dim)
+}
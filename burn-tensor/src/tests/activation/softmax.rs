@@ -1,7 +1,7 @@
 #[burn_tensor_testgen::testgen(softmax)]
 mod tests {
     use super::*;
-    use burn_tensor::{activation, Data, Tensor};
+    use burn_tensor::{activation, Bool, Data, Tensor};
 
     #[test]
     fn test_softmax_d2() {
@@ -13,4 +13,31 @@ mod tests {
         let data_expected = Data::from([[2.47e-03, 9.975e-01], [1.0, 1.1254e-07]]);
         data_actual.assert_approx_eq(&data_expected, 4);
     }
+
+    #[test]
+    fn test_masked_softmax_d2() {
+        let data = Data::from([[1.0, 7.0, 3.0], [13.0, -3.0, 2.0]]);
+        let mask = Data::from([[false, true, false], [true, true, true]]);
+        let tensor = Tensor::<TestBackend, 2>::from_data(data);
+        let mask = Tensor::<TestBackend, 2, Bool>::from_bool(mask);
+
+        let data_actual = activation::masked_softmax(tensor, mask, 1).to_data();
+
+        let data_expected = Data::from([[0.1192, 0.0, 0.8808], [0.0, 0.0, 0.0]]);
+        data_actual.assert_approx_eq(&data_expected, 4);
+        assert!(data_actual.value.iter().all(|value| !value.is_nan()));
+    }
+
+    #[test]
+    fn test_masked_softmax_should_ignore_the_masked_values() {
+        let data = Data::from([[1.0, 1.0e30, 3.0], [-1.0e4, -1.0e30, -1.0e4]]);
+        let mask = Data::from([[false, true, false], [false, true, false]]);
+        let tensor = Tensor::<TestBackend, 2>::from_data(data);
+        let mask = Tensor::<TestBackend, 2, Bool>::from_bool(mask);
+
+        let data_actual = activation::masked_softmax(tensor, mask, 1).to_data();
+
+        let data_expected = Data::from([[0.1192, 0.0, 0.8808], [0.5, 0.0, 0.5]]);
+        data_actual.assert_approx_eq(&data_expected, 4);
+    }
 }
@@ -22,10 +22,14 @@ macro_rules! testgen_all {
         burn_tensor::testgen_add!();
         burn_tensor::testgen_aggregation!();
         burn_tensor::testgen_arg!();
+        burn_tensor::testgen_bool!();
+        burn_tensor::testgen_clamp!();
         burn_tensor::testgen_cos!();
+        burn_tensor::testgen_cumulative!();
         burn_tensor::testgen_div!();
         burn_tensor::testgen_erf!();
         burn_tensor::testgen_exp!();
+        burn_tensor::testgen_finite!();
         burn_tensor::testgen_log!();
         burn_tensor::testgen_log1p!();
         burn_tensor::testgen_index!();
@@ -36,10 +40,12 @@ macro_rules! testgen_all {
         burn_tensor::testgen_matmul!();
         burn_tensor::testgen_mul!();
         burn_tensor::testgen_neg!();
+        burn_tensor::testgen_one_hot!();
         burn_tensor::testgen_powf!();
         burn_tensor::testgen_repeat!();
         burn_tensor::testgen_reshape!();
         burn_tensor::testgen_sin!();
+        burn_tensor::testgen_split!();
         burn_tensor::testgen_tanh!();
         burn_tensor::testgen_sub!();
         burn_tensor::testgen_transpose!();
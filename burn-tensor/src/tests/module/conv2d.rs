@@ -1,9 +1,43 @@
 #[burn_tensor_testgen::testgen(module_conv2d)]
 mod tests {
     use super::*;
-    use burn_tensor::module::conv2d;
+    use burn_tensor::module::{conv2d, memory_format};
+    use burn_tensor::ops::MemoryFormat;
     use burn_tensor::{Data, Tensor};
 
+    #[test]
+    fn test_conv2d_channels_last_should_match_contiguous() {
+        let x = TestTensor::from_floats([[
+            [[1., 2., 3.], [4., 5., 6.], [7., 8., 9.]],
+            [[-1., 0., 1.], [2., -2., 0.], [3., 1., -3.]],
+        ]]);
+        let weight = TestTensor::from_floats([
+            [[[1., 0.], [0., -1.]], [[2., 1.], [1., 2.]]],
+            [[[0., 1.], [1., 0.]], [[-1., 0.], [0., 1.]]],
+        ]);
+        let bias = TestTensor::from_floats([0.5, -0.5]);
+
+        let output = conv2d(
+            x.clone(),
+            weight.clone(),
+            Some(bias.clone()),
+            [1, 1],
+            [1, 1],
+        );
+        let output_channels_last = conv2d(
+            memory_format(x, MemoryFormat::ChannelsLast),
+            memory_format(weight, MemoryFormat::ChannelsLast),
+            Some(bias),
+            [1, 1],
+            [1, 1],
+        );
+        let output_channels_last = memory_format(output_channels_last, MemoryFormat::Contiguous);
+
+        output
+            .into_data()
+            .assert_approx_eq(&output_channels_last.into_data(), 3);
+    }
+
     #[test]
     fn test_conv2d_simple() {
         let test = Conv2dTestCase {
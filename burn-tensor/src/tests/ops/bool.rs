@@ -0,0 +1,73 @@
+#[burn_tensor_testgen::testgen(bool)]
+mod tests {
+    use super::*;
+    use burn_tensor::{Bool, Data, Tensor};
+
+    fn masks() -> (Tensor<TestBackend, 2, Bool>, Tensor<TestBackend, 2, Bool>) {
+        let lhs = Tensor::from_bool(Data::from([[true, false], [true, true]]));
+        let rhs = Tensor::from_bool(Data::from([[false, false], [true, false]]));
+
+        (lhs, rhs)
+    }
+
+    #[test]
+    fn should_support_bool_equal() {
+        let (lhs, rhs) = masks();
+
+        let data_actual = lhs.equal(rhs).into_data();
+
+        let data_expected = Data::from([[false, true], [true, false]]);
+        assert_eq!(data_expected, data_actual);
+    }
+
+    #[test]
+    fn should_support_bool_not() {
+        let (lhs, _) = masks();
+
+        let data_actual = lhs.bool_not().into_data();
+
+        let data_expected = Data::from([[false, true], [false, false]]);
+        assert_eq!(data_expected, data_actual);
+    }
+
+    #[test]
+    fn should_support_bool_and() {
+        let (lhs, rhs) = masks();
+
+        let data_actual = lhs.bool_and(rhs).into_data();
+
+        let data_expected = Data::from([[false, false], [true, false]]);
+        assert_eq!(data_expected, data_actual);
+    }
+
+    #[test]
+    fn should_support_bool_or() {
+        let (lhs, rhs) = masks();
+
+        let data_actual = lhs.bool_or(rhs).into_data();
+
+        let data_expected = Data::from([[true, false], [true, true]]);
+        assert_eq!(data_expected, data_actual);
+    }
+
+    #[test]
+    fn should_support_bool_any_all() {
+        let (lhs, rhs) = masks();
+        let none = Tensor::<TestBackend, 2, Bool>::from_bool(Data::from([[false, false]]));
+
+        assert_eq!(lhs.clone().any().into_data(), Data::from([true]));
+        assert_eq!(lhs.all().into_data(), Data::from([false]));
+        assert_eq!(rhs.any().into_data(), Data::from([true]));
+        assert_eq!(none.any().into_data(), Data::from([false]));
+    }
+
+    #[test]
+    fn should_support_bool_into_int() {
+        let (lhs, _) = masks();
+
+        let data_actual = lhs.into_int().into_data();
+
+        let data_expected: Data<i64, 2> = Data::from([[1, 0], [1, 1]]);
+        assert_eq!(data_expected, data_actual);
+    }
+}
@@ -0,0 +1,34 @@
+#[burn_tensor_testgen::testgen(clamp)]
+mod tests {
+    use super::*;
+    use burn_tensor::{Data, Distribution, Tensor};
+
+    #[test]
+    fn should_support_clamp_ops() {
+        let data = Data::from([[-2.0, -0.5, 0.0], [0.5, 1.0, 3.0]]);
+        let tensor = Tensor::<TestBackend, 2>::from_data(data);
+
+        let data_actual = tensor.clamp(-1.0, 1.0).into_data();
+
+        let data_expected = Data::from([[-1.0, -0.5, 0.0], [0.5, 1.0, 1.0]]);
+        assert_eq!(data_expected, data_actual);
+    }
+
+    #[test]
+    fn should_bound_every_element_when_clamping() {
+        let tensor =
+            Tensor::<TestBackend, 2>::random([8, 8], Distribution::Standard).mul_scalar(10.0);
+
+        let data = tensor.clamp(-0.5, 0.25).into_data();
+
+        assert!(data.value.iter().all(|value| (-0.5..=0.25).contains(value)));
+    }
+
+    #[test]
+    #[should_panic]
+    fn should_panic_when_clamp_min_is_greater_than_max() {
+        let tensor = Tensor::<TestBackend, 1>::ones([4]);
+
+        tensor.clamp(1.0, -1.0);
+    }
+}
@@ -0,0 +1,50 @@
+#[burn_tensor_testgen::testgen(cumulative)]
+mod tests {
+    use super::*;
+    use burn_tensor::{Data, Distribution, Tensor};
+
+    #[test]
+    fn should_support_cumsum_ops() {
+        let data = Data::from([[0.0, 1.0, 2.0], [3.0, 4.0, 5.0]]);
+        let tensor = Tensor::<TestBackend, 2>::from_data(data);
+
+        let data_actual = tensor.clone().cumsum(1).into_data();
+        let data_expected = Data::from([[0.0, 1.0, 3.0], [3.0, 7.0, 12.0]]);
+        assert_eq!(data_expected, data_actual);
+
+        let data_actual = tensor.cumsum(0).into_data();
+        let data_expected = Data::from([[0.0, 1.0, 2.0], [3.0, 5.0, 7.0]]);
+        assert_eq!(data_expected, data_actual);
+    }
+
+    #[test]
+    fn cumsum_should_match_a_manual_prefix_sum() {
+        let tensor = Tensor::<TestBackend, 1>::random([16], Distribution::Standard);
+        let values = tensor.to_data().value;
+
+        let mut sum = 0.0;
+        let prefix_sum = values
+            .iter()
+            .map(|value| {
+                sum += *value;
+                sum
+            })
+            .collect::<Vec<_>>();
+
+        tensor
+            .cumsum(0)
+            .into_data()
+            .assert_approx_eq(&Data::from(prefix_sum.as_slice()), 4);
+    }
+
+    #[test]
+    fn should_support_cumprod_ops() {
+        let data = Data::from([[1.0, 2.0, 3.0], [4.0, 5.0, 0.5]]);
+        let tensor = Tensor::<TestBackend, 2>::from_data(data);
+
+        let data_actual = tensor.cumprod(1).into_data();
+
+        let data_expected = Data::from([[1.0, 2.0, 6.0], [4.0, 20.0, 10.0]]);
+        assert_eq!(data_expected, data_actual);
+    }
+}
@@ -0,0 +1,41 @@
+#[burn_tensor_testgen::testgen(finite)]
+mod tests {
+    use super::*;
+    use burn_tensor::{Data, Tensor};
+
+    #[test]
+    fn should_not_panic_when_finite() {
+        let tensor = Tensor::<TestBackend, 2>::from_data(Data::from([[1.0, -7.0], [2.0, 3.0]]));
+
+        tensor.assert_finite("finite");
+    }
+
+    #[test]
+    fn should_detect_non_finite_values() {
+        let finite = Tensor::<TestBackend, 2>::from_data(Data::from([[1.0, -7.0], [2.0, 3.0]]));
+        let nan = Tensor::<TestBackend, 1>::from_data(Data::from([1.0, f32::NAN]));
+        let infinite = Tensor::<TestBackend, 1>::from_data(Data::from([f32::NEG_INFINITY, 1.0]));
+
+        assert!(finite.is_finite());
+        assert!(!nan.is_finite());
+        assert!(!infinite.is_finite());
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic(expected = "Tensor `activations` contains NaN or infinite values")]
+    fn should_panic_with_name_when_nan() {
+        let tensor = Tensor::<TestBackend, 2>::from_data(Data::from([[1.0, 7.0], [f32::NAN, 3.0]]));
+
+        tensor.assert_finite("activations");
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic(expected = "Tensor `logits` contains NaN or infinite values")]
+    fn should_panic_with_name_when_infinite() {
+        let tensor = Tensor::<TestBackend, 1>::from_data(Data::from([1.0, f32::INFINITY]));
+
+        tensor.assert_finite("logits");
+    }
+}
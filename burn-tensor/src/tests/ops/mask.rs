@@ -14,4 +14,19 @@ mod tests {
         let data_expected = Data::from([[2.0, 7.0], [2.0, 2.0]]);
         assert_eq!(data_expected, data_actual);
     }
+
+    #[test]
+    fn should_support_mask_broadcast() {
+        let tensor = Tensor::<TestBackend, 3>::from_data(Data::from([
+            [[1.0, 7.0], [2.0, 3.0]],
+            [[4.0, 5.0], [6.0, 8.0]],
+        ]));
+        let mask =
+            Tensor::<TestBackend, 3, Bool>::from_bool(Data::from([[[true, false], [false, true]]]));
+
+        let data_actual = tensor.mask_fill(mask, 2.0).to_data();
+
+        let data_expected = Data::from([[[2.0, 7.0], [2.0, 2.0]], [[2.0, 5.0], [6.0, 2.0]]]);
+        assert_eq!(data_expected, data_actual);
+    }
 }
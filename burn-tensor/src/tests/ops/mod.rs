@@ -1,10 +1,14 @@
 mod add;
 mod aggregation;
 mod arg;
+mod bool;
+mod clamp;
 mod cos;
+mod cumulative;
 mod div;
 mod erf;
 mod exp;
+mod finite;
 mod index;
 mod index_select;
 mod index_select_dim;
@@ -15,10 +19,12 @@ mod mask;
 mod matmul;
 mod mul;
 mod neg;
+mod one_hot;
 mod powf;
 mod repeat;
 mod reshape;
 mod sin;
+mod split;
 mod sub;
 mod tanh;
 mod transpose;
@@ -0,0 +1,24 @@
+#[burn_tensor_testgen::testgen(one_hot)]
+mod tests {
+    use super::*;
+    use burn_tensor::{Data, Int, Tensor};
+
+    #[test]
+    fn should_support_one_hot_ops() {
+        let indices = Tensor::<TestBackend, 1, Int>::from_data(Data::from([0, 2]));
+
+        let data_actual = indices.one_hot(3).into_data();
+
+        let data_expected = Data::from([[1.0, 0.0, 0.0], [0.0, 0.0, 1.0]]);
+        assert_eq!(data_expected, data_actual);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic]
+    fn one_hot_should_panic_when_index_out_of_range() {
+        let indices = Tensor::<TestBackend, 1, Int>::from_data(Data::from([0, 3]));
+
+        indices.one_hot(3);
+    }
+}
@@ -0,0 +1,66 @@
+#[burn_tensor_testgen::testgen(split)]
+mod tests {
+    use super::*;
+    use burn_tensor::{Data, Tensor};
+
+    #[test]
+    fn should_support_chunk_ops() {
+        let data = Data::from([
+            [0.0, 1.0, 2.0, 3.0, 4.0, 5.0],
+            [6.0, 7.0, 8.0, 9.0, 10.0, 11.0],
+        ]);
+        let tensor = Tensor::<TestBackend, 2>::from_data(data);
+
+        let data_actual = tensor
+            .chunk(3, 1)
+            .into_iter()
+            .map(|chunk| chunk.into_data())
+            .collect::<Vec<_>>();
+
+        let data_expected = vec![
+            Data::from([[0.0, 1.0], [6.0, 7.0]]),
+            Data::from([[2.0, 3.0], [8.0, 9.0]]),
+            Data::from([[4.0, 5.0], [10.0, 11.0]]),
+        ];
+        assert_eq!(data_expected, data_actual);
+    }
+
+    #[test]
+    fn should_support_chunk_ops_with_smaller_last_chunk() {
+        let tensor = Tensor::<TestBackend, 2>::ones([5, 2]);
+
+        let dims = tensor
+            .chunk(2, 0)
+            .into_iter()
+            .map(|chunk| chunk.dims())
+            .collect::<Vec<_>>();
+
+        assert_eq!(dims, vec![[3, 2], [2, 2]]);
+    }
+
+    #[test]
+    fn should_support_split_ops() {
+        let data = Data::from([[0.0, 1.0, 2.0], [3.0, 4.0, 5.0]]);
+        let tensor = Tensor::<TestBackend, 2>::from_data(data);
+
+        let data_actual = tensor
+            .split(&[1, 2], 1)
+            .into_iter()
+            .map(|chunk| chunk.into_data())
+            .collect::<Vec<_>>();
+
+        let data_expected = vec![
+            Data::from([[0.0], [3.0]]),
+            Data::from([[1.0, 2.0], [4.0, 5.0]]),
+        ];
+        assert_eq!(data_expected, data_actual);
+    }
+
+    #[test]
+    #[should_panic]
+    fn should_panic_when_split_sizes_dont_match_the_dim() {
+        let tensor = Tensor::<TestBackend, 2>::ones([2, 3]);
+
+        tensor.split(&[1, 1], 1);
+    }
+}
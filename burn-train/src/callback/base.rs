@@ -13,5 +13,9 @@ pub struct LearnerItem<T> {
     pub progress: Progress,
     pub epoch: usize,
     pub epoch_total: usize,
+    /// The number of items processed in the epoch, which are micro-batches when gradients
+    /// accumulation is enabled.
     pub iteration: usize,
+    /// The number of optimizer updates done in the epoch, not available during validation.
+    pub step: Option<usize>,
 }
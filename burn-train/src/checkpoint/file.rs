@@ -6,10 +6,12 @@ pub struct FileCheckpointer<P> {
     directory: String,
     name: String,
     num_keep: usize,
+    compressed: bool,
     _precision: P,
 }
 
 impl<P: Element> FileCheckpointer<P> {
+    /// Create a checkpointer saving gzip compressed states in the given directory.
     pub fn new(directory: &str, name: &str, num_keep: usize) -> Self {
         std::fs::create_dir_all(directory).ok();
 
@@ -17,11 +19,43 @@ impl<P: Element> FileCheckpointer<P> {
             directory: directory.to_string(),
             name: name.to_string(),
             num_keep,
+            compressed: true,
             _precision: P::default(),
         }
     }
+
+    /// Set if the checkpoints should be compressed with gzip, which is the default.
+    ///
+    /// Compression makes the checkpoints of large models much smaller on disk at the cost of
+    /// slower saving and loading.
+    pub fn with_compression(mut self, compressed: bool) -> Self {
+        self.compressed = compressed;
+        self
+    }
+
     fn path_for_epoch(&self, epoch: usize) -> String {
-        format!("{}/{}-{}.json.gz", self.directory, self.name, epoch)
+        self.path_for_epoch_compressed(epoch, self.compressed)
+    }
+
+    fn path_for_epoch_compressed(&self, epoch: usize, compressed: bool) -> String {
+        let extension = match compressed {
+            true => "json.gz",
+            false => "json",
+        };
+
+        format!("{}/{}-{}.{}", self.directory, self.name, epoch, extension)
+    }
+
+    /// The checkpoints of an epoch with and without compression, the configured one first.
+    fn paths_for_epoch(&self, epoch: usize) -> [String; 2] {
+        [
+            self.path_for_epoch_compressed(epoch, self.compressed),
+            self.path_for_epoch_compressed(epoch, !self.compressed),
+        ]
+    }
+
+    fn path_tmp(file_path: &str) -> String {
+        format!("{file_path}.tmp")
     }
 }
 
@@ -34,27 +68,40 @@ where
         let file_path = self.path_for_epoch(epoch);
         log::info!("Saving checkpoint {} to {}", epoch, file_path);
 
-        state
-            .convert::<P>()
-            .save(&file_path)
-            .map_err(CheckpointerError::IOError)?;
+        // The state is written to a temporary file first, then moved in place, so a crash
+        // during the write never leaves a partial checkpoint under the final name.
+        let file_path_tmp = Self::path_tmp(&file_path);
+        let state = state.convert::<P>();
+        match self.compressed {
+            true => state.save(&file_path_tmp),
+            false => state.save_uncompressed(&file_path_tmp),
+        }
+        .map_err(CheckpointerError::IOError)?;
+        std::fs::rename(&file_path_tmp, &file_path).map_err(CheckpointerError::IOError)?;
 
         if self.num_keep > epoch {
             return Ok(());
         }
 
-        let file_path_old_checkpoint = self.path_for_epoch(epoch - self.num_keep);
-
-        if std::path::Path::new(&file_path_old_checkpoint).exists() {
-            log::info!("Removing checkpoint {}", file_path_old_checkpoint);
-            std::fs::remove_file(file_path_old_checkpoint).map_err(CheckpointerError::IOError)?;
+        for file_path_old_checkpoint in self.paths_for_epoch(epoch - self.num_keep) {
+            if std::path::Path::new(&file_path_old_checkpoint).exists() {
+                log::info!("Removing checkpoint {}", file_path_old_checkpoint);
+                std::fs::remove_file(file_path_old_checkpoint)
+                    .map_err(CheckpointerError::IOError)?;
+            }
         }
 
         Ok(())
     }
 
+    /// Restore the checkpoint of the given epoch, which may have been saved with a different
+    /// [compression](FileCheckpointer::with_compression) setting.
     fn restore(&self, epoch: usize) -> Result<State<E>, CheckpointerError> {
-        let file_path = self.path_for_epoch(epoch);
+        let [file_path, file_path_other] = self.paths_for_epoch(epoch);
+        let file_path = match std::path::Path::new(&file_path).exists() {
+            false if std::path::Path::new(&file_path_other).exists() => file_path_other,
+            _ => file_path,
+        };
         log::info!("Restoring checkpoint {} from {}", epoch, file_path);
 
         let state = State::<P>::load(&file_path).map_err(CheckpointerError::StateError)?;
@@ -62,3 +109,77 @@ where
         Ok(state.convert())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use burn_core::module::Module;
+    use burn_core::nn::{Linear, LinearConfig};
+
+    type TestBackend = burn_ndarray::NdArrayBackend<f32>;
+
+    #[test]
+    fn test_compressed_checkpoint_should_be_smaller_and_load_same_state() {
+        let directory = std::env::temp_dir().join("burn-train-test-file-checkpointer");
+        let directory = directory.to_str().unwrap();
+        let model = Linear::<TestBackend>::new(&LinearConfig::new(64, 64));
+        let checkpointer_compressed = FileCheckpointer::<f32>::new(directory, "compressed", 1);
+        let checkpointer_uncompressed =
+            FileCheckpointer::<f32>::new(directory, "uncompressed", 1).with_compression(false);
+
+        checkpointer_compressed.save(1, model.state()).unwrap();
+        checkpointer_uncompressed.save(1, model.state()).unwrap();
+
+        let size = |checkpointer: &FileCheckpointer<f32>| {
+            std::fs::metadata(checkpointer.path_for_epoch(1))
+                .unwrap()
+                .len()
+        };
+        assert!(size(&checkpointer_compressed) < size(&checkpointer_uncompressed));
+
+        let state_compressed: State<f32> = checkpointer_compressed.restore(1).unwrap();
+        let state_uncompressed: State<f32> = checkpointer_uncompressed.restore(1).unwrap();
+        assert_eq!(state_compressed, model.state());
+        assert_eq!(state_uncompressed, model.state());
+    }
+
+    #[test]
+    fn test_checkpoint_should_be_restored_with_another_compression() {
+        let directory = std::env::temp_dir().join("burn-train-test-file-checkpointer-extension");
+        let directory = directory.to_str().unwrap();
+        std::fs::remove_dir_all(directory).ok();
+        let model = Linear::<TestBackend>::new(&LinearConfig::new(8, 8));
+        let checkpointer_uncompressed =
+            FileCheckpointer::<f32>::new(directory, "model", 1).with_compression(false);
+        let checkpointer_compressed = FileCheckpointer::<f32>::new(directory, "model", 1);
+
+        checkpointer_uncompressed.save(1, model.state()).unwrap();
+        let state: State<f32> = checkpointer_compressed.restore(1).unwrap();
+        assert_eq!(state, model.state());
+
+        // The old checkpoints are removed whatever their compression.
+        checkpointer_compressed.save(2, model.state()).unwrap();
+        assert!(!std::path::Path::new(&checkpointer_uncompressed.path_for_epoch(1)).exists());
+    }
+
+    #[test]
+    fn test_failed_write_should_not_corrupt_previous_checkpoint() {
+        let directory = std::env::temp_dir().join("burn-train-test-file-checkpointer-atomic");
+        let directory = directory.to_str().unwrap();
+        let model = Linear::<TestBackend>::new(&LinearConfig::new(8, 8));
+        let checkpointer = FileCheckpointer::<f32>::new(directory, "model", 2);
+        checkpointer.save(1, model.state()).unwrap();
+
+        // Simulate a crash while writing the checkpoints of the same and the next epochs.
+        for epoch in [1, 2] {
+            let file_path_tmp =
+                FileCheckpointer::<f32>::path_tmp(&checkpointer.path_for_epoch(epoch));
+            std::fs::write(file_path_tmp, b"partial").unwrap();
+        }
+        std::fs::remove_file(checkpointer.path_for_epoch(2)).ok();
+
+        let state: State<f32> = checkpointer.restore(1).unwrap();
+        assert_eq!(state, model.state());
+        assert!(!std::path::Path::new(&checkpointer.path_for_epoch(2)).exists());
+    }
+}
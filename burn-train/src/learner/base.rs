@@ -1,8 +1,11 @@
 use crate::checkpoint::Checkpointer;
-use crate::LearnerCallback;
-use burn_core::module::{ADModule, Module};
+use crate::{FreezeSchedule, LearnerCallback, NanGradAction, TrainingHistory};
+use burn_core::lr_scheduler::LrScheduler;
+use burn_core::module::{ADModule, Module, State, StateNamed};
 use burn_core::optim::Optimizer;
 use burn_core::tensor::backend::Backend;
+use burn_core::tensor::DataSerialize;
+use std::sync::{Arc, Mutex};
 
 /// Learner struct encapsulating all components necessary to train a Neural Network model.
 ///
@@ -15,17 +18,34 @@ where
     pub(super) optim: O,
     pub(super) num_epochs: usize,
     pub(super) callback: Box<dyn LearnerCallback<TO, VO>>,
+    pub(super) history: Arc<Mutex<TrainingHistory>>,
     pub(super) checkpoint: Option<usize>,
     pub(super) checkpointer_model: CheckpointModel<M>,
     pub(super) checkpointer_optimizer: CheckpointOptim<M>,
+    pub(super) checkpoint_interval: Option<usize>,
+    pub(super) checkpoint_iteration: Option<usize>,
+    pub(super) checkpointer_model_iteration: CheckpointModel<M>,
+    pub(super) checkpointer_optimizer_iteration: CheckpointOptim<M>,
+    pub(super) checkpointer_learner: CheckpointLearner,
+    pub(super) checkpointer_learner_iteration: CheckpointLearner,
     pub(super) grad_accumulation: Option<usize>,
+    pub(super) grad_accumulation_flush: bool,
+    pub(super) grad_accumulation_offload: bool,
+    pub(super) grad_accumulation_scale_lr: bool,
+    pub(super) lr_scheduler: Option<Box<dyn LrScheduler>>,
     pub(super) devices: Vec<<M::Backend as Backend>::Device>,
+    pub(super) nan_grad: Option<(NanGradAction, String)>,
+    pub(super) grad_norm_log: Option<(usize, String)>,
+    pub(super) freeze_schedule: Option<FreezeSchedule>,
+    pub(super) swa: Option<usize>,
+    pub(super) fast_dev_run: Option<usize>,
 }
 
 type CheckpointModel<M> =
     Option<Box<dyn Checkpointer<<<M as Module>::Backend as Backend>::FloatElem>>>;
 type CheckpointOptim<M> =
     Option<Box<dyn Checkpointer<<<M as Module>::Backend as Backend>::FloatElem>>>;
+type CheckpointLearner = Option<Box<dyn Checkpointer<f64>>>;
 
 impl<M, O, TO, VO> Learner<M, O, TO, VO>
 where
@@ -34,11 +54,16 @@ where
     M: ADModule,
     O: Optimizer<Backend = M::Backend>,
 {
+    /// Save the model, the optimizer and the learner state, which holds the number of optimizer
+    /// updates done since the start of the training.
+    #[allow(clippy::too_many_arguments)]
     pub(super) fn checkpoint(
         model: &M,
         optim: &O,
+        step: usize,
         checkpointer_model: &CheckpointModel<M>,
         checkpointer_optimizer: &CheckpointOptim<M>,
+        checkpointer_learner: &CheckpointLearner,
         epoch: usize,
     ) {
         if let Some(checkpointer) = &checkpointer_model {
@@ -47,9 +72,20 @@ where
         if let Some(checkpointer) = &checkpointer_optimizer {
             checkpointer.save(epoch, optim.state(model)).unwrap();
         }
+        if let Some(checkpointer) = &checkpointer_learner {
+            let mut state = StateNamed::new();
+            let step = DataSerialize {
+                value: vec![step as f64],
+                shape: vec![1],
+            };
+            state.register_state("step", State::Data(step));
+            checkpointer.save(epoch, State::StateNamed(state)).unwrap();
+        }
     }
 
-    pub(super) fn load_checkpoint(mut self, epoch: usize) -> Self {
+    /// Load the checkpoint saved after the given epoch, returning the number of optimizer updates
+    /// done before it.
+    pub(super) fn load_checkpoint(mut self, epoch: usize) -> (Self, usize) {
         if let Some(checkpointer) = &self.checkpointer_model {
             let state = checkpointer.restore(epoch).unwrap();
             self.model = self.model.load(&state).unwrap();
@@ -60,6 +96,93 @@ where
             self.optim.load(&self.model, &state).unwrap();
         }
 
-        self
+        let step = restore_step(&self.checkpointer_learner, epoch);
+
+        (self, step)
+    }
+
+    /// Load the mid-epoch checkpoint saved after the given iteration of the given epoch,
+    /// returning the number of optimizer updates done before it.
+    pub(super) fn load_checkpoint_iteration(
+        mut self,
+        epoch: usize,
+        iteration: usize,
+    ) -> (Self, usize) {
+        let key = iteration_key(self.checkpoint_interval, epoch, iteration);
+
+        if let Some(checkpointer) = &self.checkpointer_model_iteration {
+            let state = checkpointer.restore(key).unwrap();
+            self.model = self.model.load(&state).unwrap();
+        }
+
+        if let Some(checkpointer) = &self.checkpointer_optimizer_iteration {
+            let state = checkpointer.restore(key).unwrap();
+            self.optim.load(&self.model, &state).unwrap();
+        }
+
+        let step = restore_step(&self.checkpointer_learner_iteration, key);
+
+        (self, step)
+    }
+}
+
+/// The number of optimizer updates saved with the learner state. Checkpoints saved without it
+/// resume from the first step.
+fn restore_step(checkpointer: &CheckpointLearner, epoch: usize) -> usize {
+    let checkpointer = match checkpointer {
+        Some(checkpointer) => checkpointer,
+        None => return 0,
+    };
+
+    match checkpointer.restore(epoch) {
+        Ok(state) => match state.get("step") {
+            Some(State::Data(data)) => data.value[0] as usize,
+            _ => panic!("Invalid learner checkpoint {epoch}, the step is missing"),
+        },
+        Err(err) => {
+            log::warn!(
+                "No learner state in checkpoint {epoch}, the training resumes from the first \
+                 step: {err:?}"
+            );
+            0
+        }
+    }
+}
+
+/// The maximum number of mid-epoch checkpoints of a single epoch.
+const MAX_ITERATION_CHECKPOINTS: usize = 100_000;
+
+/// Mid-epoch checkpoints are numbered by epoch, then by how many intervals were started in the
+/// epoch, so that the checkpoints of an epoch are never mistaken for the ones of another epoch
+/// and the checkpointers only keep the most recent ones of each epoch.
+///
+/// The last iteration of an epoch may complete a partial interval, it is numbered like the
+/// following multiple of the interval.
+pub(super) fn iteration_key(interval: Option<usize>, epoch: usize, iteration: usize) -> usize {
+    let interval = interval.expect("A checkpoint interval is required for mid-epoch checkpoints");
+    let index = iteration / interval + usize::from(iteration % interval != 0);
+
+    if index >= MAX_ITERATION_CHECKPOINTS {
+        panic!(
+            "Iteration {iteration} exceeds the {MAX_ITERATION_CHECKPOINTS} mid-epoch checkpoints \
+             of an epoch with the checkpoint interval {interval}"
+        );
+    }
+
+    epoch * MAX_ITERATION_CHECKPOINTS + index
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_iteration_keys_should_differ_across_epochs() {
+        let keys_1: Vec<_> = [2, 4, 5].map(|i| iteration_key(Some(2), 1, i)).into();
+        let keys_2: Vec<_> = [2, 4, 5].map(|i| iteration_key(Some(2), 2, i)).into();
+
+        assert_eq!(keys_1[1] - keys_1[0], 1);
+        assert_eq!(keys_1[2] - keys_1[1], 1);
+        assert!(keys_1.iter().all(|key| !keys_2.contains(key)));
     }
 }
@@ -4,14 +4,18 @@ use crate::checkpoint::{AsyncCheckpointer, Checkpointer, FileCheckpointer};
 use crate::logger::FileMetricLogger;
 use crate::metric::dashboard::cli::CLIDashboardRenderer;
 use crate::metric::dashboard::Dashboard;
-use crate::metric::{Adaptor, Metric, Numeric};
-use crate::AsyncTrainerCallback;
+use crate::metric::{Adaptor, Aggregate, Metric, Numeric};
+use crate::{AsyncTrainerCallback, FreezeSchedule, NanGradAction};
+use burn_core::lr_scheduler::LrScheduler;
 use burn_core::module::ADModule;
 use burn_core::optim::Optimizer;
 use burn_core::tensor::backend::ADBackend;
 use burn_core::tensor::Element;
 use std::sync::Arc;
 
+/// Create the [file checkpointer](FileCheckpointer) with the given name and compression.
+type FileCheckpointerFactory<E> = Box<dyn Fn(&str, bool) -> Box<dyn Checkpointer<E>>>;
+
 /// Struct to configure and create a [learner](Learner).
 pub struct LearnerBuilder<B, T, V>
 where
@@ -20,13 +24,25 @@ where
     B: ADBackend,
 {
     dashboard: Dashboard<T, V>,
-    checkpointer_model: Option<Arc<dyn Checkpointer<B::FloatElem> + Send + Sync>>,
-    checkpointer_optimizer: Option<Arc<dyn Checkpointer<B::FloatElem> + Send + Sync>>,
+    checkpointer_file: Option<FileCheckpointerFactory<B::FloatElem>>,
+    checkpointer_file_learner: Option<FileCheckpointerFactory<f64>>,
     num_epochs: usize,
     checkpoint: Option<usize>,
+    checkpoint_iteration: Option<usize>,
+    checkpoint_interval: Option<usize>,
+    checkpoint_compression: bool,
     directory: String,
     grad_accumulation: Option<usize>,
+    grad_accumulation_flush: bool,
+    grad_accumulation_offload: bool,
+    grad_accumulation_scale_lr: bool,
+    lr_scheduler: Option<Box<dyn LrScheduler>>,
     devices: Vec<B::Device>,
+    nan_grad: Option<NanGradAction>,
+    grad_norm_interval: Option<usize>,
+    freeze_schedule: Option<FreezeSchedule>,
+    swa: Option<usize>,
+    fast_dev_run: Option<usize>,
 }
 
 impl<B, T, V> LearnerBuilder<B, T, V>
@@ -44,11 +60,23 @@ where
             dashboard: Dashboard::new(renderer, logger_train, logger_valid),
             num_epochs: 1,
             checkpoint: None,
-            checkpointer_model: None,
-            checkpointer_optimizer: None,
+            checkpoint_iteration: None,
+            checkpoint_interval: None,
+            checkpoint_compression: true,
+            checkpointer_file: None,
+            checkpointer_file_learner: None,
             directory: directory.to_string(),
             grad_accumulation: None,
+            grad_accumulation_flush: false,
+            grad_accumulation_offload: false,
+            grad_accumulation_scale_lr: false,
+            lr_scheduler: None,
             devices: vec![B::Device::default()],
+            nan_grad: None,
+            grad_norm_interval: None,
+            freeze_schedule: None,
+            swa: None,
+            fast_dev_run: None,
         }
     }
 
@@ -75,8 +103,8 @@ where
     /// # Notes
     ///
     /// When you enable gradients accumulation, the gradients object used by the optimizer will be
-    /// the sum of all gradients generated by each backward pass. It might be a good idea to
-    /// reduce the learning to compensate.
+    /// the sum of all gradients generated by each backward pass, unless the learning rate is
+    /// [scaled automatically](Self::auto_scale_lr_with_accumulation).
     ///
     /// The effect is similar to increasing the `batch size` and the `learning rate` by the `accumulation`
     /// amount.
@@ -85,6 +113,110 @@ where
         self
     }
 
+    /// Update the model with the gradients of the last accumulation group of each epoch, even
+    /// when the number of items isn't a multiple of the
+    /// [accumulation](Self::grads_accumulation). The gradients are scaled to match the magnitude
+    /// of a full group.
+    ///
+    /// Without it, the gradients of a partial group are discarded at the end of the epoch.
+    pub fn grads_accumulation_flush(mut self) -> Self {
+        self.grad_accumulation_flush = true;
+        self
+    }
+
+    /// Keep the accumulated gradients on the default device of the backend, the CPU for most
+    /// backends, between the steps of the [accumulation](Self::grads_accumulation).
+    ///
+    /// This reduces the device memory used by large models at the cost of transferring the
+    /// gradients at each step.
+    pub fn grads_accumulation_offload(mut self) -> Self {
+        self.grad_accumulation_offload = true;
+        self
+    }
+
+    /// Average the gradients of the [accumulation](Self::grads_accumulation) instead of summing
+    /// them, so the learning rate behaves as with a single batch of all the accumulated items
+    /// and doesn't need to be reduced by the accumulation amount.
+    ///
+    /// # Notes
+    ///
+    /// The gradients are divided by the number of accumulated items before the optimizer update,
+    /// which is equivalent to dividing the learning rate for optimizers like SGD.
+    pub fn auto_scale_lr_with_accumulation(mut self) -> Self {
+        self.grad_accumulation_scale_lr = true;
+        self
+    }
+
+    /// Register a [learning rate scheduler](LrScheduler), stepped before every optimizer update
+    /// to set the learning rate of the optimizer.
+    ///
+    /// When resuming from a checkpoint saved by the
+    /// [file checkpointer](Self::with_file_checkpointer), the scheduler is
+    /// [resumed](LrScheduler::resume) at the number of optimizer updates done before it.
+    pub fn lr_scheduler<S: LrScheduler + 'static>(mut self, lr_scheduler: S) -> Self {
+        self.lr_scheduler = Some(Box::new(lr_scheduler));
+        self
+    }
+
+    /// Check the gradients of each training step for NaN and infinite values, applying the given
+    /// [action](NanGradAction) when they are found.
+    ///
+    /// The gradients and the inputs of the step are dumped to the `nan-grads` directory of the
+    /// learner.
+    pub fn on_nan_grad(mut self, action: NanGradAction) -> Self {
+        self.nan_grad = Some(action);
+        self
+    }
+
+    /// Log the norm of the gradients of each parameter every `interval` training iterations, which
+    /// helps finding the layers with vanishing or exploding gradients.
+    ///
+    /// The norms are appended to the `grad-norms.csv` file of the learner directory, with the
+    /// columns `epoch,iteration,name,norm`.
+    pub fn log_grad_norms(mut self, interval: usize) -> Self {
+        self.grad_norm_interval = Some(interval);
+        self
+    }
+
+    /// Freeze some parameters of the model during the first training steps, such as the
+    /// pretrained backbone when fine-tuning, see [FreezeSchedule].
+    pub fn freeze_schedule(mut self, schedule: FreezeSchedule) -> Self {
+        self.freeze_schedule = Some(schedule);
+        self
+    }
+
+    /// Average the weights of the model at the end of each epoch from `start_epoch` with
+    /// [stochastic weight averaging](burn_core::optim::Swa), the averaged model is returned at the
+    /// end of the training.
+    ///
+    /// # Notes
+    ///
+    /// The batch normalization statistics of the averaged model are recomputed with an additional
+    /// pass of training steps over the training data, without updating the weights. The weights
+    /// collected before resuming from a checkpoint aren't part of the average.
+    pub fn with_swa(mut self, start_epoch: usize) -> Self {
+        self.swa = Some(start_epoch);
+        self
+    }
+
+    /// Run a quick sanity check of the training instead of the full training: a single epoch
+    /// where the training and the validation are limited to `num_batches` batches each.
+    ///
+    /// This catches shape and device bugs before starting a long training. No checkpoint is saved
+    /// and [stochastic weight averaging](Self::with_swa) is disabled.
+    ///
+    /// # Panics
+    ///
+    /// If the number of batches is zero.
+    pub fn fast_dev_run(mut self, num_batches: usize) -> Self {
+        if num_batches == 0 {
+            panic!("The fast dev run requires at least one batch");
+        }
+
+        self.fast_dev_run = Some(num_batches);
+        self
+    }
+
     /// Register a training metric and displays it on a plot.
     ///
     /// # Notes
@@ -101,6 +233,18 @@ where
         self
     }
 
+    /// Register a training metric and displays it on a plot, summarizing each epoch with the
+    /// given [aggregation strategy](Aggregate) instead of the mean.
+    pub fn metric_train_plot_aggregate<M>(mut self, metric: M, aggregate: Aggregate) -> Self
+    where
+        M: Metric + Numeric + 'static,
+        T: Adaptor<M::Input>,
+    {
+        self.dashboard
+            .register_train_plot_aggregate(metric, aggregate);
+        self
+    }
+
     /// Register a validation metric and displays it on a plot.
     ///
     /// # Notes
@@ -116,6 +260,18 @@ where
         self
     }
 
+    /// Register a validation metric and displays it on a plot, summarizing each epoch with the
+    /// given [aggregation strategy](Aggregate) instead of the mean.
+    pub fn metric_valid_plot_aggregate<M>(mut self, metric: M, aggregate: Aggregate) -> Self
+    where
+        M: Metric + Numeric + 'static,
+        V: Adaptor<M::Input>,
+    {
+        self.dashboard
+            .register_valid_plot_aggregate(metric, aggregate);
+        self
+    }
+
     /// The number of epochs the training should last.
     pub fn num_epochs(mut self, num_epochs: usize) -> Self {
         self.num_epochs = num_epochs;
@@ -134,46 +290,98 @@ where
         self
     }
 
+    /// Save a checkpoint every `num_iterations` training iterations, in addition to the one saved
+    /// at the end of each epoch.
+    ///
+    /// # Notes
+    ///
+    /// The last iteration of each epoch is also checkpointed when it doesn't complete an interval.
+    /// The checkpoints of each epoch are saved under distinct keys. When gradients accumulation
+    /// is enabled, the gradients not yet applied by the optimizer aren't saved.
+    pub fn checkpoint_every_iters(mut self, num_iterations: usize) -> Self {
+        self.checkpoint_interval = Some(num_iterations);
+        self
+    }
+
+    /// The epoch and the iteration from which the training must resume, using the mid-epoch
+    /// checkpoint saved at that [iteration](Self::checkpoint_every_iters).
+    pub fn checkpoint_iteration(mut self, epoch: usize, iteration: usize) -> Self {
+        self.checkpoint = Some(epoch);
+        self.checkpoint_iteration = Some(iteration);
+        self
+    }
+
+    /// Set if the checkpoints saved by the [file checkpointer](Self::with_file_checkpointer)
+    /// should be compressed with gzip, which is the default.
+    ///
+    /// Checkpoints saved with the other setting can still be restored.
+    pub fn checkpoint_compression(mut self, compressed: bool) -> Self {
+        self.checkpoint_compression = compressed;
+        self
+    }
+
     /// Register a checkpointer that will save the [optimizer](crate::optim::Optimizer) and the
-    /// [model](crate::module::Module) [states](crate::module::State).
+    /// [model](crate::module::Module) [states](crate::module::State), along with the number of
+    /// optimizer updates done, so a resumed training continues its schedules.
     ///
-    /// The number of checkpoints to be keep should be set to a minimum of two to be safe, since
-    /// they are saved and deleted asynchronously and a crash during training might make a
-    /// checkpoint non-usable.
+    /// Each checkpoint is written to a temporary file before being moved in place, so a crash
+    /// during training never corrupts a previously saved checkpoint. The old checkpoints are only
+    /// removed once the new one is successfully written.
     pub fn with_file_checkpointer<P: Element + serde::de::DeserializeOwned + serde::Serialize>(
         mut self,
         num_keep: usize,
     ) -> Self {
-        self.checkpointer_model = Some(Arc::new(FileCheckpointer::<P>::new(
-            format!("{}/checkpoint", self.directory).as_str(),
-            "model",
-            num_keep,
-        )));
-        self.checkpointer_optimizer = Some(Arc::new(FileCheckpointer::<P>::new(
-            format!("{}/checkpoint", self.directory).as_str(),
-            "optim",
-            num_keep,
-        )));
+        let directory = format!("{}/checkpoint", self.directory);
+
+        // The checkpointers are created by the build, so the compression can be set after them.
+        let directory_learner = directory.clone();
+        self.checkpointer_file = Some(Box::new(move |name: &str, compressed: bool| {
+            let checkpointer = FileCheckpointer::<P>::new(directory.as_str(), name, num_keep)
+                .with_compression(compressed);
+            let checkpointer: Box<dyn Checkpointer<B::FloatElem>> =
+                Box::new(AsyncCheckpointer::new(Arc::new(checkpointer)));
+            checkpointer
+        }));
+        // The learner state holds counters, it is saved in full precision.
+        self.checkpointer_file_learner = Some(Box::new(move |name: &str, compressed: bool| {
+            let checkpointer =
+                FileCheckpointer::<f64>::new(directory_learner.as_str(), name, num_keep)
+                    .with_compression(compressed);
+            let checkpointer: Box<dyn Checkpointer<f64>> =
+                Box::new(AsyncCheckpointer::new(Arc::new(checkpointer)));
+            checkpointer
+        }));
         self
     }
 
     /// Create the [learner](Learner) from a [module](ADModule) and an
-    pub fn build<M, O>(self, model: M, optim: O) -> Learner<M, O, T, V>
+    pub fn build<M, O>(mut self, model: M, optim: O) -> Learner<M, O, T, V>
     where
         M: ADModule<ADBackend = B>,
         O: Optimizer<Backend = B>,
     {
         self.init_logger();
+        if let Some(checkpoint) = self.checkpoint {
+            // The training restarts at the checkpoint epoch, the metrics logged by the previous
+            // run from that epoch must not be mixed with the new ones.
+            self.dashboard.resume(checkpoint);
+        }
+        let history = self.dashboard.history();
         let callack = Box::new(self.dashboard);
         let callback = Box::new(AsyncTrainerCallback::new(callack));
 
-        let create_checkpointer = |checkpointer| match checkpointer {
-            Some(checkpointer) => {
-                let checkpointer: Box<dyn Checkpointer<B::FloatElem>> =
-                    Box::new(AsyncCheckpointer::new(checkpointer));
-                Some(checkpointer)
-            }
-            None => None,
+        let checkpointer_file = self.checkpointer_file;
+        let compressed = self.checkpoint_compression;
+        let create_checkpointer = |name: &str| {
+            checkpointer_file
+                .as_ref()
+                .map(|create| create(name, compressed))
+        };
+        let checkpointer_file_learner = self.checkpointer_file_learner;
+        let create_checkpointer_learner = |name: &str| {
+            checkpointer_file_learner
+                .as_ref()
+                .map(|create| create(name, compressed))
         };
         let model = model.detach();
 
@@ -182,11 +390,31 @@ where
             optim,
             num_epochs: self.num_epochs,
             callback,
+            history,
             checkpoint: self.checkpoint,
-            checkpointer_model: create_checkpointer(self.checkpointer_model),
-            checkpointer_optimizer: create_checkpointer(self.checkpointer_optimizer),
+            checkpointer_model: create_checkpointer("model"),
+            checkpointer_optimizer: create_checkpointer("optim"),
+            checkpoint_interval: self.checkpoint_interval,
+            checkpoint_iteration: self.checkpoint_iteration,
+            checkpointer_model_iteration: create_checkpointer("model-iteration"),
+            checkpointer_optimizer_iteration: create_checkpointer("optim-iteration"),
+            checkpointer_learner: create_checkpointer_learner("learner"),
+            checkpointer_learner_iteration: create_checkpointer_learner("learner-iteration"),
             grad_accumulation: self.grad_accumulation,
+            grad_accumulation_flush: self.grad_accumulation_flush,
+            grad_accumulation_offload: self.grad_accumulation_offload,
+            grad_accumulation_scale_lr: self.grad_accumulation_scale_lr,
+            lr_scheduler: self.lr_scheduler,
             devices: self.devices,
+            nan_grad: self
+                .nan_grad
+                .map(|action| (action, format!("{}/nan-grads", self.directory))),
+            grad_norm_log: self
+                .grad_norm_interval
+                .map(|interval| (interval, format!("{}/grad-norms.csv", self.directory))),
+            freeze_schedule: self.freeze_schedule,
+            swa: self.swa,
+            fast_dev_run: self.fast_dev_run,
         }
     }
 
@@ -195,3 +423,41 @@ where
         update_log_file(file_path.as_str());
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use burn_core::nn::{Linear, LinearConfig};
+    use burn_core::optim::{Sgd, SgdConfig};
+
+    type TestADBackend = burn_autodiff::ADBackendDecorator<burn_ndarray::NdArrayBackend<f32>>;
+
+    #[test]
+    fn test_checkpoint_compression_should_not_depend_on_the_call_order() {
+        let directory = std::env::temp_dir().join("burn-train-test-builder-compression");
+        let directory = directory.to_str().unwrap();
+        std::fs::remove_dir_all(directory).ok();
+
+        let learner = LearnerBuilder::<TestADBackend, (), ()>::new(directory)
+            .with_file_checkpointer::<f32>(1)
+            .checkpoint_compression(false)
+            .build(
+                Linear::<TestADBackend>::new(&LinearConfig::new(4, 2)),
+                Sgd::new(&SgdConfig::new(0.1)),
+            );
+        Learner::<_, _, (), ()>::checkpoint(
+            &learner.model,
+            &learner.optim,
+            0,
+            &learner.checkpointer_model,
+            &learner.checkpointer_optimizer,
+            &learner.checkpointer_learner,
+            1,
+        );
+        // Wait for the asynchronous checkpointers to save the files.
+        drop(learner);
+
+        assert!(std::path::Path::new(&format!("{directory}/checkpoint/model-1.json")).exists());
+        assert!(!std::path::Path::new(&format!("{directory}/checkpoint/model-1.json.gz")).exists());
+    }
+}
@@ -1,23 +1,126 @@
 use crate::metric::{AccuracyInput, Adaptor, LossInput};
-use burn_core::tensor::backend::Backend;
+use burn_core::tensor::backend::{ADBackend, Backend};
 use burn_core::tensor::{Int, Tensor};
 
 /// Simple classification output adapted for multiple metrics.
+///
+/// The float tensors are detached from the autodiff graph when adapted, so the metrics never
+/// build graphs for their operations.
 #[derive(new)]
 pub struct ClassificationOutput<B: Backend> {
+    /// The loss of the batch.
     pub loss: Tensor<B, 1>,
+    /// The logits of shape `[batch_size, n_classes]`.
     pub output: Tensor<B, 2>,
+    /// The target classes of shape `[batch_size]`.
     pub targets: Tensor<B, 1, Int>,
 }
 
+impl<B: ADBackend> ClassificationOutput<B> {
+    /// Move the output to the inner backend.
+    ///
+    /// Returning the inner output from a [train step](crate::TrainStep) also avoids the overhead
+    /// of the autodiff backend when computing the training metrics, the gradients must be
+    /// computed from the loss before calling this method.
+    pub fn inner(self) -> ClassificationOutput<B::InnerBackend> {
+        ClassificationOutput::new(self.loss.inner(), self.output.inner(), self.targets.inner())
+    }
+}
+
 impl<B: Backend> Adaptor<AccuracyInput<B>> for ClassificationOutput<B> {
     fn adapt(&self) -> AccuracyInput<B> {
-        AccuracyInput::new(self.output.clone(), self.targets.clone())
+        AccuracyInput::new(self.output.clone().detach(), self.targets.clone())
     }
 }
 
 impl<B: Backend> Adaptor<LossInput<B>> for ClassificationOutput<B> {
     fn adapt(&self) -> LossInput<B> {
-        LossInput::new(self.loss.clone())
+        LossInput::new(self.loss.clone().detach())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metric::{AccuracyMetric, LossMetric, Metric, Numeric};
+    use burn_core::tensor::Data;
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+
+    type TestBackend = burn_ndarray::NdArrayBackend<f32>;
+    type TestADBackend = burn_autodiff::ADBackendDecorator<TestBackend>;
+
+    #[test]
+    fn test_classification_output_should_adapt_to_accuracy_and_loss() {
+        let output = ClassificationOutput::<TestBackend>::new(
+            Tensor::from_data(Data::from([0.5, 1.5])),
+            Tensor::from_data(Data::from([[0.9, 0.1], [0.2, 0.8], [0.7, 0.3], [0.4, 0.6]])),
+            Tensor::from_data(Data::from([0, 1, 1, 1])),
+        );
+        let mut accuracy = AccuracyMetric::new();
+        let mut loss = LossMetric::new();
+
+        accuracy.update(&output.adapt());
+        loss.update(&output.adapt());
+
+        assert_eq!(accuracy.value(), 75.0);
+        assert_eq!(loss.value(), 1.0);
+    }
+
+    #[test]
+    fn test_inner_classification_output_should_give_the_same_metrics() {
+        let weights = Tensor::<TestADBackend, 2>::from_data(Data::from([[1.0, -1.0], [-0.5, 2.0]]))
+            .require_grad();
+        let inputs =
+            Tensor::from_data(Data::from([[0.9, 0.1], [0.2, 0.8], [0.7, 0.3], [0.4, 0.6]]));
+        let output = inputs.matmul(weights.clone());
+        let output = ClassificationOutput::<TestADBackend>::new(
+            output.clone().mean().unsqueeze(),
+            output,
+            Tensor::from_data(Data::from([0, 1, 1, 1])),
+        );
+        let grads = output.loss.backward();
+        assert!(weights.grad(&grads).is_some());
+
+        let mut accuracy = AccuracyMetric::new();
+        let mut loss = LossMetric::new();
+        accuracy.update(&output.adapt());
+        loss.update(&output.adapt());
+        let (accuracy_expected, loss_expected) = (accuracy.value(), loss.value());
+
+        // The inner output lives on the backend without autodiff, so no graph can be retained.
+        let output: ClassificationOutput<TestBackend> = output.inner();
+        let mut accuracy = AccuracyMetric::new();
+        let mut loss = LossMetric::new();
+        accuracy.update(&output.adapt());
+        loss.update(&output.adapt());
+
+        assert_eq!(accuracy.value(), accuracy_expected);
+        assert_eq!(loss.value(), loss_expected);
+        assert_eq!(accuracy.value(), 75.0);
+    }
+
+    #[test]
+    fn test_adapted_metric_inputs_should_not_be_tracked_by_the_graph() {
+        let weights = Tensor::<TestADBackend, 2>::from_data(Data::from([[1.0, -1.0], [-0.5, 2.0]]))
+            .require_grad();
+        let inputs =
+            Tensor::from_data(Data::from([[0.9, 0.1], [0.2, 0.8], [0.7, 0.3], [0.4, 0.6]]));
+        let output = inputs.matmul(weights);
+        let output = ClassificationOutput::<TestADBackend>::new(
+            output.clone().mean().unsqueeze(),
+            output,
+            Tensor::from_data(Data::from([0, 1, 1, 1])),
+        );
+
+        let accuracy: AccuracyInput<TestADBackend> = output.adapt();
+        let loss: LossInput<TestADBackend> = output.adapt();
+
+        // Tensors without a backward step registered can't be backpropagated.
+        let is_tracked = |tensor: Tensor<TestADBackend, 1>| {
+            catch_unwind(AssertUnwindSafe(|| tensor.backward())).is_ok()
+        };
+        assert!(is_tracked(output.loss.clone()));
+        assert!(!is_tracked(accuracy.outputs.sum()));
+        assert!(!is_tracked(loss.tensor));
     }
 }
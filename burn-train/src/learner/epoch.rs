@@ -1,18 +1,28 @@
 use burn_core::{
     data::dataloader::DataLoader,
-    module::ADModule,
-    optim::{GradientsAccumulator, Optimizer},
+    lr_scheduler::LrScheduler,
+    module::{ADModule, ParamId, State, StateNamed},
+    optim::{AllReduce, GradientsAccumulator, GradientsParams, Optimizer, TreeAllReduce},
     tensor::backend::Backend,
 };
+use std::cell::{Cell, OnceCell};
+use std::collections::HashMap;
 use std::sync::Arc;
 
-use crate::{LearnerCallback, LearnerItem, MultiDevicesTrainStep, TrainStep, ValidStep};
+use super::grad_norm::{log_grad_norms, param_names};
+use super::nan::dump_grads;
+use crate::{
+    FreezeSchedule, LearnerCallback, LearnerItem, MultiDevicesTrainStep, MultiTrainStep,
+    NanGradAction, TrainStep, ValidStep,
+};
 
 #[derive(new)]
 pub struct ValidEpoch<VI> {
     dataloader: Arc<dyn DataLoader<VI>>,
     epoch: usize,
     epoch_total: usize,
+    #[new(default)]
+    max_iterations: Option<usize>,
 }
 
 #[derive(new)]
@@ -21,9 +31,44 @@ pub struct TrainEpoch<TI> {
     epoch: usize,
     epoch_total: usize,
     grad_accumulation: Option<usize>,
+    checkpoint_interval: Option<usize>,
+    #[new(default)]
+    iteration_start: usize,
+    #[new(default)]
+    max_iterations: Option<usize>,
+    #[new(default)]
+    nan_grad: Option<(NanGradAction, String)>,
+    #[new(default)]
+    grad_norm_log: Option<(usize, String)>,
+    #[new(default)]
+    grad_accumulation_flush: bool,
+    #[new(default)]
+    grad_accumulation_offload: bool,
+    #[new(default)]
+    grad_accumulation_scale_lr: bool,
+    #[new(default)]
+    step_start: usize,
+    #[new(default)]
+    freeze_schedule: Option<FreezeSchedule>,
+    #[new(default)]
+    frozen: Cell<Option<bool>>,
+    #[new(default)]
+    num_steps: Cell<usize>,
+    #[new(default)]
+    num_updates: Cell<usize>,
+    #[new(default)]
+    param_names: OnceCell<HashMap<ParamId, String>>,
+    #[new(default)]
+    stopped: Cell<bool>,
 }
 
 impl<I> ValidEpoch<I> {
+    /// Stop the epoch after the given number of iterations.
+    pub fn with_max_iterations(mut self, max_iterations: usize) -> Self {
+        self.max_iterations = Some(max_iterations);
+        self
+    }
+
     pub fn run<M, TO, VO>(&self, model: M, callback: &mut Box<dyn LearnerCallback<TO, VO>>) -> M
     where
         M: ADModule,
@@ -46,7 +91,12 @@ impl<I> ValidEpoch<I> {
                 self.epoch,
                 self.epoch_total,
                 iteration,
+                None,
             ));
+
+            if reached_max_iterations(self.max_iterations, iteration) {
+                break;
+            }
         }
         callback.on_valid_end_epoch(self.epoch);
 
@@ -55,42 +105,365 @@ impl<I> ValidEpoch<I> {
 }
 
 impl<TI> TrainEpoch<TI> {
-    pub fn run<M, O, TO, VO>(
+    /// Resume the epoch after the given number of iterations, the corresponding items are
+    /// skipped without being processed.
+    pub fn with_iteration_start(mut self, iteration: usize) -> Self {
+        self.iteration_start = iteration;
+        self
+    }
+
+    /// Stop the epoch after the given number of iterations, not counting the ones skipped when
+    /// [resuming](Self::with_iteration_start).
+    ///
+    /// With multiple devices, the epoch stops after the first group of items processed in
+    /// parallel that reaches the limit.
+    pub fn with_max_iterations(mut self, max_iterations: usize) -> Self {
+        self.max_iterations = Some(max_iterations);
+        self
+    }
+
+    /// Check the gradients of each step for non-finite values, applying the given action when
+    /// they are found. Gradients and inputs are dumped in the given directory.
+    pub fn with_nan_grad(mut self, action: NanGradAction, directory: &str) -> Self {
+        self.nan_grad = Some((action, directory.to_string()));
+        self
+    }
+
+    /// Append the norm of the gradients of each parameter to the given CSV file every `interval`
+    /// iterations.
+    pub fn with_grad_norm_log(mut self, interval: usize, file: &str) -> Self {
+        self.grad_norm_log = Some((interval, file.to_string()));
+        self
+    }
+
+    /// Update the model with the gradients of the last accumulation group at the end of the
+    /// epoch, even when it contains fewer items than the
+    /// [accumulation](crate::LearnerBuilder::grads_accumulation). The gradients are scaled by the
+    /// ratio between the accumulation and the number of items in the group, so the update has
+    /// the magnitude of a full group.
+    ///
+    /// Without it, the gradients of a partial group are discarded.
+    pub fn with_grad_accumulation_flush(mut self) -> Self {
+        self.grad_accumulation_flush = true;
+        self
+    }
+
+    /// Keep the accumulated gradients on the default device of the backend between the
+    /// accumulation steps, see [GradientsAccumulator::offloaded].
+    pub fn with_grad_accumulation_offload(mut self) -> Self {
+        self.grad_accumulation_offload = true;
+        self
+    }
+
+    /// Average the accumulated gradients instead of summing them, so the learning rate behaves
+    /// as with a single batch of all the accumulated items, see
+    /// [auto_scale_lr_with_accumulation](crate::LearnerBuilder::auto_scale_lr_with_accumulation).
+    pub fn with_grad_accumulation_scale_lr(mut self) -> Self {
+        self.grad_accumulation_scale_lr = true;
+        self
+    }
+
+    /// The number of optimizer updates done since the start of the training, before this epoch
+    /// or before the iteration it resumes from.
+    pub fn with_step_start(mut self, step_start: usize) -> Self {
+        self.step_start = step_start;
+        self
+    }
+
+    /// Freeze the parameters of the [schedule](FreezeSchedule) while its steps aren't reached.
+    pub fn with_freeze_schedule(mut self, schedule: FreezeSchedule) -> Self {
+        self.freeze_schedule = Some(schedule);
+        self
+    }
+
+    /// The number of items processed by the last run of the epoch.
+    pub fn num_steps(&self) -> usize {
+        self.num_steps.get()
+    }
+
+    /// The number of optimizer updates done since the start of the training, including the ones
+    /// of the epoch.
+    pub fn step(&self) -> usize {
+        self.step_start + self.num_updates.get()
+    }
+
+    /// If the epoch was stopped before processing every item, which happens when
+    /// [non-finite gradients](NanGradAction::DumpAndStop) are found.
+    pub fn is_stopped(&self) -> bool {
+        self.stopped.get()
+    }
+
+    /// The [state](TrainStep::inputs_state) of the inputs of a step when they are
+    /// [dumped](NanGradAction::DumpAndStop) with non-finite gradients.
+    fn inputs_state<M, TO>(&self, model: &M, item: &TI) -> Option<State<f32>>
+    where
+        M: TrainStep<TI, TO>,
+    {
+        match &self.nan_grad {
+            Some((NanGradAction::DumpAndStop, _)) => Some(model.inputs_state(item)),
+            _ => None,
+        }
+    }
+
+    /// If the gradients can be used to update the model, applying the
+    /// [non-finite gradients action](NanGradAction) otherwise.
+    fn check_grads<M: ADModule>(
+        &self,
+        model: &M,
+        grads: &GradientsParams,
+        inputs: Option<State<f32>>,
+        iteration: usize,
+    ) -> bool {
+        let (action, directory) = match &self.nan_grad {
+            Some(nan_grad) => nan_grad,
+            None => return true,
+        };
+
+        if grads.is_finite(model) {
+            return true;
+        }
+
+        match action {
+            NanGradAction::Skip => {
+                log::warn!(
+                    "Non-finite gradients at iteration {} of epoch {}, skipping the update",
+                    iteration,
+                    self.epoch
+                );
+            }
+            NanGradAction::DumpAndStop => {
+                let inputs = inputs.unwrap_or_else(|| State::StateNamed(StateNamed::new()));
+
+                match dump_grads(directory, model, grads, inputs, self.epoch, iteration) {
+                    Ok(file) => log::error!(
+                        "Non-finite gradients at iteration {} of epoch {}, saved to {}",
+                        iteration,
+                        self.epoch,
+                        file
+                    ),
+                    Err(err) => log::error!("Unable to save the non-finite gradients: {}", err),
+                }
+                self.stopped.set(true);
+            }
+        }
+
+        false
+    }
+
+    /// Log the norms of the gradients when an iteration in `iterations` reaches the
+    /// [logging interval](Self::with_grad_norm_log), the rows are labeled with the last
+    /// iteration.
+    fn log_grad_norms<M: ADModule>(
+        &self,
+        model: &M,
+        grads: &GradientsParams,
+        iterations: core::ops::RangeInclusive<usize>,
+    ) {
+        let (interval, file) = match &self.grad_norm_log {
+            Some(grad_norm_log) => grad_norm_log,
+            None => return,
+        };
+
+        let iteration = *iterations.end();
+        if !iterations.into_iter().any(|i| i % interval == 0) {
+            return;
+        }
+
+        let names = self.param_names.get_or_init(|| param_names(model));
+        if let Err(err) = log_grad_norms(file, names, model, grads, self.epoch, iteration) {
+            log::error!("Unable to log the gradient norms: {}", err);
+        }
+    }
+
+    /// Update the model with the gradients of a partial accumulation group when the
+    /// [flush](Self::with_grad_accumulation_flush) is enabled.
+    fn flush_grad_accumulation<M, O>(
+        &self,
+        model: M,
+        optim: &mut O,
+        accumulator: &mut GradientsAccumulator,
+        accumulation: usize,
+        accumulation_current: usize,
+        lr_scheduler: &mut Option<Box<dyn LrScheduler>>,
+    ) -> M
+    where
+        M: ADModule,
+        O: Optimizer<Backend = M::ADBackend>,
+    {
+        if !self.grad_accumulation_flush || accumulation_current == 0 || self.is_stopped() {
+            return model;
+        }
+
+        log::info!(
+            "Flushing the gradients of the last {} accumulated items of epoch {}",
+            accumulation_current,
+            self.epoch
+        );
+        let grads = self.accumulated_grads(&model, accumulator, accumulation, accumulation_current);
+
+        self.update_module(model, optim, grads, lr_scheduler)
+    }
+
+    /// The gradients of the current accumulation group. A partial group is scaled to the
+    /// magnitude of a full group, unless the gradients are
+    /// [averaged](Self::with_grad_accumulation_scale_lr), then they are divided by the number
+    /// of items in the group.
+    fn accumulated_grads<M: ADModule>(
+        &self,
+        model: &M,
+        accumulator: &mut GradientsAccumulator,
+        accumulation: usize,
+        accumulation_current: usize,
+    ) -> GradientsParams {
+        let grads = accumulator.grads_to_device(model);
+
+        if self.grad_accumulation_scale_lr {
+            return grads.scale(1.0 / accumulation_current as f64, model);
+        }
+
+        match accumulation_current < accumulation {
+            true => grads.scale(accumulation as f64 / accumulation_current as f64, model),
+            false => grads,
+        }
+    }
+
+    /// Update the model with the optimizer, which counts as a [step](Self::step).
+    fn update_module<M, O>(
+        &self,
+        model: M,
+        optim: &mut O,
+        grads: GradientsParams,
+        lr_scheduler: &mut Option<Box<dyn LrScheduler>>,
+    ) -> M
+    where
+        M: ADModule,
+        O: Optimizer<Backend = M::ADBackend>,
+    {
+        if let Some(lr_scheduler) = lr_scheduler {
+            optim.set_learning_rate(lr_scheduler.step());
+        }
+        self.num_updates.set(self.num_updates.get() + 1);
+
+        optim.update_module(model, grads)
+    }
+
+    fn grads_accumulator(&self) -> GradientsAccumulator {
+        match self.grad_accumulation_offload {
+            true => GradientsAccumulator::offloaded(),
+            false => GradientsAccumulator::new(),
+        }
+    }
+
+    /// Freeze or unfreeze the parameters of the [schedule](Self::with_freeze_schedule) when the
+    /// state required by the current [step](Self::step) changes.
+    fn update_freeze<M: ADModule>(&self, model: M) -> M {
+        let schedule = match &self.freeze_schedule {
+            Some(freeze_schedule) => freeze_schedule,
+            None => return model,
+        };
+
+        let step = self.step();
+        let frozen = schedule.is_frozen(step);
+        if self.frozen.get() == Some(frozen) {
+            return model;
+        }
+
+        match frozen {
+            true => log::info!("Freezing the scheduled parameters at step {}", step),
+            false => log::info!("Unfreezing the scheduled parameters at step {}", step),
+        }
+        self.frozen.set(Some(frozen));
+
+        schedule.apply(model, step)
+    }
+
+    fn skip_iterations<I: Iterator<Item = TI>>(&self, iterator: &mut I) -> usize {
+        let mut iteration = 0;
+
+        while iteration < self.iteration_start && iterator.next().is_some() {
+            iteration += 1;
+        }
+
+        iteration
+    }
+
+    fn should_checkpoint(&self, iteration: usize) -> bool {
+        match self.checkpoint_interval {
+            Some(interval) => iteration % interval == 0,
+            None => false,
+        }
+    }
+
+    /// The last iterations of the epoch are checkpointed even when they don't complete an
+    /// interval, so the end of the training split is always covered by a mid-epoch checkpoint.
+    fn should_checkpoint_last(&self, iteration: usize, iteration_start: usize) -> bool {
+        self.checkpoint_interval.is_some()
+            && iteration > iteration_start
+            && !self.should_checkpoint(iteration)
+            && !self.is_stopped()
+    }
+
+    /// Run the training epoch.
+    ///
+    /// When a learning rate scheduler is provided, it is stepped before every optimizer update.
+    /// When a checkpoint interval is set, `checkpoint` is called with the model, the optimizer and
+    /// the current iteration every time the interval is reached and after the last iteration.
+    pub fn run<M, O, TO, VO, C>(
         &self,
         mut model: M,
         mut optim: O,
         callback: &mut Box<dyn LearnerCallback<TO, VO>>,
+        lr_scheduler: &mut Option<Box<dyn LrScheduler>>,
+        mut checkpoint: C,
     ) -> (M, O)
     where
         M: ADModule,
         O: Optimizer<Backend = M::ADBackend>,
         M: TrainStep<TI, TO>,
+        C: FnMut(&M, &O, usize),
     {
         log::info!("Executing training step for epoch {}", self.epoch,);
 
         let mut iterator = self.dataloader.iter();
-        let mut iteration = 0;
-        let mut accumulator = GradientsAccumulator::new();
+        let mut iteration = self.skip_iterations(&mut iterator);
+        let iteration_start = iteration;
+        let mut accumulator = self.grads_accumulator();
         let mut accumulation_current = 0;
+        let mut step = iteration_start / self.grad_accumulation.unwrap_or(1);
+        self.num_updates.set(0);
 
         while let Some(item) = iterator.next() {
+            model = self.update_freeze(model);
             iteration += 1;
 
             let progress = iterator.progress();
-            let item = model.step(item);
+            let inputs = self.inputs_state::<M, TO>(&model, &item);
+            let item = model.step(model.prepare(item));
+            self.log_grad_norms(&model, &item.grads, iteration..=iteration);
 
-            match self.grad_accumulation {
-                Some(accumulation) => {
-                    accumulator.accumulate(&model, item.grads);
-                    accumulation_current += 1;
+            if self.check_grads(&model, &item.grads, inputs, iteration) {
+                match self.grad_accumulation {
+                    Some(accumulation) => {
+                        accumulator.accumulate(&model, item.grads);
+                        accumulation_current += 1;
 
-                    if accumulation <= accumulation_current {
-                        let grads = accumulator.grads();
-                        model = optim.update_module(model, grads);
-                        accumulation_current = 0;
+                        if accumulation <= accumulation_current {
+                            let grads = self.accumulated_grads(
+                                &model,
+                                &mut accumulator,
+                                accumulation,
+                                accumulation_current,
+                            );
+                            model = self.update_module(model, &mut optim, grads, lr_scheduler);
+                            accumulation_current = 0;
+                            step += 1;
+                        }
+                    }
+                    None => {
+                        model = self.update_module(model, &mut optim, item.grads, lr_scheduler);
+                        step += 1;
                     }
                 }
-                None => model = optim.update_module(model, item.grads),
             }
 
             callback.on_train_item(LearnerItem::new(
@@ -99,8 +472,36 @@ impl<TI> TrainEpoch<TI> {
                 self.epoch,
                 self.epoch_total,
                 iteration,
+                Some(step),
             ));
+
+            if self.is_stopped() {
+                break;
+            }
+
+            if self.should_checkpoint(iteration) {
+                checkpoint(&model, &optim, iteration);
+            }
+
+            if reached_max_iterations(self.max_iterations, iteration - iteration_start) {
+                break;
+            }
+        }
+
+        if let Some(accumulation) = self.grad_accumulation {
+            model = self.flush_grad_accumulation(
+                model,
+                &mut optim,
+                &mut accumulator,
+                accumulation,
+                accumulation_current,
+                lr_scheduler,
+            );
         }
+        if self.should_checkpoint_last(iteration, iteration_start) {
+            checkpoint(&model, &optim, iteration);
+        }
+        self.num_steps.set(iteration - iteration_start);
         callback.on_train_end_epoch(self.epoch);
 
         (model, optim)
@@ -108,12 +509,14 @@ impl<TI> TrainEpoch<TI> {
 }
 
 impl<TI> TrainEpoch<TI> {
-    pub fn run_multi_device<M, O, TO, VO>(
+    pub fn run_multi_device<M, O, TO, VO, C>(
         &self,
         mut model: M,
         mut optim: O,
         callback: &mut Box<dyn LearnerCallback<TO, VO>>,
+        lr_scheduler: &mut Option<Box<dyn LrScheduler>>,
         devices: Vec<<M::Backend as Backend>::Device>,
+        mut checkpoint: C,
     ) -> (M, O)
     where
         O: Optimizer<Backend = M::ADBackend>,
@@ -121,6 +524,7 @@ impl<TI> TrainEpoch<TI> {
         M: ADModule + 'static,
         TI: Send + 'static,
         TO: Send + 'static,
+        C: FnMut(&M, &O, usize),
     {
         log::info!(
             "Executing training step for epoch {} on devices {:?}",
@@ -129,50 +533,743 @@ impl<TI> TrainEpoch<TI> {
         );
 
         let mut iterator = self.dataloader.iter();
-        let mut iteration = 0;
-        let mut accumulator = GradientsAccumulator::new();
+        let mut iteration = self.skip_iterations(&mut iterator);
+        let iteration_start = iteration;
+        let mut accumulator = self.grads_accumulator();
         let mut accumulation_current = 0;
 
         let accumulation = self.grad_accumulation.unwrap_or(1) * devices.len();
+        let mut num_updates = iteration_start / accumulation;
+        self.num_updates.set(0);
         let step = MultiDevicesTrainStep::new(&devices);
 
         // The main device is always the first in the list.
         let device_main = devices.get(0).unwrap().clone();
+        let reducer = TreeAllReduce;
 
         loop {
-            let items = step.step(&mut iterator, &model);
+            model = self.update_freeze(model);
+            let mut inputs = StateNamed::new();
+            let items = step.step_inspect(&mut iterator, &model, |index, item| {
+                if let Some(state) = self.inputs_state::<M, TO>(&model, item) {
+                    inputs.register_state(index.to_string().as_str(), state);
+                }
+            });
             if items.is_empty() {
                 break;
             }
 
+            let mut grads = Vec::with_capacity(items.len());
+            let mut outputs = Vec::with_capacity(items.len());
+
             for item in items {
-                iteration += 1;
-                let progress = iterator.progress();
+                grads.push(item.grads);
+                outputs.push(item.item);
+            }
 
-                let grads = item.grads.to_device(&device_main, &model);
+            let num_grads = grads.len();
+            let grads = reducer
+                .reduce(&model, grads)
+                .to_device(&device_main, &model);
+            self.log_grad_norms(&model, &grads, iteration + 1..=iteration + num_grads);
 
-                log::info!("Updated device");
+            let inputs = Some(State::StateNamed(inputs));
+            if self.check_grads(&model, &grads, inputs, iteration + num_grads) {
+                accumulation_current += num_grads;
                 accumulator.accumulate(&model, grads);
-                accumulation_current += 1;
+            }
 
-                if accumulation <= accumulation_current {
-                    let grads = accumulator.grads();
-                    model = optim.update_module(model, grads);
-                    accumulation_current = 0;
-                }
+            if accumulation <= accumulation_current {
+                let grads = self.accumulated_grads(
+                    &model,
+                    &mut accumulator,
+                    accumulation,
+                    accumulation_current,
+                );
+                model = self.update_module(model, &mut optim, grads, lr_scheduler);
+                accumulation_current = 0;
+                num_updates += 1;
+            }
+
+            for item in outputs {
+                iteration += 1;
+                let progress = iterator.progress();
 
                 callback.on_train_item(LearnerItem::new(
-                    item.item,
+                    item,
                     progress,
                     self.epoch,
                     self.epoch_total,
                     iteration,
+                    Some(num_updates),
                 ));
+
+                if self.should_checkpoint(iteration) && !self.is_stopped() {
+                    checkpoint(&model, &optim, iteration);
+                }
+            }
+
+            if self.is_stopped()
+                || reached_max_iterations(self.max_iterations, iteration - iteration_start)
+            {
+                break;
             }
         }
 
+        model = self.flush_grad_accumulation(
+            model,
+            &mut optim,
+            &mut accumulator,
+            accumulation,
+            accumulation_current,
+            lr_scheduler,
+        );
+        if self.should_checkpoint_last(iteration, iteration_start) {
+            checkpoint(&model, &optim, iteration);
+        }
+        self.num_steps.set(iteration - iteration_start);
         callback.on_train_end_epoch(self.epoch);
 
         (model, optim)
     }
 }
+
+impl<TI: Clone> TrainEpoch<TI> {
+    /// Run the training epoch with multiple optimizers.
+    ///
+    /// For each item, the sub-steps are executed in the order given by the schedule, where each
+    /// entry is the index of a sub-step and of the optimizer applying its gradients. The model is
+    /// updated after every sub-step, so a sub-step sees the updates of the previous ones. The
+    /// output of every sub-step is given to the callback.
+    ///
+    /// Gradient accumulation and checkpointing aren't supported with multiple optimizers.
+    pub fn run_multi_optim<M, O, TO, VO>(
+        &self,
+        mut model: M,
+        mut optims: Vec<O>,
+        schedule: &[usize],
+        callback: &mut Box<dyn LearnerCallback<TO, VO>>,
+    ) -> (M, Vec<O>)
+    where
+        M: ADModule,
+        O: Optimizer<Backend = M::ADBackend>,
+        M: MultiTrainStep<TI, TO>,
+    {
+        log::info!(
+            "Executing training step for epoch {} with {} optimizers",
+            self.epoch,
+            optims.len()
+        );
+
+        if let Some(substep) = schedule.iter().find(|substep| **substep >= optims.len()) {
+            panic!(
+                "No optimizer registered for sub-step {}, only {} optimizers are available",
+                substep,
+                optims.len()
+            );
+        }
+
+        let mut iterator = self.dataloader.iter();
+        let mut iteration = self.skip_iterations(&mut iterator);
+        let iteration_start = iteration;
+        let mut step = iteration_start * schedule.len();
+
+        while let Some(item) = iterator.next() {
+            iteration += 1;
+            let progress = iterator.progress();
+
+            for substep in schedule {
+                let output = model.step(*substep, item.clone());
+                model = optims[*substep].update_module(model, output.grads);
+                step += 1;
+
+                callback.on_train_item(LearnerItem::new(
+                    output.item,
+                    progress.clone(),
+                    self.epoch,
+                    self.epoch_total,
+                    iteration,
+                    Some(step),
+                ));
+            }
+
+            if reached_max_iterations(self.max_iterations, iteration - iteration_start) {
+                break;
+            }
+        }
+        callback.on_train_end_epoch(self.epoch);
+
+        (model, optims)
+    }
+}
+
+fn reached_max_iterations(max_iterations: Option<usize>, iteration: usize) -> bool {
+    match max_iterations {
+        Some(max_iterations) => iteration >= max_iterations,
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{to_model_device, TrainOutput};
+    use burn_core as burn;
+    use burn_core::data::dataloader::batcher::{BatchToDevice, Batcher};
+    use burn_core::data::dataloader::DataLoaderBuilder;
+    use burn_core::data::dataset::InMemDataset;
+    use burn_core::module::{list_param_ids, Module, Param};
+    use burn_core::nn::{Initializer, Linear, LinearConfig};
+    use burn_core::optim::{Sgd, SgdConfig};
+    use burn_core::tensor::{Data, Distribution, Tensor};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Mutex;
+
+    type TestADBackend = burn_autodiff::ADBackendDecorator<burn_ndarray::NdArrayBackend<f32>>;
+
+    struct RandomBatcher;
+
+    impl Batcher<usize, Tensor<TestADBackend, 2>> for RandomBatcher {
+        fn batch(&self, items: Vec<usize>) -> Tensor<TestADBackend, 2> {
+            Tensor::random([items.len(), 4], Distribution::Standard)
+        }
+    }
+
+    impl TrainStep<Tensor<TestADBackend, 2>, ()> for Linear<TestADBackend> {
+        fn step(&self, item: Tensor<TestADBackend, 2>) -> TrainOutput<()> {
+            let loss = self.forward(item).mean();
+
+            TrainOutput::new(self, loss.backward(), ())
+        }
+
+        fn inputs_state(&self, item: &Tensor<TestADBackend, 2>) -> State<f32> {
+            BatchToDevice::<TestADBackend>::state(item)
+        }
+    }
+
+    struct NoopCallback;
+
+    impl LearnerCallback<(), ()> for NoopCallback {}
+
+    struct CountingScheduler {
+        num_steps: Arc<AtomicUsize>,
+    }
+
+    impl LrScheduler for CountingScheduler {
+        fn step(&mut self) -> f64 {
+            self.num_steps.fetch_add(1, Ordering::Relaxed);
+            0.01
+        }
+    }
+
+    fn dataloader(num_items: usize) -> Arc<dyn DataLoader<Tensor<TestADBackend, 2>>> {
+        DataLoaderBuilder::new(Arc::new(RandomBatcher))
+            .batch_size(1)
+            .build(Arc::new(InMemDataset::new((0..num_items).collect())))
+    }
+
+    fn run_epoch(checkpoint_interval: Option<usize>, iteration_start: usize) -> Vec<usize> {
+        let epoch = TrainEpoch::new(dataloader(5), 1, 1, None, checkpoint_interval)
+            .with_iteration_start(iteration_start);
+
+        let model = Linear::<TestADBackend>::new(&LinearConfig::new(4, 2));
+        let optim = Sgd::new(&SgdConfig::new(0.01));
+        let mut callback: Box<dyn LearnerCallback<(), ()>> = Box::new(NoopCallback);
+        let mut checkpoints = Vec::new();
+
+        epoch.run(model, optim, &mut callback, &mut None, |_, _, iteration| {
+            checkpoints.push(iteration)
+        });
+
+        checkpoints
+    }
+
+    #[test]
+    fn test_checkpoint_every_two_iterations() {
+        let checkpoints = run_epoch(Some(2), 0);
+
+        // The last iteration completes a partial interval.
+        assert_eq!(checkpoints, vec![2, 4, 5]);
+    }
+
+    #[test]
+    fn test_no_checkpoint_without_interval() {
+        let checkpoints = run_epoch(None, 0);
+
+        assert!(checkpoints.is_empty());
+    }
+
+    #[test]
+    fn test_resume_from_iteration_should_skip_processed_items() {
+        let checkpoints = run_epoch(Some(2), 2);
+
+        assert_eq!(checkpoints, vec![4, 5]);
+    }
+
+    #[derive(Module, Debug)]
+    struct Pair<B: Backend> {
+        first: Param<Linear<B>>,
+        second: Param<Linear<B>>,
+    }
+
+    impl MultiTrainStep<Tensor<TestADBackend, 2>, usize> for Pair<TestADBackend> {
+        fn step(&self, substep: usize, item: Tensor<TestADBackend, 2>) -> TrainOutput<usize> {
+            // The loss depends on both layers, only the gradients of one of them are returned.
+            let loss = self.second.forward(self.first.forward(item)).mean();
+
+            match substep {
+                0 => TrainOutput::new(&*self.first, loss.backward(), substep),
+                _ => TrainOutput::new(&*self.second, loss.backward(), substep),
+            }
+        }
+    }
+
+    struct SubstepCallback {
+        substeps: Arc<Mutex<Vec<usize>>>,
+    }
+
+    impl LearnerCallback<usize, ()> for SubstepCallback {
+        fn on_train_item(&mut self, item: LearnerItem<usize>) {
+            self.substeps.lock().unwrap().push(item.item);
+        }
+    }
+
+    fn run_multi_optim_epoch(schedule: &[usize]) -> (bool, bool, Vec<usize>) {
+        let epoch = TrainEpoch::new(dataloader(3), 1, 1, None, None);
+        let model = Pair {
+            first: Param::from(Linear::<TestADBackend>::new(&LinearConfig::new(4, 4))),
+            second: Param::from(Linear::<TestADBackend>::new(&LinearConfig::new(4, 2))),
+        };
+        let first = model.first.state();
+        let second = model.second.state();
+        let optims = vec![
+            Sgd::new(&SgdConfig::new(0.1)),
+            Sgd::new(&SgdConfig::new(0.1)),
+        ];
+        let substeps = Arc::new(Mutex::new(Vec::new()));
+        let mut callback: Box<dyn LearnerCallback<usize, ()>> = Box::new(SubstepCallback {
+            substeps: substeps.clone(),
+        });
+
+        let (model, _) = epoch.run_multi_optim(model, optims, schedule, &mut callback);
+        let substeps = substeps.lock().unwrap().clone();
+
+        (
+            model.first.state() != first,
+            model.second.state() != second,
+            substeps,
+        )
+    }
+
+    #[test]
+    fn test_multi_optim_should_only_update_the_module_of_each_substep() {
+        assert_eq!(run_multi_optim_epoch(&[0]), (true, false, vec![0; 3]));
+        assert_eq!(run_multi_optim_epoch(&[1]), (false, true, vec![1; 3]));
+        assert_eq!(
+            run_multi_optim_epoch(&[0, 1]),
+            (true, true, vec![0, 1, 0, 1, 0, 1])
+        );
+    }
+
+    impl TrainStep<Tensor<TestADBackend, 2>, ()> for Pair<TestADBackend> {
+        fn step(&self, item: Tensor<TestADBackend, 2>) -> TrainOutput<()> {
+            let loss = self.second.forward(self.first.forward(item)).mean();
+
+            TrainOutput::new(self, loss.backward(), ())
+        }
+    }
+
+    #[test]
+    fn test_freeze_schedule_should_only_update_the_backbone_after_unfreezing() {
+        let model = Pair {
+            first: Param::from(Linear::<TestADBackend>::new(&LinearConfig::new(4, 4))),
+            second: Param::from(Linear::<TestADBackend>::new(&LinearConfig::new(4, 2))),
+        };
+        let schedule = FreezeSchedule::new(list_param_ids(&*model.first), 3);
+        let mut optim = Sgd::new(&SgdConfig::new(0.1));
+        let mut callback: Box<dyn LearnerCallback<(), ()>> = Box::new(NoopCallback);
+        let (mut first, mut second) = (model.first.state(), model.second.state());
+        let mut model = model;
+        let mut step = 0;
+
+        // Steps are optimizer updates: with an accumulation of 2, the first epoch covers the 3
+        // frozen steps in 6 iterations, the second one starts after unfreezing.
+        for (epoch, backbone_updated) in [(1, false), (2, true)] {
+            let epoch = TrainEpoch::new(dataloader(6), epoch, 2, Some(2), None)
+                .with_step_start(step)
+                .with_freeze_schedule(schedule.clone());
+            (model, optim) = epoch.run(model, optim, &mut callback, &mut None, |_, _, _| {});
+            step = epoch.step();
+
+            assert_eq!(model.first.state() != first, backbone_updated);
+            assert_ne!(model.second.state(), second);
+            (first, second) = (model.first.state(), model.second.state());
+        }
+        assert_eq!(step, 6);
+    }
+
+    #[test]
+    fn test_lr_scheduler_should_step_once_per_optimizer_update() {
+        let epoch = TrainEpoch::new(dataloader(16), 1, 1, Some(4), None);
+        let num_steps = Arc::new(AtomicUsize::new(0));
+        let mut lr_scheduler: Option<Box<dyn LrScheduler>> = Some(Box::new(CountingScheduler {
+            num_steps: num_steps.clone(),
+        }));
+
+        let model = Linear::<TestADBackend>::new(&LinearConfig::new(4, 2));
+        let optim = Sgd::new(&SgdConfig::new(0.01));
+        let mut callback: Box<dyn LearnerCallback<(), ()>> = Box::new(NoopCallback);
+
+        epoch.run(model, optim, &mut callback, &mut lr_scheduler, |_, _, _| {});
+
+        // 16 micro-batches with an accumulation of 4 only update the parameters 4 times.
+        assert_eq!(num_steps.load(Ordering::Relaxed), 4);
+    }
+
+    fn accumulation_dataloader(batch_size: usize) -> Arc<dyn DataLoader<Tensor<TestADBackend, 2>>> {
+        // Every item has the same gradients, so each update moves the bias by the same amount.
+        DataLoaderBuilder::new(Arc::new(NanBatcher {
+            nan_item: usize::MAX,
+        }))
+        .batch_size(batch_size)
+        .build(Arc::new(InMemDataset::new((0..5).collect())))
+    }
+
+    fn run_bias_epoch(
+        epoch: TrainEpoch<Tensor<TestADBackend, 2>>,
+    ) -> (usize, Tensor<TestADBackend, 2>) {
+        let num_steps = Arc::new(AtomicUsize::new(0));
+        let mut lr_scheduler: Option<Box<dyn LrScheduler>> = Some(Box::new(CountingScheduler {
+            num_steps: num_steps.clone(),
+        }));
+
+        let config = LinearConfig::new(4, 2).with_initializer(Initializer::Zeros);
+        let model = Linear::<TestADBackend>::new(&config);
+        let optim = Sgd::new(&SgdConfig::new(0.01));
+        let mut callback: Box<dyn LearnerCallback<(), ()>> = Box::new(NoopCallback);
+
+        let (model, _) = epoch.run(model, optim, &mut callback, &mut lr_scheduler, |_, _, _| {});
+
+        // The output of a zero input is the bias.
+        let bias = model.forward(Tensor::zeros([1, 4]));
+
+        (num_steps.load(Ordering::Relaxed), bias)
+    }
+
+    fn run_accumulation_epoch(flush: bool) -> (usize, Tensor<TestADBackend, 2>) {
+        let mut epoch = TrainEpoch::new(accumulation_dataloader(1), 1, 1, Some(2), None);
+        if flush {
+            epoch = epoch.with_grad_accumulation_flush();
+        }
+
+        run_bias_epoch(epoch)
+    }
+
+    #[test]
+    fn test_grad_accumulation_flush_should_update_with_the_last_partial_group() {
+        // The gradient of the bias is 0.5 for each item, a group of 2 items moves it by 0.01.
+        let (num_steps, bias) = run_accumulation_epoch(false);
+        assert_eq!(num_steps, 2);
+        bias.into_data()
+            .assert_approx_eq(&Data::from([[-0.02, -0.02]]), 5);
+
+        // The last group only has one item, its gradients are scaled to match a full group.
+        let (num_steps, bias) = run_accumulation_epoch(true);
+        assert_eq!(num_steps, 3);
+        bias.into_data()
+            .assert_approx_eq(&Data::from([[-0.03, -0.03]]), 5);
+    }
+
+    #[test]
+    fn test_grad_accumulation_scale_lr_should_match_a_single_large_batch() {
+        // The gradient of the bias is 0.5 for each item, summed over the 2 items of a group.
+        let epoch = TrainEpoch::new(accumulation_dataloader(1), 1, 1, Some(2), None);
+        let (num_steps, bias_summed) = run_bias_epoch(epoch);
+        assert_eq!(num_steps, 2);
+        bias_summed
+            .clone()
+            .into_data()
+            .assert_approx_eq(&Data::from([[-0.02, -0.02]]), 5);
+
+        // Averaged, each update moves the bias by half as much.
+        let epoch = TrainEpoch::new(accumulation_dataloader(1), 1, 1, Some(2), None)
+            .with_grad_accumulation_scale_lr();
+        let (num_steps, bias_averaged) = run_bias_epoch(epoch);
+        assert_eq!(num_steps, 2);
+        bias_averaged
+            .clone()
+            .into_data()
+            .assert_approx_eq(&bias_summed.div_scalar(2.0).into_data(), 5);
+
+        // Which is the trajectory of the same updates with batches of 2 items.
+        let epoch =
+            TrainEpoch::new(accumulation_dataloader(2), 1, 1, None, None).with_max_iterations(2);
+        let (num_steps, bias_batched) = run_bias_epoch(epoch);
+        assert_eq!(num_steps, 2);
+        bias_averaged
+            .into_data()
+            .assert_approx_eq(&bias_batched.into_data(), 5);
+    }
+
+    struct NanBatcher {
+        nan_item: usize,
+    }
+
+    impl Batcher<usize, Tensor<TestADBackend, 2>> for NanBatcher {
+        fn batch(&self, items: Vec<usize>) -> Tensor<TestADBackend, 2> {
+            let tensor = Tensor::ones([items.len(), 4]);
+
+            match items.contains(&self.nan_item) {
+                true => tensor.mul_scalar(f32::NAN),
+                false => tensor,
+            }
+        }
+    }
+
+    struct CountingCallback {
+        num_items: Arc<AtomicUsize>,
+    }
+
+    impl LearnerCallback<(), ()> for CountingCallback {
+        fn on_train_item(&mut self, _item: LearnerItem<()>) {
+            self.num_items.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    fn run_nan_epoch(action: Option<NanGradAction>, directory: &str) -> (bool, usize, bool) {
+        let dataloader = DataLoaderBuilder::new(Arc::new(NanBatcher { nan_item: 2 }))
+            .batch_size(1)
+            .build(Arc::new(InMemDataset::new((0..5).collect())));
+        let mut epoch = TrainEpoch::new(dataloader, 1, 1, None, None);
+        if let Some(action) = action {
+            epoch = epoch.with_nan_grad(action, directory);
+        }
+
+        let model = Linear::<TestADBackend>::new(&LinearConfig::new(4, 2));
+        let optim = Sgd::new(&SgdConfig::new(0.01));
+        let num_items = Arc::new(AtomicUsize::new(0));
+        let mut callback: Box<dyn LearnerCallback<(), ()>> = Box::new(CountingCallback {
+            num_items: num_items.clone(),
+        });
+
+        let (model, _) = epoch.run(model, optim, &mut callback, &mut None, |_, _, _| {});
+        let output = model.forward(Tensor::ones([1, 4])).into_data();
+        let is_finite = output.value.iter().all(|value| value.is_finite());
+
+        (
+            is_finite,
+            num_items.load(Ordering::Relaxed),
+            epoch.is_stopped(),
+        )
+    }
+
+    #[test]
+    fn test_nan_grad_skip_should_not_update_the_model() {
+        // Without any action, the injected NaN gradients corrupt the model.
+        assert_eq!(run_nan_epoch(None, ""), (false, 5, false));
+        assert_eq!(
+            run_nan_epoch(Some(NanGradAction::Skip), ""),
+            (true, 5, false)
+        );
+    }
+
+    #[test]
+    fn test_nan_grad_dump_and_stop_should_save_the_gradients() {
+        let directory = std::env::temp_dir().join("burn-train-test-nan-grad");
+        let directory = directory.to_str().unwrap();
+        std::fs::remove_dir_all(directory).ok();
+
+        let result = run_nan_epoch(Some(NanGradAction::DumpAndStop), directory);
+
+        assert_eq!(result, (true, 3, true));
+        // The NaN values of the gradients are written as null.
+        let content =
+            std::fs::read_to_string(format!("{directory}/epoch-1-iteration-3.json")).unwrap();
+        assert!(content.contains("\"grads\""));
+        assert!(content.contains("\"inputs\""));
+        assert!(!content.contains("\"model\""));
+        assert!(content.contains("null"));
+    }
+
+    #[test]
+    fn test_grad_norm_log_should_have_one_row_per_param_at_each_interval() {
+        let file = std::env::temp_dir().join("burn-train-test-grad-norms.csv");
+        let file = file.to_str().unwrap();
+        std::fs::remove_file(file).ok();
+
+        let epoch = TrainEpoch::new(dataloader(5), 1, 1, None, None).with_grad_norm_log(2, file);
+        let model = Linear::<TestADBackend>::new(&LinearConfig::new(4, 2));
+        let optim = Sgd::new(&SgdConfig::new(0.01));
+        let mut callback: Box<dyn LearnerCallback<(), ()>> = Box::new(NoopCallback);
+
+        epoch.run(model, optim, &mut callback, &mut None, |_, _, _| {});
+
+        let content = std::fs::read_to_string(file).unwrap();
+        let rows: Vec<Vec<&str>> = content
+            .lines()
+            .skip(1)
+            .map(|line| line.split(',').collect())
+            .collect();
+        let labels: Vec<(&str, &str)> = rows.iter().map(|row| (row[1], row[2])).collect();
+
+        assert!(content.starts_with("epoch,iteration,name,norm\n"));
+        assert_eq!(
+            labels,
+            vec![
+                ("2", "bias"),
+                ("2", "weight"),
+                ("4", "bias"),
+                ("4", "weight")
+            ]
+        );
+        for row in rows {
+            assert!(row[3].parse::<f64>().unwrap() > 0.0);
+        }
+    }
+
+    type TestBackend = burn_ndarray::NdArrayBackend<f32>;
+
+    impl Batcher<usize, Tensor<TestBackend, 2>> for RandomBatcher {
+        fn batch(&self, items: Vec<usize>) -> Tensor<TestBackend, 2> {
+            Tensor::random([items.len(), 4], Distribution::Standard)
+        }
+    }
+
+    impl ValidStep<Tensor<TestBackend, 2>, ()> for Pair<TestBackend> {
+        fn step(&self, item: Tensor<TestBackend, 2>) {
+            self.second.forward(self.first.forward(item));
+        }
+    }
+
+    struct SplitCountingCallback {
+        num_items: Arc<Mutex<(usize, usize)>>,
+    }
+
+    impl LearnerCallback<(), ()> for SplitCountingCallback {
+        fn on_train_item(&mut self, _item: LearnerItem<()>) {
+            self.num_items.lock().unwrap().0 += 1;
+        }
+
+        fn on_valid_item(&mut self, _item: LearnerItem<()>) {
+            self.num_items.lock().unwrap().1 += 1;
+        }
+    }
+
+    #[test]
+    fn test_max_iterations_should_limit_the_items_of_each_split() {
+        let dataloader_valid = DataLoaderBuilder::new(Arc::new(RandomBatcher))
+            .batch_size(1)
+            .build(Arc::new(InMemDataset::new((0..5).collect())));
+        let epoch_train = TrainEpoch::new(dataloader(5), 1, 1, None, None).with_max_iterations(2);
+        let epoch_valid = ValidEpoch::new(dataloader_valid, 1, 1).with_max_iterations(2);
+
+        let model = Pair {
+            first: Param::from(Linear::<TestADBackend>::new(&LinearConfig::new(4, 4))),
+            second: Param::from(Linear::<TestADBackend>::new(&LinearConfig::new(4, 2))),
+        };
+        let optim = Sgd::new(&SgdConfig::new(0.01));
+        let num_items = Arc::new(Mutex::new((0, 0)));
+        let mut callback: Box<dyn LearnerCallback<(), ()>> = Box::new(SplitCountingCallback {
+            num_items: num_items.clone(),
+        });
+
+        let (model, _) = epoch_train.run(model, optim, &mut callback, &mut None, |_, _, _| {});
+        epoch_valid.run(model, &mut callback);
+
+        assert_eq!(epoch_train.num_steps(), 2);
+        assert_eq!(*num_items.lock().unwrap(), (2, 2));
+    }
+
+    /// The iteration and the optimizer step of each training item.
+    type ItemSteps = Arc<Mutex<Vec<(usize, Option<usize>)>>>;
+
+    struct StepCallback {
+        steps: ItemSteps,
+    }
+
+    impl LearnerCallback<(), ()> for StepCallback {
+        fn on_train_item(&mut self, item: LearnerItem<()>) {
+            self.steps.lock().unwrap().push((item.iteration, item.step));
+        }
+    }
+
+    #[test]
+    fn test_grad_accumulation_should_count_optimizer_steps() {
+        let epoch = TrainEpoch::new(dataloader(16), 1, 1, Some(4), None);
+        let model = Linear::<TestADBackend>::new(&LinearConfig::new(4, 2));
+        let optim = Sgd::new(&SgdConfig::new(0.01));
+        let steps = Arc::new(Mutex::new(Vec::new()));
+        let mut callback: Box<dyn LearnerCallback<(), ()>> = Box::new(StepCallback {
+            steps: steps.clone(),
+        });
+
+        epoch.run(model, optim, &mut callback, &mut None, |_, _, _| {});
+
+        let steps = steps.lock().unwrap();
+        assert_eq!(steps.len(), 16);
+        for (iteration, step) in steps.iter() {
+            assert_eq!(*step, Some(iteration / 4));
+        }
+        assert_eq!(steps.last(), Some(&(16, Some(4))));
+    }
+
+    #[derive(Clone, Debug)]
+    struct UnplacedBatch {
+        moved: bool,
+    }
+
+    impl BatchToDevice<TestADBackend> for UnplacedBatch {
+        fn to_device(self, _device: &<TestADBackend as Backend>::Device) -> Self {
+            Self { moved: true }
+        }
+    }
+
+    struct UnplacedBatcher;
+
+    impl Batcher<usize, UnplacedBatch> for UnplacedBatcher {
+        fn batch(&self, _items: Vec<usize>) -> UnplacedBatch {
+            UnplacedBatch { moved: false }
+        }
+    }
+
+    impl TrainStep<UnplacedBatch, bool> for Linear<TestADBackend> {
+        fn step(&self, item: UnplacedBatch) -> TrainOutput<bool> {
+            let loss = self.forward(Tensor::zeros([1, 4])).mean();
+
+            TrainOutput::new(self, loss.backward(), item.moved)
+        }
+
+        fn prepare(&self, item: UnplacedBatch) -> UnplacedBatch {
+            to_model_device(self, item)
+        }
+    }
+
+    struct MovedCallback {
+        moved: Arc<Mutex<Vec<bool>>>,
+    }
+
+    impl LearnerCallback<bool, ()> for MovedCallback {
+        fn on_train_item(&mut self, item: LearnerItem<bool>) {
+            self.moved.lock().unwrap().push(item.item);
+        }
+    }
+
+    #[test]
+    fn test_prepared_batches_should_be_moved_to_the_model_device_at_each_iteration() {
+        // The batches don't report their devices, so they are always moved.
+        let dataloader = DataLoaderBuilder::new(Arc::new(UnplacedBatcher))
+            .batch_size(1)
+            .build(Arc::new(InMemDataset::new((0..3).collect())));
+        let epoch = TrainEpoch::new(dataloader, 1, 1, None, None);
+        let model = Linear::<TestADBackend>::new(&LinearConfig::new(4, 2));
+        let optim = Sgd::new(&SgdConfig::new(0.01));
+        let moved = Arc::new(Mutex::new(Vec::new()));
+        let mut callback: Box<dyn LearnerCallback<bool, ()>> = Box::new(MovedCallback {
+            moved: moved.clone(),
+        });
+
+        epoch.run(model, optim, &mut callback, &mut None, |_, _, _| {});
+
+        assert_eq!(*moved.lock().unwrap(), vec![true; 3]);
+    }
+}
@@ -0,0 +1,185 @@
+use crate::metric::{Adaptor, Aggregate, Metric, Numeric, NumericAggregator};
+use crate::{LearnerCallback, LearnerItem, ValidEpoch, ValidStep};
+use burn_core::data::dataloader::DataLoader;
+use burn_core::module::ADModule;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// Run a single validation epoch over a model and compute the registered metrics, without the
+/// optimizer and the checkpointers required by the [learner](crate::Learner).
+///
+/// This is useful to measure the performance of a model loaded from a checkpoint.
+pub struct Evaluator<VO> {
+    metrics: Vec<Box<dyn EvaluatorMetric<VO>>>,
+}
+
+impl<VO> Default for Evaluator<VO> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<VO> Evaluator<VO> {
+    /// Create an evaluator without any metric.
+    pub fn new() -> Self {
+        Self {
+            metrics: Vec::new(),
+        }
+    }
+
+    /// Register a metric, its value is the mean of the values computed on each batch.
+    pub fn metric<M>(self, metric: M) -> Self
+    where
+        M: Metric + Numeric + 'static,
+        VO: Adaptor<M::Input> + 'static,
+    {
+        self.metric_aggregate(metric, Aggregate::Mean)
+    }
+
+    /// Register a metric, summarizing the values computed on each batch with the given
+    /// [aggregation strategy](Aggregate).
+    pub fn metric_aggregate<M>(mut self, metric: M, aggregate: Aggregate) -> Self
+    where
+        M: Metric + Numeric + 'static,
+        VO: Adaptor<M::Input> + 'static,
+    {
+        self.metrics.push(Box::new(EvaluatorMetricWrapper {
+            metric,
+            aggregator: NumericAggregator::new(aggregate),
+            name: None,
+        }));
+        self
+    }
+
+    /// Evaluate the model on the given dataloader, returning the value of each metric by name.
+    pub fn evaluate<M, VI>(
+        self,
+        model: M,
+        dataloader: Arc<dyn DataLoader<VI>>,
+    ) -> HashMap<String, f64>
+    where
+        M: ADModule,
+        M::InnerModule: ValidStep<VI, VO>,
+        VO: Send + 'static,
+    {
+        let values = Arc::new(Mutex::new(HashMap::new()));
+        let mut callback: Box<dyn LearnerCallback<(), VO>> = Box::new(EvaluatorCallback {
+            metrics: self.metrics,
+            values: values.clone(),
+        });
+
+        ValidEpoch::new(dataloader, 1, 1).run(model, &mut callback);
+
+        let values = values.lock().unwrap().clone();
+        values
+    }
+}
+
+trait EvaluatorMetric<VO>: Send {
+    fn update(&mut self, item: &VO);
+    fn value(&self) -> Option<(String, f64)>;
+}
+
+struct EvaluatorMetricWrapper<M> {
+    metric: M,
+    aggregator: NumericAggregator,
+    name: Option<String>,
+}
+
+impl<VO, M> EvaluatorMetric<VO> for EvaluatorMetricWrapper<M>
+where
+    M: Metric + Numeric + 'static,
+    VO: Adaptor<M::Input>,
+{
+    fn update(&mut self, item: &VO) {
+        let entry = self.metric.update(&item.adapt());
+
+        self.aggregator.update(self.metric.value());
+        self.name = Some(entry.name);
+    }
+
+    fn value(&self) -> Option<(String, f64)> {
+        let name = self.name.clone()?;
+
+        Some((name, self.aggregator.value()))
+    }
+}
+
+struct EvaluatorCallback<VO> {
+    metrics: Vec<Box<dyn EvaluatorMetric<VO>>>,
+    values: Arc<Mutex<HashMap<String, f64>>>,
+}
+
+impl<VO> LearnerCallback<(), VO> for EvaluatorCallback<VO> {
+    fn on_valid_item(&mut self, item: LearnerItem<VO>) {
+        for metric in self.metrics.iter_mut() {
+            metric.update(&item.item);
+        }
+    }
+
+    fn on_valid_end_epoch(&mut self, _epoch: usize) {
+        let mut values = self.values.lock().unwrap();
+
+        for metric in self.metrics.iter() {
+            if let Some((name, value)) = metric.value() {
+                values.insert(name, value);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metric::{AccuracyMetric, LossMetric};
+    use crate::ClassificationOutput;
+    use burn_core::data::dataloader::batcher::Batcher;
+    use burn_core::data::dataloader::DataLoaderBuilder;
+    use burn_core::data::dataset::InMemDataset;
+    use burn_core::nn::loss::CrossEntropyLoss;
+    use burn_core::nn::{Initializer, Linear, LinearConfig};
+    use burn_core::tensor::{Data, Int, Tensor};
+
+    type TestBackend = burn_ndarray::NdArrayBackend<f32>;
+    type TestADBackend = burn_autodiff::ADBackendDecorator<TestBackend>;
+    type Batch = (Tensor<TestBackend, 2>, Tensor<TestBackend, 1, Int>);
+
+    struct LabelBatcher;
+
+    impl Batcher<i64, Batch> for LabelBatcher {
+        fn batch(&self, items: Vec<i64>) -> Batch {
+            let inputs = Tensor::zeros([items.len(), 4]);
+            let targets = Tensor::from_data(Data::new(items.clone(), [items.len()].into()));
+
+            (inputs, targets)
+        }
+    }
+
+    impl ValidStep<Batch, ClassificationOutput<TestBackend>> for Linear<TestBackend> {
+        fn step(&self, (inputs, targets): Batch) -> ClassificationOutput<TestBackend> {
+            let output = self.forward(inputs);
+            let loss = CrossEntropyLoss::new(None).forward(output.clone(), targets.clone());
+
+            ClassificationOutput::new(loss, output, targets)
+        }
+    }
+
+    #[test]
+    fn test_evaluate_constant_model() {
+        // The model always predicts the first class with uniform probabilities.
+        let config = LinearConfig::new(4, 2).with_initializer(Initializer::Zeros);
+        let model = Linear::<TestADBackend>::new(&config);
+        let dataloader = DataLoaderBuilder::new(Arc::new(LabelBatcher))
+            .batch_size(2)
+            .build(Arc::new(InMemDataset::new(vec![0, 0, 1, 0])));
+
+        let values = Evaluator::new()
+            .metric(AccuracyMetric::new())
+            .metric(LossMetric::new())
+            .evaluate(model, dataloader);
+
+        assert_eq!(values.len(), 2);
+        assert!((values["Accuracy"] - 75.0).abs() < 1e-6);
+        assert!((values["Loss"] - 2.0f64.ln()).abs() < 1e-4);
+    }
+}
@@ -0,0 +1,35 @@
+use burn_core::module::{set_trainable, Module, ParamId};
+
+/// Freeze some parameters of the model during the first steps of the training, then make them
+/// trainable again, which is useful to train only the head of a model before fine-tuning its
+/// pretrained backbone.
+///
+/// A step is an optimizer update, so it spans all the iterations of a gradient accumulation. Steps
+/// are counted from the start of the training, including the ones done before resuming from a
+/// checkpoint. The ids of the backbone can be collected with
+/// [list_param_ids](burn_core::module::list_param_ids).
+#[derive(Clone, Debug)]
+pub struct FreezeSchedule {
+    param_ids: Vec<ParamId>,
+    num_steps: usize,
+}
+
+impl FreezeSchedule {
+    /// Freeze the given parameters for the first `num_steps` steps.
+    pub fn new(param_ids: Vec<ParamId>, num_steps: usize) -> Self {
+        Self {
+            param_ids,
+            num_steps,
+        }
+    }
+
+    /// If the parameters are frozen at the given step, starting from 0.
+    pub fn is_frozen(&self, step: usize) -> bool {
+        step < self.num_steps
+    }
+
+    /// Freeze or unfreeze the parameters of the model for the given step.
+    pub fn apply<M: Module>(&self, model: M, step: usize) -> M {
+        set_trainable(model, &self.param_ids, !self.is_frozen(step))
+    }
+}
@@ -0,0 +1,51 @@
+use burn_core::module::{list_param_names, ADModule, ParamId};
+use burn_core::optim::GradientsParams;
+use std::collections::HashMap;
+use std::fs::OpenOptions;
+use std::io::Write;
+
+/// Append the norm of the gradients of each parameter to a CSV file with the columns
+/// `epoch,iteration,name,norm`, the header is written when the file is created.
+///
+/// The names of the parameters are the paths returned by
+/// [list_param_names](burn_core::module::list_param_names).
+pub(crate) fn log_grad_norms<M: ADModule>(
+    file: &str,
+    names: &HashMap<ParamId, String>,
+    model: &M,
+    grads: &GradientsParams,
+    epoch: usize,
+    iteration: usize,
+) -> std::io::Result<()> {
+    if let Some(directory) = std::path::Path::new(file).parent() {
+        std::fs::create_dir_all(directory)?;
+    }
+
+    let is_new = !std::path::Path::new(file).exists();
+    let mut writer = OpenOptions::new().create(true).append(true).open(file)?;
+
+    if is_new {
+        writeln!(writer, "epoch,iteration,name,norm")?;
+    }
+
+    let mut norms: Vec<(&str, f64)> = grads
+        .norms(model)
+        .into_iter()
+        .map(|(id, norm)| {
+            let name = names.get(&id).map(String::as_str).unwrap_or("unknown");
+            (name, norm)
+        })
+        .collect();
+    norms.sort_by_key(|(name, _)| *name);
+
+    for (name, norm) in norms {
+        writeln!(writer, "{epoch},{iteration},{name},{norm}")?;
+    }
+
+    Ok(())
+}
+
+/// The names of the parameters of the model by id.
+pub(crate) fn param_names<M: ADModule>(model: &M) -> HashMap<ParamId, String> {
+    list_param_names(model).into_iter().collect()
+}
@@ -0,0 +1,202 @@
+use std::collections::HashMap;
+
+/// The metrics of a single epoch, keyed by metric name.
+#[derive(Debug, Clone, Default)]
+pub struct EpochHistory {
+    /// The epoch number.
+    pub epoch: usize,
+    /// The training metrics summarized over the epoch.
+    pub train: HashMap<String, f64>,
+    /// The validation metrics summarized over the epoch.
+    pub valid: HashMap<String, f64>,
+}
+
+/// The metrics recorded at each epoch of a training, returned by
+/// [fit_with_history](crate::Learner::fit_with_history).
+///
+/// # Notes
+///
+/// Numeric metrics registered for plotting are summarized with their
+/// [aggregation strategy](crate::metric::Aggregate), the other metrics with the mean of their
+/// entries that are serialized as numbers. Metrics without numeric entries aren't recorded.
+#[derive(Debug, Clone, Default)]
+pub struct TrainingHistory {
+    epochs: Vec<EpochHistory>,
+}
+
+impl TrainingHistory {
+    /// The metrics of every recorded epoch, in training order.
+    pub fn epochs(&self) -> &[EpochHistory] {
+        &self.epochs
+    }
+
+    /// The number of recorded epochs.
+    pub fn len(&self) -> usize {
+        self.epochs.len()
+    }
+
+    /// If no epoch was recorded.
+    pub fn is_empty(&self) -> bool {
+        self.epochs.is_empty()
+    }
+
+    /// The value of a training metric at each recorded epoch, NaN when it is missing.
+    pub fn train(&self, name: &str) -> Vec<f64> {
+        self.epochs
+            .iter()
+            .map(|epoch| epoch.train.get(name).copied().unwrap_or(f64::NAN))
+            .collect()
+    }
+
+    /// The value of a validation metric at each recorded epoch, NaN when it is missing.
+    pub fn valid(&self, name: &str) -> Vec<f64> {
+        self.epochs
+            .iter()
+            .map(|epoch| epoch.valid.get(name).copied().unwrap_or(f64::NAN))
+            .collect()
+    }
+
+    pub(crate) fn update_train(&mut self, epoch: usize, metrics: HashMap<String, f64>) {
+        self.entry(epoch).train.extend(metrics);
+    }
+
+    pub(crate) fn update_valid(&mut self, epoch: usize, metrics: HashMap<String, f64>) {
+        self.entry(epoch).valid.extend(metrics);
+    }
+
+    fn entry(&mut self, epoch: usize) -> &mut EpochHistory {
+        match self.epochs.iter().position(|item| item.epoch == epoch) {
+            Some(index) => &mut self.epochs[index],
+            None => {
+                self.epochs.push(EpochHistory {
+                    epoch,
+                    ..Default::default()
+                });
+                self.epochs.last_mut().unwrap()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metric::{AccuracyMetric, LossMetric};
+    use crate::{ClassificationOutput, LearnerBuilder, TrainOutput, TrainStep, ValidStep};
+    use burn_core as burn;
+    use burn_core::data::dataloader::batcher::Batcher;
+    use burn_core::data::dataloader::{DataLoader, DataLoaderBuilder};
+    use burn_core::data::dataset::InMemDataset;
+    use burn_core::module::{Module, Param};
+    use burn_core::nn::loss::CrossEntropyLoss;
+    use burn_core::nn::{Linear, LinearConfig};
+    use burn_core::optim::{Sgd, SgdConfig};
+    use burn_core::tensor::backend::Backend;
+    use burn_core::tensor::{Data, Distribution, ElementConversion, Int, Shape, Tensor};
+    use std::sync::Arc;
+
+    type TestBackend = burn_ndarray::NdArrayBackend<f32>;
+    type TestADBackend = burn_autodiff::ADBackendDecorator<TestBackend>;
+
+    struct RandomBatcher;
+
+    impl<B: Backend> Batcher<usize, (Tensor<B, 2>, Tensor<B, 1, Int>)> for RandomBatcher {
+        fn batch(&self, items: Vec<usize>) -> (Tensor<B, 2>, Tensor<B, 1, Int>) {
+            let targets = items
+                .iter()
+                .map(|item| B::IntElem::from_elem(item % 2))
+                .collect();
+
+            (
+                Tensor::random([items.len(), 4], Distribution::Standard),
+                Tensor::from_data(Data::new(targets, Shape::new([items.len()]))),
+            )
+        }
+    }
+
+    #[derive(Module, Debug)]
+    struct Classifier<B: Backend> {
+        linear: Param<Linear<B>>,
+    }
+
+    impl<B: Backend> Classifier<B> {
+        fn classify(
+            &self,
+            (inputs, targets): (Tensor<B, 2>, Tensor<B, 1, Int>),
+        ) -> ClassificationOutput<B> {
+            let output = self.linear.forward(inputs);
+            let loss = CrossEntropyLoss::new(None).forward(output.clone(), targets.clone());
+
+            ClassificationOutput::new(loss, output, targets)
+        }
+    }
+
+    impl
+        TrainStep<
+            (Tensor<TestADBackend, 2>, Tensor<TestADBackend, 1, Int>),
+            ClassificationOutput<TestADBackend>,
+        > for Classifier<TestADBackend>
+    {
+        fn step(
+            &self,
+            item: (Tensor<TestADBackend, 2>, Tensor<TestADBackend, 1, Int>),
+        ) -> TrainOutput<ClassificationOutput<TestADBackend>> {
+            let output = self.classify(item);
+
+            TrainOutput::new(self, output.loss.backward(), output)
+        }
+    }
+
+    impl
+        ValidStep<
+            (Tensor<TestBackend, 2>, Tensor<TestBackend, 1, Int>),
+            ClassificationOutput<TestBackend>,
+        > for Classifier<TestBackend>
+    {
+        fn step(
+            &self,
+            item: (Tensor<TestBackend, 2>, Tensor<TestBackend, 1, Int>),
+        ) -> ClassificationOutput<TestBackend> {
+            self.classify(item)
+        }
+    }
+
+    fn dataloader<B: Backend>() -> Arc<dyn DataLoader<(Tensor<B, 2>, Tensor<B, 1, Int>)>> {
+        DataLoaderBuilder::new(Arc::new(RandomBatcher))
+            .batch_size(2)
+            .build(Arc::new(InMemDataset::new((0..6).collect())))
+    }
+
+    #[test]
+    fn test_fit_should_return_the_metrics_of_each_epoch() {
+        let directory = std::env::temp_dir().join("burn-train-test-history");
+        std::fs::create_dir_all(&directory).unwrap();
+
+        let learner = LearnerBuilder::new(directory.to_str().unwrap())
+            .metric_train_plot(LossMetric::<TestADBackend>::new())
+            .metric_train(AccuracyMetric::<TestADBackend>::new())
+            .metric_valid(LossMetric::<TestBackend>::new())
+            .num_epochs(3)
+            .build(
+                Classifier {
+                    linear: Param::from(Linear::<TestADBackend>::new(&LinearConfig::new(4, 2))),
+                },
+                Sgd::new(&SgdConfig::new(0.01)),
+            );
+
+        let (_model, history) =
+            learner.fit_with_history(dataloader::<TestADBackend>(), dataloader::<TestBackend>());
+
+        assert_eq!(history.len(), 3);
+        for (index, epoch) in history.epochs().iter().enumerate() {
+            assert_eq!(epoch.epoch, index + 1);
+            assert!(epoch.train.contains_key("Loss"));
+            assert!(epoch.train.contains_key("Accuracy"));
+            assert!(epoch.valid.contains_key("Loss"));
+            assert!(!epoch.valid.contains_key("Accuracy"));
+        }
+        assert!(history.valid("Loss").iter().all(|loss| loss.is_finite()));
+
+        std::fs::remove_dir_all(directory).ok();
+    }
+}
@@ -2,6 +2,13 @@ mod base;
 mod builder;
 mod classification;
 mod epoch;
+mod evaluator;
+mod freeze;
+mod grad_norm;
+mod history;
+mod nan;
+mod predict;
+mod regression;
 mod step;
 mod train_val;
 
@@ -11,6 +18,12 @@ pub use base::*;
 pub use builder::*;
 pub use classification::*;
 pub use epoch::*;
+pub use evaluator::*;
+pub use freeze::*;
+pub use history::*;
+pub use nan::*;
+pub use predict::*;
+pub use regression::*;
 pub use step::*;
 pub use train::*;
 pub use train_val::*;
@@ -0,0 +1,43 @@
+use burn_core::module::{ADModule, State, StateNamed};
+use burn_core::optim::GradientsParams;
+
+/// Action taken by the [learner](crate::Learner) when a training step produces gradients with
+/// non-finite values.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NanGradAction {
+    /// Save the gradients and the inputs of the step to the `nan-grads` directory of the learner,
+    /// then stop the training.
+    ///
+    /// The inputs are the [state](crate::TrainStep::inputs_state) of the batches, which is read
+    /// at every step while this action is selected.
+    ///
+    /// The file is named after the epoch and the iteration of the step, which identifies the
+    /// items of an [ordered](burn_core::data::dataloader::DataLoaderBuilder::ordered) dataloader
+    /// that produced the gradients.
+    DumpAndStop,
+    /// Skip the optimizer update of the step.
+    Skip,
+}
+
+/// Save the gradients and the inputs of a step as `f32` to a plain JSON file
+/// `{directory}/epoch-{epoch}-iteration-{iteration}.json`, returning the path of the file.
+///
+/// JSON doesn't support non-finite numbers, they are written as `null`.
+pub(crate) fn dump_grads<M: ADModule>(
+    directory: &str,
+    model: &M,
+    grads: &GradientsParams,
+    inputs: State<f32>,
+    epoch: usize,
+    iteration: usize,
+) -> std::io::Result<String> {
+    std::fs::create_dir_all(directory)?;
+
+    let file = format!("{directory}/epoch-{epoch}-iteration-{iteration}.json");
+    let mut state = StateNamed::new();
+    state.register_state("grads", grads.state(model).convert::<f32>());
+    state.register_state("inputs", inputs);
+    State::StateNamed(state).save_uncompressed(&file)?;
+
+    Ok(file)
+}
@@ -0,0 +1,71 @@
+use crate::ValidStep;
+use burn_core::data::dataloader::DataLoader;
+use burn_core::module::ADModule;
+use std::sync::Arc;
+
+/// Run the model over every batch of the dataloader and collect the raw outputs.
+///
+/// The forward passes are executed on the inner backend, so no gradients are tracked. The outputs
+/// follow the order of the batches yielded by the dataloader, use an
+/// [ordered](burn_core::data::dataloader::DataLoaderBuilder::ordered) dataloader to keep the order
+/// of the dataset when loading with multiple workers.
+pub fn predict<M, VI, VO>(model: &M, dataloader: Arc<dyn DataLoader<VI>>) -> Vec<VO>
+where
+    M: ADModule,
+    M::InnerModule: ValidStep<VI, VO>,
+{
+    model.no_grad(|model| dataloader.iter().map(|item| model.step(item)).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use burn_core::data::dataloader::batcher::Batcher;
+    use burn_core::data::dataloader::DataLoaderBuilder;
+    use burn_core::data::dataset::InMemDataset;
+    use burn_core::nn::{Linear, LinearConfig};
+    use burn_core::tensor::{Data, Tensor};
+
+    type TestBackend = burn_ndarray::NdArrayBackend<f32>;
+    type TestADBackend = burn_autodiff::ADBackendDecorator<TestBackend>;
+
+    struct RowBatcher;
+
+    impl Batcher<usize, Tensor<TestBackend, 2>> for RowBatcher {
+        fn batch(&self, items: Vec<usize>) -> Tensor<TestBackend, 2> {
+            let values = items
+                .iter()
+                .flat_map(|item| (0..4).map(move |i| (item * 4 + i) as f32))
+                .collect();
+
+            Tensor::from_data(Data::new(values, [items.len(), 4].into()))
+        }
+    }
+
+    impl ValidStep<Tensor<TestBackend, 2>, Tensor<TestBackend, 2>> for Linear<TestBackend> {
+        fn step(&self, item: Tensor<TestBackend, 2>) -> Tensor<TestBackend, 2> {
+            self.forward(item)
+        }
+    }
+
+    #[test]
+    fn test_predictions_should_match_manual_forward_in_order() {
+        let model = Linear::<TestADBackend>::new(&LinearConfig::new(4, 3));
+        let dataloader = DataLoaderBuilder::new(Arc::new(RowBatcher))
+            .batch_size(1)
+            .num_workers(4)
+            .ordered()
+            .build(Arc::new(InMemDataset::new((0..20).collect())));
+
+        let predictions = predict(&model, dataloader);
+
+        let model = model.inner();
+        assert_eq!(predictions.len(), 20);
+        for (item, prediction) in predictions.into_iter().enumerate() {
+            let expected = model.forward(RowBatcher.batch(vec![item]));
+            prediction
+                .into_data()
+                .assert_approx_eq(&expected.into_data(), 5);
+        }
+    }
+}
@@ -0,0 +1,107 @@
+use crate::metric::{
+    Adaptor, LossInput, MeanAbsoluteErrorInput, MeanSquaredErrorInput, R2ScoreInput,
+};
+use burn_core::tensor::backend::{ADBackend, Backend};
+use burn_core::tensor::Tensor;
+
+/// Simple regression output adapted for multiple metrics.
+///
+/// The tensors are detached from the autodiff graph when adapted, so the metrics never build
+/// graphs for their operations.
+#[derive(new)]
+pub struct RegressionOutput<B: Backend> {
+    /// The loss of the batch.
+    pub loss: Tensor<B, 1>,
+    /// The predictions of shape `[batch_size, n_targets]`.
+    pub output: Tensor<B, 2>,
+    /// The targets of shape `[batch_size, n_targets]`.
+    pub targets: Tensor<B, 2>,
+}
+
+impl<B: ADBackend> RegressionOutput<B> {
+    /// Move the output to the inner backend.
+    ///
+    /// See [ClassificationOutput::inner](crate::ClassificationOutput::inner).
+    pub fn inner(self) -> RegressionOutput<B::InnerBackend> {
+        RegressionOutput::new(self.loss.inner(), self.output.inner(), self.targets.inner())
+    }
+}
+
+impl<B: Backend> Adaptor<LossInput<B>> for RegressionOutput<B> {
+    fn adapt(&self) -> LossInput<B> {
+        LossInput::new(self.loss.clone().detach())
+    }
+}
+
+impl<B: Backend> Adaptor<MeanAbsoluteErrorInput<B>> for RegressionOutput<B> {
+    fn adapt(&self) -> MeanAbsoluteErrorInput<B> {
+        MeanAbsoluteErrorInput::new(self.output.clone().detach(), self.targets.clone().detach())
+    }
+}
+
+impl<B: Backend> Adaptor<MeanSquaredErrorInput<B>> for RegressionOutput<B> {
+    fn adapt(&self) -> MeanSquaredErrorInput<B> {
+        MeanSquaredErrorInput::new(self.output.clone().detach(), self.targets.clone().detach())
+    }
+}
+
+impl<B: Backend> Adaptor<R2ScoreInput<B>> for RegressionOutput<B> {
+    fn adapt(&self) -> R2ScoreInput<B> {
+        R2ScoreInput::new(self.output.clone().detach(), self.targets.clone().detach())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metric::{
+        LossMetric, MeanAbsoluteError, MeanSquaredError, Metric, Numeric, R2Score,
+    };
+    use burn_core::tensor::Data;
+
+    type TestBackend = burn_ndarray::NdArrayBackend<f32>;
+
+    fn regression_output(output: [f32; 4], targets: [f32; 4]) -> RegressionOutput<TestBackend> {
+        RegressionOutput::new(
+            Tensor::from_data(Data::from([0.5])),
+            Tensor::<TestBackend, 1>::from_data(Data::from(output)).reshape([4, 1]),
+            Tensor::<TestBackend, 1>::from_data(Data::from(targets)).reshape([4, 1]),
+        )
+    }
+
+    #[test]
+    fn test_regression_metrics_should_match_hand_computed_values() {
+        // Errors: [0.5, -0.5, 0.0, 2.0]
+        let output = regression_output([1.5, 1.5, 3.0, 6.0], [1.0, 2.0, 3.0, 4.0]);
+        let mut mae = MeanAbsoluteError::new();
+        let mut mse = MeanSquaredError::new();
+        let mut r2 = R2Score::new();
+        let mut loss = LossMetric::new();
+
+        mae.update(&output.adapt());
+        mse.update(&output.adapt());
+        r2.update(&output.adapt());
+        loss.update(&output.adapt());
+
+        // SS_res = 0.25 + 0.25 + 0 + 4 = 4.5, SS_tot = 2.25 + 0.25 + 0.25 + 2.25 = 5
+        assert!((mae.value() - 0.75).abs() < 1e-6);
+        assert!((mse.value() - 1.125).abs() < 1e-6);
+        assert!((r2.value() - 0.1).abs() < 1e-6);
+        assert_eq!(loss.value(), 0.5);
+    }
+
+    #[test]
+    fn test_r2_score_should_accumulate_over_the_epoch() {
+        let mut r2 = R2Score::new();
+
+        // Each batch has constant targets, so its own R2 is undefined.
+        r2.update(&regression_output([1.0, 1.0, 1.0, 2.0], [1.0, 1.0, 1.0, 1.0]).adapt());
+        r2.update(&regression_output([3.0, 3.0, 3.0, 3.0], [3.0, 3.0, 3.0, 3.0]).adapt());
+
+        // SS_res = 1, SS_tot = 8 * 1^2 = 8
+        assert!((r2.value() - 0.875).abs() < 1e-6);
+
+        r2.clear();
+        assert!(r2.value().is_nan());
+    }
+}
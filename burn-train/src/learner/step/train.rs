@@ -5,9 +5,13 @@ use burn_core::{
 use std::sync::mpsc::{Receiver, Sender};
 use std::thread::spawn;
 
+/// Execute training steps on multiple devices in parallel.
+///
+/// Items are sharded deterministically: with `N` devices, the device `i` processes the items
+/// `i, i + N, i + 2N, ...` of the epoch, so every item is processed exactly once.
 pub struct MultiDevicesTrainStep<B: ADBackend, M, TI, TO> {
     workers: Vec<Worker<B, M, TI>>,
-    receiver: Receiver<TrainOutput<TO>>,
+    receiver: Receiver<(usize, TrainOutput<TO>)>,
 }
 
 struct Message<M, TI> {
@@ -16,6 +20,7 @@ struct Message<M, TI> {
 }
 
 struct Worker<B: ADBackend, M, TI> {
+    index: usize,
     sender_input: Sender<Message<M, TI>>,
     device: B::Device,
 }
@@ -34,7 +39,7 @@ where
 
     fn start<TO>(
         &self,
-        sender_output: Sender<TrainOutput<TO>>,
+        sender_output: Sender<(usize, TrainOutput<TO>)>,
         receiver_input: Receiver<Message<M, TI>>,
     ) where
         TI: Send + 'static,
@@ -42,14 +47,15 @@ where
         M: TrainStep<TI, TO> + Send + 'static,
     {
         let device = self.device.clone();
+        let index = self.index;
 
         spawn(move || loop {
             match receiver_input.recv() {
                 Ok(item) => {
                     let step = item.model.to_device(&device).detach();
-                    let output = step.step(item.item);
+                    let output = step.step(step.prepare(item.item));
 
-                    sender_output.send(output).unwrap();
+                    sender_output.send((index, output)).unwrap();
                 }
                 Err(_err) => {
                     log::info!("Closing thread on device {:?}", device);
@@ -74,9 +80,11 @@ where
         let (sender_output, receiver_output) = std::sync::mpsc::channel();
         let workers = devices
             .iter()
-            .map(|device| {
+            .enumerate()
+            .map(|(index, device)| {
                 let (sender_input, receiver_input) = std::sync::mpsc::channel();
                 let worker = Worker {
+                    index,
                     sender_input,
                     device: device.clone(),
                 };
@@ -92,18 +100,32 @@ where
         }
     }
 
+    /// Execute one training step on each device, returning the outputs ordered by device.
     pub fn step<'a>(
         &self,
         dataloader: &mut Box<dyn DataLoaderIterator<TI> + 'a>,
         model: &M,
     ) -> Vec<TrainOutput<TO>> {
-        let mut num_send = 0;
+        self.step_inspect(dataloader, model, |_, _| {})
+    }
 
-        for worker in self.workers.iter() {
-            if let Some(item) = dataloader.next() {
-                worker.register(item, model);
-                num_send += 1;
-            }
+    /// Same as [step](Self::step), `inspect` being called with the index of each device and its
+    /// item before the item is sent to the device.
+    pub fn step_inspect<'a, F>(
+        &self,
+        dataloader: &mut Box<dyn DataLoaderIterator<TI> + 'a>,
+        model: &M,
+        mut inspect: F,
+    ) -> Vec<TrainOutput<TO>>
+    where
+        F: FnMut(usize, &TI),
+    {
+        let shards = next_shards(dataloader, self.workers.len());
+        let num_send = shards.len();
+
+        for (index, item) in shards {
+            inspect(index, &item);
+            self.workers[index].register(item, model);
         }
 
         let mut outputs = Vec::with_capacity(num_send);
@@ -113,6 +135,41 @@ where
             outputs.push(output);
         }
 
-        outputs
+        outputs.sort_by_key(|(index, _)| *index);
+        outputs.into_iter().map(|(_, output)| output).collect()
+    }
+}
+
+/// Take the next item for each device, the `i`-th item being assigned to the device `i`.
+fn next_shards<I>(iterator: &mut impl Iterator<Item = I>, num_devices: usize) -> Vec<(usize, I)> {
+    iterator.take(num_devices).enumerate().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shards_cover_every_item_once() {
+        let num_devices = 2;
+        let mut iterator = 0..6;
+        let mut items_devices = vec![Vec::new(); num_devices];
+
+        loop {
+            let shards = next_shards(&mut iterator, num_devices);
+            if shards.is_empty() {
+                break;
+            }
+
+            for (index, item) in shards {
+                items_devices[index].push(item);
+            }
+        }
+
+        assert_eq!(items_devices, vec![vec![0, 2, 4], vec![1, 3, 5]]);
+
+        let mut items = items_devices.concat();
+        items.sort();
+        assert_eq!(items, vec![0, 1, 2, 3, 4, 5]);
     }
 }
@@ -1,10 +1,13 @@
+use super::base::iteration_key;
 use super::Learner;
 
-use crate::{TrainEpoch, ValidEpoch};
+use crate::{TrainEpoch, TrainingHistory, ValidEpoch};
+use burn_core::data::dataloader::batcher::BatchToDevice;
 use burn_core::data::dataloader::DataLoader;
-use burn_core::module::ADModule;
-use burn_core::optim::{GradientsParams, Optimizer};
-use burn_core::tensor::backend::ADBackend;
+use burn_core::module::{ADModule, Module, State, StateNamed};
+use burn_core::optim::{GradientsParams, Optimizer, Swa};
+use burn_core::tensor::backend::{ADBackend, Backend};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 
 pub struct TrainOutput<TO> {
@@ -26,6 +29,94 @@ impl<TO> TrainOutput<TO> {
 
 pub trait TrainStep<TI, TO> {
     fn step(&self, item: TI) -> TrainOutput<TO>;
+
+    /// The values of an item saved with the gradients of its step when they are
+    /// [dumped](crate::NanGradAction::DumpAndStop).
+    ///
+    /// Nothing is saved by default, the [state](BatchToDevice::state) of the batch can be
+    /// returned when it's implemented.
+    fn inputs_state(&self, _item: &TI) -> State<f32> {
+        State::StateNamed(StateNamed::new())
+    }
+
+    /// Prepare an item before its [step](Self::step), called with the model on the device where
+    /// the step is executed.
+    ///
+    /// The item is returned as is by default, use [to_model_device] to check the device of the
+    /// item and move it to the device of the model.
+    fn prepare(&self, item: TI) -> TI {
+        item
+    }
+}
+
+/// Move a batch to the device of the model when they differ, which is logged once as a warning
+/// since the batches should be created on the right device, e.g. with the dataloader
+/// [device](burn_core::data::dataloader::DataLoaderBuilder::device) option.
+///
+/// Meant to be called from the [prepare](TrainStep::prepare) hook of a training step. Batches that
+/// don't report their [devices](BatchToDevice::devices) are always moved.
+///
+/// # Panics
+///
+/// If the model is on multiple devices.
+pub fn to_model_device<M, TI>(model: &M, item: TI) -> TI
+where
+    M: Module,
+    TI: BatchToDevice<M::Backend>,
+    <M::Backend as Backend>::Device: PartialEq,
+{
+    static WARNED: AtomicBool = AtomicBool::new(false);
+
+    let batch_devices = item.devices();
+
+    match model_device_mismatch(&model.devices(), &batch_devices) {
+        Some(device) => {
+            if !batch_devices.is_empty() && !WARNED.swap(true, Ordering::Relaxed) {
+                log::warn!(
+                    "Moving a batch on the devices {batch_devices:?} to the device of the model \
+                     {device:?}"
+                );
+            }
+            item.to_device(device)
+        }
+        None => item,
+    }
+}
+
+/// The device of the model if a batch on the given devices must be moved to it.
+///
+/// The model devices have one entry per parameter, so the same device is usually repeated.
+fn model_device_mismatch<'a, D>(model_devices: &'a [D], batch_devices: &[D]) -> Option<&'a D>
+where
+    D: PartialEq + core::fmt::Debug,
+{
+    let device = model_devices.first()?;
+
+    if model_devices.iter().any(|item| item != device) {
+        let mut devices: Vec<&D> = Vec::new();
+        for item in model_devices {
+            if !devices.contains(&item) {
+                devices.push(item);
+            }
+        }
+        panic!("Can't move a batch to the device of a model on multiple devices {devices:?}");
+    }
+
+    if !batch_devices.is_empty() && batch_devices.iter().all(|item| item == device) {
+        return None;
+    }
+
+    Some(device)
+}
+
+/// Training step of a model updated by multiple optimizers, such as the generator and the
+/// discriminator of a GAN.
+///
+/// Each sub-step is associated with its own optimizer and should only return the gradients of the
+/// parameters that this optimizer updates, which is done by creating the
+/// [output](TrainOutput::new) from the corresponding submodule.
+pub trait MultiTrainStep<TI, TO> {
+    fn step(&self, substep: usize, item: TI) -> TrainOutput<TO>;
 }
 
 pub trait ValidStep<VI, VO> {
@@ -39,11 +130,36 @@ where
     M: ADModule,
     O: Optimizer<Backend = M::Backend>,
 {
+    /// Train the model, returning the trained model.
+    ///
+    /// See [fit_with_history](Self::fit_with_history) to also get the metrics of each epoch.
     pub fn fit<TI, VI>(
-        mut self,
+        self,
         dataloader_train: Arc<dyn DataLoader<TI>>,
         dataloader_valid: Arc<dyn DataLoader<VI>>,
     ) -> M
+    where
+        TI: Send + 'static,
+        TO: Send + 'static,
+        M: TrainStep<TI, TO> + Send + Clone + 'static,
+        M::InnerModule: ValidStep<VI, VO>,
+    {
+        let (model, _history) = self.fit_with_history(dataloader_train, dataloader_valid);
+        model
+    }
+
+    /// Train the model, returning the trained model with the [history](TrainingHistory) of the
+    /// registered metrics at each epoch.
+    ///
+    /// # Notes
+    ///
+    /// When resuming from a checkpoint, only the epochs trained by this call are part of the
+    /// history.
+    pub fn fit_with_history<TI, VI>(
+        mut self,
+        dataloader_train: Arc<dyn DataLoader<TI>>,
+        dataloader_valid: Arc<dyn DataLoader<VI>>,
+    ) -> (M, TrainingHistory)
     where
         TI: Send + 'static,
         TO: Send + 'static,
@@ -52,49 +168,236 @@ where
     {
         log::info!("Fitting {}", self.model.to_string());
 
-        let starting_epoch = match self.checkpoint {
-            Some(checkpoint) => {
-                self = self.load_checkpoint(checkpoint);
-                checkpoint
-            }
-            None => 1,
-        };
+        // The number of optimizer updates done since the start of the training.
+        let (starting_epoch, mut starting_iteration, mut step) =
+            match (self.checkpoint, self.checkpoint_iteration) {
+                (Some(checkpoint), Some(iteration)) => {
+                    let (learner, step) = self.load_checkpoint_iteration(checkpoint, iteration);
+                    self = learner;
+                    (checkpoint, iteration, step)
+                }
+                (Some(checkpoint), None) => {
+                    let (learner, step) = self.load_checkpoint(checkpoint);
+                    self = learner;
+                    (checkpoint, 0, step)
+                }
+                (None, _) => (1, 0, 0),
+            };
+
+        // The schedule continues from the updates done before the checkpoint.
+        if let Some(lr_scheduler) = &mut self.lr_scheduler {
+            lr_scheduler.resume(step);
+        }
 
         let mut model = self.model;
         let mut optim = self.optim;
+        let fast_dev_run = self.fast_dev_run;
+        let mut swa = self.swa.filter(|_| fast_dev_run.is_none()).map(Swa::new);
+
+        let num_epochs = match fast_dev_run {
+            Some(num_batches) => {
+                log::info!("Fast dev run of a single epoch with {num_batches} batches per split");
+                starting_epoch
+            }
+            None => self.num_epochs,
+        };
 
         // The reference model is always on the first device provided.
         if let Some(device) = self.devices.get(0) {
             model = model.to_device(device).detach();
         }
 
-        for epoch in starting_epoch..self.num_epochs + 1 {
-            let epoch_train = TrainEpoch::new(
+        for epoch in starting_epoch..num_epochs + 1 {
+            let mut epoch_train = TrainEpoch::new(
                 dataloader_train.clone(),
                 epoch,
-                self.num_epochs,
+                num_epochs,
                 self.grad_accumulation,
-            );
+                self.checkpoint_interval,
+            )
+            .with_iteration_start(starting_iteration)
+            .with_step_start(step);
+            starting_iteration = 0;
+
+            if let Some(num_batches) = fast_dev_run {
+                epoch_train = epoch_train.with_max_iterations(num_batches);
+            }
+
+            if let Some((action, directory)) = &self.nan_grad {
+                epoch_train = epoch_train.with_nan_grad(*action, directory);
+            }
+
+            if self.grad_accumulation_flush {
+                epoch_train = epoch_train.with_grad_accumulation_flush();
+            }
+
+            if self.grad_accumulation_offload {
+                epoch_train = epoch_train.with_grad_accumulation_offload();
+            }
+
+            if self.grad_accumulation_scale_lr {
+                epoch_train = epoch_train.with_grad_accumulation_scale_lr();
+            }
+
+            if let Some((interval, file)) = &self.grad_norm_log {
+                epoch_train = epoch_train.with_grad_norm_log(*interval, file);
+            }
+
+            if let Some(schedule) = &self.freeze_schedule {
+                epoch_train = epoch_train.with_freeze_schedule(schedule.clone());
+            }
+
+            let checkpointer_model = &self.checkpointer_model_iteration;
+            let checkpointer_optimizer = &self.checkpointer_optimizer_iteration;
+            let checkpointer_learner = &self.checkpointer_learner_iteration;
+            let interval = self.checkpoint_interval;
+            let epoch_train = &epoch_train;
+            let checkpoint = |model: &M, optim: &O, iteration: usize| {
+                if fast_dev_run.is_some() {
+                    return;
+                }
+
+                Self::checkpoint(
+                    model,
+                    optim,
+                    epoch_train.step(),
+                    checkpointer_model,
+                    checkpointer_optimizer,
+                    checkpointer_learner,
+                    iteration_key(interval, epoch, iteration),
+                )
+            };
 
             if self.devices.len() > 1 {
                 (model, optim) = epoch_train.run_multi_device(
                     model,
                     optim,
                     &mut self.callback,
+                    &mut self.lr_scheduler,
                     self.devices.clone(),
+                    checkpoint,
                 )
             } else {
-                (model, optim) = epoch_train.run(model, optim, &mut self.callback);
+                (model, optim) = epoch_train.run(
+                    model,
+                    optim,
+                    &mut self.callback,
+                    &mut self.lr_scheduler,
+                    checkpoint,
+                );
             }
 
+            step = epoch_train.step();
+
+            if epoch_train.is_stopped() {
+                log::error!("Training stopped during epoch {}", epoch);
+                break;
+            }
+
+            let mut epoch_valid = ValidEpoch::new(dataloader_valid.clone(), epoch, num_epochs);
+            if let Some(num_batches) = fast_dev_run {
+                epoch_valid = epoch_valid.with_max_iterations(num_batches);
+            }
+            model = epoch_valid.run(model, &mut self.callback);
+
+            if fast_dev_run.is_none() {
+                Self::checkpoint(
+                    &model,
+                    &optim,
+                    step,
+                    &self.checkpointer_model,
+                    &self.checkpointer_optimizer,
+                    &self.checkpointer_learner,
+                    epoch,
+                );
+            }
+
+            if let Some(swa) = &mut swa {
+                swa.update(&model, epoch);
+            }
+        }
+
+        // Wait for the callback to process the metrics of the last epoch.
+        drop(self.callback);
+        let history = self.history.lock().unwrap().clone();
+
+        let model = match swa {
+            Some(swa) if swa.num_averaged() > 0 => {
+                log::info!("Recomputing the statistics of the averaged model");
+                swa.finalize(model, dataloader_train.iter(), |model, item| {
+                    model.step(model.prepare(item));
+                })
+            }
+            _ => model,
+        };
+
+        (model, history)
+    }
+
+    /// Train the model with multiple optimizers applied alternately, such as the generator and
+    /// the discriminator of a GAN, returning the trained model.
+    ///
+    /// The optimizer of the learner applies the gradients of the sub-step 0 of the
+    /// [multi train step](MultiTrainStep), and the given optimizers the gradients of the
+    /// following sub-steps. Each iteration runs the sub-steps in the order of the schedule, see
+    /// [run_multi_optim](TrainEpoch::run_multi_optim).
+    ///
+    /// # Notes
+    ///
+    /// Only the optimizer of the learner is checkpointed with the model, the other optimizers
+    /// start from their initial state when resuming from a checkpoint. Gradient accumulation,
+    /// learning rate scheduling, mid-epoch checkpoints and multiple devices aren't supported.
+    pub fn fit_multi_optim<TI, VI>(
+        mut self,
+        optims: Vec<O>,
+        schedule: &[usize],
+        dataloader_train: Arc<dyn DataLoader<TI>>,
+        dataloader_valid: Arc<dyn DataLoader<VI>>,
+    ) -> M
+    where
+        TI: Clone,
+        M: MultiTrainStep<TI, TO>,
+        M::InnerModule: ValidStep<VI, VO>,
+    {
+        log::info!(
+            "Fitting {} with {} optimizers",
+            self.model.to_string(),
+            optims.len() + 1
+        );
+
+        let starting_epoch = match self.checkpoint {
+            Some(checkpoint) => {
+                (self, _) = self.load_checkpoint(checkpoint);
+                checkpoint
+            }
+            None => 1,
+        };
+
+        let mut model = self.model;
+        let mut optims: Vec<O> = core::iter::once(self.optim).chain(optims).collect();
+
+        // The reference model is always on the first device provided.
+        if let Some(device) = self.devices.get(0) {
+            model = model.to_device(device).detach();
+        }
+
+        for epoch in starting_epoch..self.num_epochs + 1 {
+            let epoch_train =
+                TrainEpoch::new(dataloader_train.clone(), epoch, self.num_epochs, None, None);
+            (model, optims) =
+                epoch_train.run_multi_optim(model, optims, schedule, &mut self.callback);
+
             let epoch_valid = ValidEpoch::new(dataloader_valid.clone(), epoch, self.num_epochs);
             model = epoch_valid.run(model, &mut self.callback);
 
+            // The learner state is only used by the schedules, which aren't supported here.
             Self::checkpoint(
                 &model,
-                &optim,
+                &optims[0],
+                0,
                 &self.checkpointer_model,
                 &self.checkpointer_optimizer,
+                &None,
                 epoch,
             );
         }
@@ -102,3 +405,182 @@ where
         model
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FreezeSchedule, LearnerBuilder};
+    use burn_core as burn;
+    use burn_core::data::dataloader::batcher::Batcher;
+    use burn_core::data::dataloader::DataLoaderBuilder;
+    use burn_core::data::dataset::InMemDataset;
+    use burn_core::module::{list_param_ids, Param};
+    use burn_core::nn::{Linear, LinearConfig};
+    use burn_core::optim::{Sgd, SgdConfig};
+    use burn_core::tensor::backend::Backend;
+    use burn_core::tensor::{Distribution, Tensor};
+
+    type TestBackend = burn_ndarray::NdArrayBackend<f32>;
+    type TestADBackend = burn_autodiff::ADBackendDecorator<TestBackend>;
+    type Device = <TestBackend as burn_core::tensor::backend::Backend>::Device;
+
+    #[test]
+    fn test_batch_on_another_device_should_be_moved_to_the_model_device() {
+        assert_eq!(model_device_mismatch(&[1], &[0, 1]), Some(&1));
+        assert_eq!(model_device_mismatch(&[1], &[1, 1]), None);
+        // The devices of the batch are unknown.
+        assert_eq!(model_device_mismatch(&[1], &[]), Some(&1));
+        // The model doesn't have any parameter.
+        assert_eq!(model_device_mismatch::<usize>(&[], &[0]), None);
+    }
+
+    #[test]
+    fn test_model_with_many_params_on_one_device_should_not_panic() {
+        assert_eq!(model_device_mismatch(&[1, 1, 1], &[0]), Some(&1));
+        assert_eq!(model_device_mismatch(&[1, 1, 1], &[1]), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "multiple devices")]
+    fn test_model_on_multiple_devices_should_panic() {
+        model_device_mismatch(&[0, 1], &[0]);
+    }
+
+    struct RecordingBatch {
+        device: Option<Device>,
+    }
+
+    impl BatchToDevice<TestBackend> for RecordingBatch {
+        fn to_device(self, device: &Device) -> Self {
+            Self {
+                device: Some(*device),
+            }
+        }
+    }
+
+    #[test]
+    fn test_to_model_device_should_keep_batches_on_the_model_device() {
+        let model = Linear::<TestBackend>::new(&LinearConfig::new(4, 2));
+
+        let batch = to_model_device(&model, RecordingBatch { device: None });
+        assert_eq!(batch.device, Some(Device::default()));
+
+        let batch = to_model_device(&model, Tensor::<TestBackend, 2>::zeros([2, 4]));
+        assert_eq!(batch.device(), Device::default());
+    }
+
+    struct RandomBatcher;
+
+    impl<B: Backend> Batcher<usize, Tensor<B, 2>> for RandomBatcher {
+        fn batch(&self, items: Vec<usize>) -> Tensor<B, 2> {
+            Tensor::random([items.len(), 4], Distribution::Standard)
+        }
+    }
+
+    fn dataloader<B: Backend>() -> Arc<dyn DataLoader<Tensor<B, 2>>> {
+        DataLoaderBuilder::new(Arc::new(RandomBatcher))
+            .batch_size(2)
+            .build(Arc::new(InMemDataset::new((0..6).collect())))
+    }
+
+    #[derive(Module, Debug)]
+    struct Gan<B: Backend> {
+        generator: Param<Linear<B>>,
+        discriminator: Param<Linear<B>>,
+    }
+
+    impl MultiTrainStep<Tensor<TestADBackend, 2>, ()> for Gan<TestADBackend> {
+        fn step(&self, substep: usize, item: Tensor<TestADBackend, 2>) -> TrainOutput<()> {
+            let loss = self
+                .discriminator
+                .forward(self.generator.forward(item))
+                .mean();
+
+            match substep {
+                0 => TrainOutput::new(&*self.generator, loss.backward(), ()),
+                _ => TrainOutput::new(&*self.discriminator, loss.backward(), ()),
+            }
+        }
+    }
+
+    impl ValidStep<Tensor<TestBackend, 2>, ()> for Gan<TestBackend> {
+        fn step(&self, _item: Tensor<TestBackend, 2>) {}
+    }
+
+    #[test]
+    fn test_fit_multi_optim_should_apply_each_optimizer_to_its_substep() {
+        let directory = std::env::temp_dir().join("burn-train-test-multi-optim");
+        std::fs::create_dir_all(&directory).unwrap();
+        let model = Gan {
+            generator: Param::from(Linear::<TestADBackend>::new(&LinearConfig::new(4, 4))),
+            discriminator: Param::from(Linear::<TestADBackend>::new(&LinearConfig::new(4, 2))),
+        };
+        let (generator, discriminator) = (model.generator.state(), model.discriminator.state());
+
+        // The optimizer of the discriminator has a null learning rate, so only the generator
+        // can change if the gradients are applied by the optimizer of their sub-step.
+        let learner = LearnerBuilder::<TestADBackend, (), ()>::new(directory.to_str().unwrap())
+            .num_epochs(2)
+            .build(model, Sgd::new(&SgdConfig::new(0.1)));
+        let model = learner.fit_multi_optim(
+            vec![Sgd::new(&SgdConfig::new(0.0))],
+            &[0, 1],
+            dataloader::<TestADBackend>(),
+            dataloader::<TestBackend>(),
+        );
+
+        assert_ne!(model.generator.state(), generator);
+        assert_eq!(model.discriminator.state(), discriminator);
+
+        std::fs::remove_dir_all(directory).ok();
+    }
+
+    impl TrainStep<Tensor<TestADBackend, 2>, ()> for Gan<TestADBackend> {
+        fn step(&self, item: Tensor<TestADBackend, 2>) -> TrainOutput<()> {
+            let loss = self
+                .discriminator
+                .forward(self.generator.forward(item))
+                .mean();
+
+            TrainOutput::new(self, loss.backward(), ())
+        }
+    }
+
+    #[test]
+    fn test_resumed_fit_should_continue_the_freeze_schedule() {
+        let directory = std::env::temp_dir().join("burn-train-test-resume-freeze");
+        let directory = directory.to_str().unwrap();
+        std::fs::remove_dir_all(directory).ok();
+        let model = Gan {
+            generator: Param::from(Linear::<TestADBackend>::new(&LinearConfig::new(4, 4))),
+            discriminator: Param::from(Linear::<TestADBackend>::new(&LinearConfig::new(4, 2))),
+        };
+        // Each epoch has 3 optimizer updates, the generator is unfrozen at the second update of
+        // the second epoch.
+        let schedule = FreezeSchedule::new(list_param_ids(&*model.generator), 4);
+        let learner = |num_epochs, checkpoint| {
+            let builder = LearnerBuilder::<TestADBackend, (), ()>::new(directory)
+                .with_file_checkpointer::<f32>(2)
+                .num_epochs(num_epochs)
+                .freeze_schedule(schedule.clone());
+            match checkpoint {
+                Some(epoch) => builder.checkpoint(epoch),
+                None => builder,
+            }
+        };
+
+        let generator = model.generator.state();
+        let model = learner(1, None)
+            .build(model, Sgd::new(&SgdConfig::new(0.1)))
+            .fit(dataloader::<TestADBackend>(), dataloader::<TestBackend>());
+        assert_eq!(model.generator.state(), generator);
+
+        // The checkpoints are saved once the learner is dropped at the end of the fit.
+        let model = learner(2, Some(1))
+            .build(model, Sgd::new(&SgdConfig::new(0.1)))
+            .fit(dataloader::<TestADBackend>(), dataloader::<TestBackend>());
+        assert_ne!(model.generator.state(), generator);
+
+        std::fs::remove_dir_all(directory).ok();
+    }
+}
@@ -5,6 +5,12 @@ use std::collections::HashMap;
 pub trait MetricLogger: Send {
     fn log(&mut self, item: &MetricEntry);
     fn epoch(&mut self, epoch: usize);
+
+    /// Continue logging from the given epoch when resuming a training, discarding the entries
+    /// logged by a previous run for this epoch and the following ones.
+    fn resume(&mut self, epoch: usize) {
+        self.epoch(epoch);
+    }
 }
 
 pub struct FileMetricLogger {
@@ -21,6 +27,10 @@ impl FileMetricLogger {
             epoch: 1,
         }
     }
+
+    fn epoch_directory(&self, epoch: usize) -> String {
+        format!("{}/epoch-{}", self.directory, epoch)
+    }
 }
 
 impl MetricLogger for FileMetricLogger {
@@ -31,7 +41,7 @@ impl MetricLogger for FileMetricLogger {
         let logger = match self.loggers.get_mut(key) {
             Some(val) => val,
             None => {
-                let directory = format!("{}/epoch-{}", self.directory, self.epoch);
+                let directory = self.epoch_directory(self.epoch);
                 let file_path = format!("{directory}/{key}.log");
                 std::fs::create_dir_all(&directory).ok();
 
@@ -50,4 +60,46 @@ impl MetricLogger for FileMetricLogger {
         self.loggers.clear();
         self.epoch = epoch;
     }
+
+    fn resume(&mut self, epoch: usize) {
+        self.epoch(epoch);
+
+        let mut epoch_stale = epoch;
+        while std::path::Path::new(&self.epoch_directory(epoch_stale)).exists() {
+            std::fs::remove_dir_all(self.epoch_directory(epoch_stale)).ok();
+            epoch_stale += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resume_should_keep_previous_epochs_and_replace_following_ones() {
+        let directory = std::env::temp_dir().join("burn-train-test-file-metric-logger");
+        let directory = directory.to_str().unwrap();
+        std::fs::remove_dir_all(directory).ok();
+        let entry = |value: &str| MetricEntry::new("Loss".into(), value.into(), value.into());
+        let read = |epoch: usize| {
+            std::fs::read_to_string(format!("{directory}/epoch-{epoch}/Loss.log")).ok()
+        };
+
+        let mut logger = FileMetricLogger::new(directory);
+        for epoch in 1..=4 {
+            logger.log(&entry(&format!("first run {epoch}")));
+            logger.epoch(epoch + 1);
+        }
+
+        let mut logger = FileMetricLogger::new(directory);
+        logger.resume(3);
+        logger.log(&entry("second run 3"));
+        logger.epoch(4);
+
+        assert_eq!(read(1).unwrap(), "first run 1\n");
+        assert_eq!(read(2).unwrap(), "first run 2\n");
+        assert_eq!(read(3).unwrap(), "second run 3\n");
+        assert_eq!(read(4), None);
+    }
 }
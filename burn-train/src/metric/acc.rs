@@ -2,19 +2,64 @@ use super::state::{FormatOptions, NumericMetricState};
 use super::MetricEntry;
 use crate::metric::{Metric, Numeric};
 use burn_core::tensor::backend::Backend;
-use burn_core::tensor::{Int, Tensor};
+use burn_core::tensor::{ElementConversion, Int, Tensor};
 
 /// The accuracy metric.
+///
+/// The [balanced](AccuracyMetric::with_balanced) accuracy, the mean of the accuracy of each
+/// class, can be used instead for imbalanced datasets.
 #[derive(Default)]
 pub struct AccuracyMetric<B: Backend> {
     state: NumericMetricState,
+    balanced: bool,
+    classes: ClassesState,
     _b: B,
 }
 
+/// The number of correct predictions and of items of each target class.
+#[derive(Default, Clone)]
+struct ClassesState {
+    correct: Vec<usize>,
+    total: Vec<usize>,
+}
+
+impl ClassesState {
+    fn update(&mut self, outputs: &[usize], targets: &[usize]) {
+        for (output, target) in outputs.iter().zip(targets) {
+            if *target >= self.total.len() {
+                self.correct.resize(target + 1, 0);
+                self.total.resize(target + 1, 0);
+            }
+
+            self.total[*target] += 1;
+            if output == target {
+                self.correct[*target] += 1;
+            }
+        }
+    }
+
+    /// The mean accuracy of the classes having at least one item, in percent.
+    fn value(&self) -> f64 {
+        let accuracies: Vec<f64> = self
+            .correct
+            .iter()
+            .zip(self.total.iter())
+            .filter(|(_, total)| **total > 0)
+            .map(|(correct, total)| *correct as f64 / *total as f64)
+            .collect();
+
+        if accuracies.is_empty() {
+            return f64::NAN;
+        }
+
+        100.0 * accuracies.iter().sum::<f64>() / accuracies.len() as f64
+    }
+}
+
 /// The [accuracy metric](AccuracyMetric) input type.
 #[derive(new)]
 pub struct AccuracyInput<B: Backend> {
-    outputs: Tensor<B, 2>,
+    pub(crate) outputs: Tensor<B, 2>,
     targets: Tensor<B, 1, Int>,
 }
 
@@ -23,6 +68,38 @@ impl<B: Backend> AccuracyMetric<B> {
     pub fn new() -> Self {
         Self::default()
     }
+
+    /// Compute the balanced accuracy, the mean over the classes of the accuracy of the items of
+    /// each class, instead of the accuracy of all the items.
+    ///
+    /// The number of correct predictions of each class is accumulated until the metric is
+    /// [cleared](Metric::clear) at the end of the epoch, classes without items are ignored.
+    pub fn with_balanced(mut self, balanced: bool) -> Self {
+        self.balanced = balanced;
+        self
+    }
+
+    fn update_balanced(
+        &mut self,
+        outputs: Tensor<B, 1, Int>,
+        targets: Tensor<B, 1, Int>,
+    ) -> MetricEntry {
+        let outputs = to_vec(outputs);
+        let targets = to_vec(targets);
+
+        let mut batch = ClassesState::default();
+        batch.update(&outputs, &targets);
+        self.classes.update(&outputs, &targets);
+
+        let value_epoch = self.classes.value();
+        let value_batch = batch.value();
+
+        MetricEntry::new(
+            "Balanced Accuracy".to_string(),
+            format!("epoch {value_epoch:.2} % - batch {value_batch:.2} %"),
+            value_epoch.to_string(),
+        )
+    }
 }
 
 impl<B: Backend> Metric for AccuracyMetric<B> {
@@ -39,6 +116,10 @@ impl<B: Backend> Metric for AccuracyMetric<B> {
             .to_device(&B::Device::default())
             .reshape([batch_size]);
 
+        if self.balanced {
+            return self.update_balanced(outputs, targets);
+        }
+
         let total_current =
             Into::<i64>::into(outputs.equal(targets).into_int().sum().to_data().value[0]) as usize;
         let accuracy = 100.0 * total_current as f64 / batch_size as f64;
@@ -51,12 +132,74 @@ impl<B: Backend> Metric for AccuracyMetric<B> {
     }
 
     fn clear(&mut self) {
-        self.state.reset()
+        self.state.reset();
+        self.classes = ClassesState::default();
     }
 }
 
 impl<B: Backend> Numeric for AccuracyMetric<B> {
     fn value(&self) -> f64 {
-        self.state.value()
+        match self.balanced {
+            true => self.classes.value(),
+            false => self.state.value(),
+        }
+    }
+}
+
+fn to_vec<B: Backend>(tensor: Tensor<B, 1, Int>) -> Vec<usize> {
+    tensor
+        .into_data()
+        .value
+        .into_iter()
+        .map(|value| value.elem::<i64>() as usize)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use burn_core::tensor::Data;
+
+    type TestBackend = burn_ndarray::NdArrayBackend<f32>;
+
+    fn input() -> AccuracyInput<TestBackend> {
+        // Class 0 has 4 items all predicted correctly, class 1 has 2 items with one correct.
+        let outputs = Tensor::from_floats([
+            [0.9, 0.1],
+            [0.8, 0.2],
+            [0.7, 0.3],
+            [0.6, 0.4],
+            [0.2, 0.8],
+            [0.6, 0.4],
+        ]);
+        let targets = Tensor::from_data(Data::from([0, 0, 0, 0, 1, 1]).convert());
+
+        AccuracyInput::new(outputs, targets)
+    }
+
+    #[test]
+    fn test_balanced_accuracy_should_average_the_accuracy_of_each_class() {
+        let mut metric = AccuracyMetric::<TestBackend>::new();
+        let mut metric_balanced = AccuracyMetric::<TestBackend>::new().with_balanced(true);
+
+        metric.update(&input());
+        metric_balanced.update(&input());
+
+        assert!((metric.value() - 100.0 * 5.0 / 6.0).abs() < 1e-6);
+        assert!((metric_balanced.value() - 75.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_balanced_accuracy_should_accumulate_until_cleared() {
+        let mut metric = AccuracyMetric::<TestBackend>::new().with_balanced(true);
+        let outputs = Tensor::from_floats([[0.2, 0.8]]);
+        let targets = Tensor::from_data(Data::from([1]).convert());
+
+        metric.update(&input());
+        metric.update(&AccuracyInput::new(outputs, targets));
+        assert!((metric.value() - 100.0 * (1.0 + 2.0 / 3.0) / 2.0).abs() < 1e-6);
+
+        metric.clear();
+        assert!(metric.value().is_nan());
     }
 }
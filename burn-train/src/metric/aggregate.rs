@@ -0,0 +1,93 @@
+/// How the values of a [numeric](super::Numeric) metric are aggregated over an epoch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Aggregate {
+    /// The mean of every value.
+    #[default]
+    Mean,
+    /// The sum of every value.
+    Sum,
+    /// The last value.
+    Last,
+}
+
+/// Aggregate the values of a numeric metric over an epoch following an [aggregation strategy](Aggregate).
+#[derive(Debug, Clone)]
+pub struct NumericAggregator {
+    aggregate: Aggregate,
+    sum: f64,
+    count: usize,
+    last: f64,
+}
+
+impl NumericAggregator {
+    /// Create a new aggregator with the given strategy.
+    pub fn new(aggregate: Aggregate) -> Self {
+        Self {
+            aggregate,
+            sum: 0.0,
+            count: 0,
+            last: f64::NAN,
+        }
+    }
+
+    /// Add a value to the aggregation.
+    pub fn update(&mut self, value: f64) {
+        self.sum += value;
+        self.count += 1;
+        self.last = value;
+    }
+
+    /// The aggregated value, NaN when no value has been added.
+    pub fn value(&self) -> f64 {
+        if self.count == 0 {
+            return f64::NAN;
+        }
+
+        match self.aggregate {
+            Aggregate::Mean => self.sum / self.count as f64,
+            Aggregate::Sum => self.sum,
+            Aggregate::Last => self.last,
+        }
+    }
+
+    /// The aggregation strategy.
+    pub fn aggregate(&self) -> Aggregate {
+        self.aggregate
+    }
+
+    /// Reset the aggregation.
+    pub fn reset(&mut self) {
+        self.sum = 0.0;
+        self.count = 0;
+        self.last = f64::NAN;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_each_strategy_produces_its_own_summary() {
+        let values = [1.0, 2.0, 3.0, 6.0];
+        let summary = |aggregate| {
+            let mut aggregator = NumericAggregator::new(aggregate);
+            values.iter().for_each(|value| aggregator.update(*value));
+            aggregator.value()
+        };
+
+        assert_eq!(summary(Aggregate::Mean), 3.0);
+        assert_eq!(summary(Aggregate::Sum), 12.0);
+        assert_eq!(summary(Aggregate::Last), 6.0);
+    }
+
+    #[test]
+    fn test_reset_clears_the_values() {
+        let mut aggregator = NumericAggregator::new(Aggregate::Sum);
+        aggregator.update(4.0);
+        aggregator.reset();
+        aggregator.update(1.0);
+
+        assert_eq!(aggregator.value(), 1.0);
+    }
+}
@@ -1,15 +1,18 @@
 use crate::{
     logger::MetricLogger,
-    metric::{Adaptor, Metric, MetricEntry, Numeric},
-    LearnerCallback, LearnerItem,
+    metric::{Adaptor, Aggregate, Metric, MetricEntry, Numeric, NumericAggregator},
+    LearnerCallback, LearnerItem, TrainingHistory,
 };
 use burn_core::data::dataloader::Progress;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
 
 pub struct TrainingProgress {
     pub progress: Progress,
     pub epoch: usize,
     pub epoch_total: usize,
     pub iteration: usize,
+    pub step: Option<usize>,
 }
 
 impl TrainingProgress {
@@ -22,6 +25,7 @@ impl TrainingProgress {
             epoch: 0,
             epoch_total: 0,
             iteration: 0,
+            step: None,
         }
     }
 }
@@ -50,6 +54,7 @@ where
     logger_train: Box<dyn MetricLogger>,
     logger_valid: Box<dyn MetricLogger>,
     renderer: Box<dyn DashboardRenderer>,
+    history: Arc<Mutex<TrainingHistory>>,
 }
 
 impl<T, V> Dashboard<T, V>
@@ -70,9 +75,21 @@ where
             logger_train,
             logger_valid,
             renderer,
+            history: Arc::new(Mutex::new(TrainingHistory::default())),
         }
     }
 
+    /// The [history](TrainingHistory) of the metrics, updated at the end of each epoch.
+    pub fn history(&self) -> Arc<Mutex<TrainingHistory>> {
+        self.history.clone()
+    }
+
+    /// Continue logging the metrics from the given epoch when resuming a training.
+    pub fn resume(&mut self, epoch: usize) {
+        self.logger_train.resume(epoch);
+        self.logger_valid.resume(epoch);
+    }
+
     pub fn register_train<M: Metric + 'static>(&mut self, metric: M)
     where
         T: Adaptor<M::Input>,
@@ -84,10 +101,21 @@ where
     pub fn register_train_plot<M: Numeric + Metric + 'static>(&mut self, metric: M)
     where
         T: Adaptor<M::Input>,
+    {
+        self.register_train_plot_aggregate(metric, Aggregate::default());
+    }
+
+    pub fn register_train_plot_aggregate<M: Numeric + Metric + 'static>(
+        &mut self,
+        metric: M,
+        aggregate: Aggregate,
+    ) where
+        T: Adaptor<M::Input>,
     {
         self.metrics_train_numeric
-            .push(Box::new(MetricWrapper::new(metric)));
+            .push(Box::new(NumericMetricWrapper::new(metric, aggregate)));
     }
+
     pub fn register_valid<M: Metric + 'static>(&mut self, metric: M)
     where
         V: Adaptor<M::Input>,
@@ -99,9 +127,19 @@ where
     pub fn register_valid_plot<M: Numeric + Metric + 'static>(&mut self, metric: M)
     where
         V: Adaptor<M::Input>,
+    {
+        self.register_valid_plot_aggregate(metric, Aggregate::default());
+    }
+
+    pub fn register_valid_plot_aggregate<M: Numeric + Metric + 'static>(
+        &mut self,
+        metric: M,
+        aggregate: Aggregate,
+    ) where
+        V: Adaptor<M::Input>,
     {
         self.metrics_valid_numeric
-            .push(Box::new(MetricWrapper::new(metric)));
+            .push(Box::new(NumericMetricWrapper::new(metric, aggregate)));
     }
 }
 
@@ -112,6 +150,7 @@ impl<T> From<LearnerItem<T>> for TrainingProgress {
             epoch: item.epoch,
             epoch_total: item.epoch_total,
             iteration: item.iteration,
+            step: item.step,
         }
     }
 }
@@ -158,42 +197,95 @@ where
     }
 
     fn on_train_end_epoch(&mut self, epoch: usize) {
+        let mut history = HashMap::new();
+
         for metric in self.metrics_train.iter_mut() {
+            history.extend(metric.history());
             metric.clear();
         }
         for metric in self.metrics_train_numeric.iter_mut() {
+            history.extend(metric.history());
+            if let Some(summary) = metric.summary() {
+                self.logger_train.log(&summary);
+                self.renderer
+                    .update_train(DashboardMetricState::Generic(summary));
+            }
             metric.clear();
         }
+        self.history.lock().unwrap().update_train(epoch, history);
         self.logger_train.epoch(epoch + 1);
     }
 
     fn on_valid_end_epoch(&mut self, epoch: usize) {
+        let mut history = HashMap::new();
+
         for metric in self.metrics_valid.iter_mut() {
+            history.extend(metric.history());
             metric.clear();
         }
         for metric in self.metrics_valid_numeric.iter_mut() {
+            history.extend(metric.history());
+            if let Some(summary) = metric.summary() {
+                self.logger_valid.log(&summary);
+                self.renderer
+                    .update_valid(DashboardMetricState::Generic(summary));
+            }
             metric.clear();
         }
+        self.history.lock().unwrap().update_valid(epoch, history);
         self.logger_valid.epoch(epoch + 1);
     }
 }
 
 trait DashboardNumericMetric<T>: Send + Sync {
     fn update(&mut self, item: &LearnerItem<T>) -> (MetricEntry, f64);
+    /// The entry summarizing the values aggregated since the last clear.
+    fn summary(&self) -> Option<MetricEntry>;
+    /// The name and the value of the metric recorded in the history since the last clear.
+    fn history(&self) -> Option<(String, f64)>;
     fn clear(&mut self);
 }
 
 trait DashboardMetric<T>: Send + Sync {
     fn update(&mut self, item: &LearnerItem<T>) -> MetricEntry;
+    /// The name and the value of the metric recorded in the history since the last clear.
+    fn history(&self) -> Option<(String, f64)>;
     fn clear(&mut self);
 }
 
-#[derive(new)]
 struct MetricWrapper<M> {
     metric: M,
+    aggregator: NumericAggregator,
+    name: Option<String>,
 }
 
-impl<T, M> DashboardNumericMetric<T> for MetricWrapper<M>
+impl<M> MetricWrapper<M> {
+    fn new(metric: M) -> Self {
+        Self {
+            metric,
+            aggregator: NumericAggregator::new(Aggregate::Mean),
+            name: None,
+        }
+    }
+}
+
+struct NumericMetricWrapper<M> {
+    metric: M,
+    aggregator: NumericAggregator,
+    name: Option<String>,
+}
+
+impl<M> NumericMetricWrapper<M> {
+    fn new(metric: M, aggregate: Aggregate) -> Self {
+        Self {
+            metric,
+            aggregator: NumericAggregator::new(aggregate),
+            name: None,
+        }
+    }
+}
+
+impl<T, M> DashboardNumericMetric<T> for NumericMetricWrapper<M>
 where
     T: 'static,
     M: Metric + Numeric + 'static,
@@ -203,11 +295,35 @@ where
         let update = self.metric.update(&item.item.adapt());
         let numeric = self.metric.value();
 
+        self.aggregator.update(numeric);
+        self.name = Some(update.name.clone());
+
         (update, numeric)
     }
 
+    fn summary(&self) -> Option<MetricEntry> {
+        let name = self.name.as_ref()?;
+        let value = self.aggregator.value();
+        let aggregate = format!("{:?}", self.aggregator.aggregate()).to_lowercase();
+
+        Some(MetricEntry::new(
+            format!("{name} (epoch {aggregate})"),
+            format!("{value:.4}"),
+            value.to_string(),
+        ))
+    }
+
+    fn history(&self) -> Option<(String, f64)> {
+        let name = self.name.as_ref()?;
+
+        Some((name.clone(), self.aggregator.value()))
+    }
+
     fn clear(&mut self) {
-        self.metric.clear()
+        self.metric.clear();
+        self.aggregator.reset();
+        // An epoch without any item has no summary.
+        self.name = None;
     }
 }
 
@@ -218,10 +334,110 @@ where
     T: Adaptor<M::Input>,
 {
     fn update(&mut self, item: &LearnerItem<T>) -> MetricEntry {
-        self.metric.update(&item.item.adapt())
+        let update = self.metric.update(&item.item.adapt());
+
+        // Only the entries serialized as numbers are recorded in the history.
+        if let Ok(value) = update.serialize.parse::<f64>() {
+            self.aggregator.update(value);
+            self.name = Some(update.name.clone());
+        }
+
+        update
+    }
+
+    fn history(&self) -> Option<(String, f64)> {
+        let name = self.name.as_ref()?;
+
+        Some((name.clone(), self.aggregator.value()))
     }
 
     fn clear(&mut self) {
-        self.metric.clear()
+        self.metric.clear();
+        self.aggregator.reset();
+        self.name = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logger::FileMetricLogger;
+
+    struct NoopRenderer;
+
+    impl DashboardRenderer for NoopRenderer {
+        fn update_train(&mut self, _state: DashboardMetricState) {}
+        fn update_valid(&mut self, _state: DashboardMetricState) {}
+        fn render_train(&mut self, _item: TrainingProgress) {}
+        fn render_valid(&mut self, _item: TrainingProgress) {}
+    }
+
+    struct ValueMetric {
+        value: f64,
+    }
+
+    impl Metric for ValueMetric {
+        type Input = f64;
+
+        fn update(&mut self, item: &f64) -> MetricEntry {
+            self.value = *item;
+            MetricEntry::new("Value".into(), item.to_string(), item.to_string())
+        }
+
+        fn clear(&mut self) {}
+    }
+
+    impl Numeric for ValueMetric {
+        fn value(&self) -> f64 {
+            self.value
+        }
+    }
+
+    impl Adaptor<f64> for f64 {
+        fn adapt(&self) -> f64 {
+            *self
+        }
+    }
+
+    #[test]
+    fn test_epoch_summary_should_be_logged() {
+        let directory = std::env::temp_dir().join("burn-train-test-dashboard-summary");
+        let directory = directory.to_str().unwrap();
+        std::fs::remove_dir_all(directory).ok();
+        let mut dashboard = Dashboard::<f64, f64>::new(
+            Box::new(NoopRenderer),
+            Box::new(FileMetricLogger::new(&format!("{directory}/train"))),
+            Box::new(FileMetricLogger::new(&format!("{directory}/valid"))),
+        );
+        dashboard.register_train_plot_aggregate(ValueMetric { value: 0.0 }, Aggregate::Sum);
+        let item = |value: f64, iteration: usize| {
+            LearnerItem::new(
+                value,
+                Progress {
+                    items_processed: iteration,
+                    items_total: 2,
+                },
+                1,
+                1,
+                iteration,
+                Some(iteration),
+            )
+        };
+
+        dashboard.on_train_item(item(1.0, 1));
+        dashboard.on_train_item(item(2.0, 2));
+        dashboard.on_train_end_epoch(1);
+
+        let read = |name: &str| {
+            std::fs::read_to_string(format!("{directory}/train/epoch-1/{name}.log")).unwrap()
+        };
+        assert_eq!(read("Value"), "1\n2\n");
+        assert_eq!(read("Value (epoch sum)"), "3\n");
+
+        // An epoch without any item, like a resumed epoch that was already completed.
+        dashboard.on_train_end_epoch(2);
+
+        let path = format!("{directory}/train/epoch-2/Value (epoch sum).log");
+        assert!(!std::path::Path::new(&path).exists());
     }
 }
@@ -201,11 +201,16 @@ impl CLIDashboardRenderer {
 
         let template = self.register_template_plots(String::default());
         let template = self.register_template_metrics(template);
+        let step = match self.progress.step {
+            Some(step) => format!(" Step {step}"),
+            None => String::new(),
+        };
         let template = template
             + format!(
-                "\n{}\n  - Iteration {} Epoch {}/{}\n",
+                "\n{}\n  - Iteration {}{} Epoch {}/{}\n",
                 PROGRESS_TAG,
                 self.progress.iteration,
+                step,
                 self.progress.epoch,
                 self.progress.epoch_total
             )
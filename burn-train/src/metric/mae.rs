@@ -0,0 +1,55 @@
+use super::state::{FormatOptions, NumericMetricState};
+use super::MetricEntry;
+use crate::metric::{Metric, Numeric};
+use burn_core::tensor::backend::Backend;
+use burn_core::tensor::{ElementConversion, Tensor};
+
+/// The mean absolute error metric.
+#[derive(Default)]
+pub struct MeanAbsoluteError<B: Backend> {
+    state: NumericMetricState,
+    _b: B,
+}
+
+/// The [mean absolute error metric](MeanAbsoluteError) input type.
+#[derive(new)]
+pub struct MeanAbsoluteErrorInput<B: Backend> {
+    outputs: Tensor<B, 2>,
+    targets: Tensor<B, 2>,
+}
+
+impl<B: Backend> MeanAbsoluteError<B> {
+    /// Create the metric.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<B: Backend> Metric for MeanAbsoluteError<B> {
+    type Input = MeanAbsoluteErrorInput<B>;
+
+    fn update(&mut self, input: &MeanAbsoluteErrorInput<B>) -> MetricEntry {
+        let [batch_size, _] = input.outputs.dims();
+        let errors = input.outputs.clone().sub(input.targets.clone()).into_data();
+
+        let mae = errors
+            .value
+            .iter()
+            .map(|error| f64::from_elem(*error).abs())
+            .sum::<f64>()
+            / errors.value.len() as f64;
+
+        self.state
+            .update(mae, batch_size, FormatOptions::new("MAE").precision(4))
+    }
+
+    fn clear(&mut self) {
+        self.state.reset()
+    }
+}
+
+impl<B: Backend> Numeric for MeanAbsoluteError<B> {
+    fn value(&self) -> f64 {
+        self.state.value()
+    }
+}
@@ -0,0 +1,127 @@
+use super::state::{FormatOptions, NumericMetricState};
+use super::MetricEntry;
+use crate::metric::{Metric, Numeric};
+use burn_core::tensor::backend::Backend;
+
+/// Track the memory allocated by the backend on a device.
+///
+/// The value is reported in megabytes and relies on
+/// [memory_allocated](Backend::memory_allocated), backends that don't track their memory usage
+/// are displayed as unavailable. The memory used on the whole device can be tracked
+/// [instead](Self::device_wide).
+pub struct MemoryUsageMetric<B: Backend> {
+    device: B::Device,
+    name: &'static str,
+    memory_allocated: fn(&B::Device) -> Option<usize>,
+    state: NumericMetricState,
+}
+
+impl<B: Backend> MemoryUsageMetric<B> {
+    /// Create the metric for the given device.
+    pub fn new(device: B::Device) -> Self {
+        Self {
+            device,
+            name: "Memory Usage",
+            memory_allocated: B::memory_allocated,
+            state: NumericMetricState::new(),
+        }
+    }
+
+    /// Create the metric tracking the memory [used](Backend::memory_used) on the whole device,
+    /// which includes the memory of other processes.
+    pub fn device_wide(device: B::Device) -> Self {
+        Self {
+            device,
+            name: "Device Memory Usage",
+            memory_allocated: B::memory_used,
+            state: NumericMetricState::new(),
+        }
+    }
+}
+
+impl<B: Backend> Default for MemoryUsageMetric<B> {
+    fn default() -> Self {
+        Self::new(B::Device::default())
+    }
+}
+
+impl<B: Backend> Metric for MemoryUsageMetric<B> {
+    type Input = ();
+
+    fn update(&mut self, _item: &()) -> MetricEntry {
+        let name = self.name;
+
+        match (self.memory_allocated)(&self.device) {
+            Some(bytes) => {
+                let megabytes = bytes as f64 / (1024.0 * 1024.0);
+
+                self.state.update(
+                    megabytes,
+                    1,
+                    FormatOptions::new(name).unit("MB").precision(2),
+                )
+            }
+            None => MetricEntry::new(
+                name.to_string(),
+                "unavailable".to_string(),
+                "NaN".to_string(),
+            ),
+        }
+    }
+
+    fn clear(&mut self) {
+        self.state.reset()
+    }
+}
+
+impl<B: Backend> Numeric for MemoryUsageMetric<B> {
+    fn value(&self) -> f64 {
+        self.state.value()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type TestBackend = burn_ndarray::NdArrayBackend<f32>;
+
+    /// Stub of a backend allocating 3 MB on every device.
+    fn memory_allocated(_device: &<TestBackend as Backend>::Device) -> Option<usize> {
+        Some(3 * 1024 * 1024)
+    }
+
+    #[test]
+    fn test_memory_usage_in_megabytes() {
+        let mut metric = MemoryUsageMetric::<TestBackend> {
+            memory_allocated,
+            ..MemoryUsageMetric::default()
+        };
+
+        let entry = metric.update(&());
+
+        assert_eq!(metric.value(), 3.0);
+        assert_eq!(entry.name, "Memory Usage");
+        assert_eq!(entry.serialize, "3");
+    }
+
+    #[test]
+    fn test_memory_usage_unavailable() {
+        let mut metric = MemoryUsageMetric::<TestBackend>::default();
+
+        let entry = metric.update(&());
+
+        assert!(metric.value().is_nan());
+        assert_eq!(entry.formatted, "unavailable");
+    }
+
+    #[test]
+    fn test_device_wide_memory_usage_has_its_own_name() {
+        let mut metric = MemoryUsageMetric::<TestBackend>::device_wide(Default::default());
+
+        let entry = metric.update(&());
+
+        assert_eq!(entry.name, "Device Memory Usage");
+        assert_eq!(entry.formatted, "unavailable");
+    }
+}
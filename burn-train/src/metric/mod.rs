@@ -2,11 +2,21 @@ pub mod dashboard;
 pub mod state;
 
 mod acc;
+mod aggregate;
 mod base;
 mod cuda;
 mod loss;
+mod mae;
+mod memory;
+mod mse;
+mod r2;
 
 pub use acc::*;
+pub use aggregate::*;
 pub use base::*;
 pub use cuda::*;
 pub use loss::*;
+pub use mae::*;
+pub use memory::*;
+pub use mse::*;
+pub use r2::*;
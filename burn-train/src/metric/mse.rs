@@ -0,0 +1,55 @@
+use super::state::{FormatOptions, NumericMetricState};
+use super::MetricEntry;
+use crate::metric::{Metric, Numeric};
+use burn_core::tensor::backend::Backend;
+use burn_core::tensor::{ElementConversion, Tensor};
+
+/// The mean squared error metric.
+#[derive(Default)]
+pub struct MeanSquaredError<B: Backend> {
+    state: NumericMetricState,
+    _b: B,
+}
+
+/// The [mean squared error metric](MeanSquaredError) input type.
+#[derive(new)]
+pub struct MeanSquaredErrorInput<B: Backend> {
+    outputs: Tensor<B, 2>,
+    targets: Tensor<B, 2>,
+}
+
+impl<B: Backend> MeanSquaredError<B> {
+    /// Create the metric.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<B: Backend> Metric for MeanSquaredError<B> {
+    type Input = MeanSquaredErrorInput<B>;
+
+    fn update(&mut self, input: &MeanSquaredErrorInput<B>) -> MetricEntry {
+        let [batch_size, _] = input.outputs.dims();
+        let errors = input.outputs.clone().sub(input.targets.clone()).into_data();
+
+        let mse = errors
+            .value
+            .iter()
+            .map(|error| f64::from_elem(*error).powi(2))
+            .sum::<f64>()
+            / errors.value.len() as f64;
+
+        self.state
+            .update(mse, batch_size, FormatOptions::new("MSE").precision(4))
+    }
+
+    fn clear(&mut self) {
+        self.state.reset()
+    }
+}
+
+impl<B: Backend> Numeric for MeanSquaredError<B> {
+    fn value(&self) -> f64 {
+        self.state.value()
+    }
+}
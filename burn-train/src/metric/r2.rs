@@ -0,0 +1,99 @@
+use super::MetricEntry;
+use crate::metric::{Metric, Numeric};
+use burn_core::tensor::backend::Backend;
+use burn_core::tensor::{ElementConversion, Tensor};
+
+/// The coefficient of determination, `1 - SS_res / SS_tot`, of all the targets seen during the
+/// epoch.
+///
+/// The sums of squares can't be averaged over batches, so they are accumulated until the metric
+/// is [cleared](Metric::clear) at the end of the epoch.
+#[derive(Default)]
+pub struct R2Score<B: Backend> {
+    state: R2State,
+    _b: B,
+}
+
+/// The [R2 score metric](R2Score) input type.
+#[derive(new)]
+pub struct R2ScoreInput<B: Backend> {
+    outputs: Tensor<B, 2>,
+    targets: Tensor<B, 2>,
+}
+
+#[derive(Default, Clone, Copy)]
+struct R2State {
+    count: usize,
+    sum_targets: f64,
+    sum_targets_squared: f64,
+    sum_residuals_squared: f64,
+}
+
+impl R2State {
+    fn update(&mut self, outputs: &[f64], targets: &[f64]) {
+        for (output, target) in outputs.iter().zip(targets) {
+            self.count += 1;
+            self.sum_targets += target;
+            self.sum_targets_squared += target * target;
+            self.sum_residuals_squared += (target - output).powi(2);
+        }
+    }
+
+    fn value(&self) -> f64 {
+        if self.count == 0 {
+            return f64::NAN;
+        }
+
+        let total = self.sum_targets_squared - self.sum_targets.powi(2) / self.count as f64;
+
+        1.0 - self.sum_residuals_squared / total
+    }
+}
+
+impl<B: Backend> R2Score<B> {
+    /// Create the metric.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<B: Backend> Metric for R2Score<B> {
+    type Input = R2ScoreInput<B>;
+
+    fn update(&mut self, input: &R2ScoreInput<B>) -> MetricEntry {
+        let outputs = to_vec(input.outputs.clone());
+        let targets = to_vec(input.targets.clone());
+
+        let mut batch = R2State::default();
+        batch.update(&outputs, &targets);
+        self.state.update(&outputs, &targets);
+
+        let value_epoch = self.state.value();
+        let value_batch = batch.value();
+
+        MetricEntry::new(
+            "R2".to_string(),
+            format!("epoch {value_epoch:.4} - batch {value_batch:.4}"),
+            value_epoch.to_string(),
+        )
+    }
+
+    fn clear(&mut self) {
+        self.state = R2State::default();
+    }
+}
+
+impl<B: Backend> Numeric for R2Score<B> {
+    fn value(&self) -> f64 {
+        self.state.value()
+    }
+}
+
+fn to_vec<B: Backend>(tensor: Tensor<B, 2>) -> Vec<f64> {
+    tensor
+        .into_data()
+        .value
+        .into_iter()
+        .map(|value| value.elem())
+        .collect()
+}
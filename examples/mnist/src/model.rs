@@ -107,11 +107,13 @@ impl<B: Backend> ConvBlock<B> {
     }
 }
 
-impl<B: ADBackend> TrainStep<MNISTBatch<B>, ClassificationOutput<B>> for Model<B> {
-    fn step(&self, item: MNISTBatch<B>) -> TrainOutput<ClassificationOutput<B>> {
+impl<B: ADBackend> TrainStep<MNISTBatch<B>, ClassificationOutput<B::InnerBackend>> for Model<B> {
+    fn step(&self, item: MNISTBatch<B>) -> TrainOutput<ClassificationOutput<B::InnerBackend>> {
         let item = self.forward_classification(item);
+        let grads = item.loss.backward();
 
-        TrainOutput::new(self, item.loss.backward(), item)
+        // The metrics are computed on the inner backend to avoid building autodiff graphs.
+        TrainOutput::new(self, grads, item.inner())
     }
 }
 